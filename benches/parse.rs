@@ -1,6 +1,11 @@
 use criterion::{Criterion, criterion_group, criterion_main};
 use jiff::tz::TimeZone;
-use tardis_cli::{config::Config, core, core::App, core::Preset};
+use tardis_cli::{
+    cli::InputKind,
+    config::Config,
+    core,
+    core::{App, PresetSpec, Presets, format::Format},
+};
 
 /// Fixed "now" for deterministic benchmarks: 2025-06-15T12:00:00 UTC
 fn fixed_now() -> jiff::Zoned {
@@ -27,112 +32,112 @@ fn make_app(expr: &str) -> App {
 fn bench_relative(c: &mut Criterion) {
     let app_today = make_app("today");
     c.bench_function("relative_today", |b| {
-        b.iter(|| core::process(&app_today, &[]).unwrap());
+        b.iter(|| core::process(&app_today, &Presets::new()).unwrap());
     });
 
     let app_tomorrow = make_app("tomorrow");
     c.bench_function("relative_tomorrow", |b| {
-        b.iter(|| core::process(&app_tomorrow, &[]).unwrap());
+        b.iter(|| core::process(&app_tomorrow, &Presets::new()).unwrap());
     });
 
     let app_yesterday = make_app("yesterday");
     c.bench_function("relative_yesterday", |b| {
-        b.iter(|| core::process(&app_yesterday, &[]).unwrap());
+        b.iter(|| core::process(&app_yesterday, &Presets::new()).unwrap());
     });
 
     let app_now = make_app("now");
     c.bench_function("relative_now", |b| {
-        b.iter(|| core::process(&app_now, &[]).unwrap());
+        b.iter(|| core::process(&app_now, &Presets::new()).unwrap());
     });
 }
 
 fn bench_dayref(c: &mut Criterion) {
     let app_next_friday = make_app("next friday");
     c.bench_function("dayref_next_friday", |b| {
-        b.iter(|| core::process(&app_next_friday, &[]).unwrap());
+        b.iter(|| core::process(&app_next_friday, &Presets::new()).unwrap());
     });
 
     let app_last_monday = make_app("last monday");
     c.bench_function("dayref_last_monday", |b| {
-        b.iter(|| core::process(&app_last_monday, &[]).unwrap());
+        b.iter(|| core::process(&app_last_monday, &Presets::new()).unwrap());
     });
 }
 
 fn bench_time_suffix(c: &mut Criterion) {
     let app_in_5_min = make_app("in 5 minutes");
     c.bench_function("time_in_5_minutes", |b| {
-        b.iter(|| core::process(&app_in_5_min, &[]).unwrap());
+        b.iter(|| core::process(&app_in_5_min, &Presets::new()).unwrap());
     });
 
     let app_3_hours_ago = make_app("3 hours ago");
     c.bench_function("time_3_hours_ago", |b| {
-        b.iter(|| core::process(&app_3_hours_ago, &[]).unwrap());
+        b.iter(|| core::process(&app_3_hours_ago, &Presets::new()).unwrap());
     });
 
     let app_next_mon = make_app("next monday");
     c.bench_function("time_next_monday", |b| {
-        b.iter(|| core::process(&app_next_mon, &[]).unwrap());
+        b.iter(|| core::process(&app_next_mon, &Presets::new()).unwrap());
     });
 }
 
 fn bench_duration(c: &mut Criterion) {
     let app_3_days = make_app("in 3 days");
     c.bench_function("duration_in_3_days", |b| {
-        b.iter(|| core::process(&app_3_days, &[]).unwrap());
+        b.iter(|| core::process(&app_3_days, &Presets::new()).unwrap());
     });
 
     let app_2_hours = make_app("in 2 hours");
     c.bench_function("duration_in_2_hours", |b| {
-        b.iter(|| core::process(&app_2_hours, &[]).unwrap());
+        b.iter(|| core::process(&app_2_hours, &Presets::new()).unwrap());
     });
 
     let app_1_week = make_app("in 1 week");
     c.bench_function("duration_in_1_week", |b| {
-        b.iter(|| core::process(&app_1_week, &[]).unwrap());
+        b.iter(|| core::process(&app_1_week, &Presets::new()).unwrap());
     });
 
     let app_2_weeks_ago = make_app("2 weeks ago");
     c.bench_function("duration_2_weeks_ago", |b| {
-        b.iter(|| core::process(&app_2_weeks_ago, &[]).unwrap());
+        b.iter(|| core::process(&app_2_weeks_ago, &Presets::new()).unwrap());
     });
 }
 
 fn bench_absolute(c: &mut Criterion) {
     let app_iso_date = make_app("2025-01-01");
     c.bench_function("absolute_iso_date", |b| {
-        b.iter(|| core::process(&app_iso_date, &[]).unwrap());
+        b.iter(|| core::process(&app_iso_date, &Presets::new()).unwrap());
     });
 
     let app_iso_datetime = make_app("2025-01-01");
     c.bench_function("absolute_iso_date_only", |b| {
-        b.iter(|| core::process(&app_iso_datetime, &[]).unwrap());
+        b.iter(|| core::process(&app_iso_datetime, &Presets::new()).unwrap());
     });
 
     let app_overmorrow = make_app("overmorrow");
     c.bench_function("absolute_overmorrow", |b| {
-        b.iter(|| core::process(&app_overmorrow, &[]).unwrap());
+        b.iter(|| core::process(&app_overmorrow, &Presets::new()).unwrap());
     });
 }
 
 fn bench_epoch(c: &mut Criterion) {
     let app_epoch_simple = make_app("@1735689600");
     c.bench_function("epoch_seconds", |b| {
-        b.iter(|| core::process(&app_epoch_simple, &[]).unwrap());
+        b.iter(|| core::process(&app_epoch_simple, &Presets::new()).unwrap());
     });
 
     let app_epoch_zero = make_app("@0");
     c.bench_function("epoch_zero", |b| {
-        b.iter(|| core::process(&app_epoch_zero, &[]).unwrap());
+        b.iter(|| core::process(&app_epoch_zero, &Presets::new()).unwrap());
     });
 
     let app_epoch_negative = make_app("@-86400");
     c.bench_function("epoch_negative", |b| {
-        b.iter(|| core::process(&app_epoch_negative, &[]).unwrap());
+        b.iter(|| core::process(&app_epoch_negative, &Presets::new()).unwrap());
     });
 
     let app_epoch_large = make_app("@1893456000");
     c.bench_function("epoch_large_timestamp", |b| {
-        b.iter(|| core::process(&app_epoch_large, &[]).unwrap());
+        b.iter(|| core::process(&app_epoch_large, &Presets::new()).unwrap());
     });
 }
 
@@ -144,7 +149,7 @@ fn bench_format(c: &mut Criterion) {
         Some(fixed_now()),
     );
     c.bench_function("format_complex_strftime", |b| {
-        b.iter(|| core::process(&app_complex_fmt, &[]).unwrap());
+        b.iter(|| core::process(&app_complex_fmt, &Presets::new()).unwrap());
     });
 
     let app_epoch_fmt = App::new(
@@ -154,27 +159,44 @@ fn bench_format(c: &mut Criterion) {
         Some(fixed_now()),
     );
     c.bench_function("format_epoch_output", |b| {
-        b.iter(|| core::process(&app_epoch_fmt, &[]).unwrap());
+        b.iter(|| core::process(&app_epoch_fmt, &Presets::new()).unwrap());
     });
 
     let app_unix_fmt = App::new("2025-01-01".into(), "unix".into(), utc(), Some(fixed_now()));
     c.bench_function("format_unix_output", |b| {
-        b.iter(|| core::process(&app_unix_fmt, &[]).unwrap());
+        b.iter(|| core::process(&app_unix_fmt, &Presets::new()).unwrap());
     });
 
     let app_compact = App::new("today".into(), "%Y%m%d".into(), utc(), Some(fixed_now()));
     c.bench_function("format_compact_date", |b| {
-        b.iter(|| core::process(&app_compact, &[]).unwrap());
+        b.iter(|| core::process(&app_compact, &Presets::new()).unwrap());
+    });
+
+    let now = fixed_now();
+    let tokens = "%Y-%m-%dT%H:%M:%S {tzabbr} @{beats}";
+    c.bench_function("format_tokens_compiled_each_time", |b| {
+        b.iter(|| {
+            Format::new(tokens)
+                .unwrap()
+                .render(&now, InputKind::Datetime)
+        });
+    });
+    let compiled = Format::new(tokens).unwrap();
+    c.bench_function("format_tokens_precompiled", |b| {
+        b.iter(|| compiled.render(&now, InputKind::Datetime));
     });
 }
 
 fn bench_preset(c: &mut Criterion) {
-    let presets = vec![
-        Preset::new("iso".into(), "%Y-%m-%dT%H:%M:%S".into()),
-        Preset::new("br".into(), "%d/%m/%Y".into()),
-        Preset::new("time".into(), "%H:%M".into()),
-        Preset::new("short".into(), "%Y-%m-%d".into()),
-    ];
+    let presets: Presets = [
+        ("iso", "%Y-%m-%dT%H:%M:%S"),
+        ("br", "%d/%m/%Y"),
+        ("time", "%H:%M"),
+        ("short", "%Y-%m-%d"),
+    ]
+    .into_iter()
+    .map(|(name, fmt)| (name.to_string(), PresetSpec::new(fmt.to_string())))
+    .collect();
 
     let app_preset = App::new(
         "2025-01-01 10:00".into(),
@@ -204,7 +226,7 @@ fn bench_timezone(c: &mut Criterion) {
         Some(now_sp),
     );
     c.bench_function("timezone_sao_paulo", |b| {
-        b.iter(|| core::process(&app_sao_paulo, &[]).unwrap());
+        b.iter(|| core::process(&app_sao_paulo, &Presets::new()).unwrap());
     });
 
     let tz_tokyo = TimeZone::get("Asia/Tokyo").unwrap();
@@ -218,7 +240,7 @@ fn bench_timezone(c: &mut Criterion) {
         Some(now_tokyo),
     );
     c.bench_function("timezone_tokyo", |b| {
-        b.iter(|| core::process(&app_tokyo, &[]).unwrap());
+        b.iter(|| core::process(&app_tokyo, &Presets::new()).unwrap());
     });
 }
 
@@ -231,12 +253,12 @@ fn bench_config(c: &mut Criterion) {
 fn bench_error(c: &mut Criterion) {
     let app_bad = make_app("???");
     c.bench_function("error_invalid_expression", |b| {
-        b.iter(|| core::process(&app_bad, &[]).unwrap_err());
+        b.iter(|| core::process(&app_bad, &Presets::new()).unwrap_err());
     });
 
     let app_bad_epoch = make_app("@notanumber");
     c.bench_function("error_invalid_epoch", |b| {
-        b.iter(|| core::process(&app_bad_epoch, &[]).unwrap_err());
+        b.iter(|| core::process(&app_bad_epoch, &Presets::new()).unwrap_err());
     });
 }
 
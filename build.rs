@@ -12,5 +12,64 @@ fn main() {
             .expect("failed to generate completions");
     }
 
+    embed_man_pages(&cmd, &out_dir);
+    println!("cargo:rustc-env=TD_JIFF_VERSION={}", locked_version("jiff"));
+
     println!("cargo:rerun-if-changed=src/cli_defs.rs");
+    println!("cargo:rerun-if-changed=docs/man");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+}
+
+/// The version of `package` in `Cargo.lock`, or "unknown" without one.
+fn locked_version(package: &str) -> String {
+    let lock = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let header = format!("name = \"{package}\"\n");
+    lock.split("[[package]]")
+        .find(|entry| entry.trim_start().starts_with(header.trim_start()))
+        .and_then(|entry| entry.lines().find_map(|l| l.strip_prefix("version = ")))
+        .map_or_else(
+            || "unknown".to_string(),
+            |v| v.trim_matches('"').to_string(),
+        )
+}
+
+/// Write `man_pages.rs`, embedding every page in `docs/man` unless the
+/// `man-pages` feature is off, and fail the build when a subcommand has no
+/// page.
+fn embed_man_pages(cmd: &clap::Command, out_dir: &std::path::Path) {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("docs/man");
+    let mut pages: Vec<_> = std::fs::read_dir(&dir)
+        .expect("failed to read docs/man")
+        .map(|entry| entry.expect("failed to read docs/man").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "1"))
+        .collect();
+    pages.sort();
+
+    for sub in cmd.get_subcommands().filter(|sub| sub.get_name() != "help") {
+        let page = dir.join(format!("td-{}.1", sub.get_name()));
+        assert!(
+            page.exists(),
+            "subcommand `{}` has no man page; add docs/td-{}.1.md and run `just man`",
+            sub.get_name(),
+            sub.get_name()
+        );
+    }
+
+    let embed = std::env::var_os("CARGO_FEATURE_MAN_PAGES").is_some();
+    let entries: String = pages
+        .iter()
+        .filter(|_| embed)
+        .map(|path| {
+            format!(
+                "    ({:?}, include_str!({:?})),\n",
+                path.file_name().expect("man page has a name"),
+                path
+            )
+        })
+        .collect();
+    std::fs::write(
+        out_dir.join("man_pages.rs"),
+        format!("/// Every man page, by file name.\npub const PAGES: &[(&str, &str)] = &[\n{entries}];\n"),
+    )
+    .expect("failed to write man_pages.rs");
 }
@@ -0,0 +1,323 @@
+//! BSD `date -v` adjustments for **TARDIS**.
+//!
+//! An [`Adjustment`] such as `+3d`, `-1H` or `+fri` moves the result once it
+//! is parsed, as `date -v` does on macOS and the BSDs.  A signed number
+//! shifts by that many years (`y`), months (`m`), weeks (`w`), days (`d`),
+//! hours (`H`), minutes (`M`) or seconds (`S`); an unsigned one sets that
+//! field instead, with `w` counting weekdays from 0 (Sunday) to 6.  A
+//! weekday or month name with a sign moves to the next or previous one,
+//! staying put when the date already falls on it; without a sign it sets
+//! the weekday within the week, or the month within the year.
+
+use std::{ffi::OsString, str::FromStr};
+
+use jiff::{Span, Zoned, civil::Date};
+
+use crate::{Error, Result, cli::MonthOverflow, user_input_error};
+
+/// One `-v` adjustment.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adjustment {
+    step: Step,
+    value: i64,
+    unit: Unit,
+    /// Written as a weekday or month name rather than a number.
+    named: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Forward,
+    Backward,
+    Set,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl FromStr for Adjustment {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            user_input_error!(
+                InvalidDateFormat,
+                "invalid adjustment '{}' (expected [+|-]NUMBER followed by y, m, w, d, H, M or S, or a weekday or month name)",
+                s
+            )
+        };
+        let (step, rest) = match s.strip_prefix('+') {
+            Some(rest) => (Step::Forward, rest),
+            None => match s.strip_prefix('-') {
+                Some(rest) => (Step::Backward, rest),
+                None => (Step::Set, s),
+            },
+        };
+        let named = |unit, value: i64| Self {
+            step,
+            value,
+            unit,
+            named: true,
+        };
+        if let Some(day) = crate::recur::parse_weekday(rest) {
+            return Ok(named(Unit::Week, day.to_sunday_zero_offset().into()));
+        }
+        if let Some(month) = crate::parser::lexer::month(rest) {
+            return Ok(named(Unit::Month, month.into()));
+        }
+
+        let Some(unit) = rest.chars().last() else {
+            return Err(invalid());
+        };
+        let digits = &rest[..rest.len() - unit.len_utf8()];
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let unit = match unit {
+            'y' => Unit::Year,
+            'm' => Unit::Month,
+            'w' => Unit::Week,
+            'd' => Unit::Day,
+            'H' => Unit::Hour,
+            'M' => Unit::Minute,
+            'S' => Unit::Second,
+            _ => return Err(invalid()),
+        };
+        let value: i64 = digits.parse().map_err(|_| invalid())?;
+        if step == Step::Set && unit == Unit::Week && value > 6 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            step,
+            value,
+            unit,
+            named: false,
+        })
+    }
+}
+
+impl Adjustment {
+    /// `at` with the adjustment applied.  Moving by months or years past
+    /// the end of a month follows `overflow`.
+    pub fn apply(self, at: &Zoned, overflow: MonthOverflow) -> Result<Zoned> {
+        let failed = |e: &dyn std::fmt::Display| {
+            user_input_error!(InvalidDate, "cannot apply adjustment: {}", e)
+        };
+        if let Some(months) = self.months_moved(at) {
+            let span = span(Unit::Month, months).map_err(|e| failed(&e))?;
+            return crate::parser::resolver::add_span(at, span, overflow).map_err(|e| failed(&e));
+        }
+        self.try_apply(at).map_err(|e| failed(&e))
+    }
+
+    /// The months a `+`/`-` year or month step moves `at` by.
+    fn months_moved(self, at: &Zoned) -> Option<i64> {
+        let sign = match self.step {
+            Step::Forward => 1,
+            Step::Backward => -1,
+            Step::Set => return None,
+        };
+        match (self.unit, self.named) {
+            (Unit::Year, _) => Some(sign * self.value * 12),
+            (Unit::Month, true) => Some(towards(at.month().into(), self.value, 12, sign)),
+            (Unit::Month, false) => Some(sign * self.value),
+            _ => None,
+        }
+    }
+
+    fn try_apply(self, at: &Zoned) -> std::result::Result<Zoned, jiff::Error> {
+        let sign = match self.step {
+            Step::Forward => 1,
+            Step::Backward => -1,
+            Step::Set => return self.set(at),
+        };
+        let amount = match (self.unit, self.named) {
+            (Unit::Week, true) => {
+                let today = i64::from(at.weekday().to_sunday_zero_offset());
+                return at.checked_add(span(Unit::Day, towards(today, self.value, 7, sign))?);
+            }
+            _ => sign * self.value,
+        };
+        at.checked_add(span(self.unit, amount)?)
+    }
+
+    fn set(self, at: &Zoned) -> std::result::Result<Zoned, jiff::Error> {
+        let value = i8::try_from(self.value).unwrap_or(i8::MAX);
+        match self.unit {
+            Unit::Year => {
+                // Two-digit years pivot like POSIX `%y`: 69-99 and 00-68.
+                let year = match self.value {
+                    0..=68 => self.value + 2000,
+                    69..=99 => self.value + 1900,
+                    _ => self.value,
+                };
+                let year = i16::try_from(year).unwrap_or(i16::MAX);
+                let day = at
+                    .day()
+                    .min(Date::new(year, at.month(), 1)?.days_in_month());
+                at.with().year(year).day(day).build()
+            }
+            Unit::Month => {
+                let day = at
+                    .day()
+                    .min(Date::new(at.year(), value, 1)?.days_in_month());
+                at.with().month(value).day(day).build()
+            }
+            Unit::Week => {
+                let today = i64::from(at.weekday().to_sunday_zero_offset());
+                at.checked_add(span(Unit::Day, self.value - today)?)
+            }
+            Unit::Day => at.with().day(value).build(),
+            Unit::Hour => at.with().hour(value).build(),
+            Unit::Minute => at.with().minute(value).build(),
+            Unit::Second => at.with().second(value).build(),
+        }
+    }
+}
+
+/// Steps from `from` to the next (`sign` 1) or previous (`sign` -1) `to`
+/// on a cycle of `len`, zero when they are equal.
+fn towards(from: i64, to: i64, len: i64, sign: i64) -> i64 {
+    sign * (sign * (to - from)).rem_euclid(len)
+}
+
+fn span(unit: Unit, amount: i64) -> std::result::Result<Span, jiff::Error> {
+    let span = Span::new();
+    match unit {
+        Unit::Year => span.try_years(amount),
+        Unit::Month => span.try_months(amount),
+        Unit::Week => span.try_weeks(amount),
+        Unit::Day => span.try_days(amount),
+        Unit::Hour => span.try_hours(amount),
+        Unit::Minute => span.try_minutes(amount),
+        Unit::Second => span.try_seconds(amount),
+    }
+}
+
+/// Rewrite each `-vADJ` in `args` as `--adjust=ADJ`, so BSD-style chains
+/// such as `-v+1m -v1d` reach the parser, which reads `-v` alone as
+/// `--verbose`.  A `-v` cluster that is not an adjustment, such as `-vn`,
+/// is left alone, as is everything after `--`.
+#[must_use]
+pub fn rewrite(args: Vec<OsString>) -> Vec<OsString> {
+    let mut options = true;
+    args.into_iter()
+        .map(|arg| {
+            if arg == "--" {
+                options = false;
+            }
+            match arg.to_str().and_then(|s| s.strip_prefix("-v")) {
+                Some(adj) if options && adj.parse::<Adjustment>().is_ok() => {
+                    format!("--adjust={adj}").into()
+                }
+                _ => arg,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Friday, January 31, 2025.
+    fn now() -> Zoned {
+        "2025-01-31T10:30:00+00:00[UTC]".parse().unwrap()
+    }
+
+    fn adjusted(adjustments: &[&str]) -> String {
+        adjustments
+            .iter()
+            .try_fold(now(), |at, adj| {
+                adj.parse::<Adjustment>()?.apply(&at, MonthOverflow::Clamp)
+            })
+            .unwrap()
+            .strftime("%a %F %T")
+            .to_string()
+    }
+
+    #[test]
+    fn shifts_by_signed_amounts_in_order() {
+        assert_eq!(adjusted(&["+3d", "-1H"]), "Mon 2025-02-03 09:30:00");
+        assert_eq!(adjusted(&["+1m"]), "Fri 2025-02-28 10:30:00");
+        assert_eq!(
+            adjusted(&["-2w", "+90M", "+30S"]),
+            "Fri 2025-01-17 12:00:30"
+        );
+        assert_eq!(adjusted(&["+1y"]), "Sat 2026-01-31 10:30:00");
+    }
+
+    #[test]
+    fn sets_fields_without_a_sign() {
+        assert_eq!(
+            adjusted(&["1d", "0H", "0M", "0S"]),
+            "Wed 2025-01-01 00:00:00"
+        );
+        assert_eq!(adjusted(&["2m"]), "Fri 2025-02-28 10:30:00");
+        assert_eq!(adjusted(&["99y"]), "Sun 1999-01-31 10:30:00");
+        assert_eq!(adjusted(&["0w"]), "Sun 2025-01-26 10:30:00");
+        assert_eq!(adjusted(&["mon"]), "Mon 2025-01-27 10:30:00");
+        assert_eq!(adjusted(&["march"]), "Mon 2025-03-31 10:30:00");
+    }
+
+    #[test]
+    fn names_move_to_the_next_or_previous_match() {
+        assert_eq!(adjusted(&["+fri"]), "Fri 2025-01-31 10:30:00");
+        assert_eq!(adjusted(&["+mon"]), "Mon 2025-02-03 10:30:00");
+        assert_eq!(adjusted(&["-sat"]), "Sat 2025-01-25 10:30:00");
+        assert_eq!(adjusted(&["+dec"]), "Wed 2025-12-31 10:30:00");
+        assert_eq!(adjusted(&["-nov"]), "Sat 2024-11-30 10:30:00");
+    }
+
+    #[test]
+    fn rejects_malformed_and_impossible_adjustments() {
+        for bad in ["", "+", "3", "+3x", "+d", "7w", "++3d", "someday"] {
+            assert!(bad.parse::<Adjustment>().is_err(), "{bad}");
+        }
+        let err = "32d"
+            .parse::<Adjustment>()
+            .unwrap()
+            .apply(&now(), MonthOverflow::Clamp)
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot apply adjustment"), "{err}");
+        assert!(
+            "0d".parse::<Adjustment>()
+                .unwrap()
+                .apply(&now(), MonthOverflow::Clamp)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rewrites_attached_v_values_only() {
+        let args = ["td", "-v", "-vn", "-v+3d", "-vfri", "now", "--", "-v-1H"];
+        let args: Vec<String> = rewrite(args.iter().map(OsString::from).collect())
+            .into_iter()
+            .map(|a| a.into_string().unwrap())
+            .collect();
+        assert_eq!(
+            args,
+            [
+                "td",
+                "-v",
+                "-vn",
+                "--adjust=+3d",
+                "--adjust=fri",
+                "now",
+                "--",
+                "-v-1H"
+            ]
+        );
+    }
+}
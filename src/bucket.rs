@@ -0,0 +1,144 @@
+//! Calendar buckets for `td hist`.
+//!
+//! [`start`] floors a zoned datetime to the beginning of its minute, hour,
+//! day, ISO week (Monday) or month, and [`label`] names a bucket the way it
+//! is printed.  [`counts`] tallies timestamps per bucket, including the
+//! empty ones in between, so gaps show up.
+
+use jiff::{Timestamp, ToSpan, Unit, Zoned, ZonedRound, tz::TimeZone};
+
+use crate::{Result, cli::BucketUnit, user_input_error};
+
+/// The first instant of the bucket containing `z`.
+pub fn start(z: &Zoned, unit: BucketUnit) -> Result<Zoned> {
+    let floored = match unit {
+        BucketUnit::Minute => z.round(
+            ZonedRound::new()
+                .smallest(Unit::Minute)
+                .mode(jiff::RoundMode::Trunc),
+        ),
+        BucketUnit::Hour => z.round(
+            ZonedRound::new()
+                .smallest(Unit::Hour)
+                .mode(jiff::RoundMode::Trunc),
+        ),
+        BucketUnit::Week => {
+            let back = i64::from(z.weekday().to_monday_zero_offset());
+            z.start_of_day().and_then(|d| d.checked_sub(back.days()))
+        }
+        BucketUnit::Month => z.first_of_month().and_then(|d| d.start_of_day()),
+        _ => z.start_of_day(),
+    };
+    floored.map_err(|e| user_input_error!(InvalidDate, "{}", e))
+}
+
+/// The bucket after the one starting at `start`.
+fn next(start: &Zoned, unit: BucketUnit) -> Result<Zoned> {
+    let step = match unit {
+        BucketUnit::Minute => 1.minute(),
+        BucketUnit::Hour => 1.hour(),
+        BucketUnit::Week => 1.week(),
+        BucketUnit::Month => 1.month(),
+        _ => 1.day(),
+    };
+    start
+        .checked_add(step)
+        .map_err(|e| user_input_error!(InvalidDate, "{}", e))
+}
+
+/// How a bucket starting at `start` is printed.
+#[must_use]
+pub fn label(start: &Zoned, unit: BucketUnit) -> String {
+    let fmt = match unit {
+        BucketUnit::Minute => "%Y-%m-%d %H:%M",
+        BucketUnit::Hour => "%Y-%m-%d %H:00",
+        BucketUnit::Week => "%G-W%V",
+        BucketUnit::Month => "%Y-%m",
+        _ => "%Y-%m-%d",
+    };
+    start.strftime(fmt).to_string()
+}
+
+/// Count `stamps` per bucket, from the earliest bucket to the latest with
+/// empty buckets kept.
+pub fn counts(
+    stamps: &[Timestamp],
+    unit: BucketUnit,
+    tz: &TimeZone,
+) -> Result<Vec<(Zoned, usize)>> {
+    let mut starts = stamps
+        .iter()
+        .map(|ts| start(&ts.to_zoned(tz.clone()), unit))
+        .collect::<Result<Vec<_>>>()?;
+    starts.sort();
+    let Some(last) = starts.last().cloned() else {
+        return Ok(Vec::new());
+    };
+
+    let mut buckets: Vec<(Zoned, usize)> = Vec::new();
+    let mut cursor = starts[0].clone();
+    let mut pending = starts.iter().peekable();
+    while cursor <= last {
+        let mut n = 0;
+        while pending.next_if(|s| **s == cursor).is_some() {
+            n += 1;
+        }
+        let following = next(&cursor, unit)?;
+        buckets.push((cursor, n));
+        cursor = following;
+    }
+    Ok(buckets)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn ts(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn buckets_floor_to_their_unit() {
+        let z = ts("2025-01-15T10:37:12Z").to_zoned(TimeZone::UTC);
+        let at = |unit| label(&start(&z, unit).unwrap(), unit);
+        assert_eq!(at(BucketUnit::Minute), "2025-01-15 10:37");
+        assert_eq!(at(BucketUnit::Hour), "2025-01-15 10:00");
+        assert_eq!(at(BucketUnit::Day), "2025-01-15");
+        assert_eq!(at(BucketUnit::Week), "2025-W03");
+        assert_eq!(at(BucketUnit::Month), "2025-01");
+        assert_eq!(
+            start(&z, BucketUnit::Week).unwrap().date().to_string(),
+            "2025-01-13"
+        );
+    }
+
+    #[test]
+    fn counts_keep_empty_buckets() {
+        let stamps = [
+            ts("2025-01-15T12:05:00Z"),
+            ts("2025-01-15T10:10:00Z"),
+            ts("2025-01-15T10:50:00Z"),
+        ];
+        let counts: Vec<(String, usize)> = counts(&stamps, BucketUnit::Hour, &TimeZone::UTC)
+            .unwrap()
+            .iter()
+            .map(|(z, n)| (label(z, BucketUnit::Hour), *n))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                ("2025-01-15 10:00".to_string(), 2),
+                ("2025-01-15 11:00".to_string(), 0),
+                ("2025-01-15 12:00".to_string(), 1),
+            ]
+        );
+        assert!(
+            super::counts(&[], BucketUnit::Day, &TimeZone::UTC)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! On-disk cache for derived data.
+//!
+//! Entries live under `$XDG_CACHE_HOME/tardis` (or the OS cache directory)
+//! as one small file per key, so the cache stays as small as its fixed set
+//! of keys.  The cache is stamped with a fingerprint of
+//! the config file and the `td` version; when either changes, every entry is
+//! dropped the next time the cache is opened.  Callers treat the cache as
+//! best-effort: a failure to read or write it never fails a command.
+
+use std::{
+    env, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{Result, system_error};
+
+const APP_DIR: &str = "tardis";
+const STAMP_FILE: &str = "fingerprint";
+
+/// Handle to an opened, fingerprint-checked cache directory.
+#[must_use]
+#[derive(Debug)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+/// Resolve the cache directory: `$TARDIS_CACHE_DIR`, else
+/// `$XDG_CACHE_HOME/tardis`, else `tardis` in the OS cache directory.
+#[must_use = "cache_dir returns a PathBuf that should not be discarded"]
+pub fn cache_dir() -> Result<PathBuf> {
+    if let Some(dir) = env::var_os("TARDIS_CACHE_DIR").filter(|v| !v.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    let base_dir = env::var_os("XDG_CACHE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(dirs::cache_dir)
+        .ok_or_else(|| {
+            system_error!(
+                Config,
+                "Could not locate cache directory; set $TARDIS_CACHE_DIR or ensure the OS default exists."
+            )
+        })?;
+
+    Ok(base_dir.join(APP_DIR))
+}
+
+/// Fingerprint of `config` for this build of `td`.
+#[must_use]
+pub fn fingerprint(config: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    config.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Delete every cache entry, returning how many files were removed.
+pub fn clear() -> Result<usize> {
+    clear_dir(&cache_dir()?)
+}
+
+fn clear_dir(dir: &Path) -> Result<usize> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut removed = 0;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() {
+            fs::remove_file(&path)?;
+            if path.file_name().is_some_and(|n| n != STAMP_FILE) {
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+impl Cache {
+    /// Open the cache, invalidating it if `fingerprint` does not match the
+    /// one it was written with.
+    pub fn open(fingerprint: &str) -> Result<Self> {
+        Self::open_at(cache_dir()?, fingerprint)
+    }
+
+    fn open_at(dir: PathBuf, fingerprint: &str) -> Result<Self> {
+        let stamp = dir.join(STAMP_FILE);
+        if fs::read_to_string(&stamp).ok().as_deref() != Some(fingerprint) {
+            clear_dir(&dir)?;
+            fs::create_dir_all(&dir)?;
+            fs::write(&stamp, fingerprint)?;
+        }
+        Ok(Self { dir })
+    }
+
+    /// Read the entry stored under `key`, if any.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<String> {
+        fs::read_to_string(self.dir.join(key)).ok()
+    }
+
+    /// Store `value` under `key`.
+    pub fn put(&self, key: &str, value: &str) -> Result<()> {
+        fs::write(self.dir.join(key), value)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use assert_fs::TempDir;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn entries_survive_reopen_with_same_fingerprint() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("tardis");
+
+        let cache = Cache::open_at(dir.clone(), "abc").unwrap();
+        cache.put("presets", "ok").unwrap();
+
+        let cache = Cache::open_at(dir, "abc").unwrap();
+        assert_eq!(cache.get("presets").as_deref(), Some("ok"));
+    }
+
+    #[test]
+    fn changed_fingerprint_invalidates_entries() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("tardis");
+
+        Cache::open_at(dir.clone(), "abc")
+            .unwrap()
+            .put("presets", "ok")
+            .unwrap();
+
+        let cache = Cache::open_at(dir, "def").unwrap();
+        assert_eq!(cache.get("presets"), None);
+    }
+
+    #[test]
+    fn clear_counts_entries_but_not_stamp() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("tardis");
+
+        let cache = Cache::open_at(dir.clone(), "abc").unwrap();
+        cache.put("a", "1").unwrap();
+        cache.put("b", "2").unwrap();
+
+        assert_eq!(clear_dir(&dir).unwrap(), 2);
+        assert_eq!(clear_dir(&dir).unwrap(), 0);
+    }
+
+    #[test]
+    fn clear_missing_dir_is_noop() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(clear_dir(&tmp.path().join("absent")).unwrap(), 0);
+    }
+
+    #[test]
+    fn fingerprint_tracks_config_contents() {
+        assert_eq!(fingerprint("a"), fingerprint("a"));
+        assert_ne!(fingerprint("a"), fingerprint("b"));
+    }
+}
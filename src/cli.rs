@@ -9,7 +9,7 @@ use std::{
 use clap::Parser;
 use jiff::Timestamp;
 
-use crate::{Result, user_input_error};
+use crate::{Result, adjust::Adjustment, parser::ParseOptions, user_input_error};
 
 #[path = "cli_defs.rs"]
 mod cli_defs_mod;
@@ -24,10 +24,30 @@ pub struct Command {
     pub format: Option<String>,
     pub timezone: Option<String>,
     pub now: Option<Timestamp>,
+    pub params: Vec<(String, String)>,
+    pub defines: Vec<(String, String)>,
+    pub adjust: Vec<Adjustment>,
+    pub preset_group: Option<String>,
+    pub keep_offset: bool,
+    pub convert: bool,
+    pub scale: TimeScale,
+    pub allow_extreme: bool,
+    pub require: Option<InputKind>,
+    pub parse_options: ParseOptions,
     pub json: bool,
     pub no_newline: bool,
+    pub line_ending: LineEnding,
     pub verbose: bool,
     pub skip_errors: bool,
+    pub on_error: OnError,
+    pub strict: bool,
+    pub output: OutputMode,
+    pub exec: Option<String>,
+    pub dry_run: bool,
+    pub relative: bool,
+    pub if_past: Option<String>,
+    pub if_future: Option<String>,
+    pub count: Option<usize>,
 }
 
 impl Command {
@@ -40,10 +60,30 @@ impl Command {
             format: self.format.clone(),
             timezone: self.timezone.clone(),
             now: self.now,
+            params: self.params.clone(),
+            defines: self.defines.clone(),
+            adjust: self.adjust.clone(),
+            preset_group: self.preset_group.clone(),
+            keep_offset: self.keep_offset,
+            convert: self.convert,
+            scale: self.scale,
+            allow_extreme: self.allow_extreme,
+            require: self.require,
+            parse_options: self.parse_options,
             json: self.json,
             no_newline: self.no_newline,
+            line_ending: self.line_ending,
             verbose: self.verbose,
             skip_errors: self.skip_errors,
+            on_error: self.on_error,
+            strict: self.strict,
+            output: self.output,
+            exec: self.exec.clone(),
+            dry_run: self.dry_run,
+            relative: self.relative,
+            if_past: self.if_past.clone(),
+            if_future: self.if_future.clone(),
+            count: self.count,
         }
     }
 }
@@ -106,15 +146,63 @@ impl Command {
                 )
             })?;
 
+        let adjust = cli
+            .adjust
+            .iter()
+            .map(|a| a.parse())
+            .collect::<Result<_>>()?;
+        let default_time = match &cli.default_time {
+            Some(value) => crate::parser::parse_default_time(value).ok_or_else(|| {
+                user_input_error!(
+                    InvalidDateFormat,
+                    "invalid --default-time '{}'; use a time such as 09:00 or 9am, midnight or end-of-day",
+                    value
+                )
+            })?,
+            None => jiff::civil::Time::midnight(),
+        };
+        let format = if cli.rfc_email {
+            Some(crate::core::RFC_EMAIL_FORMAT.to_owned())
+        } else {
+            cli.iso_8601.map(|p| p.format().to_owned()).or(cli.format)
+        };
+
         Ok(Command {
             input,
-            format: cli.format,
+            format,
             timezone: cli.timezone,
             now,
+            params: cli.params,
+            defines: cli.defines,
+            adjust,
+            preset_group: cli.preset_group,
+            keep_offset: cli.keep_offset,
+            convert: cli.convert,
+            scale: cli.scale,
+            allow_extreme: cli.allow_extreme,
+            require: cli.require,
+            parse_options: ParseOptions {
+                month_overflow: cli.month_overflow,
+                default_time,
+            },
             json: cli.json,
             no_newline: cli.no_newline,
+            line_ending: cli.line_ending,
             verbose: cli.verbose,
             skip_errors: cli.skip_errors,
+            on_error: match cli.on_error {
+                Some(mode) => mode,
+                None if cli.skip_errors => OnError::Skip,
+                None => OnError::Abort,
+            },
+            strict: cli.strict || cli.skip_errors,
+            output: cli.output,
+            exec: cli.exec,
+            dry_run: cli.dry_run,
+            relative: cli.relative,
+            if_past: cli.if_past,
+            if_future: cli.if_future,
+            count: cli.count,
         })
     }
 }
@@ -186,6 +274,18 @@ mod tests {
         assert_eq!(cmd.input, "tomorrow");
     }
 
+    #[test]
+    fn rfc_email_and_iso_flags_set_the_format() {
+        let cmd = parse_ok(&["td", "now", "-R"]);
+        assert_eq!(cmd.format.as_deref(), Some("%a, %d %b %Y %H:%M:%S %z"));
+        let cmd = parse_ok(&["td", "now", "-I"]);
+        assert_eq!(cmd.format.as_deref(), Some("%Y-%m-%d"));
+        let cmd = parse_ok(&["td", "-Iminutes", "now"]);
+        assert_eq!(cmd.format.as_deref(), Some("%Y-%m-%dT%H:%M%:z"));
+        let cmd = parse_ok(&["td", "now", "--iso-8601=ns"]);
+        assert_eq!(cmd.format.as_deref(), Some("%Y-%m-%dT%H:%M:%S,%N%:z"));
+    }
+
     #[test]
     fn json_flag_parsed() {
         let cmd = parse_ok(&["td", "now", "--json"]);
@@ -202,6 +302,36 @@ mod tests {
     fn skip_errors_flag_parsed() {
         let cmd = parse_ok(&["td", "now", "--skip-errors"]);
         assert!(cmd.skip_errors);
+        assert_eq!(cmd.on_error, OnError::Skip);
+        assert!(cmd.strict);
+    }
+
+    #[test]
+    fn on_error_defaults_to_abort_and_is_lenient() {
+        let cmd = parse_ok(&["td", "now"]);
+        assert_eq!(cmd.on_error, OnError::Abort);
+        let cmd = parse_ok(&["td", "now", "--on-error", "record"]);
+        assert_eq!(cmd.on_error, OnError::Record);
+        assert!(!cmd.strict);
+    }
+
+    #[test]
+    fn output_mode_defaults_to_text() {
+        let cmd = parse_ok(&["td", "now"]);
+        assert_eq!(cmd.output, OutputMode::Text);
+    }
+
+    #[test]
+    fn output_mode_git_env_parsed() {
+        let cmd = parse_ok(&["td", "now", "--output", "git-env"]);
+        assert_eq!(cmd.output, OutputMode::GitEnv);
+    }
+
+    #[test]
+    fn exec_template_parsed() {
+        let cmd = parse_ok(&["td", "now", "--exec", "echo {iso}", "--dry-run"]);
+        assert_eq!(cmd.exec.as_deref(), Some("echo {iso}"));
+        assert!(cmd.dry_run);
     }
 
     #[test]
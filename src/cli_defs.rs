@@ -18,11 +18,13 @@ pub const AFTER_LONG_HELP: &str = cstr!(
   <bold><blue>TARDIS_FORMAT</blue></bold>     Default output format or preset name.
   <bold><blue>TARDIS_TIMEZONE</blue></bold>   Default IANA time zone (e.g. America/Sao_Paulo).
   <bold><blue>TARDIS_NOW</blue></bold>        Override "now" (RFC 3339). Same as --now.
+  <bold><blue>TARDIS_CONFIG_DIR</blue></bold> Directory holding config.toml, instead of the default.
 
 <green><bold>Configuration File:</bold></green>
+  <blue><bold>$TARDIS_CONFIG_DIR</bold>/config.toml</blue>, else
   <blue><bold>$XDG_CONFIG_HOME</bold>/tardis/config.toml</blue>
 
-  if XDG_CONFIG_HOME is unset:
+  if neither is set:
         • Linux:   ~/.config/tardis/config.toml
         • macOS:   ~/Library/Application Support/tardis/config.toml
         • Windows: %APPDATA%\tardis\config.toml
@@ -50,6 +52,90 @@ Supports arithmetic (<bold>"tomorrow + 3 hours"</bold>), periods (<bold>"this we
 "#
 );
 
+const CLASSIC_EXIT_CODES_HELP: &str = cstr!(
+    r#"
+<bold>Exit with 1 on every failure</bold> instead of the sysexits codes: 64 for
+bad input, 74 for I/O errors and 78 for configuration errors.  Usage
+errors exit with 1 as well, instead of 2.
+
+For CI systems that treat some codes as infrastructure failures.  The
+<bold>[exit_codes]</bold> table of the config file sets each code instead; this flag
+wins over it, and also covers a config file that cannot be read.
+"#
+);
+
+const ADJUST_HELP: &str = cstr!(
+    r#"
+<bold>Adjust the result like BSD date -v.</bold>  Repeatable; applied in order.
+
+  <bold>+N</bold>UNIT, <bold>-N</bold>UNIT   shift by N years (y), months (m), weeks (w), days (d),
+                 hours (H), minutes (M) or seconds (S)
+  <bold>N</bold>UNIT         set that field; for w, 0 (Sunday) to 6
+  <bold>+</bold>NAME, <bold>-</bold>NAME   move to the next or previous weekday or month of that
+                 name, staying put if already on it
+  NAME          set the weekday within the week, or the month
+
+<bold>-v</bold> with the adjustment attached is short for this option, so BSD
+chains work unchanged, while <bold>-v</bold> alone is still --verbose:
+
+  td now -v+1m -v1d -v-1d -f %F     # last day of this month
+"#
+);
+
+const ISO_8601_HELP: &str = cstr!(
+    r#"
+<bold>Print ISO 8601 to the given precision.</bold>
+
+  <bold>date</bold>      2025-01-15 (the default)
+  <bold>hours</bold>     2025-01-15T10+00:00
+  <bold>minutes</bold>   2025-01-15T10:30+00:00
+  <bold>seconds</bold>   2025-01-15T10:30:00+00:00
+  <bold>ns</bold>        2025-01-15T10:30:00,000000000+00:00
+
+Write the precision right after the flag, as in <bold>-Iseconds</bold> or
+<bold>--iso-8601=minutes</bold>, like GNU date.  A bare <bold>-I</bold> goes after the
+expression, since the word that follows it is read as the precision.
+"#
+);
+
+const COMPAT_HELP: &str = cstr!(
+    r#"
+<bold>Read the rest of the command line like another tool would.</bold>
+
+<bold>date</bold> accepts the common GNU date options, so existing scripts can switch
+with an alias such as <bold>alias date='td --compat date'</bold>:
+
+  -d, --date STRING        the input expression
+  -u, --utc, --universal   print in UTC
+  +FORMAT                  the output format
+  -R, --rfc-email          RFC 5322 date and time
+  -I[SPEC], --iso-8601[=SPEC]
+                           ISO 8601; SPEC is date (default), hours, minutes,
+                           seconds or ns
+
+Without a format the result is printed like <bold>date</bold> does, as in
+<bold>Wed Jan 15 10:30:00 UTC 2025</bold>.  Other options, such as --now or --json,
+keep their td meaning.
+
+  td --compat date -u -d tomorrow +%F
+"#
+);
+
+const COUNT_HELP: &str = cstr!(
+    r#"
+<bold>Print the next N occurrences of a named recurrence.</bold>
+
+When the input is a name from the <bold>[recurrences]</bold> table of the config
+file, td prints its next occurrence, or the next N with this option, one
+per line:
+
+  [recurrences]
+  sprint-start = "every other monday starting 2025-01-06"
+
+  td sprint-start --count 3 -f %F
+"#
+);
+
 const FORMAT_HELP: &str = cstr!(
     r#"
 <bold>Output format.</bold>
@@ -57,7 +143,13 @@ const FORMAT_HELP: &str = cstr!(
 Accepts strftime patterns (e.g. <bold>"%Y‑%m‑%d"</bold>) or a named
 preset defined in the config file.
 
-Special values: <bold>"epoch"</bold> or <bold>"unix"</bold> output a Unix timestamp (seconds).
+Special values: <bold>"epoch"</bold> or <bold>"unix"</bold> output a Unix timestamp (seconds),
+<bold>"unix_ns"</bold> one in nanoseconds.
+
+Zone tokens: <bold>{tzid}</bold> (IANA ID, e.g. America/Sao_Paulo), <bold>{tzabbr}</bold>
+(abbreviation, as <bold>%Z</bold>) and <bold>{tzoffset}</bold> (UTC offset in seconds).
+<bold>{beats}</bold> is Swatch Internet Time; the <bold>"beats"</bold> format prints it as @347.
+<bold>{kind}</bold> is date, time or datetime, as for <bold>--require</bold>.
 
 Reference:
 <underline>https://github.com/hvpaiva/tardis-cli/blob/main/docs/FORMAT-SPECIFIERS.md</underline>
@@ -73,6 +165,7 @@ pub const TIMEZONE_HELP: &str = cstr!(
 <bold>Time‑zone to apply</bold> (IANA/Olson ID). If not provided, uses system local time.
 
 Examples: <italic>"UTC", "America/Sao_Paulo", "Europe/London".</italic>
+City names such as <italic>"Tokyo"</italic> or <italic>"Portland, US"</italic> also work.
 
 Reference:
 <underline>https://www.iana.org/time-zones</underline>
@@ -89,6 +182,160 @@ Override "now". Format <bold>RFC 3339</bold>, e.g. <italic>2025‑06‑24T09:00:
 "#
 );
 
+const OUTPUT_HELP: &str = cstr!(
+    r#"
+<bold>Output layout.</bold>
+
+  <bold>text</bold>     The formatted datetime (default).
+  <bold>git-env</bold>  <bold>GIT_AUTHOR_DATE=… GIT_COMMITTER_DATE=…</bold> in git's internal
+           <bold>@<<epoch>><<offset>></bold> form, ready for <bold>env $(td … -o git-env) git commit</bold>.
+           The <bold>--format</bold> flag is ignored in this mode.
+"#
+);
+
+const DENY_WARNINGS_HELP: &str = cstr!(
+    r#"
+<bold>Treat warnings as errors.</bold>
+
+Warnings such as a preset shadowing a built-in format or an ambiguous
+time-zone abbreviation abort the command with exit code <bold>64</bold>
+before any output is written.
+"#
+);
+
+const PARAM_HELP: &str = cstr!(
+    r#"
+<bold>Fill a preset placeholder.</bold> Repeatable.
+
+A preset such as <bold>meeting = "%Y-%m-%d %H:%M ({label})"</bold> has a <bold>{label}</bold>
+placeholder; <bold>-f meeting --param label=standup</bold> fills it. Every placeholder
+must be given, and a name the preset does not use is an error.
+"#
+);
+
+const DEFINE_HELP: &str = cstr!(
+    r#"
+<bold>Declare or override a preset for this run only.</bold> Repeatable.
+
+<bold>--define day=%F -f day</bold> works whatever the config file holds. A name that
+is already in <bold>[formats]</bold> is replaced, table defaults included, and other
+presets that reference it see the new format. Grouped names such as
+<bold>work.iso</bold> are accepted. An invalid format is an error.
+"#
+);
+
+const NO_PRESETS_HELP: &str = cstr!(
+    r#"
+<bold>Ignore every preset in the config file.</bold>
+
+<bold>-f</bold> is then read as a format string or a built-in name such as <bold>iso</bold>,
+whatever <bold>[formats]</bold> and <bold>preset_group</bold> hold on this machine, and their
+warnings are not printed. Presets given with <bold>--define</bold> still apply, so a
+shared script can rely on exactly the presets it declares.
+"#
+);
+
+const PRESET_GROUP_HELP: &str = cstr!(
+    r#"
+<bold>Preset group to search first.</bold>
+
+Presets in a <bold>[formats.work]</bold> table are named <bold>work.iso</bold>, <bold>work.short</bold>, ...
+With <bold>--preset-group work</bold>, <bold>-f iso</bold> picks <bold>work.iso</bold> when it exists and
+falls back to the top-level <bold>iso</bold> otherwise. Overrides <bold>preset_group</bold> in
+the config file.
+"#
+);
+
+const PRINT_MAN_DIR_HELP: &str = cstr!(
+    r#"
+<bold>Install the bundled man pages and print where they are.</bold>
+
+Writes the pages for <bold>td</bold> and every subcommand under
+<bold>$XDG_DATA_HOME/tardis/man</bold> (pages already up to date are left alone)
+and prints that directory, ready for <bold>MANPATH</bold>:
+
+  MANPATH="$(td --print-man-dir):" man td-diff
+
+Packagers can copy <bold>man1/</bold> from it into the system man directory.
+"#
+);
+
+const TRACE_FORMAT_HELP: &str = cstr!(
+    r#"
+<bold>How --verbose diagnostics are written.</bold>
+
+<bold>text</bold> (default) prints one tagged line per step.  <bold>json</bold> prints one JSON
+object per line instead, with <bold>timestamp</bold>, <bold>target</bold> (config, parse,
+resolve or timing) and <bold>message</bold> fields, for log collectors in CI.
+
+Has no effect without <bold>-v</bold>.  Applies to subcommands as well.
+"#
+);
+
+const PLAIN_HELP: &str = cstr!(
+    r#"
+<bold>Plain output</bold> for screen readers, dumb terminals and scripts.
+
+No ANSI colors or cursor movement, even on a terminal. Tables and
+label/value listings separate their columns with a single tab instead of
+padding with spaces, and <bold>td hist</bold> draws its bars with <bold>#</bold>. JSON
+output is compact. Applies to subcommands as well.
+"#
+);
+
+const ASCII_HELP: &str = cstr!(
+    r#"
+<bold>Draw with ASCII only</bold>, for terminals or fonts without Unicode.
+
+Decorations such as the bars of <bold>td hist</bold> use <bold>#</bold> instead of block
+characters. Turned on by itself when the locale (<bold>LC_ALL</bold>, <bold>LC_CTYPE</bold> or
+<bold>LANG</bold>) names a character set other than UTF-8, such as <bold>C</bold> or
+<bold>en_US.ISO-8859-1</bold>. Implied by <bold>--plain</bold>. Applies to subcommands as well.
+"#
+);
+
+const BUFFERING_HELP: &str = cstr!(
+    r#"
+<bold>Choose when output is flushed.</bold>
+
+By default each line is written as soon as it is ready on a terminal and in
+<bold>td stamp</bold>, <bold>td delta</bold> and <bold>td rewrite</bold>, which pass stdin through as it
+arrives. Elsewhere output is written in blocks, which is much faster for a
+large batch piped through <bold>td</bold>. <bold>--line-buffered</bold> flushes every line, for a
+consumer that reads as results come; <bold>--block-buffered</bold> buffers even the
+streaming commands, for throughput.
+
+  cat app.log | td rewrite --block-buffered > app-iso.log
+"#
+);
+
+const LINE_ENDING_HELP: &str = cstr!(
+    r#"
+<bold>Line terminator for results.</bold>
+
+  <bold>auto</bold>  CRLF on Windows, LF elsewhere (default)
+  <bold>lf</bold>    \n, for Unix tools
+  <bold>crlf</bold>  \r\n, for Windows batch scripts and legacy tools
+
+Applies to every result line, in batch mode too, and to the blank line
+<bold>--skip-errors</bold> writes for a failed input. <bold>-n</bold> still drops the final one.
+"#
+);
+
+const COLUMNS_HELP: &str = cstr!(
+    r#"
+<bold>Choose table columns and their width.</bold>
+
+Commands that print tables (<bold>td tz list</bold>, <bold>td worldclock</bold>, <bold>td meet</bold>,
+<bold>td stats</bold>, <bold>td hist</bold>, <bold>td group-by</bold>, <bold>td sum --group-by</bold>) name their
+columns. <bold>--columns</bold> lists the ones to show, in order; an unknown name is
+an error that lists the choices. <bold>--max-width</bold> narrows the widest columns,
+cutting their cells short with <bold>…</bold>, until each line fits.
+
+  td tz list --columns zone,offset,abbr --max-width 40
+"#
+);
+
 const SKIP_ERRORS_HELP: &str = cstr!(
     r#"
 <bold>Skip unparseable lines in batch mode</bold> instead of aborting.
@@ -100,6 +347,166 @@ Exit code is <bold>1</bold> if any line failed, <bold>0</bold> if all succeeded.
 "#
 );
 
+const ON_ERROR_HELP: &str = cstr!(
+    r#"
+<bold>Choose what batch mode does with a line that fails.</bold>
+
+  <bold>abort</bold>   Stop at the first failure (default).
+  <bold>skip</bold>    Print the error to stderr and an empty line in its place.
+  <bold>record</bold>  Write a JSON record in its place, for log pipelines:
+          {"error":"...","input":"...","line":2}
+
+Either way the run goes on, a count of failed lines is printed to stderr at
+the end, and the exit code is <bold>0</bold>. <bold>--strict</bold> exits with <bold>1</bold> when any line
+failed. <bold>--skip-errors</bold> is the same as <bold>--on-error skip --strict</bold>.
+"#
+);
+
+const MONTH_OVERFLOW_HELP: &str = cstr!(
+    r#"
+<bold>Choose what adding months or years does past the end of a month.</bold>
+
+Jan 31 + 1 month and Feb 29 + 1 year land on days that do not exist:
+
+  <bold>clamp</bold>     Use the month's last day: Feb 28, Feb 28 (default).
+  <bold>overflow</bold>  Carry the extra days over: Mar 3, Mar 1.
+  <bold>error</bold>     Fail, naming the date that does not exist.
+
+Applies to expressions such as <bold>"2025-01-31 + 1 month"</bold> and
+<bold>"in 1 year"</bold>, to <bold>--adjust +1m</bold>, and to every subcommand.
+"#
+);
+
+const RELATIVE_HELP: &str = cstr!(
+    r#"
+<bold>Print how far the result is from now</bold> instead of formatting it:
+"in 1 week 6 days", "3 hours ago", "yesterday", "now".
+
+The number of units and the thresholds for "now", "yesterday" and
+"tomorrow" come from the <bold>[humanize]</bold> config table, shared with
+<bold>td humanize</bold>. The description parses back as a td expression.
+"#
+);
+
+const IF_PAST_HELP: &str = cstr!(
+    r#"
+<bold>Print a text chosen by whether the result has passed.</bold>
+
+<bold>--if-past</bold> applies when the result is before now, <bold>--if-future</bold> otherwise.
+When only one is given, nothing is printed in the other case. The text may
+use <bold>{iso}</bold>, <bold>{epoch}</bold> and <bold>{fmt}</bold> as in <bold>--exec</bold>, and <bold>{relative}</bold> for
+the distance from now, e.g. "2 days":
+
+  td "deadline" --if-past "OVERDUE" --if-future "{relative} left"
+"#
+);
+
+const KEEP_OFFSET_HELP: &str = cstr!(
+    r#"
+<bold>Keep the offset written in the input</bold> instead of converting to <bold>-t</bold>.
+
+An input may carry a UTC offset: an RFC 3339 timestamp such as
+<bold>2025-06-24T15:00:00-03:00</bold>, or an expression ending in one, such as
+<bold>"today 3pm UTC+2"</bold> or <bold>"tomorrow 9:00 -03:00"</bold>. It is always read in
+that offset; with this flag the result is also printed in it. Inputs
+without an offset are unaffected.
+"#
+);
+
+const CONVERT_HELP: &str = cstr!(
+    r#"
+<bold>Convert machine-readable input only</bold>, skipping the expression parser.
+
+The input must be an RFC 3339 timestamp (<bold>2025-06-24T15:00:00-03:00</bold>), an
+RFC 2822 date (<bold>"Tue, 24 Jun 2025 15:00:00 -0300"</bold>) or a Unix timestamp
+(<bold>1750788000</bold>, <bold>@1750788000123ms</bold>; bare numbers use the same smart
+precision as <bold>@</bold>). Anything else is an error, so a typo can never be read
+as a relative expression. Combine with <bold>-t</bold> and <bold>-f</bold> to reformat feeds.
+"#
+);
+
+const SCALE_HELP: &str = cstr!(
+    r#"
+<bold>Print the result on another time scale.</bold>
+
+  <bold>utc</bold>  Coordinated Universal Time (default)
+  <bold>tai</bold>  International Atomic Time: UTC plus the leap seconds since 1972 (37 today)
+  <bold>gps</bold>  GPS time: TAI minus 19 seconds (UTC plus 18 today)
+
+The offset comes from a bundled leap-second table. Dates past the end of the
+table print a warning, since a newer leap second may be missing.
+"#
+);
+
+const ALLOW_EXTREME_HELP: &str = cstr!(
+    r#"
+<bold>Read epoch values too large for 64 bits.</bold>
+
+Dates must fall between the years <bold>-9999</bold> and <bold>9999</bold>; anything outside is an
+error naming that range. Epoch values normally have to fit in 64 bits, which
+caps nanosecond timestamps at 2262. With this flag they are read as 128-bit
+numbers, so <bold>@99999999999999999999</bold> (nanoseconds) resolves to the year 5138.
+"#
+);
+
+const REQUIRE_HELP: &str = cstr!(
+    r#"
+<bold>Fail unless the input spells the given kind of date:</bold>
+
+  <bold>date</bold>      a day without a time: "tomorrow", "next friday", "2025-03-01"
+  <bold>time</bold>      a time of day without a day
+  <bold>datetime</bold>  both, or an exact instant: "tomorrow 9am", "now", "in 2 hours"
+
+Periods and boundaries such as "next month" and "eod" are dates; epochs,
+RFC 3339 timestamps and offsets from now are datetimes. Any other kind exits
+with 64, so a script can refuse "friday" where it needs "friday 9am":
+
+  td "$WHEN" --require datetime -f touch --exec 'at -t {fmt}'
+
+The kind is also the <bold>{kind}</bold> token of <bold>-f</bold> and <bold>--exec</bold> and the "kind" field of <bold>--json</bold>.
+"#
+);
+
+const DEFAULT_TIME_HELP: &str = cstr!(
+    r#"
+<bold>Resolve dates given without a time of day to TIME</bold> instead of midnight.
+
+TIME is written as after a date (<bold>09:00</bold>, <bold>9am</bold>, <bold>18h</bold>), or is <bold>midnight</bold> (the
+default) or <bold>end-of-day</bold>, the last instant of the day as for <bold>eod</bold>:
+
+  td "next friday" --default-time 9am        # 2025-01-17T09:00:00
+  td "2025-03-01" --default-time end-of-day  # 2025-03-01T23:59:59
+
+Applies to "today", "next friday", "2025-03-01" and the like, and to
+arithmetic on them; periods and boundaries such as "next month" and "sod"
+keep their own times. Overrides <bold>default_time</bold> in the config file.
+"#
+);
+
+const EXEC_HELP: &str = cstr!(
+    r#"
+<bold>Run a command with the resolved date</bold> instead of printing it.
+
+The template is split into words like a shell command line, then these
+tokens are replaced inside each word:
+
+  <bold>{iso}</bold>    RFC 3339 datetime, e.g. 2025-01-17T00:00:00+00:00
+  <bold>{epoch}</bold>  Unix timestamp in seconds
+  <bold>{fmt}</bold>    the output as it would have been printed (honours <bold>-f</bold>)
+  <bold>{tzid}</bold>   IANA time zone ID, e.g. America/Sao_Paulo
+  <bold>{tzabbr}</bold> time zone abbreviation, as <bold>%Z</bold>
+  <bold>{tzoffset}</bold> UTC offset in seconds, e.g. -10800
+  <bold>{beats}</bold>  Swatch Internet Time, e.g. 347
+  <bold>{kind}</bold>   what the input spelled: date, time or datetime
+
+Write <bold>{{</bold> and <bold>}}</bold> for literal braces. The command runs directly, not
+through a shell, so a value with spaces stays one argument. td exits with
+the command's exit code when it fails.
+
+  td "next friday" --exec 'gh milestone create --due {iso}'
+"#
+);
+
 /// Long about text displayed in `--help` output.
 pub const ABOUT_HELP: &str = cstr!(
     r#"
@@ -119,11 +526,10 @@ like <bold>"next Friday at 2:00"</bold> or <bold>"in 3 days"</bold> into machine
     about,
     long_about = ABOUT_HELP,
     version,
-    color = clap::ColorChoice::Auto,
     after_long_help = AFTER_LONG_HELP,
     after_help = cstr!("For more information, visit <underline>https://github.com/hvpaiva/tardis-cli</underline>"),
-    styles = STYLES,
 )]
+#[cfg_attr(feature = "color", command(color = clap::ColorChoice::Auto, styles = STYLES))]
 pub struct Cli {
     #[arg(help = INPUT_HELP)]
     pub input: Option<String>,
@@ -132,6 +538,23 @@ pub struct Cli {
     #[arg(value_name = "FMT", short, long, long_help = FORMAT_HELP)]
     pub format: Option<String>,
 
+    /// Print RFC 5322 dates, as in "Wed, 15 Jan 2025 10:30:00 +0000".
+    #[arg(short = 'R', long, conflicts_with_all = ["format", "iso_8601"])]
+    pub rfc_email: bool,
+
+    /// Print ISO 8601 to the given precision (date, hours, minutes, seconds, ns).
+    #[arg(
+        value_name = "SPEC",
+        short = 'I',
+        long = "iso-8601",
+        value_enum,
+        num_args = 0..=1,
+        default_missing_value = "date",
+        conflicts_with = "format",
+        long_help = ISO_8601_HELP
+    )]
+    pub iso_8601: Option<IsoPrecision>,
+
     /// Time-zone to apply (IANA/Olson ID). If not provided, uses system local time.
     #[arg(value_name = "TZ", short, long, long_help = TIMEZONE_HELP)]
     pub timezone: Option<String>,
@@ -140,6 +563,54 @@ pub struct Cli {
     #[arg(value_name = "DATETIME", long, long_help = NOW_HELP)]
     pub now: Option<String>,
 
+    /// Fill a preset placeholder (NAME=VALUE). Repeatable.
+    #[arg(value_name = "NAME=VALUE", long = "param", value_parser = parse_param, long_help = PARAM_HELP)]
+    pub params: Vec<(String, String)>,
+
+    /// Declare or override a preset for this run (NAME=FMT). Repeatable.
+    #[arg(value_name = "NAME=FMT", long = "define", value_parser = parse_param, long_help = DEFINE_HELP)]
+    pub defines: Vec<(String, String)>,
+
+    /// Ignore the presets in the config file; -f is a format string or built-in.
+    #[arg(long, conflicts_with = "preset_group", long_help = NO_PRESETS_HELP)]
+    pub no_presets: bool,
+
+    /// Preset group to search first for format names.
+    #[arg(value_name = "GROUP", long, long_help = PRESET_GROUP_HELP)]
+    pub preset_group: Option<String>,
+
+    /// Adjust the result like BSD date -v (e.g. +3d, -1H, +fri). Repeatable.
+    #[arg(
+        value_name = "ADJ",
+        long,
+        allow_hyphen_values = true,
+        long_help = ADJUST_HELP
+    )]
+    pub adjust: Vec<String>,
+
+    /// Print the result in the UTC offset written in the input, if any.
+    #[arg(long, long_help = KEEP_OFFSET_HELP)]
+    pub keep_offset: bool,
+
+    #[arg(long, long_help = CONVERT_HELP)]
+    pub convert: bool,
+
+    /// Read epoch values too large for 64 bits.
+    #[arg(long, long_help = ALLOW_EXTREME_HELP)]
+    pub allow_extreme: bool,
+
+    /// Time scale of the output (utc, tai, gps).
+    #[arg(long, value_enum, default_value_t, long_help = SCALE_HELP)]
+    pub scale: TimeScale,
+
+    /// Fail unless the input is of this kind (date, time, datetime).
+    #[arg(value_name = "KIND", long, value_enum, long_help = REQUIRE_HELP)]
+    pub require: Option<InputKind>,
+
+    /// Time of day for dates given without one (e.g. 09:00, end-of-day).
+    #[arg(value_name = "TIME", long, long_help = DEFAULT_TIME_HELP)]
+    pub default_time: Option<String>,
+
     /// Output as JSON instead of plain text.
     #[arg(short, long)]
     pub json: bool,
@@ -148,14 +619,164 @@ pub struct Cli {
     #[arg(short = 'n', long = "no-newline")]
     pub no_newline: bool,
 
+    /// Line terminator for results (lf, crlf, auto).
+    #[arg(
+        value_name = "EOL",
+        long,
+        value_enum,
+        default_value_t,
+        long_help = LINE_ENDING_HELP
+    )]
+    pub line_ending: LineEnding,
+
     /// Print verbose diagnostics to stderr (config, parse steps, timing).
     #[arg(short = 'v', long)]
     pub verbose: bool,
 
     /// Skip unparseable lines in batch mode.
-    #[arg(long, long_help = SKIP_ERRORS_HELP)]
+    #[arg(long, conflicts_with = "on_error", long_help = SKIP_ERRORS_HELP)]
     pub skip_errors: bool,
 
+    /// What batch mode does with a line that fails (abort, skip, record).
+    #[arg(value_name = "MODE", long, value_enum, long_help = ON_ERROR_HELP)]
+    pub on_error: Option<OnError>,
+
+    /// Exit with 1 when any batch line failed.
+    #[arg(long, long_help = ON_ERROR_HELP)]
+    pub strict: bool,
+
+    /// When adding months or years passes the end of a month (clamp, overflow, error).
+    #[arg(
+        value_name = "POLICY",
+        long,
+        global = true,
+        value_enum,
+        default_value_t,
+        long_help = MONTH_OVERFLOW_HELP
+    )]
+    pub month_overflow: MonthOverflow,
+
+    /// Output layout (text, git-env).
+    #[arg(
+        value_name = "MODE",
+        short,
+        long,
+        value_enum,
+        default_value = "text",
+        conflicts_with = "json",
+        long_help = OUTPUT_HELP
+    )]
+    pub output: OutputMode,
+
+    /// Run a command template with {iso}, {epoch} and {fmt} filled in.
+    #[arg(
+        value_name = "TEMPLATE",
+        long,
+        conflicts_with_all = ["json", "output"],
+        long_help = EXEC_HELP
+    )]
+    pub exec: Option<String>,
+
+    /// With --exec, print the command instead of running it.
+    #[arg(long, requires = "exec")]
+    pub dry_run: bool,
+
+    /// Print the result relative to now, e.g. "in 3 days" or "yesterday".
+    #[arg(long, conflicts_with_all = ["json", "output", "exec"], long_help = RELATIVE_HELP)]
+    pub relative: bool,
+
+    /// Print TEXT instead when the result is in the past.
+    #[arg(
+        value_name = "TEXT",
+        long,
+        conflicts_with_all = ["json", "output", "exec", "relative"],
+        long_help = IF_PAST_HELP
+    )]
+    pub if_past: Option<String>,
+
+    /// Print TEXT instead when the result is now or in the future.
+    #[arg(
+        value_name = "TEXT",
+        long,
+        conflicts_with_all = ["json", "output", "exec", "relative"],
+        long_help = IF_PAST_HELP
+    )]
+    pub if_future: Option<String>,
+
+    /// Print the next N occurrences when the input names a recurrence.
+    #[arg(value_name = "N", long, long_help = COUNT_HELP)]
+    pub count: Option<usize>,
+
+    /// Read the command line like another tool (date).
+    #[arg(value_name = "TOOL", long, value_enum, long_help = COMPAT_HELP)]
+    pub compat: Option<Compat>,
+
+    /// Suppress warnings on stderr.
+    #[arg(short, long, global = true, conflicts_with = "deny_warnings")]
+    pub quiet: bool,
+
+    /// Treat warnings as errors.
+    #[arg(long, global = true, long_help = DENY_WARNINGS_HELP)]
+    pub deny_warnings: bool,
+
+    /// Exit with 1 on every failure instead of the sysexits codes.
+    #[arg(long, global = true, long_help = CLASSIC_EXIT_CODES_HELP)]
+    pub classic_exit_codes: bool,
+
+    /// Format of --verbose diagnostics (text, json).
+    #[arg(
+        value_name = "FORMAT",
+        long,
+        global = true,
+        value_enum,
+        default_value_t,
+        long_help = TRACE_FORMAT_HELP
+    )]
+    pub trace_format: TraceFormat,
+
+    /// Plain output: no colors, tab-separated columns, ASCII only.
+    #[arg(long, global = true, long_help = PLAIN_HELP)]
+    pub plain: bool,
+
+    /// Draw with ASCII only, for terminals or fonts without Unicode.
+    #[arg(long, global = true, long_help = ASCII_HELP)]
+    pub ascii: bool,
+
+    /// Flush output after every line, even in a pipeline.
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "block_buffered",
+        long_help = BUFFERING_HELP
+    )]
+    pub line_buffered: bool,
+
+    /// Write output in blocks, even in streaming commands.
+    #[arg(long, global = true, long_help = BUFFERING_HELP)]
+    pub block_buffered: bool,
+
+    /// Never page long listings through $PAGER.
+    #[arg(long, global = true)]
+    pub no_pager: bool,
+
+    /// Table columns to show, by name and in order, e.g. zone,offset.
+    #[arg(
+        value_name = "NAMES",
+        long,
+        global = true,
+        value_delimiter = ',',
+        long_help = COLUMNS_HELP
+    )]
+    pub columns: Option<Vec<String>>,
+
+    /// Cut table cells so each line fits in N characters.
+    #[arg(value_name = "N", long, global = true, long_help = COLUMNS_HELP)]
+    pub max_width: Option<usize>,
+
+    /// Install the bundled man pages and print their MANPATH directory.
+    #[arg(long, exclusive = true, long_help = PRINT_MAN_DIR_HELP)]
+    pub print_man_dir: bool,
+
     #[command(subcommand)]
     pub subcmd: Option<SubCmd>,
 }
@@ -169,11 +790,18 @@ pub enum SubCmd {
         #[command(subcommand)]
         action: ConfigAction,
     },
-    /// Generate shell completions.
-    Completions {
-        /// Shell to generate completions for.
-        shell: ShellType,
+    /// Manage the on-disk cache.
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Manage the history of the interactive prompt.
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
     },
+    /// Generate or install shell completions.
+    Completions(CompletionsArgs),
     /// Compute the difference between two dates.
     Diff(DiffArgs),
     /// Convert a date between formats.
@@ -184,6 +812,221 @@ pub enum SubCmd {
     Info(InfoArgs),
     /// Expand a date expression into a start/end range.
     Range(RangeArgs),
+    /// Divide a range into equal slots and print each one's start and end.
+    Split(SplitArgs),
+    /// Exit 0 when two ranges overlap, optionally printing where.
+    Overlaps(OverlapsArgs),
+    /// Print the working days of the week containing a date.
+    Workweek(WorkweekArgs),
+    /// Print the start and end of the calendar or fiscal quarter holding a date.
+    Quarter(QuarterArgs),
+    /// Print the next time a month and day come around, for anniversaries.
+    NextOccurrence(NextOccurrenceArgs),
+    /// Exit 0 when a date is a weekend, weekday, workday, holiday or given day.
+    Is(IsArgs),
+    /// Query the holidays of the [holidays] table in the config file.
+    Holiday {
+        #[command(subcommand)]
+        action: HolidayAction,
+    },
+    /// Exit 0 when an instant falls within the working hours of the config file.
+    IsOpen(IsOpenArgs),
+    /// Print when working hours next begin, or the instant itself when open.
+    NextOpen(NextOpenArgs),
+    /// Explain a format string and warn about likely mistakes.
+    LintFormat(LintFormatArgs),
+    /// Print one line for a shell prompt, never failing.
+    Prompt(PromptArgs),
+    /// Find windows when everyone is within working hours.
+    Meet(MeetArgs),
+    /// Show one time across a list of favorite zones.
+    Worldclock(WorldclockArgs),
+    /// Convert between Unix timestamps and dates, in whichever direction fits.
+    Epoch(EpochArgs),
+    /// Print an instant in UTC and in the target zone side by side.
+    Span(SpanArgs),
+    /// Block until a date expression is reached.
+    SleepUntil(SleepUntilArgs),
+    /// Run a command on a recurring schedule.
+    Every(EveryArgs),
+    /// Prefix each line read from stdin with the current time.
+    Stamp(StampArgs),
+    /// Show the time between consecutive timestamped lines from stdin.
+    Delta(DeltaArgs),
+    /// Normalize the timestamp of each log line read from stdin.
+    Rewrite(RewriteArgs),
+    /// Add up durations read from stdin, one per line.
+    Sum(SumArgs),
+    /// Summarize the timestamps of lines read from stdin.
+    Stats(StatsArgs),
+    /// Chart how the timestamps of stdin lines spread over time.
+    Hist(HistArgs),
+    /// Group stdin lines by the day, week or month of their timestamp.
+    GroupBy(GroupByArgs),
+    /// Say how far a date is from now, e.g. "in 1 week 6 days".
+    Humanize(HumanizeArgs),
+    /// Check how close a date is, exiting 0/1/2 like a Nagios plugin.
+    Until(UntilArgs),
+    /// Move a date into the range given by --min and --max.
+    Clamp(ClampArgs),
+    /// Print the earliest of several dates.
+    Min(ExtremeArgs),
+    /// Print the latest of several dates.
+    Max(ExtremeArgs),
+    /// Snap dates back to the last occurrence of a schedule.
+    Floor(SnapArgs),
+    /// Snap dates forward to the next occurrence of a schedule.
+    Ceil(SnapArgs),
+}
+
+/// Output layout for the main command.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputMode {
+    /// The formatted datetime
+    #[default]
+    Text,
+    /// Git author/committer date environment assignments
+    GitEnv,
+}
+
+/// Line terminator for the main command's results.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LineEnding {
+    /// CRLF on Windows, LF elsewhere
+    #[default]
+    Auto,
+    /// Line feed
+    Lf,
+    /// Carriage return and line feed
+    Crlf,
+}
+
+impl LineEnding {
+    /// The terminator to write.
+    #[must_use]
+    pub fn terminator(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+            LineEnding::Auto if cfg!(windows) => "\r\n",
+            LineEnding::Auto => "\n",
+        }
+    }
+}
+
+/// What calendar arithmetic does when adding months or years lands on a
+/// day the target month lacks, as in Jan 31 + 1 month.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum MonthOverflow {
+    /// Use the month's last day (Feb 28)
+    #[default]
+    Clamp,
+    /// Carry the extra days into the next month (Mar 3)
+    Overflow,
+    /// Fail with an error
+    Error,
+}
+
+/// What a date expression spells: a calendar date, a clock time, or both.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InputKind {
+    /// A day without a time of day ("tomorrow", "2025-03-01")
+    Date,
+    /// A time of day without a day
+    Time,
+    /// Both, or an exact instant ("tomorrow 9am", "now", "@1735689600")
+    Datetime,
+}
+
+impl InputKind {
+    /// The name used in `{kind}`, JSON and `--require`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InputKind::Date => "date",
+            InputKind::Time => "time",
+            InputKind::Datetime => "datetime",
+        }
+    }
+}
+
+/// What batch mode does with a line that fails.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OnError {
+    /// Stop at the first failure
+    #[default]
+    Abort,
+    /// Print the error to stderr and an empty line in its place
+    Skip,
+    /// Write a JSON error record in its place
+    Record,
+}
+
+/// How `--verbose` diagnostics are written to stderr.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TraceFormat {
+    /// Tagged lines for people
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Time scale the main command prints on.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TimeScale {
+    /// Coordinated Universal Time
+    #[default]
+    Utc,
+    /// International Atomic Time
+    Tai,
+    /// GPS time
+    Gps,
+}
+
+/// Precision of `-I`/`--iso-8601` output.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum IsoPrecision {
+    /// Date only
+    #[default]
+    Date,
+    /// Date and hour
+    Hours,
+    /// Date, hour and minute
+    Minutes,
+    /// Date and time to the second
+    Seconds,
+    /// Date and time to the nanosecond
+    Ns,
+}
+
+impl IsoPrecision {
+    /// The strftime format printing this precision.
+    #[must_use]
+    pub fn format(self) -> &'static str {
+        match self {
+            IsoPrecision::Date => "%Y-%m-%d",
+            IsoPrecision::Hours => "%Y-%m-%dT%H%:z",
+            IsoPrecision::Minutes => "%Y-%m-%dT%H:%M%:z",
+            IsoPrecision::Seconds => "%Y-%m-%dT%H:%M:%S%:z",
+            IsoPrecision::Ns => "%Y-%m-%dT%H:%M:%S,%N%:z",
+        }
+    }
+}
+
+/// Tools whose command line `--compat` can read.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Compat {
+    /// GNU date
+    Date,
 }
 
 /// Output format for diff results.
@@ -195,6 +1038,8 @@ pub enum DiffOutput {
     Seconds,
     /// ISO 8601 duration format (e.g., P2M14D)
     Iso,
+    /// Decimal amount of the largest whole unit (e.g., "1.5h", "2.25d")
+    Decimal,
 }
 
 /// Arguments for the `diff` subcommand.
@@ -207,6 +1052,18 @@ pub struct DiffArgs {
     /// Select which diff format to output
     #[arg(short, long, value_enum, default_value = "human")]
     pub output: DiffOutput,
+    /// Decimal places of the decimal layout (default: configured, else 2)
+    #[arg(long, value_name = "N")]
+    pub decimals: Option<u32>,
+    /// Rounding of the decimal layout (default: configured, else nearest)
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub rounding: Option<Rounding>,
+    /// Also print both dates, one above the other, with the differing parts highlighted
+    #[arg(long, conflicts_with = "json")]
+    pub visual: bool,
+    /// Format of the dates printed by --visual (default: RFC 3339)
+    #[arg(value_name = "FMT", short, long, requires = "visual")]
+    pub format: Option<String>,
     /// Output as JSON
     #[arg(short, long)]
     pub json: bool,
@@ -232,7 +1089,7 @@ pub struct ConvertArgs {
     /// Input format (strptime pattern or preset name). Auto-detected if omitted.
     #[arg(long)]
     pub from: Option<String>,
-    /// Output format (strftime pattern, preset name, or builtin: iso8601, rfc3339, rfc2822, epoch, unix)
+    /// Output format (strftime pattern, preset name, or builtin: iso8601, rfc3339, rfc2822, epoch, unix, unix_ns)
     #[arg(long)]
     pub to: String,
     /// Output as JSON
@@ -254,15 +1111,19 @@ pub struct ConvertArgs {
 
 /// Arguments for the `tz` subcommand.
 #[derive(Debug, clap::Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct TzArgs {
+    #[command(subcommand)]
+    pub action: Option<TzAction>,
     /// Input datetime expression
-    pub input: String,
+    #[arg(required = true)]
+    pub input: Option<String>,
     /// Source timezone (auto-detected from system or input if omitted)
     #[arg(long)]
     pub from: Option<String>,
     /// Target timezone (required, IANA name like "America/Sao_Paulo")
-    #[arg(long)]
-    pub to: String,
+    #[arg(long, required = true)]
+    pub to: Option<String>,
     /// Output as JSON
     #[arg(short, long)]
     pub json: bool,
@@ -277,12 +1138,138 @@ pub struct TzArgs {
     pub verbose: bool,
 }
 
-/// Arguments for the `info` subcommand.
-#[derive(Debug, clap::Args)]
+/// Subactions for `td tz`.
+#[non_exhaustive]
+#[derive(Debug, Subcommand)]
+pub enum TzAction {
+    /// Search time zones by name and print the one picked.
+    Pick(TzPickArgs),
+    /// Show the time in a place given as a zone, abbreviation or city name.
+    At(TzAtArgs),
+    /// List every time zone in the tz database.
+    List(TzListArgs),
+    /// List the UTC-offset changes of a zone in a year or range of years.
+    Transitions(TzTransitionsArgs),
+    /// Show how the offset between two zones changes over the coming months.
+    Compare(TzCompareArgs),
+    /// Print when the next UTC-offset change happens and how far away it is.
+    NextChange(TzNextChangeArgs),
+}
+
+/// Arguments for `td tz next-change`.
+#[derive(Debug, clap::Args)]
+pub struct TzNextChangeArgs {
+    /// Zone, abbreviation or city (default: configured)
+    pub place: Option<String>,
+    /// Output format (strftime pattern or built-in name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for `td tz compare`.
+#[derive(Debug, clap::Args)]
+pub struct TzCompareArgs {
+    /// First zone, abbreviation or city
+    pub a: String,
+    /// Second zone, abbreviation or city
+    pub b: String,
+    /// Number of months to look ahead
+    #[arg(long, value_name = "N", default_value_t = 12)]
+    pub months: u16,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for `td tz transitions`.
+#[derive(Debug, clap::Args)]
+pub struct TzTransitionsArgs {
+    /// Zone, abbreviation or city, e.g. "Europe/London" or "sydney"
+    pub place: String,
+    /// Year, or inclusive range such as "2025..2027" (defaults to this year)
+    pub years: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for `td tz list`.
+#[derive(Debug, clap::Args)]
+pub struct TzListArgs {
+    /// Order zones by name, current UTC offset or country
+    #[arg(long, value_enum, default_value_t)]
+    pub sort: TzSort,
+    /// Reverse the order
+    #[arg(short, long)]
+    pub reverse: bool,
+    /// Only zones at this UTC offset now, e.g. "+05:30" or "-3"
+    #[arg(long, value_name = "OFFSET", allow_hyphen_values = true)]
+    pub offset: Option<String>,
+    /// Only zones observing daylight saving time now
+    #[arg(long)]
+    pub dst_active: bool,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Order of `td tz list`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TzSort {
+    /// Zone identifier, alphabetically
+    #[default]
+    Name,
+    /// Current UTC offset, west to east
+    Offset,
+    /// ISO 3166 country code; zones without one come last
+    Country,
+}
+
+/// Arguments for `td tz at`.
+#[derive(Debug, clap::Args)]
+pub struct TzAtArgs {
+    /// Zone, abbreviation or city, e.g. "tokyo" or "Portland, US"
+    pub place: String,
+    /// Date expression to convert (defaults to "now")
+    #[arg(default_value = "now")]
+    pub input: String,
+    /// Print the resolved zone identifier instead of a time
+    #[arg(long)]
+    pub id: bool,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for `td tz pick`.
+#[derive(Debug, clap::Args)]
+pub struct TzPickArgs {
+    /// Search text, e.g. "sao paulo" or "pst" (prompted for in a terminal)
+    pub query: Option<String>,
+    /// Print every match, best first, instead of picking one
+    #[arg(short, long)]
+    pub list: bool,
+    /// Number of candidates offered in a terminal
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    pub limit: usize,
+    /// Save the pick as the default timezone in the config file
+    #[arg(long)]
+    pub save: bool,
+}
+
+/// Arguments for the `info` subcommand.
+#[derive(Debug, clap::Args)]
 pub struct InfoArgs {
     /// Date expression to inspect (defaults to "now")
     #[arg(default_value = "now")]
     pub input: String,
+    /// Report the td version and environment, for bug reports, instead of a date
+    #[arg(long, conflicts_with_all = ["now", "timezone"])]
+    pub env: bool,
     /// Output as JSON
     #[arg(short, long)]
     pub json: bool,
@@ -328,6 +1315,765 @@ pub struct RangeArgs {
     pub verbose: bool,
 }
 
+/// Arguments for the `split` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct SplitArgs {
+    /// Range to divide: a period such as "tomorrow", or "START to END"
+    pub input: String,
+    /// Length of each slot, e.g. "30m" or "1h30m"
+    #[arg(long, value_name = "DURATION")]
+    pub by: String,
+    /// Output format (strftime pattern or preset name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+    /// Delimiter between a slot's start and end
+    #[arg(short = 'd', long, default_value = "\t")]
+    pub delimiter: String,
+    /// Drop a last slot shorter than --by
+    #[arg(long)]
+    pub full: bool,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Arguments for the `overlaps` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct OverlapsArgs {
+    /// Two ranges, each a period such as "tomorrow", "[from] START to END",
+    /// or START and END as two arguments
+    #[arg(value_name = "RANGE", required = true, num_args = 2..=4)]
+    pub ranges: Vec<String>,
+    /// Print the start and end of the intersection
+    #[arg(short, long)]
+    pub print: bool,
+    /// Output format (strftime pattern or preset name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+    /// Delimiter between the intersection's start and end
+    #[arg(short = 'd', long, default_value = "\t")]
+    pub delimiter: String,
+    /// Output as JSON, with the intersection when there is one
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Arguments for the `workweek` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct WorkweekArgs {
+    /// Date expression inside the week (defaults to "today")
+    #[arg(default_value = "today")]
+    pub input: String,
+    /// Working days, e.g. "mon,tue,thu" (default: configured, else Monday-Friday)
+    #[arg(long, value_name = "DAYS", value_delimiter = ',')]
+    pub days: Option<Vec<String>>,
+    /// First day of the week (default: configured, else Monday)
+    #[arg(long, value_name = "DAY")]
+    pub week_start: Option<String>,
+    /// Print only the first and last working day
+    #[arg(short, long)]
+    pub range: bool,
+    /// Output format (strftime pattern or preset name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+    /// Delimiter between the days (default: newline)
+    #[arg(short = 'd', long, default_value = "\n")]
+    pub delimiter: String,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Arguments for the `quarter` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct QuarterArgs {
+    /// Date expression inside the quarter (defaults to "today")
+    #[arg(default_value = "today")]
+    pub input: String,
+    /// First month of the fiscal year, e.g. "april" or "10" (default: configured, else January)
+    #[arg(long, value_name = "MONTH")]
+    pub fiscal_start: Option<String>,
+    /// Use calendar quarters, ignoring the configured fiscal year
+    #[arg(long, conflicts_with = "fiscal_start")]
+    pub calendar: bool,
+    /// Print the quarter's name, such as "FY2025 Q3", instead of its bounds
+    #[arg(long, conflicts_with = "json")]
+    pub name: bool,
+    /// Output format (strftime pattern or preset name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+    /// Delimiter between start and end (default: newline)
+    #[arg(short = 'd', long, default_value = "\n")]
+    pub delimiter: String,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Arguments for the `next-occurrence` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct NextOccurrenceArgs {
+    /// Month and day as MM-DD, or a full date such as a birthday
+    #[arg(allow_hyphen_values = true)]
+    pub input: String,
+    /// Where February 29 falls in common years
+    #[arg(long, value_name = "POLICY", default_value = "feb28")]
+    pub feb29: LeapDay,
+    /// Output format (strftime pattern or preset name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Arguments for the `is` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct IsArgs {
+    /// What to test: weekend, weekday, workday, holiday or a day name such as "friday"
+    #[arg(value_name = "PREDICATE")]
+    pub predicate: String,
+    /// Date expression to test (defaults to "today")
+    #[arg(default_value = "today")]
+    pub input: String,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `is-open` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct IsOpenArgs {
+    /// Date expression to test (defaults to "now")
+    #[arg(default_value = "now")]
+    pub input: String,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `next-open` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct NextOpenArgs {
+    /// Date expression to start from (defaults to "now")
+    #[arg(default_value = "now")]
+    pub input: String,
+    /// Output format (strftime pattern or preset name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Subactions for `td holiday`.
+#[non_exhaustive]
+#[derive(Debug, Subcommand)]
+pub enum HolidayAction {
+    /// Print the next holiday, on or after a date.
+    Next(HolidayNextArgs),
+    /// Exit 0 when a date is a holiday, printing its name, and 1 when not.
+    Is(HolidayIsArgs),
+    /// List the holidays in a range.
+    List(HolidayListArgs),
+}
+
+/// Arguments for `td holiday next`.
+#[derive(Debug, clap::Args)]
+pub struct HolidayNextArgs {
+    /// Date expression to look from (defaults to "today")
+    #[arg(default_value = "today")]
+    pub input: String,
+    /// Output format (strftime pattern or preset name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Arguments for `td holiday is`.
+#[derive(Debug, clap::Args)]
+pub struct HolidayIsArgs {
+    /// Date expression to check (defaults to "today")
+    #[arg(default_value = "today")]
+    pub input: String,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for `td holiday list`.
+#[derive(Debug, clap::Args)]
+pub struct HolidayListArgs {
+    /// Range to list: a period such as "this year", or "START to END"
+    #[arg(default_value = "this year")]
+    pub range: String,
+    /// Output format (strftime pattern or preset name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Where a February 29 anniversary falls in a common year.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LeapDay {
+    /// On February 28
+    #[default]
+    Feb28,
+    /// On March 1
+    Mar1,
+    /// Not at all; wait for the next leap year
+    Skip,
+}
+
+/// Arguments for the `lint-format` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct LintFormatArgs {
+    /// Format to check (strftime pattern, preset or built-in name)
+    #[arg(value_name = "FMT", required_unless_present = "presets")]
+    pub format: Option<String>,
+    /// Check every preset in the config file instead
+    #[arg(long, conflicts_with = "format")]
+    pub presets: bool,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Arguments for the `prompt` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct PromptArgs {
+    /// Date expression to render (defaults to "now")
+    #[arg(default_value = "now")]
+    pub input: String,
+    /// Output format (strftime pattern or preset name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone to apply (IANA/Olson ID)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+    /// Text printed instead of an error
+    #[arg(long, default_value = "?")]
+    pub fallback: String,
+    /// Remember the system time-zone in the cache between runs
+    #[arg(long)]
+    pub cache_tz: bool,
+}
+
+/// Arguments for the `meet` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct MeetArgs {
+    /// Participant zones; append =HH:MM-HH:MM for their own working hours
+    #[arg(value_name = "ZONE", required = true)]
+    pub zones: Vec<String>,
+    /// Day to search, in the first zone (defaults to "today")
+    #[arg(long, value_name = "EXPR", default_value = "today")]
+    pub on: String,
+    /// Working hours for zones given without their own (default 09:00-17:00)
+    #[arg(long, value_name = "HH:MM-HH:MM")]
+    pub hours: Option<String>,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `worldclock` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct WorldclockArgs {
+    /// Date expression to show (defaults to "now")
+    #[arg(default_value = "now")]
+    pub input: String,
+    /// Zone to show instead of the configured list. Repeatable.
+    #[arg(short, long = "zone", value_name = "ZONE")]
+    pub zones: Vec<String>,
+    /// Time format (strftime pattern or builtin name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Unit of a Unix timestamp.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EpochUnit {
+    /// Seconds
+    S,
+    /// Milliseconds
+    Ms,
+    /// Microseconds
+    Us,
+    /// Nanoseconds
+    Ns,
+}
+
+/// Arguments for the `epoch` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct EpochArgs {
+    /// A Unix timestamp to turn into a date, or an expression to turn into one
+    #[arg(allow_hyphen_values = true)]
+    pub input: String,
+    /// Timestamp unit (default: seconds, or detected from the digits of a timestamp)
+    #[arg(short, long, value_enum)]
+    pub unit: Option<EpochUnit>,
+    /// Time-zone for resolution and output
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+    /// Suppress trailing newline
+    #[arg(short = 'n', long = "no-newline")]
+    pub no_newline: bool,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `span` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct SpanArgs {
+    /// Date expression (defaults to "now")
+    #[arg(default_value = "now", allow_hyphen_values = true)]
+    pub input: String,
+    /// Output format for both renderings (default: RFC 3339)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Target time-zone, used for resolution and the second rendering
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+    /// Delimiter between the UTC and the local rendering
+    #[arg(short = 'd', long, default_value = "\t")]
+    pub delimiter: String,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Arguments for the `sleep-until` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct SleepUntilArgs {
+    /// Date expression to wait for, e.g. "9am" or "in 10 minutes"
+    pub input: String,
+    /// Fail instead of waiting longer than this, e.g. "2h" or "1h 30m"
+    #[arg(long, value_name = "DURATION")]
+    pub max_wait: Option<String>,
+    /// Time-zone for resolution
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `every` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct EveryArgs {
+    /// Schedule, e.g. "every 15 minutes" or "every weekday at 9"
+    pub schedule: String,
+    /// Command to run, after `--`
+    #[arg(last = true, value_name = "COMMAND")]
+    pub command: Vec<String>,
+    /// Stop after this many runs
+    #[arg(short, long, value_name = "N")]
+    pub count: Option<usize>,
+    /// Print the next occurrences (--count, default 5) instead of running
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Time-zone for the schedule
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `stamp` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct StampArgs {
+    /// Timestamp format (strftime pattern or builtin name)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Prefix the time elapsed since td started instead of the clock time
+    #[arg(short, long)]
+    pub elapsed: bool,
+    /// Time-zone for the clock time
+    #[arg(value_name = "TZ", short, long, conflicts_with = "elapsed")]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long, conflicts_with = "elapsed")]
+    pub now: Option<String>,
+}
+
+/// Where a log line keeps its timestamp.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogProfile {
+    /// ISO 8601, e.g. 2025-01-15T10:30:00Z or 2025-01-15 10:30:00.250
+    #[default]
+    Iso,
+    /// BSD syslog, e.g. "Jan 15 10:30:00" (year taken from "now")
+    Syslog,
+    /// nginx/Apache access logs, e.g. 15/Jan/2025:10:30:00 +0000
+    Nginx,
+    /// log4j/logback (2025-01-15 10:30:00,250) or java.util.logging
+    Java,
+    /// Unix time: 10 digits of seconds (1736937000[.250]) or 13 of milliseconds
+    Epoch,
+}
+
+/// Arguments for the `delta` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct DeltaArgs {
+    /// Mark gaps longer than this, e.g. "500ms" or "2m"
+    #[arg(long, value_name = "DURATION")]
+    pub threshold: Option<String>,
+    /// Print only the deltas, in seconds, one per timestamped line
+    #[arg(short, long)]
+    pub seconds: bool,
+    /// Timestamp layout of the log
+    #[arg(short, long, value_enum, default_value_t)]
+    pub profile: LogProfile,
+    /// Time-zone for timestamps without an offset
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+}
+
+/// Arguments for the `rewrite` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct RewriteArgs {
+    /// Timestamp layout of the log
+    #[arg(short, long, value_enum, default_value_t)]
+    pub profile: LogProfile,
+    /// Output format (strftime pattern or builtin name; default: RFC 3339)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone to read offset-less timestamps in and to write them in
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339), which supplies the syslog year
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Output layout for `td sum` totals.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SumOutput {
+    /// Human-readable duration (e.g., "4h 30m")
+    #[default]
+    Human,
+    /// Hours and minutes on a clock (e.g., "4:30")
+    Clock,
+    /// Decimal hours (e.g., "4.5")
+    Hours,
+    /// Decimal minutes
+    Minutes,
+    /// Decimal seconds
+    Seconds,
+    /// Decimal amount of the largest whole unit (e.g., "4.5h", "2.25d")
+    Decimal,
+}
+
+/// Direction decimal amounts are rounded in.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Rounding {
+    /// To the nearest value, halves away from zero
+    #[default]
+    Nearest,
+    /// Away from zero, as billing by started increments does
+    Up,
+    /// Toward zero
+    Down,
+}
+
+/// Arguments for the `sum` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct SumArgs {
+    /// Layout of the total
+    #[arg(short, long, value_enum, default_value_t)]
+    pub output: SumOutput,
+    /// Treat the first word of each line as a label and total per label
+    #[arg(short, long)]
+    pub group_by: bool,
+    /// Print the duration of every line before the total
+    #[arg(short, long, conflicts_with = "group_by")]
+    pub each: bool,
+    /// Decimal places of decimal layouts (default: configured, else 2)
+    #[arg(long, value_name = "N")]
+    pub decimals: Option<u32>,
+    /// Rounding of decimal layouts (default: configured, else nearest)
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub rounding: Option<Rounding>,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+}
+
+/// Arguments for the `stats` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct StatsArgs {
+    /// Timestamp layout of the input
+    #[arg(short, long, value_enum, default_value_t)]
+    pub profile: LogProfile,
+    /// Report each day separately
+    #[arg(long)]
+    pub per_day: bool,
+    /// Format for earliest/latest (strftime pattern or builtin name; default: RFC 3339)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone for offset-less input, days and output
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Zone whose calendar days --per-day follows (default: --timezone)
+    #[arg(value_name = "TZ", long)]
+    pub day_boundary: Option<String>,
+    /// Output as JSON
+    #[arg(short, long)]
+    pub json: bool,
+    /// Override "now" reference (RFC 3339), which supplies the syslog year
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Calendar unit timestamps are bucketed by.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum BucketUnit {
+    Minute,
+    #[default]
+    Hour,
+    Day,
+    /// ISO week, starting on Monday
+    Week,
+    Month,
+}
+
+/// Arguments for the `hist` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct HistArgs {
+    /// Bucket size
+    #[arg(short, long, value_enum, default_value_t)]
+    pub by: BucketUnit,
+    /// Timestamp layout of the input
+    #[arg(short, long, value_enum, default_value_t)]
+    pub profile: LogProfile,
+    /// Length of the longest bar, in characters
+    #[arg(short, long, value_name = "N", default_value_t = 40)]
+    pub width: usize,
+    /// Print bucket,count CSV instead of a chart
+    #[arg(long)]
+    pub csv: bool,
+    /// Time-zone for offset-less input and bucket boundaries
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Zone whose days, hours, weeks and months buckets follow (default: --timezone)
+    #[arg(value_name = "TZ", long)]
+    pub day_boundary: Option<String>,
+    /// Override "now" reference (RFC 3339), which supplies the syslog year
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `group-by` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct GroupByArgs {
+    /// Calendar unit to group by
+    #[arg(value_enum)]
+    pub unit: BucketUnit,
+    /// Print the lines of each group under a header instead of counts
+    #[arg(short, long)]
+    pub lines: bool,
+    /// Timestamp layout of the input
+    #[arg(short, long, value_enum, default_value_t)]
+    pub profile: LogProfile,
+    /// Time-zone for offset-less input and group boundaries (default: configured)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Zone whose days, hours, weeks and months groups follow (default: --timezone)
+    #[arg(value_name = "TZ", long)]
+    pub day_boundary: Option<String>,
+    /// Override "now" reference (RFC 3339), which supplies the syslog year
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `humanize` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct HumanizeArgs {
+    /// Date expression to describe
+    pub input: String,
+    /// Number of units to show, largest first (default: configured, else 1)
+    #[arg(short, long)]
+    pub granularity: Option<usize>,
+    /// Time-zone for resolution and calendar days (default: configured)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `until` subcommand.
+#[derive(Debug, clap::Args)]
+pub struct UntilArgs {
+    /// Date expression to count down to, e.g. "2025-09-01" or a name from [dates]
+    #[arg(required_unless_present = "all")]
+    pub input: Option<String>,
+    /// Count down to every date in the [dates] table of the config file
+    #[arg(long, conflicts_with_all = ["input", "label"])]
+    pub all: bool,
+    /// Exit 1 (WARNING) when the date is this close, e.g. "30d" or "2w"
+    #[arg(short, long, value_name = "DURATION")]
+    pub warn: Option<String>,
+    /// Exit 2 (CRITICAL) when the date is this close, e.g. "7d"
+    #[arg(short, long, value_name = "DURATION")]
+    pub crit: Option<String>,
+    /// Name of the deadline in the status line, e.g. "cert expiry"
+    #[arg(short, long)]
+    pub label: Option<String>,
+    /// Time-zone for resolution (default: configured)
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `clamp` subcommand.
+#[derive(Debug, clap::Args)]
+#[command(group(clap::ArgGroup::new("bounds").args(["min", "max"]).required(true).multiple(true)))]
+pub struct ClampArgs {
+    /// Date expression to clamp
+    pub input: String,
+    /// Earliest allowed result, e.g. "today 22:00"
+    #[arg(long, value_name = "EXPR")]
+    pub min: Option<String>,
+    /// Latest allowed result, e.g. "tomorrow 02:00"
+    #[arg(long, value_name = "EXPR")]
+    pub max: Option<String>,
+    /// Output format (strftime pattern or builtin name; default: RFC 3339)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone for resolution
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `min` and `max` subcommands.
+#[derive(Debug, clap::Args)]
+pub struct ExtremeArgs {
+    /// Date expressions to compare (default: one per line from stdin)
+    pub inputs: Vec<String>,
+    /// Output format (strftime pattern or builtin name; default: RFC 3339)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone for resolution
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
+/// Arguments for the `floor` and `ceil` subcommands.
+#[derive(Debug, clap::Args)]
+pub struct SnapArgs {
+    /// Schedule, e.g. "every 15 minutes", a cron expression such as
+    /// "*/5 * * * *", or a name from [recurrences]
+    pub schedule: String,
+    /// Date expressions to snap (default: one per line from stdin, or "now"
+    /// in a terminal)
+    pub inputs: Vec<String>,
+    /// Output format (strftime pattern or builtin name; default: RFC 3339)
+    #[arg(value_name = "FMT", short, long)]
+    pub format: Option<String>,
+    /// Time-zone for resolution and the schedule
+    #[arg(value_name = "TZ", short, long)]
+    pub timezone: Option<String>,
+    /// Override "now" reference (RFC 3339)
+    #[arg(long)]
+    pub now: Option<String>,
+}
+
 /// Subactions for `td config`.
 #[non_exhaustive]
 #[derive(Debug, Subcommand)]
@@ -340,11 +2086,102 @@ pub enum ConfigAction {
     Edit,
     /// List all available format presets.
     Presets,
+    /// Upgrade the configuration file to the current schema version.
+    Migrate(ConfigMigrateArgs),
+    /// Check the configuration file for errors and likely mistakes.
+    Validate(ConfigValidateArgs),
+    /// Print a JSON Schema for the configuration file.
+    Schema,
+    /// Print the effective value of a key, such as `timezone` or `formats.br`.
+    Get {
+        /// Dotted key
+        key: String,
+    },
+    /// Set a key in the configuration file, keeping its comments.
+    Set {
+        /// Dotted key, such as `timezone` or `formats.work.iso`
+        key: String,
+        /// New value; read as TOML when the key takes a number, boolean or list
+        value: String,
+    },
+}
+
+/// Arguments for `td config migrate`.
+#[derive(Debug, clap::Args)]
+pub struct ConfigMigrateArgs {
+    /// Show a unified diff of the upgrade without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Directory for the backup of the original file (default: next to it)
+    #[arg(long, value_name = "DIR", conflicts_with = "dry_run")]
+    pub backup_dir: Option<std::path::PathBuf>,
+}
+
+/// Arguments for `td config validate`.
+#[derive(Debug, clap::Args)]
+pub struct ConfigValidateArgs {
+    /// File to check (default: the configuration file)
+    #[arg(value_name = "FILE")]
+    pub file: Option<std::path::PathBuf>,
+    /// Print a JSON report of errors and warnings with their keys and lines
+    #[arg(long)]
+    pub ci: bool,
+}
+
+/// Subactions for `td cache`.
+#[non_exhaustive]
+#[derive(Debug, Subcommand)]
+pub enum CacheAction {
+    /// Print the path to the cache directory.
+    Path,
+    /// Delete all cached data.
+    Clear,
+}
+
+/// Subactions for `td history`.
+#[non_exhaustive]
+#[derive(Debug, Subcommand)]
+pub enum HistoryAction {
+    /// Print the recorded expressions, oldest first.
+    Show,
+    /// Print the path to the history file.
+    Path,
+    /// Delete the history.
+    Clear,
+}
+
+/// Arguments for the `completions` subcommand.
+#[derive(Debug, clap::Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct CompletionsArgs {
+    #[command(subcommand)]
+    pub action: Option<CompletionsAction>,
+    /// Shell to generate completions for.
+    #[arg(required = true)]
+    pub shell: Option<ShellType>,
+}
+
+/// Subactions for `td completions`.
+#[non_exhaustive]
+#[derive(Debug, Subcommand)]
+pub enum CompletionsAction {
+    /// Install completions for your shell where it looks for them.
+    Install(CompletionsInstallArgs),
+}
+
+/// Arguments for `td completions install`.
+#[derive(Debug, clap::Args)]
+pub struct CompletionsInstallArgs {
+    /// Shell to install for (detected from $SHELL if omitted)
+    pub shell: Option<ShellType>,
+    /// Print where the script would go without writing it
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 /// Supported shell types for completion generation.
 #[non_exhaustive]
-#[derive(Debug, Clone, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum ShellType {
     Bash,
     Zsh,
@@ -352,3 +2189,11 @@ pub enum ShellType {
     Elvish,
     Powershell,
 }
+
+/// Parse a `--param NAME=VALUE` argument.
+fn parse_param(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("expected NAME=VALUE, got '{arg}'")),
+    }
+}
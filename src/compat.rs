@@ -0,0 +1,172 @@
+//! GNU `date` compatibility for **TARDIS**.
+//!
+//! `td --compat date` reads the rest of the command line the way GNU `date`
+//! would and rewrites it into the arguments of `td` before they are parsed:
+//! `-d STRING` becomes the input, `-u` the UTC time zone and `+FORMAT` the
+//! output format, while `-R` and `-I[SPEC]` are `td` options as well.
+//! Without a format the result is printed like `date` does, as in
+//! `Wed Jan 15 10:30:00 UTC 2025`, and without `-d` it is the current time.
+//! Any other argument, such as `--now` or `--json`, is passed on unchanged.
+
+use std::ffi::OsString;
+
+use clap::ValueEnum;
+
+use crate::{Result, cli::IsoPrecision, user_input_error};
+
+/// The format GNU `date` prints by default, in the C locale.
+pub const DATE_FORMAT: &str = "%a %b %e %H:%M:%S %Z %Y";
+
+/// Rewrite `args`, the whole command line, when it asks for `--compat date`;
+/// any other command line is returned unchanged.
+pub fn rewrite(args: Vec<OsString>) -> Result<Vec<OsString>> {
+    let at = args
+        .iter()
+        .take_while(|a| *a != "--")
+        .position(|a| a == "--compat=date");
+    let (at, taken) = match at {
+        Some(at) => (at, 1),
+        None => match args
+            .windows(2)
+            .take_while(|w| w[0] != "--")
+            .position(|w| w[0] == "--compat" && w[1] == "date")
+        {
+            Some(at) => (at, 2),
+            None => return Ok(args),
+        },
+    };
+    let mut args = args;
+    args.drain(at..at + taken);
+    gnu_date(args)
+}
+
+/// Map the arguments of GNU `date`, after the program name, onto `td`.
+fn gnu_date(args: Vec<OsString>) -> Result<Vec<OsString>> {
+    let mut args = args.into_iter();
+    let mut out: Vec<OsString> = args.next().into_iter().collect();
+    let mut input = None;
+    let mut format = None;
+    let mut native_format = false;
+    let mut utc = false;
+
+    while let Some(arg) = args.next() {
+        let Some(s) = arg.to_str() else {
+            out.push(arg);
+            continue;
+        };
+        let mut value = |flag: &str| {
+            args.next()
+                .ok_or_else(|| user_input_error!(MissingArgument, "{} needs a value", flag))
+        };
+        match s {
+            "-d" | "--date" => input = Some(value(s)?),
+            "-u" | "--utc" | "--universal" => utc = true,
+            "-R" | "--rfc-email" => {
+                native_format = true;
+                out.push("--rfc-email".into());
+            }
+            _ if s.starts_with('+') => format = Some(s[1..].to_owned()),
+            _ if s.starts_with("--date=") => input = Some(s["--date=".len()..].into()),
+            _ if s.starts_with("-d") => input = Some(s[2..].into()),
+            _ if s.starts_with("-I") || s.starts_with("--iso-8601") => {
+                let (flag, spec) = match s.strip_prefix("-I") {
+                    Some(spec) => ("-I", spec),
+                    None => (
+                        "--iso-8601",
+                        s["--iso-8601".len()..].trim_start_matches('='),
+                    ),
+                };
+                if !spec.is_empty() && IsoPrecision::from_str(spec, false).is_err() {
+                    return Err(user_input_error!(
+                        UnsupportedFormat,
+                        "invalid argument '{}' for {}; choose from date, hours, minutes, seconds, ns",
+                        spec,
+                        flag
+                    ));
+                }
+                native_format = true;
+                out.push(
+                    format!("--iso-8601={}", if spec.is_empty() { "date" } else { spec }).into(),
+                );
+            }
+            _ => out.push(arg),
+        }
+    }
+
+    if let Some(format) = format.or((!native_format).then(|| DATE_FORMAT.to_owned())) {
+        out.push("--format".into());
+        out.push(format.into());
+    }
+    if utc {
+        out.push("--timezone".into());
+        out.push("UTC".into());
+    }
+    // `date` never reads STDIN, and its input may start with a dash.
+    out.push("--".into());
+    out.push(input.unwrap_or_else(|| "now".into()));
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn rewritten(argv: &[&str]) -> Result<Vec<String>> {
+        let args = argv.iter().map(OsString::from).collect();
+        Ok(rewrite(args)?
+            .into_iter()
+            .map(|a| a.into_string().unwrap())
+            .collect())
+    }
+
+    #[test]
+    fn maps_gnu_flags_onto_td() {
+        assert_eq!(
+            rewritten(&["td", "--compat", "date", "-u", "-d", "tomorrow", "+%F"]).unwrap(),
+            [
+                "td",
+                "--format",
+                "%F",
+                "--timezone",
+                "UTC",
+                "--",
+                "tomorrow"
+            ]
+        );
+        assert_eq!(
+            rewritten(&["td", "--compat=date", "--date=@0", "-Iminutes", "--json"]).unwrap(),
+            ["td", "--iso-8601=minutes", "--json", "--", "@0"]
+        );
+        assert_eq!(
+            rewritten(&["td", "--compat", "date", "-R"]).unwrap(),
+            ["td", "--rfc-email", "--", "now"]
+        );
+        assert_eq!(
+            rewritten(&["td", "--compat", "date"]).unwrap(),
+            ["td", "--format", DATE_FORMAT, "--", "now"]
+        );
+    }
+
+    #[test]
+    fn leaves_other_command_lines_alone() {
+        assert_eq!(
+            rewritten(&["td", "-d", "+%F"]).unwrap(),
+            ["td", "-d", "+%F"]
+        );
+        assert_eq!(
+            rewritten(&["td", "--", "--compat", "date"]).unwrap(),
+            ["td", "--", "--compat", "date"]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_iso_specs_and_missing_values() {
+        let err = rewritten(&["td", "--compat", "date", "-Iweeks"]).unwrap_err();
+        assert!(err.to_string().contains("for -I; choose from"), "{err}");
+        let err = rewritten(&["td", "--compat", "date", "--iso-8601=x"]).unwrap_err();
+        assert!(err.to_string().contains("for --iso-8601;"), "{err}");
+        assert!(rewritten(&["td", "--compat", "date", "-d"]).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Shell completion scripts for `td completions`.
+//!
+//! [`script`] renders the completion script for a shell.  [`target`] says
+//! where `td completions install` puts it: bash and fish load completions
+//! from a per-user directory, so the script is written there; zsh, elvish
+//! and PowerShell need a line in a startup file instead, which is printed
+//! for the user to add.  [`install`] only writes when the file differs, so
+//! running it again is harmless.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{Result, cli::ShellType, system_error};
+
+/// Where the completions for a shell go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// A script file the shell loads on its own.
+    File(PathBuf),
+    /// A `line` to add to the startup file `rc`.
+    Snippet {
+        rc: &'static str,
+        line: &'static str,
+    },
+}
+
+/// The completion script for `shell`, or an error when `td` was built
+/// without the `completions` feature.
+#[cfg(feature = "completions")]
+pub fn script(shell: ShellType) -> Result<Vec<u8>> {
+    use clap::CommandFactory;
+    use clap_complete::{Shell, generate};
+
+    let shell = match shell {
+        ShellType::Bash => Shell::Bash,
+        ShellType::Zsh => Shell::Zsh,
+        ShellType::Fish => Shell::Fish,
+        ShellType::Elvish => Shell::Elvish,
+        _ => Shell::PowerShell,
+    };
+    let mut out = Vec::new();
+    generate(shell, &mut crate::cli::Cli::command(), "td", &mut out);
+    Ok(out)
+}
+
+/// The completion script for `shell`, or an error when `td` was built
+/// without the `completions` feature.
+#[cfg(not(feature = "completions"))]
+pub fn script(_shell: ShellType) -> Result<Vec<u8>> {
+    Err(system_error!(
+        Config,
+        "this td was built without shell completions (the `completions` feature)"
+    ))
+}
+
+/// The shell named by a `$SHELL` value such as `/usr/bin/zsh`.
+#[must_use]
+pub fn detect(shell_var: &str) -> Option<ShellType> {
+    let name = Path::new(shell_var).file_name()?.to_str()?;
+    match name.strip_suffix(".exe").unwrap_or(name) {
+        "bash" => Some(ShellType::Bash),
+        "zsh" => Some(ShellType::Zsh),
+        "fish" => Some(ShellType::Fish),
+        "elvish" => Some(ShellType::Elvish),
+        "pwsh" | "powershell" => Some(ShellType::Powershell),
+        _ => None,
+    }
+}
+
+/// Where to install the completions for `shell`, following
+/// `$XDG_DATA_HOME` and `$XDG_CONFIG_HOME`.
+pub fn target(shell: ShellType) -> Result<Target> {
+    let xdg = |var: &str, fallback: &str| {
+        env::var_os(var)
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(fallback)))
+            .ok_or_else(|| {
+                system_error!(
+                    Config,
+                    "Could not locate your home directory; set ${} instead.",
+                    var
+                )
+            })
+    };
+    Ok(match shell {
+        ShellType::Bash => Target::File(
+            xdg("XDG_DATA_HOME", ".local/share")?.join("bash-completion/completions/td"),
+        ),
+        ShellType::Fish => {
+            Target::File(xdg("XDG_CONFIG_HOME", ".config")?.join("fish/completions/td.fish"))
+        }
+        ShellType::Zsh => Target::Snippet {
+            rc: "the end of ~/.zshrc",
+            line: "source <(td completions zsh)",
+        },
+        ShellType::Elvish => Target::Snippet {
+            rc: "~/.config/elvish/rc.elv",
+            line: "eval (td completions elvish | slurp)",
+        },
+        _ => Target::Snippet {
+            rc: "your PowerShell $PROFILE",
+            line: "td completions powershell | Out-String | Invoke-Expression",
+        },
+    })
+}
+
+/// Write `script` to `path`, creating its directory.  Returns `false`
+/// without writing when the file already holds `script`.
+pub fn install(path: &Path, script: &[u8]) -> Result<bool> {
+    if fs::read(path).is_ok_and(|current| current == script) {
+        return Ok(false);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, script)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn detects_the_shell_from_its_path() {
+        assert_eq!(detect("/usr/bin/zsh"), Some(ShellType::Zsh));
+        assert_eq!(detect("/opt/homebrew/bin/fish"), Some(ShellType::Fish));
+        assert_eq!(detect("bash"), Some(ShellType::Bash));
+        assert_eq!(detect("pwsh.exe"), Some(ShellType::Powershell));
+        assert_eq!(detect("/bin/tcsh"), None);
+        assert_eq!(detect(""), None);
+    }
+
+    #[test]
+    fn install_writes_once() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("completions/td");
+        assert!(install(&path, b"complete -F _td td\n").unwrap());
+        assert!(!install(&path, b"complete -F _td td\n").unwrap());
+        assert!(install(&path, b"complete -F _td2 td\n").unwrap());
+        assert_eq!(fs::read(&path).unwrap(), b"complete -F _td2 td\n");
+    }
+}
@@ -1,23 +1,43 @@
 //! Configuration loading and helpers for **TARDIS**.
 //!
 //! * Reads `config.toml` from the user-specific config directory
-//!   (`$XDG_CONFIG_HOME/tardis` or OS default).
+//!   (`$TARDIS_CONFIG_DIR`, `$XDG_CONFIG_HOME/tardis` or the OS default).
+//! * Merges the `[when.os.<os>]` and `[when.host.<name>]` tables that match
+//!   this machine over the top level.
 //! * Overlays values from environment variables prefixed with **`TARDIS_`**.
 //! * Automatically bootstraps the file from an embedded template on first run.
+//! * Upgrades older layouts in place (see [`crate::migrate`]), keeping a
+//!   backup of the original file.
 
 use std::{
     collections::HashMap,
     env, fs,
+    num::NonZeroU8,
+    ops::Range,
     path::{Path, PathBuf},
 };
 
+use jiff::civil::Weekday;
 use serde::Deserialize;
+use toml::de::{DeTable, DeValue};
 
-use crate::{Error, Result, core::Preset, errors::SystemError, system_error};
+use crate::{
+    Error, Result, cache,
+    core::{self, Presets, builtin_format},
+    diagnostics::Diagnostics,
+    errors::{ExitCodes, SystemError},
+    i18n, migrate, system_error, user_input_error,
+};
 
 const APP_DIR: &str = "tardis";
 const CONFIG_FILE: &str = "config.toml";
 const TEMPLATE: &str = include_str!("../assets/config_template.toml");
+/// JSON Schema for `config.toml`, for editor completion and validation.
+pub const SCHEMA: &str = include_str!("../assets/config.schema.json");
+/// Timestamped backups kept by [`save`]; older ones are removed.
+const BACKUPS_KEPT: usize = 5;
+/// Cache key holding the preset validation report for the current config.
+const PRESET_CHECK_KEY: &str = "preset-check";
 
 /// In-memory representation of the user configuration.
 #[must_use]
@@ -28,64 +48,1425 @@ pub struct Config {
     pub format: String,
     /// Time-zone identifier (IANA name, e.g. `"America/Sao_Paulo"`).
     pub timezone: String,
-    /// User-defined named formats.
-    pub formats: Option<HashMap<String, String>>,
+    /// User-defined named formats, optionally grouped in sub-tables.
+    pub formats: Option<HashMap<String, FormatEntry>>,
+    /// Preset group searched first for unqualified names, so with
+    /// `preset_group = "work"` the name `iso` means `work.iso` when it exists.
+    #[serde(default)]
+    pub preset_group: Option<String>,
+    /// Time of day for dates given without one, such as `"09:00"` or
+    /// `"end-of-day"`; midnight when unset.
+    #[serde(default)]
+    pub default_time: Option<String>,
+    /// Prompt for an expression when `td` runs in a terminal without one,
+    /// instead of defaulting to "now".
+    #[serde(default)]
+    pub interactive: bool,
+    /// Page long listings such as `td history show` when stdout is a
+    /// terminal.
+    #[serde(default = "default_pager")]
+    pub pager: bool,
+    /// Defaults for `td meet`.
+    #[serde(default)]
+    pub meet: MeetConfig,
+    /// Defaults for `td worldclock`.
+    #[serde(default)]
+    pub worldclock: WorldclockConfig,
+    /// Thresholds for `td humanize` and `--relative`.
+    #[serde(default)]
+    pub humanize: HumanizeConfig,
+    /// Working days for `td workweek`.
+    #[serde(default)]
+    pub workweek: WorkweekConfig,
+    /// Fiscal year for `td quarter`.
+    #[serde(default)]
+    pub fiscal: FiscalConfig,
+    /// Named schedules, such as `sprint-start = "every other monday"`,
+    /// usable as input to the main command and `td every`.
+    #[serde(default)]
+    pub recurrences: HashMap<String, String>,
+    /// Named dates, such as `release = "2025-10-01T12:00:00Z"`, usable
+    /// inside expressions.
+    #[serde(default)]
+    pub dates: HashMap<String, String>,
+    /// Holidays for `td holiday`, such as `christmas = "12-25"`.
+    #[serde(default)]
+    pub holidays: HashMap<String, String>,
+    /// Working hours for `td is-open` and `td next-open`, such as
+    /// `mon-fri = "09:00-18:00"`.
+    #[serde(default)]
+    pub hours: HashMap<String, String>,
+    /// Exit codes per error category, such as `config = 1`.
+    #[serde(default)]
+    pub exit_codes: ExitCodesConfig,
+    /// `formats`, validated once at load time.
+    #[serde(skip)]
+    pub(crate) presets: Presets,
+}
+
+/// One entry of the `[formats]` table.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum FormatEntry {
+    /// A named format: `br = "%d/%m/%Y"`.
+    Format(String),
+    /// A named format with its own defaults:
+    /// `jp = { format = "%Y年%m月%d日", timezone = "Asia/Tokyo" }`.
+    Preset(PresetTable),
+    /// A `[formats.<group>]` table whose formats are addressed as
+    /// `<group>.<name>`.
+    Group(HashMap<String, FormatEntry>),
+}
+
+/// A preset written as a table, carrying defaults applied when it is
+/// selected.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PresetTable {
+    /// strftime-style format.
+    pub format: String,
+    /// Time-zone used when the preset is selected and `-t` is not given.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Locale for month and weekday names, such as `pt-BR`.
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+/// The `[meet]` table.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MeetConfig {
+    /// Working hours for participants given without their own, such as
+    /// `"09:00-17:00"`.
+    #[serde(default)]
+    pub hours: Option<String>,
+}
+
+/// The `[worldclock]` table.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorldclockConfig {
+    /// Zones listed by `td worldclock`, in order.
+    #[serde(default)]
+    pub zones: Vec<String>,
+    /// Time format for the table.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// The `[workweek]` table.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkweekConfig {
+    /// Working days, such as `["mon", "tue", "wed", "thu", "fri"]`.
+    #[serde(default)]
+    pub days: Option<Vec<String>>,
+    /// First day of the week, such as `"monday"` or `"sunday"`.
+    #[serde(default)]
+    pub week_start: Option<String>,
+}
+
+impl WorkweekConfig {
+    /// The first day of the week: `flag`, else `week_start`, else Monday.
+    pub fn week_start(&self, flag: Option<&str>) -> Result<Weekday> {
+        match flag.or(self.week_start.as_deref()) {
+            Some(name) => weekday(name),
+            None => Ok(Weekday::Monday),
+        }
+    }
+
+    /// The working days: `flag`, else `days`, else Monday to Friday.
+    pub fn days(&self, flag: Option<&[String]>) -> Result<Vec<Weekday>> {
+        match flag.or(self.days.as_deref()) {
+            Some([]) => Err(user_input_error!(
+                InvalidDate,
+                "the work week needs at least one day"
+            )),
+            Some(names) => names.iter().map(|name| weekday(name)).collect(),
+            None => Ok(vec![
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+            ]),
+        }
+    }
+}
+
+fn weekday(name: &str) -> Result<Weekday> {
+    crate::recur::parse_weekday(name)
+        .ok_or_else(|| user_input_error!(InvalidDate, "unknown weekday '{}'", name))
+}
+
+/// The `[fiscal]` table.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FiscalConfig {
+    /// First month of the fiscal year, such as `"april"`, `"oct"` or `"7"`.
+    #[serde(default)]
+    pub year_start: Option<String>,
+}
+
+impl FiscalConfig {
+    /// The first month of the fiscal year: `flag`, else `year_start`, else
+    /// January.
+    pub fn year_start(&self, flag: Option<&str>) -> Result<i8> {
+        let Some(name) = flag.or(self.year_start.as_deref()) else {
+            return Ok(1);
+        };
+        crate::parser::lexer::month(name)
+            .or_else(|| name.trim().parse().ok().filter(|m| (1..=12).contains(m)))
+            .ok_or_else(|| user_input_error!(InvalidDate, "unknown month '{}'", name))
+    }
+}
+
+/// The `[humanize]` table.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HumanizeConfig {
+    /// How many units to show, largest first.
+    #[serde(default)]
+    pub granularity: Option<usize>,
+    /// Moments closer than this to now read "now", such as `"10s"`.
+    #[serde(default)]
+    pub now_within: Option<String>,
+    /// Moments on the adjacent day read "yesterday" or "tomorrow" once they
+    /// are at least this far away, such as `"12h"`.
+    #[serde(default)]
+    pub named_day_after: Option<String>,
+    /// Decimal places of decimal durations, such as `1.5h`.
+    #[serde(default)]
+    pub decimals: Option<u32>,
+    /// How decimal durations are rounded: `"nearest"`, `"up"` or `"down"`.
+    #[serde(default)]
+    pub rounding: Option<String>,
+}
+
+/// The `[exit_codes]` table.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExitCodesConfig {
+    /// Exit code for user input errors (default 64).
+    #[serde(default)]
+    pub usage: Option<NonZeroU8>,
+    /// Exit code for I/O errors (default 74).
+    #[serde(default)]
+    pub io: Option<NonZeroU8>,
+    /// Exit code for configuration errors (default 78).
+    #[serde(default)]
+    pub config: Option<NonZeroU8>,
+}
+
+impl ExitCodesConfig {
+    /// The codes to exit with, defaulting to the POSIX sysexits ones.
+    pub fn codes(&self) -> ExitCodes {
+        let code = |set: Option<NonZeroU8>, default| set.map_or(default, |c| c.get().into());
+        let default = ExitCodes::default();
+        ExitCodes {
+            usage: code(self.usage, default.usage),
+            io: code(self.io, default.io),
+            config: code(self.config, default.config),
+        }
+    }
+}
+
+fn default_pager() -> bool {
+    true
 }
 
 impl Config {
     /// Load the effective configuration, creating the file from the embedded
     /// template if it does not yet exist.
     pub fn load() -> Result<Self> {
+        Self::load_with(&mut Diagnostics::default())
+    }
+
+    /// Like [`Config::load`], reporting non-fatal problems (such as a preset
+    /// shadowing a built-in format) to `diag`.
+    pub fn load_with(diag: &mut Diagnostics) -> Result<Self> {
+        Self::load_inner(diag, true)
+    }
+
+    /// Like [`Config::load_with`], leaving out `[formats]` and
+    /// `preset_group` (`--no-presets`), so a format name is never looked up
+    /// among the user's presets and their warnings are not reported.
+    pub fn load_without_presets(diag: &mut Diagnostics) -> Result<Self> {
+        Self::load_inner(diag, false)
+    }
+
+    fn load_inner(diag: &mut Diagnostics, presets: bool) -> Result<Self> {
         let path = config_path()?;
         create_config_if_missing(&path)?;
 
-        let contents = fs::read_to_string(&path)?;
-        let mut cfg: Config = toml::from_str(&contents)
-            .map_err(|e| system_error!(Config, "failed to parse config: {}", e))?;
+        let contents = migrate_if_needed(&path, fs::read_to_string(&path)?, diag)?;
+        let mut cfg = parse(&contents).map_err(|e| e.into_error(&path, &contents))?;
+
+        cfg.apply_env();
+        if !presets {
+            cfg.formats = None;
+            cfg.preset_group = None;
+            return Ok(cfg);
+        }
+        cfg.build_presets();
+
+        for name in cfg.preset_names() {
+            if builtin_format(name).is_some() {
+                diag.warn(format!("preset '{name}' shadows a built-in format"));
+            }
+        }
+
+        cfg.check_presets(&contents, diag);
+        Ok(cfg)
+    }
+
+    /// Warn about presets with invalid formats, reusing the cached report
+    /// when the config file is unchanged since it was last validated.
+    fn check_presets(&self, contents: &str, diag: &mut Diagnostics) {
+        let cache = cache::Cache::open(&cache::fingerprint(contents)).ok();
+        let report = match cache.as_ref().and_then(|c| c.get(PRESET_CHECK_KEY)) {
+            Some(report) => report,
+            None => {
+                let report = self.preset_report();
+                if let Some(c) = &cache {
+                    let _ = c.put(PRESET_CHECK_KEY, &report);
+                }
+                report
+            }
+        };
+        for line in report.lines() {
+            diag.warn(line);
+        }
+    }
+
+    /// One line per unusable preset (invalid format, unknown reference or
+    /// reference cycle), sorted by name.
+    fn preset_report(&self) -> String {
+        self.preset_names()
+            .into_iter()
+            .filter_map(|name| {
+                let error = self.presets[name].error.as_ref()?;
+                Some(format!("preset '{name}' {error}\n"))
+            })
+            .collect()
+    }
+
+    /// Read the configuration without creating, migrating or caching
+    /// anything, for callers that must stay fast and side-effect free.
+    ///
+    /// Returns `None` when the file does not exist, otherwise the parsed
+    /// config (with environment overrides) and the raw file contents.
+    pub fn load_readonly() -> Result<Option<(Self, String)>> {
+        let path = config_path()?;
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let mut cfg = parse(&contents).map_err(|e| e.into_error(&path, &contents))?;
+        cfg.apply_env();
+        cfg.build_presets();
+        Ok(Some((cfg, contents)))
+    }
 
+    /// Overlay `TARDIS_FORMAT` and `TARDIS_TIMEZONE`.
+    fn apply_env(&mut self) {
         if let Ok(val) = env::var("TARDIS_FORMAT") {
             if !val.is_empty() {
-                cfg.format = val;
+                self.format = val;
             }
         }
         if let Ok(val) = env::var("TARDIS_TIMEZONE") {
             if !val.is_empty() {
-                cfg.timezone = val;
+                self.timezone = val;
             }
         }
-        Ok(cfg)
     }
 
-    /// Convert the `[formats]` table into a list of [`Preset`]s.
-    pub fn presets(&self) -> Vec<Preset> {
-        self.formats
-            .as_ref()
-            .map(|m| {
-                m.iter()
-                    .map(|(name, fmt)| Preset::new(name.clone(), fmt.clone()))
-                    .collect()
+    /// The `[formats]` table with groups flattened into `group.name` keys
+    /// and every entry in table form.
+    #[must_use]
+    pub fn preset_defs(&self) -> HashMap<String, PresetTable> {
+        fn flatten(
+            prefix: &str,
+            entries: &HashMap<String, FormatEntry>,
+            out: &mut HashMap<String, PresetTable>,
+        ) {
+            for (name, entry) in entries {
+                let name = format!("{prefix}{name}");
+                match entry {
+                    FormatEntry::Format(format) => {
+                        let def = PresetTable {
+                            format: format.clone(),
+                            timezone: None,
+                            locale: None,
+                        };
+                        out.insert(name, def);
+                    }
+                    FormatEntry::Preset(def) => {
+                        out.insert(name, def.clone());
+                    }
+                    FormatEntry::Group(group) => flatten(&format!("{name}."), group, out),
+                }
+            }
+        }
+
+        let mut out = HashMap::new();
+        if let Some(formats) = &self.formats {
+            flatten("", formats, &mut out);
+        }
+        out
+    }
+
+    /// Like [`Config::preset_defs`], keeping only the format strings.
+    #[must_use]
+    pub fn format_table(&self) -> HashMap<String, String> {
+        self.preset_defs()
+            .into_iter()
+            .map(|(name, def)| (name, def.format))
+            .collect()
+    }
+
+    /// Expand and validate the `[formats]` table into [`Presets`] once, so
+    /// lookups in batch mode do not re-check every format.
+    fn build_presets(&mut self) {
+        self.build_presets_with(&[]);
+    }
+
+    /// Declare or replace presets for this run only (`--define NAME=FMT`).
+    ///
+    /// A defined name replaces the whole `[formats]` entry, table defaults
+    /// included, and presets referencing it are rebuilt to see the new
+    /// format.  A defined format that cannot be used is an error right away.
+    pub fn define_presets(&mut self, defines: &[(String, String)]) -> Result<()> {
+        if defines.is_empty() {
+            return Ok(());
+        }
+        self.build_presets_with(defines);
+        for (name, _) in defines {
+            if let Some(error) = &self.presets[name].error {
+                return Err(user_input_error!(
+                    UnsupportedFormat,
+                    "defined preset '{}' {}",
+                    name,
+                    error
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build [`Presets`] from `[formats]` with `defines` laid over it.
+    fn build_presets_with(&mut self, defines: &[(String, String)]) {
+        let mut defs = self.preset_defs();
+        for (name, format) in defines {
+            let def = PresetTable {
+                format: format.clone(),
+                timezone: None,
+                locale: None,
+            };
+            defs.insert(name.clone(), def);
+        }
+        let table = defs
+            .iter()
+            .map(|(name, def)| (name.clone(), def.format.clone()))
+            .collect();
+        self.presets = core::build_presets(&table);
+        for (name, def) in defs {
+            if let Some(spec) = self.presets.get_mut(&name) {
+                spec.timezone = def.timezone;
+                spec.locale = def.locale;
+                if let Some(names) = spec.names() {
+                    spec.compiled = spec.compiled.take().map(|f| f.localized(names));
+                }
+            }
+        }
+    }
+
+    /// User-defined presets, keyed by name.
+    #[must_use]
+    pub fn presets(&self) -> &Presets {
+        &self.presets
+    }
+
+    /// Preset names in sorted order, for stable listings.
+    #[must_use]
+    pub fn preset_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.presets.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// `default_time` read as a time of day, if set.
+    pub fn default_time(&self) -> Result<Option<jiff::civil::Time>> {
+        self.default_time
+            .as_deref()
+            .map(|value| {
+                crate::parser::parse_default_time(value).ok_or_else(|| {
+                    user_input_error!(
+                        InvalidConfig,
+                        "invalid value for 'default_time': '{}'; use a time such as \"09:00\" or \"9am\", \"midnight\" or \"end-of-day\"",
+                        value
+                    )
+                })
             })
-            .unwrap_or_default()
+            .transpose()
     }
 }
 
 /// Resolve the absolute path to `config.toml`.
 #[must_use = "config_path returns a PathBuf that should not be discarded"]
 pub fn config_path() -> Result<PathBuf> {
-    let base_dir = env::var_os("XDG_CONFIG_HOME")
-        .map(PathBuf::from)
-        .or_else(dirs::config_dir)
-        .ok_or_else(|| {
-            system_error!(
-                Config,
-                "Could not locate configuration directory; set $XDG_CONFIG_HOME or ensure the OS default exists."
+    let var = |name: &str| {
+        env::var_os(name)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    };
+    let dir = config_dir_from(env::consts::OS, var, dirs::config_dir()).ok_or_else(|| {
+        system_error!(
+            Config,
+            "Could not locate configuration directory; set $TARDIS_CONFIG_DIR or ensure the OS default exists."
+        )
+    })?;
+
+    Ok(dir.join(CONFIG_FILE))
+}
+
+/// The directory holding `config.toml` on `os`, given the non-empty
+/// environment variables `var` returns and the OS default `os_default`:
+///
+/// 1. `$TARDIS_CONFIG_DIR`, as is;
+/// 2. `$XDG_CONFIG_HOME/tardis`, when that is an absolute path (the XDG
+///    spec says to ignore a relative one);
+/// 3. `tardis` in the OS default: `~/.config`, `~/Library/Application
+///    Support`, or the roaming `%APPDATA%` on Windows, so the file follows
+///    the user between machines like other settings do;
+/// 4. without an OS default, the same places built from `$APPDATA`,
+///    `%USERPROFILE%` or `$HOME`, which may be UNC paths such as
+///    `\\server\home\user`.
+fn config_dir_from(
+    os: &str,
+    var: impl Fn(&str) -> Option<PathBuf>,
+    os_default: Option<PathBuf>,
+) -> Option<PathBuf> {
+    if let Some(dir) = var("TARDIS_CONFIG_DIR") {
+        return Some(dir);
+    }
+    if let Some(base) = var("XDG_CONFIG_HOME").filter(|p| p.is_absolute()) {
+        return Some(base.join(APP_DIR));
+    }
+    let base = os_default.or_else(|| match os {
+        "windows" => var("APPDATA")
+            .or_else(|| var("USERPROFILE").map(|home| home.join("AppData").join("Roaming"))),
+        "macos" => var("HOME").map(|home| home.join("Library").join("Application Support")),
+        _ => var("HOME").map(|home| home.join(".config")),
+    })?;
+    Some(base.join(APP_DIR))
+}
+
+/// Set `timezone` in the config file, keeping its comments and layout.
+///
+/// Creates the file from the template first if needed, and returns its path.
+pub fn set_default_timezone(zone: &str) -> Result<PathBuf> {
+    let path = config_path()?;
+    create_config_if_missing(&path)?;
+    let mut doc = parse_document(&path, &fs::read_to_string(&path)?)?;
+    doc["timezone"] = toml_edit::value(zone);
+    save(&path, &doc.to_string())?;
+    Ok(path)
+}
+
+/// Set the dotted `key` (such as `timezone` or `formats.work.iso`) to
+/// `raw` in the config file, keeping its comments and layout.
+///
+/// `raw` is read as a TOML value when that fits the key (`2`, `true`,
+/// `["UTC"]`) and as a string otherwise.  The key must be one the
+/// [`SCHEMA`] knows, and the edited file must still load -- with a known
+/// time zone and a usable preset format -- before anything is written.
+/// Creates the file from the template first if needed, and returns its path.
+pub fn set_value(key: &str, raw: &str) -> Result<PathBuf> {
+    let path = config_path()?;
+    create_config_if_missing(&path)?;
+    let contents = fs::read_to_string(&path)?;
+    let segments: Vec<&str> = key.split('.').collect();
+    if segments.iter().any(|s| s.is_empty()) || !schema_has_key(&segments) {
+        return Err(user_input_error!(
+            InvalidConfig,
+            "unknown config key '{}'; see `td config schema`",
+            key
+        ));
+    }
+
+    let literal = raw.parse::<toml_edit::Value>().ok();
+    let candidates = literal.into_iter().chain([toml_edit::Value::from(raw)]);
+    let mut first_error = None;
+    for candidate in candidates {
+        let mut doc = parse_document(&path, &contents)?;
+        insert_value(&mut doc, &segments, candidate)?;
+        let edited = doc.to_string();
+        match check_edit(&edited, key) {
+            Ok(()) => {
+                save(&path, &edited)?;
+                return Ok(path);
+            }
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    Err(first_error.unwrap_or_else(|| user_input_error!(InvalidConfig, "invalid value")))
+}
+
+/// The value of the dotted `key` as `td` sees it: `format` and `timezone`
+/// include their `TARDIS_*` overrides, other keys come from the file with
+/// its matching `[when]` tables merged, and unset keys with a default in
+/// the [`SCHEMA`] report that default.  Strings are printed bare; anything
+/// else as TOML.
+pub fn get_value(key: &str) -> Result<String> {
+    let cfg = Config::load()?;
+    match key {
+        "format" => return Ok(cfg.format),
+        "timezone" => return Ok(cfg.timezone),
+        _ => {}
+    }
+    let segments: Vec<&str> = key.split('.').collect();
+    let path = config_path()?;
+    let contents = fs::read_to_string(&path)?;
+    let table = merged_table(&contents, &Machine::current())
+        .and_then(|table| {
+            Ok(toml::Table::deserialize(toml::de::Deserializer::from(
+                table,
+            ))?)
+        })
+        .map_err(|e| e.into_error(&path, &contents))?;
+    let mut value: Option<&toml::Value> = None;
+    for segment in &segments {
+        let next = match value {
+            None => table.get(*segment),
+            Some(toml::Value::Table(t)) => t.get(*segment),
+            Some(_) => None,
+        };
+        match next {
+            Some(next) => value = Some(next),
+            None => {
+                return schema_default(&segments)
+                    .ok_or_else(|| user_input_error!(MissingArgument, "'{}' is not set", key));
+            }
+        }
+    }
+    Ok(match value {
+        Some(toml::Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => String::new(),
+    })
+}
+
+/// Write `value` at `segments`, creating the tables on the way and keeping
+/// the comments around a value it replaces.
+fn insert_value(
+    doc: &mut toml_edit::DocumentMut,
+    segments: &[&str],
+    mut value: toml_edit::Value,
+) -> Result<()> {
+    let (last, parents) = segments
+        .split_last()
+        .ok_or_else(|| user_input_error!(InvalidConfig, "empty config key"))?;
+    let mut table: &mut dyn toml_edit::TableLike = doc.as_table_mut();
+    for (depth, segment) in parents.iter().enumerate() {
+        let entry = table.entry(segment).or_insert_with(|| {
+            let mut t = toml_edit::Table::new();
+            t.set_implicit(depth + 1 < parents.len());
+            toml_edit::Item::Table(t)
+        });
+        table = entry.as_table_like_mut().ok_or_else(|| {
+            user_input_error!(
+                InvalidConfig,
+                "'{}' is not a table",
+                segments[..=depth].join(".")
+            )
+        })?;
+    }
+    match table.get_mut(last) {
+        Some(slot) => {
+            if let Some(old) = slot.as_value() {
+                *value.decor_mut() = old.decor().clone();
+            }
+            *slot = toml_edit::Item::Value(value);
+        }
+        None => {
+            table.insert(last, toml_edit::Item::Value(value));
+        }
+    }
+    Ok(())
+}
+
+/// Check that `contents` still loads after `key` was set, rejecting an
+/// unknown time zone or a preset format that cannot be used.
+fn check_edit(contents: &str, key: &str) -> Result<()> {
+    let mut cfg = parse(contents).map_err(|e| {
+        user_input_error!(InvalidConfig, "invalid value for '{}': {}", key, e.message)
+    })?;
+    let mut diag = Diagnostics::default();
+    if key == "timezone" && !cfg.timezone.is_empty() {
+        core::lookup_timezone(&cfg.timezone, &mut diag)?;
+    }
+    if key == "worldclock.zones" {
+        for zone in &cfg.worldclock.zones {
+            core::lookup_timezone(zone, &mut diag)?;
+        }
+    }
+    if let Some(hours) = cfg.meet.hours.as_deref().filter(|_| key == "meet.hours") {
+        let _ = hours.parse::<crate::meet::Hours>()?;
+    }
+    if key.starts_with("humanize.") {
+        let _ = crate::humanize::Humanizer::from_config(&cfg.humanize)?;
+        let _ = crate::duration::Decimals::from_config(&cfg.humanize, None, None)?;
+    }
+    if let Some(expr) = key
+        .strip_prefix("recurrences.")
+        .and_then(|name| cfg.recurrences.get(name))
+    {
+        let _ = crate::recur::Recurrence::parse(expr, &jiff::Zoned::now())?;
+    }
+    if let Some(value) = key
+        .strip_prefix("dates.")
+        .and_then(|name| cfg.dates.get(name))
+    {
+        crate::parser::parse(
+            value,
+            &jiff::Zoned::now(),
+            &crate::parser::ParseOptions::default(),
+        )
+        .map_err(|e| {
+            user_input_error!(
+                InvalidConfig,
+                "invalid value for '{}': {}",
+                key,
+                e.format_message()
             )
         })?;
+    }
+    if key.starts_with("holidays.") {
+        let _ = crate::holiday::Calendar::from_table(&cfg.holidays, &jiff::Zoned::now())?;
+    }
+    if key.starts_with("hours.") {
+        let _ = crate::hours::Schedule::from_table(
+            &cfg.hours,
+            &[],
+            crate::holiday::Calendar::default(),
+            &jiff::Zoned::now(),
+        )?;
+    }
+    if key == "fiscal.year_start" {
+        cfg.fiscal.year_start(None)?;
+    }
+    if key.starts_with("workweek.") {
+        cfg.workweek.week_start(None)?;
+        cfg.workweek.days(None)?;
+    }
+
+    let Some(entry) = key.strip_prefix("formats.") else {
+        return Ok(());
+    };
+    cfg.build_presets();
+    let (name, field) = match entry.rsplit_once('.') {
+        Some((name, field @ ("format" | "timezone" | "locale"))) => (name, Some(field)),
+        _ => (entry, None),
+    };
+    let Some(preset) = cfg.presets.get(name) else {
+        return Err(user_input_error!(
+            InvalidConfig,
+            "'formats.{}' is not a preset; set its format first",
+            name
+        ));
+    };
+    if let Some(error) = &preset.error {
+        return Err(user_input_error!(
+            InvalidConfig,
+            "preset '{}' {}",
+            name,
+            error
+        ));
+    }
+    if let Some(zone) = preset
+        .timezone
+        .as_deref()
+        .filter(|_| field == Some("timezone"))
+    {
+        core::lookup_timezone(zone, &mut diag)?;
+    }
+    Ok(())
+}
+
+/// How serious a [`Finding`] of [`validate`] is.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The file does not load, or `td` rejects a value when it is used.
+    Error,
+    /// The file works, but something in it is likely a mistake.
+    Warning,
+}
+
+/// One problem [`validate`] found in the config file.
+#[must_use]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Dotted key the problem is about, such as `formats.br`.
+    pub key: Option<String>,
+    /// 1-based line and column of the key, or of a syntax error, in the file.
+    pub location: Option<(usize, usize)>,
+    pub message: String,
+}
+
+/// Check the config file at `path` the way `td` reads it on this machine,
+/// without creating, migrating or caching anything.
+///
+/// Errors stop `td` from loading the file or from using a value: a syntax
+/// error, an unknown time zone, a date that does not parse.  Warnings are
+/// for what works but is likely a mistake: a key `td` ignores, a format
+/// `td lint-format` flags, a preset shadowing a built-in, a file that
+/// `td config migrate` would upgrade.  Errors come first, then warnings,
+/// each in file order.
+pub fn validate(path: &Path) -> Result<Vec<Finding>> {
+    let contents = fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            system_error!(Config, "{} does not exist", path.display())
+        }
+        _ => e.into(),
+    })?;
+    Ok(validate_contents(&contents, &jiff::Zoned::now()))
+}
+
+/// The findings of [`validate`] for `contents`, resolving relative dates
+/// against `now`.
+fn validate_contents(contents: &str, now: &jiff::Zoned) -> Vec<Finding> {
+    let parsed = merged_table(contents, &Machine::current())
+        .and_then(|table| Ok((table.into_inner(), parse(contents)?)));
+    let (table, mut cfg) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let location = e
+                .span
+                .filter(|span| span.start < contents.len())
+                .map(|span| line_column(contents, span.start));
+            return vec![Finding {
+                severity: Severity::Error,
+                key: None,
+                location,
+                message: e.message,
+            }];
+        }
+    };
+    cfg.build_presets();
+    let mut check = Checker {
+        contents,
+        table,
+        findings: Vec::new(),
+    };
+
+    if let Ok(mut doc) = contents.parse::<toml_edit::DocumentMut>() {
+        match migrate::migrate(&mut doc) {
+            Ok(Some(report)) => check.warn(
+                "version",
+                format!(
+                    "the file is at version {}; `td config migrate` upgrades it to {}",
+                    report.from, report.to
+                ),
+            ),
+            Ok(None) => {}
+            Err(e) => check.error("version", e.to_string()),
+        }
+    }
+    check.unknown_keys();
+
+    if !cfg.timezone.is_empty() {
+        check.run("timezone", |diag| {
+            core::lookup_timezone(&cfg.timezone, diag)
+        });
+    }
+    check.run("format", |diag| {
+        if cfg.format.trim().is_empty() {
+            return Err(user_input_error!(
+                MissingArgument,
+                "no output format specified"
+            ));
+        }
+        let scoped = cfg
+            .preset_group
+            .as_ref()
+            .map(|g| format!("{g}.{}", cfg.format));
+        if scoped
+            .iter()
+            .chain([&cfg.format])
+            .any(|name| cfg.presets.contains_key(name))
+        {
+            return Ok(());
+        }
+        lint_format(&cfg.format, diag)
+    });
+    check.run("default_time", |_| cfg.default_time());
+    if let Some(group) = &cfg.preset_group {
+        let prefix = format!("{group}.");
+        if !cfg.presets.keys().any(|name| name.starts_with(&prefix)) {
+            check.warn(
+                "preset_group",
+                format!("preset group '{group}' is not defined in [formats]"),
+            );
+        }
+    }
+    for name in cfg.preset_names() {
+        let preset = &cfg.presets[name];
+        let key = format!("formats.{name}");
+        if builtin_format(name).is_some() {
+            check.warn(&key, format!("preset '{name}' shadows a built-in format"));
+        }
+        match &preset.error {
+            Some(error) => check.error(&key, format!("preset '{name}' {error}")),
+            None => {
+                check.run(&key, |diag| {
+                    let mut own = Diagnostics::default();
+                    lint_format(&preset.format, &mut own)?;
+                    for warning in own.warnings() {
+                        diag.warn(format!("preset '{name}': {warning}"));
+                    }
+                    Ok(())
+                });
+            }
+        }
+        if let Some(locale) = preset
+            .locale
+            .as_ref()
+            .filter(|locale| i18n::catalog_lang(locale).is_none())
+        {
+            check.warn(
+                &format!("{key}.locale"),
+                format!(
+                    "preset '{name}' sets locale '{locale}', which td has no names for; names stay in English"
+                ),
+            );
+        }
+        if let Some(zone) = &preset.timezone {
+            check.run(&format!("{key}.timezone"), |diag| {
+                core::lookup_timezone(zone, diag)
+            });
+        }
+    }
+
+    for zone in &cfg.worldclock.zones {
+        check.run("worldclock.zones", |diag| core::lookup_timezone(zone, diag));
+    }
+    if let Some(format) = &cfg.worldclock.format {
+        check.run("worldclock.format", |diag| match builtin_format(format) {
+            Some(_) => Ok(()),
+            None => lint_format(format, diag),
+        });
+    }
+    if let Some(hours) = &cfg.meet.hours {
+        check.run("meet.hours", |_| hours.parse::<crate::meet::Hours>());
+    }
+    if cfg.humanize != HumanizeConfig::default() {
+        check.run("humanize", |_| {
+            let _ = crate::humanize::Humanizer::from_config(&cfg.humanize)?;
+            crate::duration::Decimals::from_config(&cfg.humanize, None, None)
+        });
+    }
+    check.run("fiscal.year_start", |_| cfg.fiscal.year_start(None));
+    check.run("workweek.week_start", |_| cfg.workweek.week_start(None));
+    check.run("workweek.days", |_| cfg.workweek.days(None));
+
+    for (name, expr) in sorted(&cfg.recurrences) {
+        check.run(&format!("recurrences.{name}"), |_| {
+            crate::recur::Recurrence::parse(expr, now)
+        });
+    }
+    for (name, value) in sorted(&cfg.dates) {
+        check.run(&format!("dates.{name}"), |_| {
+            crate::parser::parse(value, now, &crate::parser::ParseOptions::default())
+                .map_err(|e| user_input_error!(InvalidDate, "{}", e.format_message()))
+        });
+    }
+    for (name, value) in sorted(&cfg.holidays) {
+        let one = HashMap::from([(name.clone(), value.clone())]);
+        check.run(&format!("holidays.{name}"), |_| {
+            crate::holiday::Calendar::from_table(&one, now)
+        });
+    }
+    // Each entry on its own first, so a bad one is reported at its key;
+    // then together, for days two entries both set.
+    let schedule = |table: &HashMap<String, String>| {
+        crate::hours::Schedule::from_table(table, &[], crate::holiday::Calendar::default(), now)
+    };
+    let mut hours_ok = true;
+    for (name, value) in sorted(&cfg.hours) {
+        let one = HashMap::from([(name.clone(), value.clone())]);
+        hours_ok &= check.run(&format!("hours.{name}"), |_| schedule(&one));
+    }
+    if hours_ok && !cfg.hours.is_empty() {
+        check.run("hours", |_| schedule(&cfg.hours));
+    }
+
+    let mut findings = check.findings;
+    findings.sort_by_key(|f| (f.severity, f.location.is_none(), f.location));
+    findings
+}
+
+/// Entries of `table` sorted by key, for findings in a stable order.
+fn sorted(table: &HashMap<String, String>) -> Vec<(&String, &String)> {
+    let mut entries: Vec<_> = table.iter().collect();
+    entries.sort_unstable();
+    entries
+}
+
+/// Fail on an unsupported specifier in `format` and warn about the likely
+/// mistakes `td lint-format` reports.
+fn lint_format(format: &str, diag: &mut Diagnostics) -> Result<()> {
+    for warning in crate::lint::lint(format)?.warnings {
+        diag.warn(warning);
+    }
+    Ok(())
+}
+
+/// Collects the [`Finding`]s of [`validate`], locating each key in the
+/// file.
+struct Checker<'i> {
+    contents: &'i str,
+    /// The file's top-level table with this machine's `[when]` tables
+    /// merged in.
+    table: DeTable<'i>,
+    findings: Vec<Finding>,
+}
+
+impl Checker<'_> {
+    fn error(&mut self, key: &str, message: String) {
+        self.report(Severity::Error, key, message);
+    }
+
+    fn warn(&mut self, key: &str, message: String) {
+        self.report(Severity::Warning, key, message);
+    }
+
+    fn report(&mut self, severity: Severity, key: &str, message: String) {
+        let location = self
+            .key_span(key)
+            .map(|span| line_column(self.contents, span.start));
+        self.findings.push(Finding {
+            severity,
+            key: Some(key.to_string()),
+            location,
+            message,
+        });
+    }
+
+    /// Report the error `check` returns and the warnings it raises against
+    /// `key`; true when it succeeded.
+    fn run<T>(&mut self, key: &str, check: impl FnOnce(&mut Diagnostics) -> Result<T>) -> bool {
+        let mut diag = Diagnostics::default();
+        let result = check(&mut diag);
+        for warning in diag.warnings() {
+            self.warn(key, warning.clone());
+        }
+        match result {
+            Ok(_) => true,
+            Err(e) => {
+                self.error(key, e.to_string());
+                false
+            }
+        }
+    }
+
+    /// Where the dotted `key` is written, or the closest table holding it.
+    fn key_span(&self, key: &str) -> Option<Range<usize>> {
+        let mut table = &self.table;
+        let mut span = None;
+        for segment in key.split('.') {
+            let Some((name, value)) = table.get_key_value(segment) else {
+                break;
+            };
+            span = Some(name.span());
+            match value.get_ref() {
+                DeValue::Table(inner) => table = inner,
+                _ => break,
+            }
+        }
+        span
+    }
+
+    /// Warn about keys the [`SCHEMA`] does not know, which `td` ignores.
+    fn unknown_keys(&mut self) {
+        fn walk(table: &DeTable<'_>, path: &mut Vec<String>, unknown: &mut Vec<String>) {
+            for (name, value) in table {
+                path.push(name.get_ref().to_string());
+                let segments: Vec<&str> = path.iter().map(String::as_str).collect();
+                if !schema_has_key(&segments) {
+                    unknown.push(path.join("."));
+                } else if let DeValue::Table(inner) = value.get_ref() {
+                    walk(inner, path, unknown);
+                }
+                path.pop();
+            }
+        }
+        let mut unknown = Vec::new();
+        walk(&self.table, &mut Vec::new(), &mut unknown);
+        for key in unknown {
+            self.warn(&key, format!("unknown key '{key}' is ignored"));
+        }
+    }
+}
+
+/// The [`SCHEMA`] node for the dotted path `segments`, if the schema
+/// allows it.
+fn schema_node(segments: &[&str]) -> Option<serde_json::Value> {
+    fn resolve<'a>(
+        root: &'a serde_json::Value,
+        node: &'a serde_json::Value,
+    ) -> &'a serde_json::Value {
+        match node["$ref"]
+            .as_str()
+            .and_then(|r| r.strip_prefix("#/$defs/"))
+        {
+            Some(name) => &root["$defs"][name],
+            None => node,
+        }
+    }
+    fn walk(
+        root: &serde_json::Value,
+        node: &serde_json::Value,
+        segments: &[&str],
+    ) -> Option<serde_json::Value> {
+        let node = resolve(root, node);
+        let Some((first, rest)) = segments.split_first() else {
+            return Some(node.clone());
+        };
+        if let Some(branches) = node["anyOf"].as_array() {
+            return branches.iter().find_map(|b| walk(root, b, segments));
+        }
+        if let Some(child) = node["properties"].get(first) {
+            return walk(root, child, rest);
+        }
+        match &node["additionalProperties"] {
+            child @ serde_json::Value::Object(_) => walk(root, child, rest),
+            _ => None,
+        }
+    }
+    let root: serde_json::Value = serde_json::from_str(SCHEMA).ok()?;
+    walk(&root, &root, segments)
+}
+
+fn schema_has_key(segments: &[&str]) -> bool {
+    schema_node(segments).is_some()
+}
 
-    Ok(base_dir.join(APP_DIR).join(CONFIG_FILE))
+fn schema_default(segments: &[&str]) -> Option<String> {
+    let default = schema_node(segments)?.get("default")?.clone();
+    Some(match default {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    })
+}
+
+/// What the `[when]` tables are matched against.
+#[derive(Debug)]
+struct Machine {
+    /// Names for `[when.os.<name>]`, most general first.
+    os: Vec<String>,
+    /// Names for `[when.host.<name>]`, most general first.
+    host: Vec<String>,
+}
+
+impl Machine {
+    /// This machine: its OS family and OS (`unix`, `linux`), and its short
+    /// and full hostname.
+    fn current() -> Self {
+        let host = gethostname::gethostname().to_string_lossy().into_owned();
+        let short = host.split('.').next().unwrap_or_default().to_string();
+        let host = if short == host {
+            vec![host]
+        } else {
+            vec![short, host]
+        };
+        Self {
+            os: vec![env::consts::FAMILY.into(), env::consts::OS.into()],
+            host,
+        }
+    }
+}
+
+/// A problem with the config file, and where in the file it is when known.
+#[derive(Debug)]
+struct ParseError {
+    message: String,
+    span: Option<Range<usize>>,
+}
+
+impl ParseError {
+    fn at(span: Range<usize>, message: String) -> Self {
+        Self {
+            message,
+            span: Some(span),
+        }
+    }
+
+    /// Report the problem in `contents`, read from `path`, as a config
+    /// error: the message, then the file, line and column, and the line
+    /// itself with the offending part underlined.
+    fn into_error(self, path: &Path, contents: &str) -> Error {
+        let Some(span) = self.span.filter(|span| span.start < contents.len()) else {
+            return system_error!(
+                Config,
+                "failed to parse config: {}\n --> {}",
+                self.message,
+                path.display()
+            );
+        };
+        let line_start = contents[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = contents[span.start..]
+            .find('\n')
+            .map_or(contents.len(), |i| span.start + i);
+        let (line, column) = line_column(contents, span.start);
+        let end = span.end.clamp(span.start, line_end);
+        let width = contents[span.start..end].chars().count().max(1);
+        let gutter = " ".repeat(line.to_string().len());
+        system_error!(
+            Config,
+            "failed to parse config: {}\n{gutter}--> {}:{line}:{column}\n{gutter} |\n{line} | {}\n{gutter} | {}{}",
+            self.message,
+            path.display(),
+            contents[line_start..line_end].trim_end_matches('\r'),
+            " ".repeat(column - 1),
+            "^".repeat(width)
+        )
+    }
+}
+
+/// The 1-based line and column of the byte `offset` in `contents`.
+fn line_column(contents: &str, offset: usize) -> (usize, usize) {
+    let line_start = contents[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = contents[..offset].matches('\n').count() + 1;
+    (line, contents[line_start..offset].chars().count() + 1)
+}
+
+impl From<toml::de::Error> for ParseError {
+    fn from(e: toml::de::Error) -> Self {
+        Self {
+            message: e.message().trim_end().to_string(),
+            span: e.span(),
+        }
+    }
+}
+
+impl From<toml_edit::TomlError> for ParseError {
+    fn from(e: toml_edit::TomlError) -> Self {
+        Self {
+            message: e.message().trim_end().to_string(),
+            span: e.span(),
+        }
+    }
+}
+
+/// Parse `contents` into a [`Config`] for this machine.
+fn parse(contents: &str) -> std::result::Result<Config, ParseError> {
+    let table = merged_table(contents, &Machine::current())?;
+    Config::deserialize(toml::de::Deserializer::from(table)).map_err(|e| {
+        let mut e = ParseError::from(e);
+        // The document itself spans `0..0`, so a missing top-level key has
+        // nowhere to point.
+        if e.span == Some(0..0) {
+            e.span = None;
+        }
+        e
+    })
+}
+
+/// Parse `contents` for editing, reporting errors against `path`.
+fn parse_document(path: &Path, contents: &str) -> Result<toml_edit::DocumentMut> {
+    contents
+        .parse()
+        .map_err(|e: toml_edit::TomlError| ParseError::from(e).into_error(path, contents))
+}
+
+/// The top-level table of `contents` with the `[when]` tables matching
+/// `machine` merged over it: OS tables first, then host tables, each from
+/// most general to most specific.  Names match case-insensitively.  Values
+/// keep their place in `contents`, so errors found later point into it.
+fn merged_table<'i>(
+    contents: &'i str,
+    machine: &Machine,
+) -> std::result::Result<toml::Spanned<DeTable<'i>>, ParseError> {
+    let mut root = DeTable::parse(contents)?;
+    let Some((when_key, when)) = root.get_mut().remove_entry("when") else {
+        return Ok(root);
+    };
+    let DeValue::Table(mut when) = when.into_inner() else {
+        return Err(ParseError::at(
+            when_key.span(),
+            "`when` must be a table".into(),
+        ));
+    };
+    if let Some(kind) = when
+        .keys()
+        .find(|k| !matches!(k.get_ref().as_ref(), "os" | "host"))
+    {
+        return Err(ParseError::at(
+            kind.span(),
+            format!("unknown condition `when.{kind}`; expected `when.os` or `when.host`"),
+        ));
+    }
+    for (kind, names) in [("os", &machine.os), ("host", &machine.host)] {
+        let Some(blocks) = when.remove(kind) else {
+            continue;
+        };
+        let blocks_span = blocks.span();
+        let DeValue::Table(mut blocks) = blocks.into_inner() else {
+            return Err(ParseError::at(
+                blocks_span,
+                format!("`when.{kind}` must be a table"),
+            ));
+        };
+        if let Some((name, value)) = blocks.iter().find(|(_, v)| !v.get_ref().is_table()) {
+            return Err(ParseError::at(
+                value.span(),
+                format!("`when.{kind}.{name}` must be a table"),
+            ));
+        }
+        for name in names {
+            let key = blocks
+                .keys()
+                .find(|k| k.get_ref().eq_ignore_ascii_case(name))
+                .cloned();
+            if let Some(DeValue::Table(block)) = key
+                .and_then(|k| blocks.remove(&k))
+                .map(toml::Spanned::into_inner)
+            {
+                merge_table(root.get_mut(), block);
+            }
+        }
+    }
+    Ok(root)
+}
+
+/// Merge `overrides` into `base`, recursing into tables present in both and
+/// replacing everything else.
+fn merge_table<'i>(base: &mut DeTable<'i>, overrides: DeTable<'i>) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(inner), value) if inner.get_ref().is_table() && value.get_ref().is_table() => {
+                if let (DeValue::Table(inner), DeValue::Table(value)) =
+                    (inner.get_mut(), value.into_inner())
+                {
+                    merge_table(inner, value);
+                }
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// A pending upgrade of the config file to the current schema version.
+#[must_use]
+#[derive(Debug)]
+pub struct MigrationPlan {
+    /// The config file.
+    pub path: PathBuf,
+    /// The steps that would be applied.
+    pub report: migrate::MigrationReport,
+    original: String,
+    migrated: String,
+}
+
+impl MigrationPlan {
+    /// Work out how `contents`, read from `path`, would be upgraded, or
+    /// `None` when it is already current.
+    pub fn new(path: &Path, contents: String) -> Result<Option<Self>> {
+        let mut doc = parse_document(path, &contents)?;
+        Ok(migrate::migrate(&mut doc)?.map(|report| Self {
+            path: path.to_path_buf(),
+            report,
+            original: contents,
+            migrated: doc.to_string(),
+        }))
+    }
+
+    /// A unified diff from the current file to the migrated one.
+    #[must_use]
+    pub fn diff(&self) -> String {
+        let old = format!("{} (v{})", self.path.display(), self.report.from);
+        let new = format!("{} (v{})", self.path.display(), self.report.to);
+        similar::TextDiff::from_lines(&self.original, &self.migrated)
+            .unified_diff()
+            .header(&old, &new)
+            .to_string()
+    }
+
+    /// Copy the original to `config.toml.v<N>.bak` in `backup_dir` (the
+    /// config directory by default), then write the migrated file.  Returns
+    /// the backup path.
+    pub fn apply(&self, backup_dir: Option<&Path>) -> Result<PathBuf> {
+        let name = format!("{CONFIG_FILE}.v{}.bak", self.report.from);
+        let backup = match backup_dir {
+            Some(dir) => {
+                fs::create_dir_all(dir)?;
+                dir.join(name)
+            }
+            None => self.path.with_file_name(name),
+        };
+        write_atomic(&backup, &self.original)?;
+        write_atomic(&self.path, &self.migrated)?;
+        Ok(backup)
+    }
+}
+
+/// Plan the upgrade of the config file, or `None` when it is missing or
+/// already current.
+pub fn plan_migration() -> Result<Option<MigrationPlan>> {
+    let path = config_path()?;
+    match fs::read_to_string(&path) {
+        Ok(contents) => MigrationPlan::new(&path, contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Upgrade the file at `path` to the current schema version, if needed.
+///
+/// The original is copied to `config.toml.v<N>.bak` before the migrated
+/// document is written back; the applied steps are reported as a note.
+fn migrate_if_needed(path: &Path, contents: String, diag: &mut Diagnostics) -> Result<String> {
+    let Some(plan) = MigrationPlan::new(path, contents.clone())? else {
+        return Ok(contents);
+    };
+    let backup = plan.apply(None)?;
+
+    diag.note(format!(
+        "upgraded {} from version {} to {} ({}); backup saved to {}",
+        path.display(),
+        plan.report.from,
+        plan.report.to,
+        plan.report.changes.join("; "),
+        backup.display()
+    ));
+    Ok(plan.migrated)
 }
 
 /// Create the configuration file (and parent directory) if it is missing.
+///
+/// Many shells starting at once (a restored tmux session) race to do this,
+/// so the template is written to a temporary file and hard-linked into
+/// place: the link fails when another process got there first, and never
+/// replaces a file that process may already have edited.
 fn create_config_if_missing(path: &Path) -> Result<()> {
     if path.exists() {
         return Ok(());
@@ -95,10 +1476,114 @@ fn create_config_if_missing(path: &Path) -> Result<()> {
         fs::create_dir_all(parent)?;
     }
 
-    fs::write(path, TEMPLATE.trim_start())?;
+    let tmp = write_temp(path, TEMPLATE.trim_start())?;
+    let linked = fs::hard_link(&tmp, path);
+    let _ = fs::remove_file(&tmp);
+    match linked {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(()),
+        // No hard links on this file system: create the file exclusively
+        // instead, at the cost of a moment where it is being written.
+        Err(_) => {
+            use std::io::Write;
+
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+            {
+                Ok(mut file) => Ok(file.write_all(TEMPLATE.trim_start().as_bytes())?),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(()),
+                Err(e) => Err(e.into()),
+            }
+        }
+    }
+}
+
+/// Replace the config file at `path` with `contents`, first copying the
+/// current file to `config.toml.<UTC time>.bak` next to it and keeping only
+/// the newest [`BACKUPS_KEPT`] of those.
+fn save(path: &Path, contents: &str) -> Result<()> {
+    if let Ok(current) = fs::read_to_string(path) {
+        let stamp = jiff::Timestamp::now().strftime("%Y%m%dT%H%M%S%.3fZ");
+        let backup = path.with_file_name(format!("{CONFIG_FILE}.{stamp}.bak"));
+        // Two saves within a millisecond: the first backup is the older
+        // state, so keep it.
+        if !backup.exists() {
+            write_atomic(&backup, &current)?;
+        }
+        prune_backups(path)?;
+    }
+    write_atomic(path, contents)
+}
+
+/// Remove all but the newest [`BACKUPS_KEPT`] timestamped backups of the
+/// config file at `path`.  Migration backups (`.v<N>.bak`) are left alone.
+fn prune_backups(path: &Path) -> Result<()> {
+    let Some(dir) = path.parent() else {
+        return Ok(());
+    };
+    let prefix = format!("{CONFIG_FILE}.");
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix(&prefix)?.strip_suffix(".bak"))
+                .is_some_and(|stamp| {
+                    stamp.ends_with('Z') && stamp.starts_with(|c: char| c.is_ascii_digit())
+                })
+        })
+        .collect();
+    backups.sort();
+    let excess = backups.len().saturating_sub(BACKUPS_KEPT);
+    for old in &backups[..excess] {
+        fs::remove_file(old)?;
+    }
+    Ok(())
+}
+
+/// Write `contents` to `path` through a temporary file in the same
+/// directory and a rename, so readers see either the old file or the new
+/// one and never a partial write.
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let tmp = write_temp(path, contents)?;
+    if let Err(e) = fs::rename(&tmp, path) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e.into());
+    }
     Ok(())
 }
 
+/// Write `contents` to a new temporary file next to `path`, named after it,
+/// this process and a counter so concurrent writers never share one.
+fn write_temp(path: &Path, contents: &str) -> Result<PathBuf> {
+    use std::{
+        io::Write,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(CONFIG_FILE);
+    let tmp = path.with_file_name(format!(
+        ".{name}.{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let written = fs::File::create(&tmp).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    Ok(tmp)
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::System(SystemError::Io(e))
@@ -191,7 +1676,7 @@ short = "%H:%M"
         assert_eq!(cfg.format, "%Y");
         assert_eq!(cfg.timezone, "UTC");
         assert_eq!(cfg.presets().len(), 1);
-        assert_eq!(cfg.presets()[0].name, "short");
+        assert_eq!(cfg.preset_names(), ["short"]);
     }
 
     #[test]
@@ -234,23 +1719,40 @@ short = "%H:%M"
 
     #[test]
     fn presets_conversion_from_formats_table() {
-        let cfg = Config {
+        let mut cfg = Config {
             format: "%Y".into(),
             timezone: "UTC".into(),
 
             formats: Some(
                 [
-                    ("iso".to_string(), "%Y-%m-%d".to_string()),
-                    ("time".to_string(), "%H:%M".to_string()),
+                    ("iso".to_string(), FormatEntry::Format("%Y-%m-%d".into())),
+                    ("time".to_string(), FormatEntry::Format("%H:%M".into())),
                 ]
                 .into_iter()
                 .collect(),
             ),
+            preset_group: None,
+            default_time: None,
+            interactive: false,
+            pager: true,
+            meet: MeetConfig::default(),
+            worldclock: WorldclockConfig::default(),
+            humanize: HumanizeConfig::default(),
+            workweek: WorkweekConfig::default(),
+            fiscal: FiscalConfig::default(),
+            recurrences: HashMap::new(),
+            dates: HashMap::new(),
+            holidays: HashMap::new(),
+            hours: HashMap::new(),
+            exit_codes: ExitCodesConfig::default(),
+            presets: Presets::new(),
         };
+        cfg.build_presets();
         let presets = cfg.presets();
         assert_eq!(presets.len(), 2);
-        assert!(presets.iter().any(|p| p.name == "iso"));
-        assert!(presets.iter().any(|p| p.format == "%H:%M"));
+        assert_eq!(presets["iso"].format, "%Y-%m-%d");
+        assert!(presets["time"].is_valid());
+        assert_eq!(cfg.preset_names(), ["iso", "time"]);
     }
 
     #[test]
@@ -260,10 +1762,49 @@ short = "%H:%M"
             timezone: "UTC".into(),
 
             formats: None,
+            preset_group: None,
+            default_time: None,
+            interactive: false,
+            pager: true,
+            meet: MeetConfig::default(),
+            worldclock: WorldclockConfig::default(),
+            humanize: HumanizeConfig::default(),
+            workweek: WorkweekConfig::default(),
+            fiscal: FiscalConfig::default(),
+            recurrences: HashMap::new(),
+            dates: HashMap::new(),
+            holidays: HashMap::new(),
+            hours: HashMap::new(),
+            exit_codes: ExitCodesConfig::default(),
+            presets: Presets::new(),
         };
         assert!(cfg.presets().is_empty());
     }
 
+    #[test]
+    fn defined_presets_override_the_formats_table() {
+        let contents = "format = \"%Y\"\ntimezone = \"UTC\"\n[formats]\nday = { format = \"%d/%m\", timezone = \"Asia/Tokyo\" }\nlog = \"{day} %H:%M\"\n";
+        let mut cfg = parse(contents).unwrap();
+        cfg.build_presets();
+        let defines = [
+            ("day".to_string(), "%F".to_string()),
+            ("work.short".to_string(), "%m-%d".to_string()),
+        ];
+        cfg.define_presets(&defines).unwrap();
+        assert_eq!(cfg.presets()["day"].format, "%F");
+        assert_eq!(cfg.presets()["day"].timezone, None);
+        assert_eq!(cfg.presets()["log"].format, "%F %H:%M");
+        assert_eq!(cfg.presets()["work.short"].format, "%m-%d");
+
+        let err = cfg
+            .define_presets(&[("bad".to_string(), "%Q".to_string())])
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("defined preset 'bad' has an invalid format: %Q")
+        );
+    }
+
     #[test]
     #[serial]
     fn load_fails_on_invalid_toml() {
@@ -274,6 +1815,489 @@ short = "%H:%M"
         assert!(Config::load().is_err());
     }
 
+    #[test]
+    fn parse_errors_point_into_the_file() {
+        let path = Path::new("/cfg/config.toml");
+        let contents = "format = \"%Y\"\ntimezone = \"UTC\"\n\n[humanize]\ngranularity = \"two\"\n";
+        let err = parse(contents).unwrap_err().into_error(path, contents);
+        assert_eq!(
+            err.to_string(),
+            "Configuration error: failed to parse config: invalid type: string \"two\", expected usize\n --> /cfg/config.toml:5:15\n  |\n5 | granularity = \"two\"\n  |               ^^^^^"
+        );
+
+        let contents = "format = \"%Y\"\n";
+        let err = parse(contents).unwrap_err().into_error(path, contents);
+        assert!(
+            err.to_string()
+                .ends_with("missing field `timezone`\n --> /cfg/config.toml")
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn load_with_warns_when_preset_shadows_builtin() {
+        let tmp = TempDir::new().unwrap();
+        let _home = EnvGuard::set("XDG_CONFIG_HOME", tmp.path());
+        write_config(
+            &tmp,
+            "format = \"%Y\"\ntimezone = \"UTC\"\n[formats]\nbr = \"%d/%m/%Y\"\norg = \"%F\"\n",
+        );
+
+        let mut diag = Diagnostics::default();
+        let _ = Config::load_with(&mut diag).unwrap();
+        assert_eq!(diag.warnings(), ["preset 'org' shadows a built-in format"]);
+    }
+
+    #[test]
+    #[serial]
+    fn load_flattens_preset_groups() {
+        let tmp = TempDir::new().unwrap();
+        let _home = EnvGuard::set("XDG_CONFIG_HOME", tmp.path());
+        write_config(
+            &tmp,
+            "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\npreset_group = \"work\"\n[formats]\nbr = \"%d/%m/%Y\"\n[formats.work]\nbr = \"%m/%d/%Y\"\nlog = \"{br} %H\"\n",
+        );
+
+        let cfg = Config::load().unwrap();
+        assert_eq!(cfg.preset_group.as_deref(), Some("work"));
+        assert_eq!(cfg.preset_names(), ["br", "work.br", "work.log"]);
+        assert_eq!(cfg.presets()["work.log"].format, "%m/%d/%Y %H");
+    }
+
+    #[test]
+    #[serial]
+    fn interactive_defaults_to_off() {
+        let tmp = TempDir::new().unwrap();
+        let _home = EnvGuard::set("XDG_CONFIG_HOME", tmp.path());
+        write_config(&tmp, "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n");
+        assert!(!Config::load().unwrap().interactive);
+
+        write_config(
+            &tmp,
+            "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\ninteractive = true\n",
+        );
+        assert!(Config::load().unwrap().interactive);
+    }
+
+    #[test]
+    #[serial]
+    fn worldclock_table_is_read() {
+        let tmp = TempDir::new().unwrap();
+        let _home = EnvGuard::set("XDG_CONFIG_HOME", tmp.path());
+        write_config(
+            &tmp,
+            "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[worldclock]\nzones = [\"UTC\", \"Asia/Tokyo\"]\n",
+        );
+        let cfg = Config::load().unwrap();
+        assert_eq!(cfg.worldclock.zones, ["UTC", "Asia/Tokyo"]);
+        assert_eq!(cfg.worldclock.format, None);
+        assert_eq!(cfg.meet, MeetConfig::default());
+    }
+
+    #[test]
+    #[serial]
+    fn workweek_table_is_read() {
+        let tmp = TempDir::new().unwrap();
+        let _home = EnvGuard::set("XDG_CONFIG_HOME", tmp.path());
+        write_config(
+            &tmp,
+            "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[workweek]\ndays = [\"Sun\", \"thursday\"]\nweek_start = \"sun\"\n",
+        );
+        let ww = Config::load().unwrap().workweek;
+        assert_eq!(ww.week_start(None).unwrap(), Weekday::Sunday);
+        assert_eq!(ww.week_start(Some("mon")).unwrap(), Weekday::Monday);
+        assert_eq!(ww.days(None).unwrap(), [Weekday::Sunday, Weekday::Thursday]);
+        assert_eq!(WorkweekConfig::default().days(None).unwrap().len(), 5);
+        assert!(ww.days(Some(&["funday".to_string()])).is_err());
+    }
+
+    #[test]
+    fn fiscal_year_start_takes_names_and_numbers() {
+        let fiscal = FiscalConfig {
+            year_start: Some("April".into()),
+        };
+        assert_eq!(fiscal.year_start(None).unwrap(), 4);
+        assert_eq!(fiscal.year_start(Some("10")).unwrap(), 10);
+        assert_eq!(fiscal.year_start(Some("Sep")).unwrap(), 9);
+        assert_eq!(FiscalConfig::default().year_start(None).unwrap(), 1);
+        assert!(fiscal.year_start(Some("13")).is_err());
+        assert!(fiscal.year_start(Some("smarch")).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn load_reads_table_presets() {
+        let tmp = TempDir::new().unwrap();
+        let _home = EnvGuard::set("XDG_CONFIG_HOME", tmp.path());
+        write_config(
+            &tmp,
+            "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n[formats]\njp = { format = \"%Y年%m月%d日\", timezone = \"Asia/Tokyo\", locale = \"ja\" }\n[formats.work]\nny = { format = \"%F\", timezone = \"America/New_York\" }\n",
+        );
+
+        let mut diag = Diagnostics::default();
+        let cfg = Config::load_with(&mut diag).unwrap();
+        let jp = &cfg.presets()["jp"];
+        assert_eq!(jp.format, "%Y年%m月%d日");
+        assert_eq!(jp.timezone.as_deref(), Some("Asia/Tokyo"));
+        assert_eq!(jp.locale.as_deref(), Some("ja"));
+        assert_eq!(
+            cfg.presets()["work.ny"].timezone.as_deref(),
+            Some("America/New_York")
+        );
+        assert!(diag.warnings().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn load_migrates_unversioned_config_with_backup() {
+        let tmp = TempDir::new().unwrap();
+        let _home = EnvGuard::set("XDG_CONFIG_HOME", tmp.path());
+        let original = "# mine\nformat = \"%Y\"\ntimezone = \"UTC\"\n";
+        write_config(&tmp, original);
+
+        let mut diag = Diagnostics::default();
+        let cfg = Config::load_with(&mut diag).unwrap();
+        assert_eq!(cfg.format, "%Y");
+        assert_eq!(diag.notes().len(), 1);
+
+        let dir = tmp.child("tardis");
+        let backup = fs::read_to_string(dir.child("config.toml.v0.bak").path()).unwrap();
+        assert_eq!(backup, original);
+        let migrated = fs::read_to_string(dir.child("config.toml").path()).unwrap();
+        assert!(migrated.starts_with("# mine\n"));
+        assert!(migrated.contains("version = 1"));
+
+        let mut again = Diagnostics::default();
+        let _ = Config::load_with(&mut again).unwrap();
+        assert!(again.notes().is_empty());
+    }
+
+    #[test]
+    fn schema_describes_every_template_key() {
+        let schema: serde_json::Value = serde_json::from_str(SCHEMA).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        let commented_tables = [
+            "meet",
+            "worldclock",
+            "workweek",
+            "fiscal",
+            "recurrences",
+            "dates",
+            "holidays",
+            "hours",
+            "humanize",
+            "exit_codes",
+            "when",
+        ];
+        let template: toml::Table = toml::from_str(TEMPLATE).unwrap();
+        for key in template.keys().map(String::as_str).chain(commented_tables) {
+            assert!(properties.contains_key(key), "schema lacks `{key}`");
+        }
+        assert_eq!(
+            properties["humanize"]["properties"]
+                .as_object()
+                .unwrap()
+                .len(),
+            5
+        );
+    }
+
+    #[test]
+    fn when_tables_merge_from_general_to_specific() {
+        let machine = Machine {
+            os: vec!["unix".into(), "linux".into()],
+            host: vec!["work-laptop".into(), "work-laptop.example.com".into()],
+        };
+        let contents = r#"
+            format = "%Y"
+            timezone = "UTC"
+            [formats]
+            jp = { format = "%F", timezone = "Asia/Tokyo" }
+            [when.os.Linux]
+            format = "%m"
+            [when.os.unix]
+            format = "%H"
+            timezone = "Europe/Paris"
+            [when.os.windows]
+            format = "%d"
+            [when.host."work-laptop.example.com".formats.jp]
+            timezone = "Europe/Lisbon"
+            [when.host.work-laptop.formats]
+            br = "%d/%m/%Y"
+        "#;
+        let table = merged_table(contents, &machine).unwrap();
+        let cfg = Config::deserialize(toml::de::Deserializer::from(table)).unwrap();
+        assert_eq!(cfg.format, "%m");
+        assert_eq!(cfg.timezone, "Europe/Paris");
+        let formats = cfg.formats.unwrap();
+        assert_eq!(formats["br"], FormatEntry::Format("%d/%m/%Y".into()));
+        let FormatEntry::Preset(jp) = &formats["jp"] else {
+            panic!("jp is not a preset table");
+        };
+        assert_eq!(jp.format, "%F");
+        assert_eq!(jp.timezone.as_deref(), Some("Europe/Lisbon"));
+
+        let err = merged_table("format = \"%Y\"\n[when.user.me]\n", &machine).unwrap_err();
+        assert!(err.message.starts_with("unknown condition `when.user`"));
+        let err = merged_table("when.os.linux = 1\n", &machine).unwrap_err();
+        assert_eq!(err.message, "`when.os.linux` must be a table");
+        assert_eq!(err.span, Some(16..17));
+    }
+
+    #[test]
+    fn schema_resolves_dotted_keys() {
+        assert!(schema_has_key(&["humanize", "granularity"]));
+        assert!(schema_has_key(&["formats", "work", "iso"]));
+        assert!(schema_has_key(&["formats", "jp", "timezone"]));
+        assert!(!schema_has_key(&["humanize", "units"]));
+        assert!(!schema_has_key(&["tz"]));
+        assert_eq!(
+            schema_default(&["humanize", "now_within"]).as_deref(),
+            Some("10s")
+        );
+        assert_eq!(schema_default(&["meet", "hours"]), None);
+    }
+
+    #[test]
+    fn insert_value_keeps_comments_and_creates_tables() {
+        let mut doc: toml_edit::DocumentMut = "# tz\ntimezone = \"UTC\" # zone\n".parse().unwrap();
+        insert_value(&mut doc, &["timezone"], "Asia/Tokyo".into()).unwrap();
+        insert_value(&mut doc, &["formats", "work", "iso"], "%F".into()).unwrap();
+        assert_eq!(
+            doc.to_string(),
+            "# tz\ntimezone = \"Asia/Tokyo\" # zone\n\n[formats.work]\niso = \"%F\"\n"
+        );
+        assert!(insert_value(&mut doc, &["timezone", "x"], 1.into()).is_err());
+    }
+
+    #[test]
+    fn concurrent_creation_writes_the_template_once() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("tardis").join(CONFIG_FILE);
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| create_config_if_missing(&path).unwrap());
+            }
+        });
+        assert_eq!(fs::read_to_string(&path).unwrap(), TEMPLATE.trim_start());
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+
+        fs::write(&path, "format = \"%Y\"\n").unwrap();
+        create_config_if_missing(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "format = \"%Y\"\n");
+    }
+
+    #[test]
+    fn config_dir_follows_each_platform() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| PathBuf::from(v))
+            }
+        };
+        let tardis = |base: &str| Path::new(base).join(APP_DIR);
+
+        // The override wins everywhere, and is used as is.
+        let vars = env(&[
+            ("TARDIS_CONFIG_DIR", "/dots/td"),
+            ("XDG_CONFIG_HOME", "/xdg"),
+        ]);
+        for os in ["linux", "macos", "windows"] {
+            assert_eq!(
+                config_dir_from(os, vars, Some("/os".into())),
+                Some(PathBuf::from("/dots/td"))
+            );
+        }
+
+        // XDG applies when absolute; a relative one is ignored.
+        let vars = env(&[("XDG_CONFIG_HOME", "/xdg")]);
+        assert_eq!(config_dir_from("linux", vars, None), Some(tardis("/xdg")));
+        let vars = env(&[("XDG_CONFIG_HOME", "xdg"), ("HOME", "/home/me")]);
+        assert_eq!(
+            config_dir_from("linux", vars, None),
+            Some(tardis("/home/me/.config"))
+        );
+
+        // The OS default comes before rebuilding it from the environment.
+        let vars = env(&[("APPDATA", r"C:\Users\me\AppData\Roaming")]);
+        assert_eq!(
+            config_dir_from("windows", vars, Some(r"D:\Roaming".into())),
+            Some(tardis(r"D:\Roaming"))
+        );
+
+        // Windows without a known folder: roaming AppData, then the profile,
+        // which may live on a network share.
+        let vars = env(&[
+            ("APPDATA", r"C:\Users\me\AppData\Roaming"),
+            ("LOCALAPPDATA", r"C:\Users\me\AppData\Local"),
+        ]);
+        assert_eq!(
+            config_dir_from("windows", vars, None),
+            Some(tardis(r"C:\Users\me\AppData\Roaming"))
+        );
+        let vars = env(&[("USERPROFILE", r"\\server\home\me")]);
+        assert_eq!(
+            config_dir_from("windows", vars, None),
+            Some(
+                Path::new(r"\\server\home\me")
+                    .join("AppData")
+                    .join("Roaming")
+                    .join(APP_DIR)
+            )
+        );
+
+        let vars = env(&[("HOME", "/Users/me")]);
+        assert_eq!(
+            config_dir_from("macos", vars, None),
+            Some(
+                Path::new("/Users/me")
+                    .join("Library")
+                    .join("Application Support")
+                    .join(APP_DIR)
+            )
+        );
+        assert_eq!(config_dir_from("linux", env(&[]), None), None);
+    }
+
+    #[test]
+    fn save_backs_up_and_prunes() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(CONFIG_FILE);
+        save(&path, "format = \"%Y\"\n").unwrap();
+        let backups = || {
+            let mut names: Vec<String> = fs::read_dir(tmp.path())
+                .unwrap()
+                .map(|e| e.unwrap().file_name().into_string().unwrap())
+                .filter(|n| n.ends_with(".bak"))
+                .collect();
+            names.sort();
+            names
+        };
+        assert!(backups().is_empty());
+
+        fs::write(tmp.path().join("config.toml.v0.bak"), "old\n").unwrap();
+        fs::write(tmp.path().join("config.toml.20200101T000000.000Z.bak"), "").unwrap();
+        for i in 0..6 {
+            save(&path, &format!("format = \"%{i}\"\n")).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        let names = backups();
+        assert_eq!(names.len(), BACKUPS_KEPT + 1);
+        assert!(names.contains(&"config.toml.v0.bak".to_string()));
+        assert!(!names.contains(&"config.toml.20200101T000000.000Z.bak".to_string()));
+        let newest = tmp.path().join(&names[BACKUPS_KEPT - 1]);
+        assert_eq!(fs::read_to_string(newest).unwrap(), "format = \"%4\"\n");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "format = \"%5\"\n");
+        assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), BACKUPS_KEPT + 2);
+    }
+
+    #[test]
+    fn migration_plan_diffs_and_backs_up_elsewhere() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        let original = "format = \"%Y\"\n";
+        fs::write(&path, original).unwrap();
+
+        let plan = MigrationPlan::new(&path, original.into()).unwrap().unwrap();
+        let diff = plan.diff();
+        assert!(diff.contains("(v0)\n+++ "));
+        assert!(diff.ends_with(" format = \"%Y\"\n+version = 1\n"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+
+        let backup = plan.apply(Some(&tmp.path().join("old"))).unwrap();
+        assert_eq!(backup, tmp.path().join("old/config.toml.v0.bak"));
+        assert_eq!(fs::read_to_string(backup).unwrap(), original);
+        assert!(
+            MigrationPlan::new(&path, fs::read_to_string(&path).unwrap())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn invalid_preset_is_reported_and_cached() {
+        let tmp = TempDir::new().unwrap();
+        let _home = EnvGuard::set("XDG_CONFIG_HOME", tmp.path());
+        let _cache = EnvGuard::set("XDG_CACHE_HOME", tmp.path().join("cache"));
+        write_config(
+            &tmp,
+            "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n[formats]\nbad = \"%Q\"\nok = \"%F\"\n",
+        );
+
+        let expected = ["preset 'bad' has an invalid format: %Q"];
+        let mut diag = Diagnostics::default();
+        let _ = Config::load_with(&mut diag).unwrap();
+        assert_eq!(diag.warnings(), expected);
+
+        let entry = tmp.path().join("cache/tardis/preset-check");
+        assert_eq!(
+            fs::read_to_string(&entry).unwrap(),
+            format!("{}\n", expected[0])
+        );
+
+        fs::write(&entry, "served from cache\n").unwrap();
+        let mut again = Diagnostics::default();
+        let _ = Config::load_with(&mut again).unwrap();
+        assert_eq!(again.warnings(), ["served from cache"]);
+    }
+
+    #[test]
+    #[serial]
+    fn load_readonly_does_not_create_file() {
+        let tmp = TempDir::new().unwrap();
+        let _home = EnvGuard::set("XDG_CONFIG_HOME", tmp.path());
+
+        assert!(Config::load_readonly().unwrap().is_none());
+        assert!(!tmp.child("tardis/config.toml").exists());
+    }
+
+    #[test]
+    #[serial]
+    fn load_readonly_does_not_migrate() {
+        let tmp = TempDir::new().unwrap();
+        let _home = EnvGuard::set("XDG_CONFIG_HOME", tmp.path());
+        let original = "format = \"%H:%M\"\ntimezone = \"UTC\"\n";
+        write_config(&tmp, original);
+
+        let (cfg, contents) = Config::load_readonly().unwrap().unwrap();
+        assert_eq!(cfg.format, "%H:%M");
+        assert_eq!(contents, original);
+        let on_disk = fs::read_to_string(tmp.child("tardis/config.toml").path()).unwrap();
+        assert_eq!(on_disk, original);
+    }
+
+    #[test]
+    fn validate_separates_errors_from_warnings_and_locates_keys() {
+        let contents = "version = 1\nformat = \"%Y-%m-%d %M\"\ntimezone = \"Mars/Base\"\ntimezon = \"UTC\"\n\n[formats]\nbr = \"%d/%m/%Q\"\n\n[holidays]\nbad = \"13-45\"\n";
+        let now = jiff::Zoned::now();
+        let findings = validate_contents(contents, &now);
+        let summary: Vec<_> = findings
+            .iter()
+            .map(|f| (f.severity, f.key.as_deref().unwrap(), f.location.unwrap()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Severity::Error, "timezone", (3, 1)),
+                (Severity::Error, "formats.br", (7, 1)),
+                (Severity::Error, "holidays.bad", (10, 1)),
+                (Severity::Warning, "format", (2, 1)),
+                (Severity::Warning, "timezon", (4, 1)),
+            ]
+        );
+
+        let findings = validate_contents("format = 1\n", &now);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].location, Some((1, 10)));
+        assert!(
+            validate_contents("version = 1\nformat = \"%F\"\ntimezone = \"UTC\"\n", &now)
+                .is_empty()
+        );
+    }
+
     #[test]
     fn create_config_is_noop_if_file_exists() {
         let tmp = TempDir::new().unwrap();
@@ -3,9 +3,24 @@
 //! Converts a natural-language date expression into a formatted string,
 //! applying optional presets and an explicit time-zone/context "now".
 
-use jiff::{Zoned, tz::TimeZone};
+pub mod format;
 
-use crate::{Result, cli::Command, config::Config, parser, user_input_error};
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
+
+use jiff::{Timestamp, Zoned, tz::TimeZone};
+
+use crate::{
+    Result,
+    adjust::Adjustment,
+    cli::{Command, InputKind, TimeScale},
+    config::Config,
+    diagnostics::Diagnostics,
+    i18n::{self, Names},
+    parser::{self, ParseOptions},
+    timescale, user_input_error,
+    zones::{self, CityMatch},
+};
+use format::Format;
 
 /// Immutable application context passed to [`process`].
 #[must_use]
@@ -20,15 +35,194 @@ pub struct App {
     pub timezone: TimeZone,
     /// Optional "now" (useful for deterministic tests).
     pub now: Option<Zoned>,
+    /// Values for preset placeholders (`--param NAME=VALUE`).
+    pub params: Vec<(String, String)>,
+    /// BSD-style adjustments applied to the result, in order (`--adjust`).
+    pub adjust: Vec<Adjustment>,
+    /// Render in the offset written in the input, when it has one
+    /// (`--keep-offset`).
+    pub keep_offset: bool,
+    /// Accept only RFC 3339, RFC 2822 or epoch input (`--convert`).
+    pub convert: bool,
+    /// Time scale the result is printed on (`--scale`).
+    pub scale: TimeScale,
+    /// Read epoch values beyond 64 bits (`--allow-extreme`).
+    pub allow_extreme: bool,
+    /// Reject input of any other kind (`--require`).
+    pub require: Option<InputKind>,
+    /// Month-overflow and default-time policies for parsing `date`.
+    pub parse_options: ParseOptions,
 }
 
-/// Pairing of a **named** preset with a strftime format string.
+/// A preset's format string, expanded and validated once when the preset
+/// is built.
 #[must_use]
 #[non_exhaustive]
-#[derive(Debug, Clone)]
-pub struct Preset {
-    pub name: String,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetSpec {
+    /// strftime-style format (or `epoch`/`unix`), with `{name}` references
+    /// to other presets already expanded.
     pub format: String,
+    /// Names of the `{name}` placeholders still in `format`, filled from
+    /// `--param` when the preset is used.
+    pub params: Vec<String>,
+    /// Time-zone applied when the preset is selected and `-t` is not given.
+    pub timezone: Option<String>,
+    /// Locale of the month and weekday names, already applied to
+    /// `compiled`.
+    pub locale: Option<String>,
+    /// Why the preset cannot be used, phrased to follow "preset 'name'"
+    /// (e.g. `"has an invalid format: %Q"`).
+    pub error: Option<String>,
+    /// `format` compiled, when it is valid and has no placeholders.
+    pub compiled: Option<Format>,
+}
+
+/// User-defined presets keyed by name.
+pub type Presets = HashMap<String, PresetSpec>;
+
+/// Built-in named formats, available everywhere a format name is accepted.
+///
+/// User-defined presets take precedence over these names.
+pub const BUILTIN_FORMATS: &[(&str, &str)] = &[
+    ("iso8601", "%Y-%m-%dT%H:%M:%S%:z"),
+    ("iso", "%Y-%m-%dT%H:%M:%S%:z"),
+    ("rfc3339", "%Y-%m-%dT%H:%M:%S%:z"),
+    ("rfc2822", RFC_EMAIL_FORMAT),
+    ("org", "<%Y-%m-%d %a>"),
+    ("org-inactive", "[%Y-%m-%d %a %H:%M]"),
+    ("touch", "%Y%m%d%H%M.%S"),
+    ("date-set", "%Y-%m-%d %H:%M:%S"),
+    ("exif", "%Y:%m:%d %H:%M:%S"),
+    ("at", "%H:%M %Y-%m-%d"),
+    ("beats", "@{beats}"),
+];
+
+/// Git's internal date format (`@<epoch><offset>`), written without the
+/// usual space so the value survives shell word splitting.
+pub const GIT_DATE_FORMAT: &str = "@%s%z";
+
+/// The RFC 5322 date of email headers, printed by `-R`.
+pub const RFC_EMAIL_FORMAT: &str = "%a, %d %b %Y %H:%M:%S %z";
+
+/// Common time-zone abbreviations accepted in place of an IANA name.  A
+/// name the time-zone database knows, such as `EST`, is never looked up here.
+///
+/// Abbreviations are ambiguous (`CST` is also China, `IST` is also Ireland
+/// and Israel), so using one always raises a warning naming the zone chosen.
+pub const TZ_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("EST", "America/New_York"),
+    ("EDT", "America/New_York"),
+    ("CST", "America/Chicago"),
+    ("CDT", "America/Chicago"),
+    ("MST", "America/Denver"),
+    ("MDT", "America/Denver"),
+    ("PST", "America/Los_Angeles"),
+    ("PDT", "America/Los_Angeles"),
+    ("BST", "Europe/London"),
+    ("CEST", "Europe/Paris"),
+    ("IST", "Asia/Kolkata"),
+    ("JST", "Asia/Tokyo"),
+    ("AEST", "Australia/Sydney"),
+    ("AEDT", "Australia/Sydney"),
+];
+
+/// Resolve a time-zone name: an IANA ID, else one of the abbreviations in
+/// [`TZ_ABBREVIATIONS`] with a warning, else a city name
+/// (see [`zones::find_city`]).
+pub fn lookup_timezone(name: &str, diag: &mut Diagnostics) -> Result<TimeZone> {
+    // IANA IDs come first: `EST` and `MST` are fixed-offset zones of their
+    // own, not the abbreviations of New York and Denver.
+    if let Ok(tz) = TimeZone::get(name) {
+        return Ok(tz);
+    }
+    if let Some((abbr, iana)) = TZ_ABBREVIATIONS
+        .iter()
+        .find(|(abbr, _)| abbr.eq_ignore_ascii_case(name))
+    {
+        diag.warn(format!(
+            "timezone abbreviation '{abbr}' is ambiguous, assuming {iana}"
+        ));
+        return get_zone(iana);
+    }
+    match zones::find_city(name) {
+        CityMatch::Unique(city) => get_zone(city.zone),
+        CityMatch::Likely { city, others } => {
+            let others: Vec<String> = others.iter().map(ToString::to_string).collect();
+            diag.warn(format!(
+                "city '{name}' is ambiguous, assuming {city}; also {}",
+                others.join("; ")
+            ));
+            get_zone(city.zone)
+        }
+        CityMatch::Ambiguous(cands) => {
+            let listed: Vec<String> = cands.iter().map(ToString::to_string).collect();
+            let hint = cands.first().map_or("US", |c| c.country);
+            Err(user_input_error!(
+                UnsupportedTimezone,
+                "city '{}' is ambiguous: {}; add a country code, e.g. '{}, {}'",
+                name,
+                listed.join("; "),
+                name,
+                hint
+            ))
+        }
+        _ => Err(user_input_error!(
+            UnsupportedTimezone,
+            "invalid timezone ID: {}",
+            name
+        )),
+    }
+}
+
+fn get_zone(name: &str) -> Result<TimeZone> {
+    TimeZone::get(name)
+        .map_err(|_| user_input_error!(UnsupportedTimezone, "invalid timezone ID: {}", name))
+}
+
+/// Look up a built-in format by name (case-insensitive).
+#[must_use]
+pub fn builtin_format(name: &str) -> Option<&'static str> {
+    BUILTIN_FORMATS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, f)| *f)
+}
+
+/// Tokens accepted in formats and `--exec` templates next to the strftime
+/// specifiers: the time zone's IANA ID, abbreviation and UTC offset in
+/// seconds, the Swatch Internet Time of the moment, and the kind of input
+/// it was read from.
+pub const FORMAT_TOKENS: [&str; 5] = ["tzid", "tzabbr", "tzoffset", "beats", "kind"];
+
+/// The value of each of [`FORMAT_TOKENS`] for `zoned`, read from input of
+/// `kind`.  `tzid` falls back to the `+HH:MM` offset for zones without an
+/// IANA name.
+#[must_use]
+pub fn format_tokens(zoned: &Zoned, kind: InputKind) -> [(&'static str, String); 5] {
+    FORMAT_TOKENS.map(|token| (token, format_token(zoned, kind, token)))
+}
+
+/// The value of `token`, one of [`FORMAT_TOKENS`], for `zoned`.
+fn format_token(zoned: &Zoned, kind: InputKind, token: &str) -> String {
+    match token {
+        "kind" => kind.as_str().to_string(),
+        "tzid" => match zoned.time_zone().iana_name() {
+            Some(name) => name.to_string(),
+            None => zoned.strftime("%:z").to_string(),
+        },
+        "tzabbr" => zoned.strftime("%Z").to_string(),
+        "tzoffset" => zoned.offset().seconds().to_string(),
+        _ => format!("{:03}", beats(zoned.timestamp())),
+    }
+}
+
+/// Swatch Internet Time: the thousandths of a day, of 86.4 seconds each,
+/// elapsed since midnight in Biel (UTC+1, with no daylight saving).
+#[must_use]
+pub fn beats(ts: Timestamp) -> i64 {
+    const BIEL_OFFSET_MS: i64 = 3_600_000;
+    (ts.as_millisecond() + BIEL_OFFSET_MS).rem_euclid(86_400_000) / 86_400
 }
 
 /// Result of processing a date expression.
@@ -40,42 +234,181 @@ pub struct ProcessOutput {
     pub formatted: String,
     /// Unix epoch timestamp (seconds).
     pub epoch: i64,
+    /// Unix epoch timestamp (nanoseconds).
+    pub epoch_ns: i128,
+    /// RFC 3339 rendering with offset, e.g. `2025-01-17T00:00:00+00:00`.
+    pub iso: String,
+    /// [`FORMAT_TOKENS`] and their values.
+    pub tokens: [(&'static str, String); 5],
+    /// Whether the input spelled a date, a time or both.
+    pub kind: InputKind,
 }
 
 /// Parse `app.date`, resolve the effective format, and render a string.
 ///
-/// * `presets` is passed as a slice to avoid unnecessary allocation.
+/// * `presets` is built once per config load; formats found there (or among
+///   the built-ins) are not re-validated on every call.
 /// * All error paths bubble up via [`Result`], ready for unit testing.
 #[must_use = "process returns a ProcessOutput that should not be discarded"]
-pub fn process(app: &App, presets: &[Preset]) -> Result<ProcessOutput> {
+pub fn process(app: &App, presets: &Presets) -> Result<ProcessOutput> {
     let now = app
         .now
         .clone()
         .unwrap_or_else(|| Zoned::now().with_time_zone(app.timezone.clone()));
 
-    let fmt = resolve_format(&app.format, presets)?;
+    let fmt = resolve_format(&app.format, presets, &app.params)?;
+
+    let (zoned, offset) = if app.convert {
+        parser::parse_machine(&app.date, &app.timezone).ok_or_else(|| {
+            user_input_error!(
+                InvalidDateFormat,
+                "--convert expects RFC 3339, RFC 2822 or a Unix timestamp, got '{}'",
+                app.date
+            )
+        })?
+    } else if app.allow_extreme {
+        parser::parse_extreme(&app.date, &now, &app.parse_options)
+            .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?
+    } else {
+        parser::parse_with_offset(&app.date, &now, &app.parse_options)
+            .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?
+    };
+
+    let kind = if app.convert {
+        InputKind::Datetime
+    } else {
+        parser::kind(&app.date)
+    };
+    if let Some(required) = app.require.filter(|&r| r != kind) {
+        return Err(user_input_error!(
+            InvalidDateFormat,
+            "'{}' is a {}, but --require asks for a {}",
+            app.date,
+            kind.as_str(),
+            required.as_str()
+        ));
+    }
 
-    let zoned = parser::parse(&app.date, &now)
-        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+    let zoned = match offset {
+        Some(offset) if app.keep_offset => zoned.with_time_zone(TimeZone::fixed(offset)),
+        _ => zoned,
+    };
+    let zoned = app.adjust.iter().try_fold(zoned, |at, adj| {
+        adj.apply(&at, app.parse_options.month_overflow)
+    })?;
+    let zoned = timescale::convert(&zoned, app.scale)?;
 
-    let formatted = format_output(&zoned, &fmt)?;
+    let formatted = match (fmt.compiled, fmt.names) {
+        (Some(compiled), _) => compiled.render(&zoned, kind),
+        (None, Some(names)) => format::cached(&fmt.format, fmt.checked)?
+            .as_ref()
+            .clone()
+            .localized(names)
+            .render(&zoned, kind),
+        (None, None) => format::cached(&fmt.format, fmt.checked)?.render(&zoned, kind),
+    };
     Ok(ProcessOutput {
         formatted,
         epoch: zoned.timestamp().as_second(),
+        epoch_ns: zoned.timestamp().as_nanosecond(),
+        iso: zoned.strftime("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+        tokens: format_tokens(&zoned, kind),
+        kind,
     })
 }
 
-/// Format a zoned datetime, handling special "epoch"/"unix" format.
-fn format_output(zoned: &Zoned, fmt: &str) -> Result<String> {
-    if fmt == "epoch" || fmt == "unix" {
-        return Ok(zoned.timestamp().as_second().to_string());
+/// Replace each name from the `[dates]` table in `input` with the instant
+/// it stands for, written as `@<epoch>`, so "3 days before release" reaches
+/// the parser as "3 days before @1759320000".  Names match whole words,
+/// ignoring case; a value without an offset is read in `now`'s zone.
+pub fn expand_dates<'a>(
+    input: &'a str,
+    dates: &HashMap<String, String>,
+    now: &Zoned,
+    opts: &ParseOptions,
+) -> Result<Cow<'a, str>> {
+    if dates.is_empty() {
+        return Ok(Cow::Borrowed(input));
+    }
+    let is_word = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find(is_word) {
+        out.push_str(&rest[..start]);
+        let len = rest[start..]
+            .find(|c: char| !is_word(c))
+            .unwrap_or(rest.len() - start);
+        let word = &rest[start..start + len];
+        match dates
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(word))
+        {
+            Some((name, value)) => {
+                let at = parser::parse(value, now, opts).map_err(|e| {
+                    user_input_error!(
+                        InvalidConfig,
+                        "invalid value for 'dates.{}': {}",
+                        name,
+                        e.format_message()
+                    )
+                })?;
+                out.push_str(&format!("@{}", at.timestamp().as_second()));
+            }
+            None => out.push_str(word),
+        }
+        rest = &rest[start + len..];
     }
+    out.push_str(rest);
+    Ok(Cow::Owned(out))
+}
 
-    let output = zoned.strftime(fmt).to_string();
+/// The Unix timestamp `fmt` asks for, if it is one of the special epoch
+/// formats: `epoch`/`unix` in seconds or `unix_ns` in nanoseconds.
+#[must_use]
+pub fn epoch_output(ts: Timestamp, fmt: &str) -> Option<String> {
+    match fmt {
+        "epoch" | "unix" => Some(ts.as_second().to_string()),
+        "unix_ns" => Some(ts.as_nanosecond().to_string()),
+        _ => None,
+    }
+}
 
-    validate_format_output(fmt, &output)?;
+/// Read `text` as a Unix timestamp in the unit of the special epoch format
+/// `fmt`, or `None` when `fmt` is not one.  Nanoseconds are read as 128-bit
+/// values, so every representable instant round-trips.
+pub fn epoch_input(text: &str, fmt: &str) -> Option<Result<Timestamp>> {
+    let (unit, per_second): (&str, i128) = match fmt {
+        "epoch" | "unix" => ("seconds", 1),
+        "unix_ns" => ("nanoseconds", 1_000_000_000),
+        _ => return None,
+    };
+    let text = text.trim();
+    Some(
+        text.parse::<i128>()
+            .ok()
+            .and_then(|n| n.checked_mul(1_000_000_000 / per_second))
+            .and_then(|nanos| Timestamp::from_nanosecond(nanos).ok())
+            .ok_or_else(|| {
+                user_input_error!(
+                    InvalidDateFormat,
+                    "'{}' is not a Unix timestamp in {} between {} and {}",
+                    text,
+                    unit,
+                    i128::from(Timestamp::MIN.as_second()) * per_second,
+                    i128::from(Timestamp::MAX.as_second()) * per_second
+                )
+            }),
+    )
+}
 
-    Ok(output)
+/// Check that `fmt` only uses strftime specifiers `td` understands.
+///
+/// The special names `epoch`, `unix` and `unix_ns` are accepted as-is.
+pub fn validate_format(fmt: &str) -> Result<()> {
+    if matches!(fmt, "epoch" | "unix" | "unix_ns") {
+        return Ok(());
+    }
+    validate_format_output(fmt, "")
 }
 
 /// Detect unknown strftime specifiers by checking if any `%X` sequence
@@ -142,20 +475,87 @@ fn validate_format_output(fmt: &str, output: &str) -> Result<()> {
     Ok(())
 }
 
+/// A format string from a preset, a built-in or the raw input.
+#[derive(Debug, PartialEq, Eq)]
+struct Resolved<'a> {
+    format: Cow<'a, str>,
+    /// Already validated, so rendering can skip the check.
+    checked: bool,
+    /// Compiled when the preset was built.
+    compiled: Option<&'a Format>,
+    /// Names from the preset's locale, for a format compiled at use time.
+    names: Option<&'static Names>,
+}
+
 /// Return the format string corresponding to `input`.
 ///
 /// *If* `input` matches the name of a preset, that preset's format is returned;
-/// otherwise `input` itself is treated as the format string.
-fn resolve_format(input: &str, presets: &[Preset]) -> Result<String> {
+/// then built-in names are checked; otherwise `input` itself is treated as the
+/// format string.  `params` fill a preset's placeholders and are rejected
+/// for anything else.
+fn resolve_format<'a>(
+    input: &'a str,
+    presets: &'a Presets,
+    params: &[(String, String)],
+) -> Result<Resolved<'a>> {
     if input.is_empty() {
         return Err(user_input_error!(MissingArgument, "empty --format"));
     }
 
-    Ok(presets
-        .iter()
-        .find(|p| p.name == input)
-        .map(|p| p.format.clone())
-        .unwrap_or_else(|| input.to_owned()))
+    if let Some(spec) = presets.get(input) {
+        if let Some(error) = &spec.error {
+            return Err(user_input_error!(
+                UnsupportedFormat,
+                "preset '{}' {}",
+                input,
+                error
+            ));
+        }
+        return Ok(Resolved {
+            format: spec.fill(input, params)?,
+            checked: true,
+            compiled: spec.compiled.as_ref(),
+            names: spec.names(),
+        });
+    }
+    if !params.is_empty() {
+        return Err(user_input_error!(
+            UnsupportedFormat,
+            "--param needs a preset with placeholders, not '{}'",
+            input
+        ));
+    }
+    Ok(match builtin_format(input) {
+        Some(format) => Resolved {
+            format: Cow::Borrowed(format),
+            checked: true,
+            compiled: None,
+            names: None,
+        },
+        None => Resolved {
+            format: Cow::Borrowed(input),
+            checked: false,
+            compiled: None,
+            names: None,
+        },
+    })
+}
+
+/// Resolve `name` as the main command's `-f` does, as a preset, a built-in
+/// format or a strftime pattern, and compile it.  For subcommands that print
+/// dates, which take no `--param`.
+pub fn compile_format(name: &str, presets: &Presets) -> Result<Arc<Format>> {
+    let fmt = resolve_format(name, presets, &[])?;
+    match (fmt.compiled, fmt.names) {
+        (Some(compiled), _) => Ok(Arc::new(compiled.clone())),
+        (None, Some(names)) => Ok(Arc::new(
+            format::cached(&fmt.format, fmt.checked)?
+                .as_ref()
+                .clone()
+                .localized(names),
+        )),
+        (None, None) => format::cached(&fmt.format, fmt.checked),
+    }
 }
 
 impl App {
@@ -166,6 +566,14 @@ impl App {
             format,
             timezone,
             now,
+            params: Vec::new(),
+            adjust: Vec::new(),
+            keep_offset: false,
+            convert: false,
+            scale: TimeScale::Utc,
+            allow_extreme: false,
+            require: None,
+            parse_options: ParseOptions::default(),
         }
     }
 
@@ -174,7 +582,32 @@ impl App {
     /// * CLI values **override** config values.
     /// * If no time-zone is provided anywhere, falls back to the OS local TZ.
     pub fn from_cli(cmd: &Command, cfg: &Config) -> Result<Self> {
-        let format = cmd.format.clone().unwrap_or_else(|| cfg.format.clone());
+        Self::from_cli_with(cmd, cfg, &mut Diagnostics::default())
+    }
+
+    /// Like [`App::from_cli`], reporting warnings (e.g. an ambiguous
+    /// time-zone abbreviation) to `diag`.
+    ///
+    /// With a preset group (`--preset-group` or `preset_group` in config), a
+    /// format name found in that group is qualified, e.g. `iso` becomes
+    /// `work.iso`.  A preset with its own `timezone` applies it unless `-t`
+    /// is given.
+    pub fn from_cli_with(cmd: &Command, cfg: &Config, diag: &mut Diagnostics) -> Result<Self> {
+        let mut format = cmd.format.clone().unwrap_or_else(|| cfg.format.clone());
+
+        if let Some(group) = cmd.preset_group.as_ref().or(cfg.preset_group.as_ref()) {
+            let prefix = format!("{group}.");
+            let presets = cfg.presets();
+            if !presets.keys().any(|name| name.starts_with(&prefix)) {
+                diag.warn(format!(
+                    "preset group '{group}' is not defined in [formats]"
+                ));
+            }
+            let scoped = format!("{prefix}{format}");
+            if presets.contains_key(&scoped) {
+                format = scoped;
+            }
+        }
 
         if format.trim().is_empty() {
             return Err(user_input_error!(
@@ -183,9 +616,21 @@ impl App {
             ));
         }
 
+        let preset = cfg.presets().get(&format);
+        if let Some(locale) = preset
+            .and_then(|p| p.locale.as_ref())
+            .filter(|locale| i18n::catalog_lang(locale).is_none())
+        {
+            diag.warn(format!(
+                "preset '{format}' sets locale '{locale}', which td has no names for; names stay in English"
+            ));
+        }
+        // A preset's own time-zone beats the configured default, but not `-t`.
+        let preset_tz = preset.and_then(|p| p.timezone.clone());
         let tz_raw = cmd
             .timezone
             .clone()
+            .or(preset_tz)
             .unwrap_or_else(|| cfg.timezone.clone())
             .trim()
             .to_owned();
@@ -193,42 +638,256 @@ impl App {
         let timezone: TimeZone = if tz_raw.is_empty() {
             TimeZone::system()
         } else {
-            TimeZone::get(&tz_raw).map_err(|_| {
-                user_input_error!(UnsupportedTimezone, "invalid timezone ID: {}", tz_raw)
-            })?
+            lookup_timezone(&tz_raw, diag)?
         };
 
         let now = cmd.now.map(|ts| ts.to_zoned(timezone.clone()));
+        let date = expand_dates(
+            &cmd.input,
+            &cfg.dates,
+            &now.clone()
+                .unwrap_or_else(|| Zoned::now().with_time_zone(timezone.clone())),
+            &cmd.parse_options,
+        )?;
 
         Ok(Self {
-            date: cmd.input.clone(),
+            date: date.into_owned(),
             format,
             timezone,
             now,
+            params: cmd.params.clone(),
+            adjust: cmd.adjust.clone(),
+            keep_offset: cmd.keep_offset,
+            convert: cmd.convert,
+            scale: cmd.scale,
+            allow_extreme: cmd.allow_extreme,
+            require: cmd.require,
+            parse_options: cmd.parse_options,
         })
     }
 }
 
-impl Preset {
+impl PresetSpec {
+    /// Wrap `format`, validating it up front.
+    ///
+    /// `{name}` placeholders left in `format` become parameters that must be
+    /// supplied (via `--param`) when the preset is used.
+    pub fn new(format: String) -> Self {
+        let mut params = Vec::new();
+        let _ = substitute(&format, true, &mut |name| {
+            if !FORMAT_TOKENS.contains(&name) && !params.iter().any(|p| p == name) {
+                params.push(name.to_string());
+            }
+            Ok(None)
+        });
+        let format = if params.is_empty() {
+            substitute(&format, false, &mut |_| Ok(None)).unwrap_or(format)
+        } else {
+            format
+        };
+        let error = validate_format(&format)
+            .err()
+            .map(|_| format!("has an invalid format: {format}"));
+        let compiled = (error.is_none() && params.is_empty()).then(|| Format::compile(&format));
+        Self {
+            format,
+            params,
+            timezone: None,
+            locale: None,
+            error,
+            compiled,
+        }
+    }
+
+    /// The month and weekday names of the preset's `locale`, when td has
+    /// them and they differ from strftime's English.
+    #[must_use]
+    pub fn names(&self) -> Option<&'static Names> {
+        i18n::catalog_lang(self.locale.as_deref()?)?.names()
+    }
+
+    /// Whether the preset can be used.
+    #[must_use]
     #[inline]
-    pub fn new(name: String, format: String) -> Self {
-        Self { name, format }
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
     }
+
+    /// Fill the preset's placeholders from `values`, borrowing the format
+    /// when it has none.  `name` is only used in error messages.
+    pub fn fill<'a>(&'a self, name: &str, values: &[(String, String)]) -> Result<Cow<'a, str>> {
+        if let Some((unused, _)) = values.iter().find(|(k, _)| !self.params.contains(k)) {
+            return Err(user_input_error!(
+                UnsupportedFormat,
+                "preset '{}' has no {{{}}} placeholder",
+                name,
+                unused
+            ));
+        }
+        if self.params.is_empty() {
+            return Ok(Cow::Borrowed(&self.format));
+        }
+        substitute(&self.format, false, &mut |param| {
+            if FORMAT_TOKENS.contains(&param) {
+                return Ok(None);
+            }
+            values
+                .iter()
+                .rev()
+                .find(|(k, _)| k == param)
+                .map(|(_, v)| Some(v.replace('%', "%%")))
+                .ok_or_else(|| param.to_string())
+        })
+        .map(Cow::Owned)
+        .map_err(|param| {
+            user_input_error!(
+                MissingArgument,
+                "preset '{}' needs --param {}=VALUE",
+                name,
+                param
+            )
+        })
+    }
+}
+
+/// Build [`Presets`] from a flattened `[formats]` table.
+///
+/// A `{name}` reference inside a format expands to the preset (or built-in
+/// format) called `name`, so `logline = "{iso} [%Z]"` reuses `iso`; within a
+/// group (`work.logline`) the group's own `work.iso` is preferred.  Any
+/// other `{name}` is a parameter filled at use time.  `{{` and `}}` produce
+/// literal braces.  Presets with a reference cycle are kept, carrying an
+/// [`error`](PresetSpec::error).
+pub fn build_presets(table: &HashMap<String, String>) -> Presets {
+    table
+        .keys()
+        .map(|name| {
+            let spec = match expand_preset(name, table, &mut Vec::new()) {
+                Ok(format) => PresetSpec::new(format),
+                Err(error) => PresetSpec {
+                    format: table[name].clone(),
+                    params: Vec::new(),
+                    timezone: None,
+                    locale: None,
+                    error: Some(error),
+                    compiled: None,
+                },
+            };
+            (name.clone(), spec)
+        })
+        .collect()
+}
+
+/// Expand the references in preset `name`, keeping brace escapes and
+/// parameters intact; `stack` holds the presets being expanded, for cycle
+/// detection.
+fn expand_preset(
+    name: &str,
+    table: &HashMap<String, String>,
+    stack: &mut Vec<String>,
+) -> std::result::Result<String, String> {
+    if let Some(pos) = stack.iter().position(|n| n == name) {
+        let mut cycle = stack[pos..].to_vec();
+        cycle.push(name.to_string());
+        return Err(format!("has a reference cycle: {}", cycle.join(" -> ")));
+    }
+    stack.push(name.to_string());
+    let group = name.rsplit_once('.').map(|(group, _)| group);
+    let out = substitute(&table[name], true, &mut |reference| {
+        // Inside a group, `{iso}` prefers the group's own `iso`.
+        let scoped = group
+            .map(|group| format!("{group}.{reference}"))
+            .filter(|scoped| table.contains_key(scoped));
+        match scoped
+            .as_deref()
+            .or(table.contains_key(reference).then_some(reference))
+        {
+            Some(target) => expand_preset(target, table, stack).map(Some),
+            // `{beats}` is the token, not the built-in format of that name.
+            None if FORMAT_TOKENS.contains(&reference) => Ok(None),
+            None => Ok(builtin_format(reference).map(str::to_owned)),
+        }
+    })?;
+    stack.pop();
+    Ok(out)
+}
+
+/// Rewrite each `{name}` in `template` with the result of `replace`, which
+/// may return `None` to leave the reference untouched.
+///
+/// `{{` and `}}` are copied as-is when `keep_escapes` is set (so the result
+/// can be substituted again) and collapsed to single braces otherwise.
+fn substitute(
+    template: &str,
+    keep_escapes: bool,
+    replace: &mut dyn FnMut(&str) -> std::result::Result<Option<String>, String>,
+) -> std::result::Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            out.push_str(if keep_escapes { &rest[..2] } else { &rest[..1] });
+            rest = &rest[2..];
+        } else if let Some(reference) = preset_reference(rest) {
+            let whole = &rest[..reference.len() + 2];
+            match replace(reference)? {
+                Some(value) => out.push_str(&value),
+                None => out.push_str(whole),
+            }
+            rest = &rest[whole.len()..];
+        } else {
+            out.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Name inside a `{name}` reference at the start of `s`, if there is one.
+fn preset_reference(s: &str) -> Option<&str> {
+    let body = s.strip_prefix('{')?;
+    let name = &body[..body.find('}')?];
+    let is_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    is_name.then_some(name)
 }
 
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used, clippy::expect_used)]
     use super::*;
-    use crate::Error;
+    use crate::{
+        Error,
+        config::{
+            ExitCodesConfig, FiscalConfig, FormatEntry, HumanizeConfig, MeetConfig, WorkweekConfig,
+            WorldclockConfig,
+        },
+    };
     use jiff::{Timestamp, civil, tz::TimeZone};
     use pretty_assertions::assert_eq;
     use std::collections::HashMap;
 
+    fn presets(pairs: &[(&str, &str)]) -> Presets {
+        let table: HashMap<String, String> = pairs
+            .iter()
+            .map(|(name, fmt)| (name.to_string(), fmt.to_string()))
+            .collect();
+        build_presets(&table)
+    }
+
     fn utc() -> TimeZone {
         TimeZone::get("UTC").unwrap()
     }
 
+    fn format_output(zoned: &Zoned, fmt: &str) -> Result<String> {
+        Ok(Format::new(fmt)?.render(zoned, InputKind::Datetime))
+    }
+
     fn zoned_utc(year: i16, month: i8, day: i8, hour: i8, min: i8, sec: i8) -> Zoned {
         let dt = civil::date(year, month, day).at(hour, min, sec, 0);
         utc().to_ambiguous_zoned(dt).compatible().unwrap()
@@ -236,32 +895,196 @@ mod tests {
 
     #[test]
     fn resolve_format_returns_preset_when_found() {
-        let presets = [
-            Preset::new("iso".into(), "%Y-%m-%d".into()),
-            Preset::new("time".into(), "%H:%M".into()),
-        ];
-        let out = super::resolve_format("iso", &presets).unwrap();
-        assert_eq!(out, "%Y-%m-%d");
+        let presets = presets(&[("iso", "%Y-%m-%d"), ("time", "%H:%M")]);
+        let out = super::resolve_format("iso", &presets, &[]).unwrap();
+        assert_eq!(out.format, "%Y-%m-%d");
+        assert!(out.checked);
     }
 
     #[test]
     fn resolve_format_returns_raw_when_not_preset() {
-        let presets = [Preset::new("iso".into(), "%Y-%m-%d".into())];
-        let out = super::resolve_format("%H:%M", &presets).unwrap();
-        assert_eq!(out, "%H:%M");
+        let presets = presets(&[("iso", "%Y-%m-%d")]);
+        let out = super::resolve_format("%H:%M", &presets, &[]).unwrap();
+        assert_eq!(out.format, "%H:%M");
+        assert!(!out.checked);
+    }
+
+    #[test]
+    fn resolve_format_falls_back_to_builtin() {
+        let presets = Presets::new();
+        let out = super::resolve_format("org", &presets, &[]).unwrap();
+        assert_eq!(out.format, "<%Y-%m-%d %a>");
+    }
+
+    #[test]
+    fn resolve_format_user_preset_shadows_builtin() {
+        let presets = presets(&[("org", "%F")]);
+        let out = super::resolve_format("org", &presets, &[]).unwrap();
+        assert_eq!(out.format, "%F");
+    }
+
+    #[test]
+    fn org_presets_render_weekday() {
+        let zoned = zoned_utc(2025, 6, 27, 9, 0, 0);
+        let active = format_output(&zoned, builtin_format("org").unwrap()).unwrap();
+        let inactive = format_output(&zoned, builtin_format("org-inactive").unwrap()).unwrap();
+        assert_eq!(active, "<2025-06-27 Fri>");
+        assert_eq!(inactive, "[2025-06-27 Fri 09:00]");
+    }
+
+    #[test]
+    fn touch_and_date_set_presets() {
+        let zoned = zoned_utc(2025, 6, 27, 9, 5, 7);
+        let touch = format_output(&zoned, builtin_format("touch").unwrap()).unwrap();
+        let set = format_output(&zoned, builtin_format("date-set").unwrap()).unwrap();
+        assert_eq!(touch, "202506270905.07");
+        assert_eq!(set, "2025-06-27 09:05:07");
+    }
+
+    #[test]
+    fn beats_count_from_midnight_in_biel() {
+        let at = |h, m, s| beats(zoned_utc(2025, 6, 27, h, m, s).timestamp());
+        assert_eq!(at(23, 0, 0), 0);
+        assert_eq!(at(22, 59, 59), 999);
+        assert_eq!(at(7, 19, 41), 347);
+
+        let tz = TimeZone::get("America/Sao_Paulo").unwrap();
+        let app = App::new("2025-06-27T04:19:41-03:00".into(), "beats".into(), tz, None);
+        assert_eq!(process(&app, &Presets::new()).unwrap().formatted, "@347");
+        let presets = presets(&[("bar", "%H:%M {beats}")]);
+        assert!(presets["bar"].params.is_empty());
+        let zoned = zoned_utc(2025, 6, 27, 23, 0, 0);
+        assert_eq!(
+            format_output(&zoned, &presets["bar"].format).unwrap(),
+            "23:00 000"
+        );
+    }
+
+    #[test]
+    fn exif_preset_round_trips() {
+        let tz = utc();
+        let app = App::new("2025:06:27 09:00:00".into(), "exif".into(), tz, None);
+        let out = process(&app, &Presets::new()).unwrap();
+        assert_eq!(out.formatted, "2025:06:27 09:00:00");
+    }
+
+    #[test]
+    fn git_date_format_renders_epoch_and_offset() {
+        let tz = TimeZone::get("Europe/Berlin").unwrap();
+        let now = "2024-06-24T12:00:00Z"
+            .parse::<Timestamp>()
+            .unwrap()
+            .to_zoned(tz.clone());
+        let app = App::new("now".into(), GIT_DATE_FORMAT.into(), tz, Some(now));
+        let out = process(&app, &Presets::new()).unwrap();
+        assert_eq!(out.formatted, "@1719230400+0200");
+    }
+
+    #[test]
+    fn at_preset_puts_time_first() {
+        let tz = utc();
+        let now = zoned_utc(2025, 6, 27, 23, 0, 0);
+        let app = App::new("in 90 minutes".into(), "at".into(), tz, Some(now));
+        let out = process(&app, &Presets::new()).unwrap();
+        assert_eq!(out.formatted, "00:30 2025-06-28");
+    }
+
+    #[test]
+    fn compile_format_expands_presets_and_rejects_bad_patterns() {
+        let presets = presets(&[("br", "%d/%m/%Y")]);
+        let zoned = zoned_utc(2025, 6, 27, 10, 30, 0);
+        let render = |name: &str| {
+            compile_format(name, &presets).map(|f| f.render(&zoned, InputKind::Datetime))
+        };
+
+        assert_eq!(render("br").unwrap(), "27/06/2025");
+        assert_eq!(render("org").unwrap(), "<2025-06-27 Fri>");
+        assert_eq!(render("%H:%M").unwrap(), "10:30");
+        assert_eq!(render("epoch").unwrap(), "1751020200");
+        assert!(render("%").is_err());
     }
 
     #[test]
     fn resolve_format_fails_on_empty() {
-        let presets: [Preset; 0] = [];
-        assert!(super::resolve_format("", &presets).is_err());
+        assert!(super::resolve_format("", &Presets::new(), &[]).is_err());
+    }
+
+    #[test]
+    fn invalid_preset_fails_when_used() {
+        let presets = presets(&[("bad", "%Q"), ("ok", "%F")]);
+        assert!(!presets["bad"].is_valid());
+        assert!(presets["ok"].is_valid());
+        assert!(super::resolve_format("bad", &presets, &[]).is_err());
+        assert!(super::resolve_format("ok", &presets, &[]).is_ok());
+    }
+
+    #[test]
+    fn preset_references_expand() {
+        let presets = presets(&[
+            ("day", "%Y-%m-%d"),
+            ("stamp", "{day} {clock}"),
+            ("clock", "%H:%M"),
+            ("log", "{stamp} [{org}] {{x}}"),
+        ]);
+        assert_eq!(presets["stamp"].format, "%Y-%m-%d %H:%M");
+        assert_eq!(presets["log"].format, "%Y-%m-%d %H:%M [<%Y-%m-%d %a>] {x}");
+        assert!(presets["log"].is_valid());
+    }
+
+    #[test]
+    fn preset_reference_cycle_is_reported() {
+        let presets = presets(&[
+            ("a", "{b}"),
+            ("b", "x {a}"),
+            ("c", "{a}"),
+            ("self", "{self}"),
+        ]);
+        assert_eq!(
+            presets["a"].error.as_deref(),
+            Some("has a reference cycle: a -> b -> a")
+        );
+        assert_eq!(
+            presets["c"].error.as_deref(),
+            Some("has a reference cycle: a -> b -> a")
+        );
+        assert_eq!(
+            presets["self"].error.as_deref(),
+            Some("has a reference cycle: self -> self")
+        );
+        let err = super::resolve_format("a", &presets, &[]).unwrap_err();
+        assert!(err.to_string().contains("preset 'a' has a reference cycle"));
+    }
+
+    #[test]
+    fn unknown_reference_becomes_parameter() {
+        let presets = presets(&[("meeting", "%H:%M ({label}) {{x}}"), ("braces", "{%Y}")]);
+        let spec = &presets["meeting"];
+        assert_eq!(spec.params, ["label"]);
+        assert!(spec.is_valid());
+        assert_eq!(presets["braces"].format, "{%Y}");
+
+        let params = [("label".to_string(), "50% done".to_string())];
+        let out = super::resolve_format("meeting", &presets, &params).unwrap();
+        assert_eq!(out.format, "%H:%M (50%% done) {x}");
+    }
+
+    #[test]
+    fn parameter_errors() {
+        let presets = presets(&[("meeting", "%H:%M ({label})"), ("day", "%F")]);
+        let extra = [("room".to_string(), "B".to_string())];
+
+        let err = super::resolve_format("meeting", &presets, &[]).unwrap_err();
+        assert!(err.to_string().contains("needs --param label=VALUE"));
+        let err = super::resolve_format("day", &presets, &extra).unwrap_err();
+        assert!(err.to_string().contains("has no {room} placeholder"));
+        assert!(super::resolve_format("%F", &presets, &extra).is_err());
     }
 
     #[test]
     fn process_with_preset_full_flow() {
         let tz = utc();
         let app = App::new("2025-06-24 10:00".into(), "iso".into(), tz, None);
-        let presets = [Preset::new("iso".into(), "%Y-%m-%dT%H:%M:%S".into())];
+        let presets = presets(&[("iso", "%Y-%m-%dT%H:%M:%S")]);
         let out = process(&app, &presets).unwrap();
         assert_eq!(out.formatted, "2025-06-24T10:00:00");
     }
@@ -271,22 +1094,45 @@ mod tests {
         let tz = utc();
         let now = zoned_utc(2025, 6, 24, 0, 0, 0);
         let app = App::new("tomorrow".into(), "%Y-%m-%d".into(), tz, Some(now));
-        let out = process(&app, &[]).unwrap();
+        let out = process(&app, &Presets::new()).unwrap();
         assert_eq!(out.formatted, "2025-06-25");
     }
 
+    #[test]
+    fn zone_tokens_render_in_formats_and_presets() {
+        let tz = TimeZone::get("America/Sao_Paulo").unwrap();
+        let presets = presets(&[("zoned", "%H:%M {tzid} {label}")]);
+        let mut app = App::new(
+            "2025-06-24T15:00:00-03:00".into(),
+            "%F {tzid} {tzabbr} {tzoffset} {other}".into(),
+            tz,
+            None,
+        );
+        let out = process(&app, &presets).unwrap();
+        assert_eq!(
+            out.formatted,
+            "2025-06-24 America/Sao_Paulo -03 -10800 {other}"
+        );
+
+        assert_eq!(presets["zoned"].params, ["label"]);
+        app.format = "zoned".into();
+        app.params = vec![("label".into(), "x".into())];
+        let out = process(&app, &presets).unwrap();
+        assert_eq!(out.formatted, "15:00 America/Sao_Paulo x");
+    }
+
     #[test]
     fn process_errors_on_bad_date_expression() {
         let tz = utc();
         let app = App::new("???".into(), "%Y".into(), tz, None);
-        assert!(process(&app, &[]).is_err());
+        assert!(process(&app, &Presets::new()).is_err());
     }
 
     #[test]
     fn process_errors_on_empty_format() {
         let tz = utc();
         let app = App::new("today".into(), "".into(), tz, None);
-        let err = process(&app, &[]).unwrap_err();
+        let err = process(&app, &Presets::new()).unwrap_err();
         assert!(matches!(err, Error::UserInput(_)));
     }
 
@@ -302,10 +1148,30 @@ mod tests {
             timezone: timezone.map(|s| s.to_string()),
 
             now: now.map(|s| s.parse::<Timestamp>().unwrap()),
+            params: Vec::new(),
+            defines: Vec::new(),
+            adjust: Vec::new(),
+            preset_group: None,
+            keep_offset: false,
+            convert: false,
+            scale: TimeScale::Utc,
+            allow_extreme: false,
+            require: None,
+            parse_options: ParseOptions::default(),
             json: false,
             no_newline: false,
+            line_ending: crate::cli::LineEnding::Lf,
             verbose: false,
             skip_errors: false,
+            on_error: crate::cli::OnError::Abort,
+            strict: false,
+            output: crate::cli::OutputMode::Text,
+            exec: None,
+            dry_run: false,
+            relative: false,
+            if_past: None,
+            if_future: None,
+            count: None,
         }
     }
 
@@ -315,6 +1181,21 @@ mod tests {
             timezone: timezone.to_string(),
 
             formats: None,
+            preset_group: None,
+            default_time: None,
+            interactive: false,
+            pager: true,
+            meet: MeetConfig::default(),
+            worldclock: WorldclockConfig::default(),
+            humanize: HumanizeConfig::default(),
+            workweek: WorkweekConfig::default(),
+            fiscal: FiscalConfig::default(),
+            recurrences: HashMap::new(),
+            dates: HashMap::new(),
+            holidays: HashMap::new(),
+            hours: HashMap::new(),
+            exit_codes: ExitCodesConfig::default(),
+            presets: Presets::new(),
         }
     }
 
@@ -361,16 +1242,53 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn timezone_abbreviation_resolves_with_warning() {
+        let cli = make_cmd("2025-01-01", Some("%Y"), Some("pst"), None);
+        let cfg = make_cfg("%Y", "UTC");
+        let mut diag = Diagnostics::default();
+        let app = App::from_cli_with(&cli, &cfg, &mut diag).unwrap();
+        assert_eq!(tz_name(&app.timezone), "America/Los_Angeles");
+        assert_eq!(
+            diag.warnings(),
+            ["timezone abbreviation 'PST' is ambiguous, assuming America/Los_Angeles"]
+        );
+    }
+
+    #[test]
+    fn iana_timezone_raises_no_warning() {
+        let cli = make_cmd("2025-01-01", Some("%Y"), Some("Europe/London"), None);
+        let cfg = make_cfg("%Y", "UTC");
+        let mut diag = Diagnostics::default();
+        let _ = App::from_cli_with(&cli, &cfg, &mut diag).unwrap();
+        assert!(diag.warnings().is_empty());
+    }
+
     #[test]
     fn preset_name_kept_in_app() {
         let cli = make_cmd("2030-12-31", Some("br"), None, None);
         let mut fmts = HashMap::new();
-        fmts.insert("br".into(), "%d/%m/%Y".into());
+        fmts.insert("br".into(), FormatEntry::Format("%d/%m/%Y".into()));
         let cfg = Config {
             format: "%F".into(),
             timezone: "UTC".into(),
 
             formats: Some(fmts),
+            preset_group: None,
+            default_time: None,
+            interactive: false,
+            pager: true,
+            meet: MeetConfig::default(),
+            worldclock: WorldclockConfig::default(),
+            humanize: HumanizeConfig::default(),
+            workweek: WorkweekConfig::default(),
+            fiscal: FiscalConfig::default(),
+            recurrences: HashMap::new(),
+            dates: HashMap::new(),
+            holidays: HashMap::new(),
+            hours: HashMap::new(),
+            exit_codes: ExitCodesConfig::default(),
+            presets: Presets::new(),
         };
         let app = App::from_cli(&cli, &cfg).unwrap();
         assert_eq!(app.format, "br");
@@ -393,7 +1311,7 @@ mod tests {
     fn epoch_input_valid() {
         let tz = utc();
         let app = App::new("@1735689600".into(), "%Y-%m-%d".into(), tz, None);
-        let out = process(&app, &[]).unwrap();
+        let out = process(&app, &Presets::new()).unwrap();
         assert_eq!(out.formatted, "2025-01-01");
         assert_eq!(out.epoch, 1735689600);
     }
@@ -402,7 +1320,7 @@ mod tests {
     fn epoch_input_invalid_not_a_number() {
         let tz = utc();
         let app = App::new("@abc".into(), "%Y".into(), tz, None);
-        let err = process(&app, &[]).unwrap_err();
+        let err = process(&app, &Presets::new()).unwrap_err();
         assert!(matches!(
             err,
             Error::UserInput(crate::errors::UserInputError::InvalidDateFormat(_))
@@ -413,7 +1331,7 @@ mod tests {
     fn epoch_input_smart_precision() {
         let tz = utc();
         let app = App::new("@99999999999999999".into(), "%Y".into(), tz, None);
-        let out = process(&app, &[]).unwrap();
+        let out = process(&app, &Presets::new()).unwrap();
         assert!(!out.formatted.is_empty());
     }
 
@@ -422,7 +1340,7 @@ mod tests {
         let tz = utc();
         let now = zoned_utc(2025, 1, 1, 0, 0, 0);
         let app = App::new("today".into(), "epoch".into(), tz, Some(now));
-        let out = process(&app, &[]).unwrap();
+        let out = process(&app, &Presets::new()).unwrap();
         assert_eq!(out.formatted, "1735689600");
     }
 
@@ -431,15 +1349,35 @@ mod tests {
         let tz = utc();
         let now = zoned_utc(2025, 1, 1, 0, 0, 0);
         let app = App::new("today".into(), "unix".into(), tz, Some(now));
-        let out = process(&app, &[]).unwrap();
+        let out = process(&app, &Presets::new()).unwrap();
         assert_eq!(out.formatted, "1735689600");
     }
 
+    #[test]
+    fn unix_ns_round_trips_in_128_bits() {
+        let tz = utc();
+        let app = App::new("@1735689600123456789ns".into(), "unix_ns".into(), tz, None);
+        let out = process(&app, &Presets::new()).unwrap();
+        assert_eq!(out.formatted, "1735689600123456789");
+        assert_eq!(out.epoch_ns, 1_735_689_600_123_456_789);
+
+        let far = epoch_input("253402207200000000000", "unix_ns")
+            .unwrap()
+            .unwrap();
+        assert_eq!(far.to_string(), "9999-12-30T22:00:00Z");
+        assert_eq!(
+            epoch_output(far, "unix_ns").unwrap(),
+            "253402207200000000000"
+        );
+        assert!(epoch_input("1e9", "epoch").unwrap().is_err());
+        assert!(epoch_input("1735689600", "%s").is_none());
+    }
+
     #[test]
     fn epoch_input_with_epoch_output() {
         let tz = utc();
         let app = App::new("@1735689600".into(), "epoch".into(), tz, None);
-        let out = process(&app, &[]).unwrap();
+        let out = process(&app, &Presets::new()).unwrap();
         assert_eq!(out.formatted, "1735689600");
         assert_eq!(out.epoch, 1735689600);
     }
@@ -449,7 +1387,7 @@ mod tests {
         let tz = utc();
         let now = zoned_utc(2025, 6, 24, 0, 0, 0);
         let app = App::new("tomorrow".into(), "%Y-%m-%d".into(), tz, Some(now));
-        let out = process(&app, &[]).unwrap();
+        let out = process(&app, &Presets::new()).unwrap();
         assert_eq!(out.formatted, "2025-06-25");
         assert_eq!(out.epoch, 1750809600);
     }
@@ -458,28 +1396,63 @@ mod tests {
     fn epoch_negative_timestamp() {
         let tz = utc();
         let app = App::new("@-86400".into(), "%Y-%m-%d".into(), tz, None);
-        let out = process(&app, &[]).unwrap();
+        let out = process(&app, &Presets::new()).unwrap();
         assert_eq!(out.formatted, "1969-12-31");
     }
 
     #[test]
     fn format_output_with_literal_text() {
         let zoned = zoned_utc(2025, 1, 1, 0, 0, 0);
-        let out = super::format_output(&zoned, "Year: %Y").unwrap();
+        let out = format_output(&zoned, "Year: %Y").unwrap();
         assert_eq!(out, "Year: 2025");
     }
 
     #[test]
     fn format_output_epoch() {
         let zoned = zoned_utc(2025, 1, 1, 0, 0, 0);
-        let out = super::format_output(&zoned, "epoch").unwrap();
+        let out = format_output(&zoned, "epoch").unwrap();
         assert_eq!(out, "1735689600");
     }
 
+    #[test]
+    fn validate_format_accepts_known_and_rejects_unknown() {
+        assert!(validate_format("%Y-%m-%d %:z").is_ok());
+        assert!(validate_format("epoch").is_ok());
+        assert!(validate_format("%Q").is_err());
+    }
+
+    #[test]
+    fn expand_dates_replaces_whole_names() {
+        let now = zoned_utc(2025, 1, 15, 10, 30, 0);
+        let dates = HashMap::from([
+            ("release".to_string(), "2025-10-01T12:00:00Z".to_string()),
+            ("kickoff".to_string(), "2025-02-03".to_string()),
+        ]);
+        let out = expand_dates(
+            "3 days before Release",
+            &dates,
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(out, "3 days before @1759320000");
+        assert_eq!(
+            expand_dates("kickoff + 1h", &dates, &now, &ParseOptions::default()).unwrap(),
+            "@1738540800 + 1h"
+        );
+        assert_eq!(
+            expand_dates("releases", &dates, &now, &ParseOptions::default()).unwrap(),
+            "releases"
+        );
+
+        let bad = HashMap::from([("soon".to_string(), "whenever".to_string())]);
+        assert!(expand_dates("soon", &bad, &now, &ParseOptions::default()).is_err());
+    }
+
     #[test]
     fn format_output_unix() {
         let zoned = zoned_utc(2025, 1, 1, 0, 0, 0);
-        let out = super::format_output(&zoned, "unix").unwrap();
+        let out = format_output(&zoned, "unix").unwrap();
         assert_eq!(out, "1735689600");
     }
 }
@@ -0,0 +1,275 @@
+//! Output formats compiled once and rendered many times.
+//!
+//! A [`Format`] is a format string validated and split into strftime runs
+//! and [`FORMAT_TOKENS`](super::FORMAT_TOKENS) up front, so that rendering
+//! a batch of dates neither re-validates the string nor rebuilds it with
+//! the token values for every line.  Presets are compiled when the config
+//! is loaded; other formats go through [`cached`], which compiles each
+//! distinct string once per process.  A format can also be
+//! [`localized`](Format::localized) to print month and weekday names in
+//! another language.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex, PoisonError},
+};
+
+use jiff::Zoned;
+
+use crate::{Result, cli::InputKind, i18n::Names};
+
+/// Compiled formats kept by [`cached`] before the cache is emptied.
+const CACHE_LIMIT: usize = 64;
+
+static CACHE: LazyLock<Mutex<HashMap<String, Arc<Format>>>> = LazyLock::new(Mutex::default);
+
+/// A format string ready to render.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    pieces: Vec<Piece>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    /// Text for jiff's strftime, brace escapes included.
+    Strftime(String),
+    /// One of the [`FORMAT_TOKENS`](super::FORMAT_TOKENS).
+    Token(&'static str),
+    /// The `epoch`/`unix` format.
+    Seconds,
+    /// The `unix_ns` format.
+    Nanoseconds,
+    /// A month or weekday name from a catalog.
+    Name(Name, &'static Names),
+}
+
+/// Which name a [`Piece::Name`] prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Name {
+    /// `%A`
+    Weekday,
+    /// `%a`
+    WeekdayAbbr,
+    /// `%B`
+    Month,
+    /// `%b` and `%h`
+    MonthAbbr,
+}
+
+impl Format {
+    /// Validate `fmt` and compile it.
+    pub fn new(fmt: &str) -> Result<Self> {
+        super::validate_format(fmt)?;
+        Ok(Self::compile(fmt))
+    }
+
+    /// Compile `fmt`, which is already known to be valid.
+    pub(crate) fn compile(fmt: &str) -> Self {
+        let piece = match fmt {
+            "epoch" | "unix" => Some(Piece::Seconds),
+            "unix_ns" => Some(Piece::Nanoseconds),
+            _ => None,
+        };
+        if let Some(piece) = piece {
+            return Self {
+                pieces: vec![piece],
+            };
+        }
+
+        let mut pieces = Vec::new();
+        let mut run = String::new();
+        let mut rest = fmt;
+        while let Some(i) = rest.find(['{', '}']) {
+            run.push_str(&rest[..i]);
+            rest = &rest[i..];
+            let token = super::preset_reference(rest)
+                .and_then(|name| super::FORMAT_TOKENS.iter().find(|t| **t == name));
+            match token {
+                Some(token) => {
+                    if !run.is_empty() {
+                        pieces.push(Piece::Strftime(std::mem::take(&mut run)));
+                    }
+                    pieces.push(Piece::Token(token));
+                    rest = &rest[token.len() + 2..];
+                }
+                None => {
+                    let len = if rest.starts_with("{{") || rest.starts_with("}}") {
+                        2
+                    } else {
+                        1
+                    };
+                    run.push_str(&rest[..len]);
+                    rest = &rest[len..];
+                }
+            }
+        }
+        run.push_str(rest);
+        if !run.is_empty() || pieces.is_empty() {
+            pieces.push(Piece::Strftime(run));
+        }
+        Self { pieces }
+    }
+
+    /// This format printing month and weekday names from `names`.
+    pub fn localized(self, names: &'static Names) -> Self {
+        let mut pieces = Vec::with_capacity(self.pieces.len());
+        for piece in self.pieces {
+            let Piece::Strftime(run) = piece else {
+                pieces.push(piece);
+                continue;
+            };
+            let mut text = String::new();
+            let mut rest = run.as_str();
+            while let Some(i) = rest.find('%') {
+                text.push_str(&rest[..i]);
+                rest = &rest[i..];
+                // Flags pad numbers, so a name ignores them.
+                let spec = rest[1..].trim_start_matches(['-', '0', '_']);
+                let name = match spec.chars().next() {
+                    Some('A') => Some(Name::Weekday),
+                    Some('a') => Some(Name::WeekdayAbbr),
+                    Some('B') => Some(Name::Month),
+                    Some('b' | 'h') => Some(Name::MonthAbbr),
+                    _ => None,
+                };
+                let len = rest.len() - spec.len() + spec.chars().next().map_or(0, char::len_utf8);
+                match name {
+                    Some(name) => {
+                        if !text.is_empty() {
+                            pieces.push(Piece::Strftime(std::mem::take(&mut text)));
+                        }
+                        pieces.push(Piece::Name(name, names));
+                    }
+                    None => text.push_str(&rest[..len]),
+                }
+                rest = &rest[len..];
+            }
+            text.push_str(rest);
+            if !text.is_empty() {
+                pieces.push(Piece::Strftime(text));
+            }
+        }
+        Self { pieces }
+    }
+
+    /// `zoned`, read from input of `kind`, in this format.
+    #[must_use]
+    pub fn render(&self, zoned: &Zoned, kind: InputKind) -> String {
+        let mut out = String::new();
+        for piece in &self.pieces {
+            match piece {
+                Piece::Strftime(run) => out.push_str(&zoned.strftime(run).to_string()),
+                Piece::Token(token) => out.push_str(&super::format_token(zoned, kind, token)),
+                Piece::Seconds => out.push_str(&zoned.timestamp().as_second().to_string()),
+                Piece::Nanoseconds => {
+                    out.push_str(&zoned.timestamp().as_nanosecond().to_string());
+                }
+                Piece::Name(name, names) => {
+                    let month = usize::from(zoned.month().unsigned_abs()) - 1;
+                    let weekday =
+                        usize::from(zoned.weekday().to_monday_zero_offset().unsigned_abs());
+                    out.push_str(match name {
+                        Name::Weekday => names.weekdays[weekday],
+                        Name::WeekdayAbbr => names.weekdays_abbr[weekday],
+                        Name::Month => names.months[month],
+                        Name::MonthAbbr => names.months_abbr[month],
+                    });
+                }
+            }
+        }
+        out
+    }
+}
+
+/// `fmt` compiled, from the cache when it was compiled before.  Unless
+/// `checked`, it is validated first.
+pub fn cached(fmt: &str, checked: bool) -> Result<Arc<Format>> {
+    let mut cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(format) = cache.get(fmt) {
+        return Ok(Arc::clone(format));
+    }
+    let format = Arc::new(if checked {
+        Format::compile(fmt)
+    } else {
+        Format::new(fmt)?
+    });
+    if cache.len() >= CACHE_LIMIT {
+        cache.clear();
+    }
+    cache.insert(fmt.to_owned(), Arc::clone(&format));
+    Ok(format)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn render(fmt: &str) -> String {
+        let zoned: Zoned = "2025-06-27T09:05:07-03:00[America/Sao_Paulo]"
+            .parse()
+            .unwrap();
+        Format::new(fmt).unwrap().render(&zoned, InputKind::Date)
+    }
+
+    #[test]
+    fn splits_tokens_from_strftime_runs() {
+        assert_eq!(
+            Format::new("%H:%M {tzabbr}!").unwrap().pieces,
+            [
+                Piece::Strftime("%H:%M ".into()),
+                Piece::Token("tzabbr"),
+                Piece::Strftime("!".into())
+            ]
+        );
+        assert_eq!(
+            render("%F {tzid} {tzoffset}"),
+            "2025-06-27 America/Sao_Paulo -10800"
+        );
+        assert_eq!(render("@{beats}"), "@545");
+        assert_eq!(render("{beats}{beats}"), "545545");
+        assert_eq!(render("%F ({kind})"), "2025-06-27 (date)");
+    }
+
+    #[test]
+    fn keeps_escapes_and_unknown_names_literal() {
+        assert_eq!(render("{{tzid}} {name} {"), "{{tzid}} {name} {");
+        assert_eq!(render(""), "");
+    }
+
+    #[test]
+    fn localized_formats_print_catalog_names() {
+        let zoned: Zoned = "2025-06-27T09:05:07-03:00[America/Sao_Paulo]"
+            .parse()
+            .unwrap();
+        let names = crate::i18n::Lang::PtBr.names().unwrap();
+        let render = |fmt: &str| {
+            Format::new(fmt)
+                .unwrap()
+                .localized(names)
+                .render(&zoned, InputKind::Date)
+        };
+        assert_eq!(
+            render("%A, %d de %B de %Y"),
+            "sexta-feira, 27 de junho de 2025"
+        );
+        assert_eq!(render("%a %-d %b|%h {tzabbr}"), "sex 27 jun|jun -03");
+        assert_eq!(render("100%% %H:%M"), "100% 09:05");
+    }
+
+    #[test]
+    fn renders_epoch_formats() {
+        assert_eq!(render("epoch"), "1751025907");
+        assert_eq!(render("unix_ns"), "1751025907000000000");
+    }
+
+    #[test]
+    fn caches_by_format_string() {
+        let first = cached("%Y {tzid}", false).unwrap();
+        assert!(Arc::ptr_eq(&first, &cached("%Y {tzid}", false).unwrap()));
+        assert!(cached("%Q", false).is_err());
+        assert!(cached("%Q", true).is_ok());
+    }
+}
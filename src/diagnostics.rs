@@ -0,0 +1,151 @@
+//! Non-fatal warnings for **TARDIS**.
+//!
+//! A [`Diagnostics`] sink is threaded through configuration loading and
+//! [`App`](crate::core::App) construction.  Warnings are buffered and only
+//! reach stderr when the caller [`flush`](Diagnostics::flush)es them, which
+//! lets `--quiet` drop them and `--deny-warnings` turn them into errors
+//! before any output is written.  Informational notes (such as a config
+//! migration report) share the same sink but are never promoted to errors.
+
+use crate::{Result, user_input_error};
+
+/// Collector for warnings raised while preparing a command.
+#[must_use]
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    quiet: bool,
+    deny: bool,
+    warnings: Vec<String>,
+    flushed: usize,
+    notes: Vec<String>,
+}
+
+impl Diagnostics {
+    /// Create a sink honouring `--quiet` and `--deny-warnings`.
+    #[inline]
+    pub fn new(quiet: bool, deny: bool) -> Self {
+        Self {
+            quiet,
+            deny,
+            ..Self::default()
+        }
+    }
+
+    /// Record a warning.  Identical messages are only kept once, so batch
+    /// mode does not repeat the same warning for every line.
+    pub fn warn(&mut self, msg: impl Into<String>) {
+        let msg = msg.into();
+        if !self.warnings.contains(&msg) {
+            self.warnings.push(msg);
+        }
+    }
+
+    /// Record an informational note, shown unless `--quiet` is given.
+    pub fn note(&mut self, msg: impl Into<String>) {
+        self.notes.push(msg.into());
+    }
+
+    /// Whether warnings are turned into errors (`--deny-warnings`).
+    #[must_use]
+    pub fn denies_warnings(&self) -> bool {
+        self.deny
+    }
+
+    /// All warnings recorded so far, flushed or not.
+    #[must_use]
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Notes recorded since the last flush.
+    #[must_use]
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// Emit pending notes and warnings to stderr.
+    ///
+    /// With `--deny-warnings` the first pending warning is returned as an
+    /// error instead; with `--quiet` pending output is discarded.
+    pub fn flush(&mut self) -> Result<()> {
+        let notes = std::mem::take(&mut self.notes);
+        let pending = &self.warnings[self.flushed..];
+        self.flushed = self.warnings.len();
+
+        let color = crate::term::stderr_color();
+        if !self.quiet {
+            for msg in notes {
+                eprintln!("{}: {msg}", paint("note", "\x1b[36m", color));
+            }
+        }
+
+        if self.deny {
+            if let Some(first) = pending.first() {
+                return Err(user_input_error!(DeniedWarning, "{}", first));
+            }
+        } else if !self.quiet {
+            for msg in pending {
+                eprintln!("{}: {msg}", paint("warning", "\x1b[33m", color));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wrap `label` in an ANSI color when `color` is set.
+fn paint(label: &str, ansi: &str, color: bool) -> String {
+    if color {
+        format!("{ansi}{label}\x1b[0m")
+    } else {
+        label.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use crate::{Error, errors::UserInputError};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn duplicate_warnings_are_collapsed() {
+        let mut diag = Diagnostics::default();
+        diag.warn("a");
+        diag.warn("b");
+        diag.warn("a");
+        assert_eq!(diag.warnings(), ["a", "b"]);
+    }
+
+    #[test]
+    fn deny_turns_first_pending_warning_into_error() {
+        let mut diag = Diagnostics::new(false, true);
+        assert!(diag.flush().is_ok());
+        diag.warn("preset 'iso' shadows a built-in format");
+        let err = diag.flush().unwrap_err();
+        assert_eq!(
+            err,
+            Error::UserInput(UserInputError::DeniedWarning(
+                "preset 'iso' shadows a built-in format".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn notes_are_never_denied() {
+        let mut diag = Diagnostics::new(false, true);
+        diag.note("config upgraded");
+        assert!(diag.flush().is_ok());
+        assert!(diag.notes().is_empty());
+    }
+
+    #[test]
+    fn flush_only_reports_new_warnings() {
+        let mut diag = Diagnostics::new(false, true);
+        diag.warn("a");
+        assert!(diag.flush().is_err());
+        assert!(diag.flush().is_ok());
+        diag.warn("a");
+        assert!(diag.flush().is_ok());
+    }
+}
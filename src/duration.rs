@@ -0,0 +1,302 @@
+//! Durations as people write them, for `td sum`.
+//!
+//! [`parse`] accepts jiff's friendly format (`1h30m`, `45m`, `1h 30m`,
+//! `1.5h`), ISO 8601 (`PT1H30M`) and clock notation (`2:15` for two hours
+//! fifteen minutes, `0:00:45` with seconds).  [`parse_entry`] also takes
+//! the time intervals of a timesheet, `09:00-12:30` or
+//! `2025-01-15 22:00 - 2025-01-16 02:00`.  [`render`] prints a total in
+//! one of the [`SumOutput`] layouts, and [`Decimals`] says how the decimal
+//! layouts round, as billing and time-tracking exports need.
+
+use clap::ValueEnum;
+use jiff::{
+    SignedDuration, SignedDurationRound, Unit,
+    civil::{Date, Time},
+};
+
+use crate::{
+    Result,
+    cli::{Rounding, SumOutput},
+    config::HumanizeConfig,
+    user_input_error,
+};
+
+/// Units of the decimal layout, largest first: a day is 24 hours.
+const DECIMAL_UNITS: [(i64, &str); 4] = [(86_400, "d"), (3_600, "h"), (60, "m"), (1, "s")];
+
+/// How decimal amounts are rounded.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decimals {
+    /// Decimal places kept, up to 9.
+    pub places: u32,
+    pub rounding: Rounding,
+}
+
+impl Default for Decimals {
+    /// Two places, to the nearest.
+    fn default() -> Self {
+        Self {
+            places: 2,
+            rounding: Rounding::Nearest,
+        }
+    }
+}
+
+impl Decimals {
+    /// The defaults, overridden by the `[humanize]` config table and then
+    /// by the `--decimals` and `--rounding` flags.
+    pub fn from_config(
+        cfg: &HumanizeConfig,
+        places: Option<u32>,
+        rounding: Option<Rounding>,
+    ) -> Result<Self> {
+        let places = places.or(cfg.decimals).unwrap_or(2);
+        if places > 9 {
+            return Err(user_input_error!(
+                InvalidDate,
+                "decimals must be between 0 and 9, got {}",
+                places
+            ));
+        }
+        let rounding = match (rounding, &cfg.rounding) {
+            (Some(r), _) => r,
+            (None, Some(name)) => Rounding::from_str(name, true).map_err(|_| {
+                user_input_error!(
+                    InvalidConfig,
+                    "[humanize] rounding = '{}' is not one of nearest, up, down",
+                    name
+                )
+            })?,
+            (None, None) => Rounding::Nearest,
+        };
+        Ok(Self { places, rounding })
+    }
+
+    /// `d` as a decimal number of units `unit_secs` seconds long, without
+    /// trailing zeros.
+    #[must_use]
+    pub fn amount(self, d: SignedDuration, unit_secs: i64) -> String {
+        let scale = 10_u128.pow(self.places);
+        let num = d.as_nanos().unsigned_abs() * scale;
+        let den = u128::from(unit_secs.unsigned_abs()) * 1_000_000_000;
+        let (mut q, r) = (num / den, num % den);
+        q += u128::from(match self.rounding {
+            Rounding::Up => r > 0,
+            Rounding::Down => false,
+            Rounding::Nearest => r * 2 >= den,
+        });
+        let sign = if d.is_negative() && q > 0 { "-" } else { "" };
+        let (whole, frac) = (q / scale, q % scale);
+        if frac == 0 {
+            return format!("{sign}{whole}");
+        }
+        let frac = format!("{frac:0width$}", width = self.places as usize);
+        format!("{sign}{whole}.{}", frac.trim_end_matches('0'))
+    }
+
+    /// `d` in the largest unit it holds at least one of, such as `1.5h` or
+    /// `2.25d`.
+    #[must_use]
+    pub fn largest_unit(self, d: SignedDuration) -> String {
+        let secs = d.as_secs().unsigned_abs();
+        let (unit, suffix) = DECIMAL_UNITS
+            .into_iter()
+            .find(|(unit, _)| secs >= unit.unsigned_abs())
+            .unwrap_or((1, "s"));
+        format!("{}{suffix}", self.amount(d, unit))
+    }
+}
+
+/// Parse one duration.
+pub fn parse(s: &str) -> Result<SignedDuration> {
+    let s = s.trim();
+    if let Some(clock) = parse_clock(s) {
+        return Ok(clock);
+    }
+    s.parse()
+        .map_err(|_| user_input_error!(InvalidDate, "invalid duration '{}'", s))
+}
+
+/// Parse one timesheet entry: a duration, or a time interval standing for
+/// its length.  An interval whose end has no date and is not after its
+/// start runs past midnight.
+pub fn parse_entry(s: &str) -> Result<SignedDuration> {
+    match parse_interval(s.trim()) {
+        Some(d) => Ok(d),
+        None => parse(s),
+    }
+}
+
+/// `START-END` or `START - END`, splitting at the first `-` that leaves a
+/// time, optionally after a date, on both sides.
+fn parse_interval(s: &str) -> Option<SignedDuration> {
+    s.match_indices('-').find_map(|(i, _)| {
+        let (start, end) = (read_stamp(&s[..i])?, read_stamp(&s[i + 1..])?);
+        let first = start.0.unwrap_or(Date::ZERO).to_datetime(start.1);
+        let mut last = end.0.unwrap_or(first.date()).to_datetime(end.1);
+        if end.0.is_none() && last <= first {
+            last = last.tomorrow().ok()?;
+        }
+        let d = first.duration_until(last);
+        (!d.is_negative()).then_some(d)
+    })
+}
+
+/// `HH:MM[:SS]`, optionally after `YYYY-MM-DD` and a space or `T`.
+fn read_stamp(s: &str) -> Option<(Option<Date>, Time)> {
+    let s = s.trim();
+    let (date, time) = match s.split_once([' ', 'T']) {
+        Some((date, time)) => (Some(date.parse().ok()?), time.trim()),
+        None => (None, s),
+    };
+    if !time.contains(':') {
+        return None;
+    }
+    Some((date, time.parse().ok()?))
+}
+
+/// `[-]H:MM` or `[-]H:MM:SS`, with any number of hours.
+fn parse_clock(s: &str) -> Option<SignedDuration> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let mut parts = s.split(':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let mut rest = [0_i64; 2];
+    let mut count = 0;
+    for (slot, part) in rest.iter_mut().zip(parts.by_ref()) {
+        if part.len() != 2 {
+            return None;
+        }
+        *slot = part.parse().ok().filter(|n| (0..60).contains(n))?;
+        count += 1;
+    }
+    if count == 0 || parts.next().is_some() {
+        return None;
+    }
+    let secs = hours.checked_mul(3600)? + rest[0] * 60 + rest[1];
+    Some(SignedDuration::from_secs(if negative {
+        -secs
+    } else {
+        secs
+    }))
+}
+
+/// Render `d` in the `output` layout, rounding decimal layouts as
+/// `decimals` says.
+#[must_use]
+pub fn render(d: SignedDuration, output: SumOutput, decimals: Decimals) -> String {
+    match output {
+        SumOutput::Clock => {
+            let secs = d.as_secs().unsigned_abs();
+            let sign = if d.is_negative() { "-" } else { "" };
+            let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+            if s == 0 {
+                format!("{sign}{h}:{m:02}")
+            } else {
+                format!("{sign}{h}:{m:02}:{s:02}")
+            }
+        }
+        SumOutput::Hours => decimals.amount(d, 3600),
+        SumOutput::Minutes => decimals.amount(d, 60),
+        SumOutput::Seconds => decimals.amount(d, 1),
+        SumOutput::Decimal => decimals.largest_unit(d),
+        _ => {
+            let rounded = d
+                .round(SignedDurationRound::new().smallest(Unit::Second))
+                .unwrap_or(d);
+            format!("{rounded:#}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn secs(s: &str) -> i64 {
+        parse(s).unwrap().as_secs()
+    }
+
+    #[test]
+    fn parses_friendly_iso_and_clock_forms() {
+        assert_eq!(secs("1h30m"), 5400);
+        assert_eq!(secs("1h 30m"), 5400);
+        assert_eq!(secs("45m"), 2700);
+        assert_eq!(secs("1.5h"), 5400);
+        assert_eq!(secs("PT2H"), 7200);
+        assert_eq!(secs("2:15"), 8100);
+        assert_eq!(secs("0:00:45"), 45);
+        assert_eq!(secs("-0:30"), -1800);
+        assert!(parse("2:75").is_err());
+        assert!(parse("soon").is_err());
+    }
+
+    #[test]
+    fn parses_time_intervals() {
+        let secs = |s| parse_entry(s).unwrap().as_secs();
+        assert_eq!(secs("09:00-12:30"), 12_600);
+        assert_eq!(secs("13:15 - 17:45"), 16_200);
+        assert_eq!(secs("22:00-02:00"), 14_400);
+        assert_eq!(secs("2025-01-15 09:00-12:30"), 12_600);
+        assert_eq!(secs("2025-01-15T22:00 - 2025-01-17T02:00:30"), 100_830);
+        assert_eq!(secs("1h30m"), 5400);
+        assert_eq!(secs("-0:30"), -1800);
+        assert!(parse_entry("2025-01-16 09:00 - 2025-01-15 10:00").is_err());
+        assert!(parse_entry("09:00-lunch").is_err());
+    }
+
+    #[test]
+    fn renders_each_layout() {
+        let d = SignedDuration::from_secs(27 * 3600 + 30 * 60);
+        let render = |d, output| render(d, output, Decimals::default());
+        assert_eq!(render(d, SumOutput::Human), "27h 30m");
+        assert_eq!(render(d, SumOutput::Clock), "27:30");
+        assert_eq!(render(d, SumOutput::Hours), "27.5");
+        assert_eq!(render(d, SumOutput::Minutes), "1650");
+        assert_eq!(render(d, SumOutput::Decimal), "1.15d");
+        assert_eq!(
+            render(SignedDuration::from_secs(-61), SumOutput::Clock),
+            "-0:01:01"
+        );
+    }
+
+    #[test]
+    fn decimals_round_as_configured() {
+        let d = SignedDuration::from_secs(100 * 60);
+        let with = |places, rounding| Decimals { places, rounding };
+        assert_eq!(Decimals::default().amount(d, 3600), "1.67");
+        assert_eq!(with(1, Rounding::Down).amount(d, 3600), "1.6");
+        assert_eq!(with(0, Rounding::Up).amount(d, 3600), "2");
+        assert_eq!(with(2, Rounding::Up).amount(-d, 3600), "-1.67");
+        assert_eq!(
+            Decimals::default().largest_unit(SignedDuration::from_secs(54 * 3600)),
+            "2.25d"
+        );
+        assert_eq!(Decimals::default().largest_unit(SignedDuration::ZERO), "0s");
+
+        let cfg = HumanizeConfig {
+            decimals: Some(3),
+            rounding: Some("UP".into()),
+            ..HumanizeConfig::default()
+        };
+        assert_eq!(
+            Decimals::from_config(&cfg, None, None).unwrap(),
+            with(3, Rounding::Up)
+        );
+        assert_eq!(
+            Decimals::from_config(&cfg, Some(0), Some(Rounding::Down)).unwrap(),
+            with(0, Rounding::Down)
+        );
+        let cfg = HumanizeConfig {
+            rounding: Some("sideways".into()),
+            ..HumanizeConfig::default()
+        };
+        assert!(Decimals::from_config(&cfg, None, None).is_err());
+        assert!(Decimals::from_config(&HumanizeConfig::default(), Some(10), None).is_err());
+    }
+}
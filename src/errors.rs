@@ -3,15 +3,80 @@
 //! This module provides a single [`Error`] enum that groups together all
 //! *user* and *system* failures plus two convenience macros for constructing
 //! those errors ergonomically.  [`Error::exit`] maps any error value to an
-//! appropriate process exit code.  All public items live behind concise
-//! documentation so that generated docs.rs output remains immediately useful
-//! without excessive inline comments.
+//! appropriate process exit code, which [`ExitCodes`] lets the config file
+//! or `--classic-exit-codes` override per category.  All public items live
+//! behind concise documentation so that generated docs.rs output remains
+//! immediately useful without excessive inline comments.
+
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 
 /// POSIX sysexits-compatible exit codes.
 const EX_USAGE: i32 = 64;
 const EX_IOERR: i32 = 74;
 const EX_CONFIG: i32 = 78;
 
+static USAGE_CODE: AtomicI32 = AtomicI32::new(EX_USAGE);
+static IO_CODE: AtomicI32 = AtomicI32::new(EX_IOERR);
+static CONFIG_CODE: AtomicI32 = AtomicI32::new(EX_CONFIG);
+static CLASSIC: AtomicBool = AtomicBool::new(false);
+
+/// The exit code of each error category.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExitCodes {
+    /// User input errors (64 by default).
+    pub usage: i32,
+    /// I/O errors (74 by default).
+    pub io: i32,
+    /// Configuration errors (78 by default).
+    pub config: i32,
+}
+
+impl Default for ExitCodes {
+    fn default() -> Self {
+        Self {
+            usage: EX_USAGE,
+            io: EX_IOERR,
+            config: EX_CONFIG,
+        }
+    }
+}
+
+impl ExitCodes {
+    /// Every failure exits with 1.
+    pub const CLASSIC: Self = Self {
+        usage: 1,
+        io: 1,
+        config: 1,
+    };
+
+    /// Use these codes for the rest of the process, unless
+    /// [`ExitCodes::use_classic`] was called.
+    pub fn install(self) {
+        USAGE_CODE.store(self.usage, Ordering::Relaxed);
+        IO_CODE.store(self.io, Ordering::Relaxed);
+        CONFIG_CODE.store(self.config, Ordering::Relaxed);
+    }
+
+    /// Exit with 1 on every failure for the rest of the process
+    /// (`--classic-exit-codes`), whatever codes are installed.
+    pub fn use_classic() {
+        CLASSIC.store(true, Ordering::Relaxed);
+    }
+
+    /// The codes in effect.
+    pub fn current() -> Self {
+        if CLASSIC.load(Ordering::Relaxed) {
+            return Self::CLASSIC;
+        }
+        Self {
+            usage: USAGE_CODE.load(Ordering::Relaxed),
+            io: IO_CODE.load(Ordering::Relaxed),
+            config: CONFIG_CODE.load(Ordering::Relaxed),
+        }
+    }
+}
+
 /// All possible failures surfaced by the CLI.
 #[non_exhaustive]
 #[derive(thiserror::Error, Debug, PartialEq, Eq)]
@@ -43,6 +108,10 @@ pub enum UserInputError {
     InvalidNow(String),
     #[error("Missing required argument: {0}")]
     MissingArgument(String),
+    #[error("Warning treated as error: {0}")]
+    DeniedWarning(String),
+    #[error("Invalid config setting: {0}")]
+    InvalidConfig(String),
 }
 
 /// Failures that stem from the operating environment or runtime.
@@ -53,6 +122,13 @@ pub enum SystemError {
     Config(String),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    /// A command run for the user, as by `--exec`, failed; td exits with
+    /// the command's own code.
+    #[error("command '{program}' failed: {status}")]
+    Command {
+        program: String,
+        status: std::process::ExitStatus,
+    },
 }
 
 /// Crate‑wide `Result` alias that uses the consolidated [`Error`] type.
@@ -61,29 +137,35 @@ pub type Result<T> = std::result::Result<T, Error>;
 impl Error {
     /// Print a diagnostic message to stderr and exit with the appropriate code.
     pub fn exit(self) -> ! {
+        let code = self.exit_code();
         match self {
             Error::UserInput(err) => {
                 eprintln!("{}", colorize_suggestion(&format!("{err}")));
-                std::process::exit(EX_USAGE);
             }
             Error::System(err) => {
                 eprintln!("System error: {}", err);
-
-                match err {
-                    SystemError::Config(_) => std::process::exit(EX_CONFIG),
-                    SystemError::Io(_) => std::process::exit(EX_IOERR),
-                }
             }
         }
+        std::process::exit(code)
+    }
+
+    /// The code [`Error::exit`] exits with, from [`ExitCodes::current`].
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        let codes = ExitCodes::current();
+        match self {
+            Error::UserInput(_) => codes.usage,
+            Error::System(SystemError::Config(_)) => codes.config,
+            Error::System(SystemError::Io(_)) => codes.io,
+            Error::System(SystemError::Command { status, .. }) => status.code().unwrap_or(1),
+        }
     }
 }
 
 /// Apply yellow ANSI coloring to the suggested word in "Did you mean '...'?" messages.
-/// Only colorizes when stderr is a terminal and NO_COLOR is not set.
+/// Only colorizes when [`crate::term::stderr_color`] allows it.
 fn colorize_suggestion(msg: &str) -> String {
-    use std::io::IsTerminal;
-
-    if !std::io::stderr().is_terminal() || std::env::var("NO_COLOR").is_ok() {
+    if !crate::term::stderr_color() {
         return msg.to_string();
     }
 
@@ -108,6 +190,16 @@ impl PartialEq for SystemError {
         match (self, other) {
             (Config(a), Config(b)) => a == b,
             (Io(a), Io(b)) => a.kind() == b.kind(),
+            (
+                Command {
+                    program: a,
+                    status: x,
+                },
+                Command {
+                    program: b,
+                    status: y,
+                },
+            ) => a == b && x == y,
             _ => false,
         }
     }
@@ -214,6 +306,14 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn exit_codes_default_to_sysexits() {
+        assert_eq!(user_input_error!(InvalidDate).exit_code(), 64);
+        assert_eq!(system_error!(Config).exit_code(), 78);
+        let io: Error = std::io::Error::from(std::io::ErrorKind::BrokenPipe).into();
+        assert_eq!(io.exit_code(), 74);
+    }
+
     #[test]
     fn conversion_from_io_error() {
         let err: Error = std::io::Error::from(std::io::ErrorKind::PermissionDenied).into();
@@ -0,0 +1,244 @@
+//! Command templates for `td --exec`.
+//!
+//! A template is split into words the way a POSIX shell would split a
+//! simple command (single quotes, double quotes and backslashes are
+//! honoured; nothing is expanded), and then the tokens `{iso}`, `{epoch}`,
+//! `{fmt}` and the [format tokens](crate::core::FORMAT_TOKENS), `{kind}`
+//! among them, are substituted inside each word.  Because substitution
+//! happens after splitting, a value containing spaces or quotes always
+//! stays a single argument.  `{{` and `}}` stand for literal braces.
+//!
+//! The `--if-past` / `--if-future` texts use the same tokens, plus
+//! `{relative}`, without any word splitting.
+
+use crate::{Result, core::ProcessOutput, user_input_error};
+
+/// Split `template` into words and substitute the output tokens.
+pub fn expand(template: &str, output: &ProcessOutput) -> Result<Vec<String>> {
+    let words = split(template)?;
+    if words.is_empty() {
+        return Err(user_input_error!(MissingArgument, "--exec needs a command"));
+    }
+    let tokens = tokens(output);
+    words
+        .iter()
+        .map(|w| substitute(w, &tokens, unknown_token))
+        .collect()
+}
+
+/// Substitute the output tokens and `{relative}` in an `--if-past` or
+/// `--if-future` text.
+pub fn fill(text: &str, output: &ProcessOutput, relative: &str) -> Result<String> {
+    let mut tokens = tokens(output);
+    tokens.push(("relative", relative.to_string()));
+    substitute(text, &tokens, |token| {
+        user_input_error!(
+            UnsupportedFormat,
+            "unknown token '{}' in --if-past/--if-future; use {{iso}}, {{epoch}}, {{fmt}}, {{kind}}, {{relative}} or a zone token",
+            token
+        )
+    })
+}
+
+/// Render `argv` as a line a shell would split back into the same words.
+#[must_use]
+pub fn quote(argv: &[String]) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c);
+    argv.iter()
+        .map(|w| {
+            if !w.is_empty() && w.chars().all(safe) {
+                w.clone()
+            } else {
+                format!("'{}'", w.replace('\'', r"'\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) fn split(template: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                words.extend(word.take());
+            }
+            '\'' => {
+                let buf = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => buf.push(c),
+                        None => return Err(unterminated(template)),
+                    }
+                }
+            }
+            '"' => {
+                let buf = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => buf.push(c),
+                            Some(c) => {
+                                buf.push('\\');
+                                buf.push(c);
+                            }
+                            None => return Err(unterminated(template)),
+                        },
+                        Some(c) => buf.push(c),
+                        None => return Err(unterminated(template)),
+                    }
+                }
+            }
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    word.get_or_insert_with(String::new).push(c);
+                }
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+fn unterminated(template: &str) -> crate::Error {
+    user_input_error!(
+        InvalidDateFormat,
+        "unterminated quote in --exec template: {}",
+        template
+    )
+}
+
+fn tokens(output: &ProcessOutput) -> Vec<(&'static str, String)> {
+    let mut tokens = vec![
+        ("iso", output.iso.clone()),
+        ("epoch", output.epoch.to_string()),
+        ("fmt", output.formatted.clone()),
+    ];
+    tokens.extend(output.tokens.iter().cloned());
+    tokens
+}
+
+fn substitute(
+    word: &str,
+    tokens: &[(&str, String)],
+    unknown: fn(&str) -> crate::Error,
+) -> Result<String> {
+    let mut out = String::with_capacity(word.len());
+    let mut rest = word;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if let Some(after) = tail.strip_prefix("{{").or_else(|| tail.strip_prefix("}}")) {
+            out.push_str(&tail[..1]);
+            rest = after;
+            continue;
+        }
+        let Some(end) = tail.find('}').filter(|_| tail.starts_with('{')) else {
+            return Err(unknown(tail));
+        };
+        match tokens.iter().find(|(name, _)| *name == &tail[1..end]) {
+            Some((_, value)) => out.push_str(value),
+            None => return Err(unknown(&tail[..=end])),
+        }
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn unknown_token(token: &str) -> crate::Error {
+    user_input_error!(
+        UnsupportedFormat,
+        "unknown --exec token '{}'; use {{iso}}, {{epoch}}, {{fmt}}, {{kind}}, {{tzid}}, {{tzabbr}}, {{tzoffset}} or {{beats}} ({{{{ and }}}} for braces)",
+        token
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use crate::cli::InputKind;
+    use pretty_assertions::assert_eq;
+
+    fn output() -> ProcessOutput {
+        ProcessOutput {
+            formatted: "Friday 17".into(),
+            epoch: 1_737_072_000,
+            epoch_ns: 1_737_072_000_000_000_000,
+            iso: "2025-01-17T00:00:00+00:00".into(),
+            tokens: [
+                ("tzid", "America/Sao_Paulo".into()),
+                ("tzabbr", "-03".into()),
+                ("tzoffset", "-10800".into()),
+                ("beats", "125".into()),
+                ("kind", "date".into()),
+            ],
+            kind: InputKind::Date,
+        }
+    }
+
+    #[test]
+    fn tokens_stay_single_words() {
+        let argv = expand("gh milestone create --due {iso} --title '{fmt}'", &output()).unwrap();
+        assert_eq!(
+            argv,
+            [
+                "gh",
+                "milestone",
+                "create",
+                "--due",
+                "2025-01-17T00:00:00+00:00",
+                "--title",
+                "Friday 17"
+            ]
+        );
+        assert_eq!(expand("echo {fmt}", &output()).unwrap()[1], "Friday 17");
+        assert_eq!(
+            expand("echo {tzid}:{tzoffset}", &output()).unwrap()[1],
+            "America/Sao_Paulo:-10800"
+        );
+        assert_eq!(expand("echo {kind}", &output()).unwrap()[1], "date");
+    }
+
+    #[test]
+    fn quotes_and_escapes_split_like_a_shell() {
+        assert_eq!(
+            split(r#"a "b c" 'd e' f\ g "h\"i" ''"#).unwrap(),
+            ["a", "b c", "d e", "f g", "h\"i", ""]
+        );
+        assert!(split("echo 'oops").is_err());
+    }
+
+    #[test]
+    fn braces_escape_and_unknown_tokens_fail() {
+        assert_eq!(
+            expand("echo {{{epoch}}}", &output()).unwrap()[1],
+            "{1737072000}"
+        );
+        assert!(expand("echo {date}", &output()).is_err());
+        assert!(expand("echo }", &output()).is_err());
+        assert!(expand("   ", &output()).is_err());
+    }
+
+    #[test]
+    fn fill_keeps_spaces_and_adds_relative() {
+        assert_eq!(
+            fill("{relative} left, due {fmt}", &output(), "2 days").unwrap(),
+            "2 days left, due Friday 17"
+        );
+        assert!(fill("{when}", &output(), "2 days").is_err());
+    }
+
+    #[test]
+    fn quote_round_trips_awkward_words() {
+        let argv = vec!["echo".to_string(), "it's here".into(), String::new()];
+        assert_eq!(quote(&argv), r"echo 'it'\''s here' ''");
+    }
+}
@@ -0,0 +1,136 @@
+//! History of the expressions typed at the interactive `date> ` prompt.
+//!
+//! Entries live in `$XDG_STATE_HOME/tardis/history` (or the OS equivalent),
+//! one per line, oldest first.  Unlike the cache this is state: nothing can
+//! rebuild it, so it is kept apart from both the config and the cache
+//! directories.  The file holds at most [`LIMIT`] entries; older ones are
+//! dropped as new ones arrive.  Callers treat recording as best-effort: a
+//! failure to write the history never fails a command.
+
+use std::{
+    env, fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{Result, system_error};
+
+const APP_DIR: &str = "tardis";
+const HISTORY_FILE: &str = "history";
+
+/// Most entries kept in the history file.
+pub const LIMIT: usize = 1000;
+
+/// Resolve the state directory: `$TARDIS_STATE_DIR`, else
+/// `$XDG_STATE_HOME/tardis`, else `tardis` in the OS state directory (or
+/// the local data directory where there is none).
+#[must_use = "state_dir returns a PathBuf that should not be discarded"]
+pub fn state_dir() -> Result<PathBuf> {
+    if let Some(dir) = env::var_os("TARDIS_STATE_DIR").filter(|v| !v.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    let base_dir = env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(dirs::state_dir)
+        .or_else(dirs::data_local_dir)
+        .ok_or_else(|| {
+            system_error!(
+                Config,
+                "Could not locate state directory; set $TARDIS_STATE_DIR or $XDG_STATE_HOME."
+            )
+        })?;
+
+    Ok(base_dir.join(APP_DIR))
+}
+
+/// Resolve the path to the history file.
+#[must_use = "path returns a PathBuf that should not be discarded"]
+pub fn path() -> Result<PathBuf> {
+    Ok(state_dir()?.join(HISTORY_FILE))
+}
+
+/// The recorded entries, oldest first.
+pub fn entries() -> Result<Vec<String>> {
+    entries_at(&path()?)
+}
+
+/// Append `entry`, unless it repeats the latest one, keeping the newest
+/// [`LIMIT`] entries.
+pub fn record(entry: &str) -> Result<()> {
+    record_at(&path()?, entry)
+}
+
+/// Delete the history, returning how many entries it held.
+pub fn clear() -> Result<usize> {
+    clear_at(&path()?)
+}
+
+fn entries_at(path: &Path) -> Result<Vec<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents.lines().map(str::to_string).collect()),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn record_at(path: &Path, entry: &str) -> Result<()> {
+    let entry = entry.trim();
+    if entry.is_empty() || entry.contains('\n') {
+        return Ok(());
+    }
+    let mut entries = entries_at(path)?;
+    if entries.last().is_some_and(|last| last == entry) {
+        return Ok(());
+    }
+    entries.push(entry.to_string());
+    let keep = &entries[entries.len().saturating_sub(LIMIT)..];
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(
+        path,
+        keep.iter().map(|e| format!("{e}\n")).collect::<String>(),
+    )?;
+    Ok(())
+}
+
+fn clear_at(path: &Path) -> Result<usize> {
+    let count = entries_at(path)?.len();
+    match fs::remove_file(path) {
+        Ok(()) => Ok(count),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(0),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use assert_fs::TempDir;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn records_skip_repeats_and_keep_the_newest() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("tardis").join(HISTORY_FILE);
+
+        record_at(&path, "tomorrow").unwrap();
+        record_at(&path, "tomorrow").unwrap();
+        record_at(&path, "  next friday ").unwrap();
+        record_at(&path, "").unwrap();
+        assert_eq!(entries_at(&path).unwrap(), ["tomorrow", "next friday"]);
+
+        for i in 0..LIMIT {
+            record_at(&path, &format!("{i} days ago")).unwrap();
+        }
+        let entries = entries_at(&path).unwrap();
+        assert_eq!(entries.len(), LIMIT);
+        assert_eq!(entries[0], "0 days ago");
+
+        assert_eq!(clear_at(&path).unwrap(), LIMIT);
+        assert_eq!(clear_at(&path).unwrap(), 0);
+    }
+}
@@ -0,0 +1,189 @@
+//! Holiday calendars for **TARDIS**.
+//!
+//! A [`Calendar`] is built from the `[holidays]` table of the config file,
+//! which maps a holiday's name to when it falls: a month and day such as
+//! `"12-25"` repeats every year, while any other date expression, such as
+//! `"2025-04-18"`, names a single day.  A February 29 holiday is skipped in
+//! common years.
+
+use std::collections::HashMap;
+
+use jiff::{Zoned, civil::Date};
+
+use crate::{Result, parser, user_input_error};
+
+/// When a holiday falls.
+#[must_use]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum When {
+    /// The same month and day every year.
+    Yearly { month: i8, day: i8 },
+    /// One day only.
+    Once(Date),
+}
+
+/// A named holiday.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Holiday {
+    pub name: String,
+    pub when: When,
+}
+
+/// The holidays of the `[holidays]` table.
+#[must_use]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Calendar {
+    holidays: Vec<Holiday>,
+}
+
+impl When {
+    /// Parse `"MM-DD"` (or `"--MM-DD"`) as a yearly date, and anything else
+    /// as a date expression resolved against `now`.
+    pub fn parse(s: &str, now: &Zoned) -> Result<Self> {
+        Self::read(s, now).map_err(|message| user_input_error!(InvalidDate, "{}", message))
+    }
+
+    fn read(s: &str, now: &Zoned) -> std::result::Result<Self, String> {
+        if let Some((month, day)) = parse_month_day(s) {
+            date_in(2000, month, day).ok_or_else(|| format!("no such day '{}'", s.trim()))?;
+            return Ok(Self::Yearly { month, day });
+        }
+        let day = parser::parse(s, now, &parser::ParseOptions::default())
+            .map_err(|e| e.format_message())?;
+        Ok(Self::Once(day.date()))
+    }
+
+    /// The day this falls on in `year`, if any.
+    #[must_use]
+    pub fn in_year(self, year: i16) -> Option<Date> {
+        match self {
+            Self::Yearly { month, day } => date_in(year, month, day),
+            Self::Once(d) => (d.year() == year).then_some(d),
+        }
+    }
+}
+
+impl Calendar {
+    /// Build the calendar from the `[holidays]` table, resolving one-off
+    /// dates against `now`.
+    pub fn from_table(table: &HashMap<String, String>, now: &Zoned) -> Result<Self> {
+        let mut holidays = table
+            .iter()
+            .map(|(name, value)| {
+                let when = When::read(value, now).map_err(|message| {
+                    user_input_error!(
+                        InvalidConfig,
+                        "invalid value for 'holidays.{}': {}",
+                        name,
+                        message
+                    )
+                })?;
+                Ok(Holiday {
+                    name: name.clone(),
+                    when,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        holidays.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Self { holidays })
+    }
+
+    /// Whether the calendar has no holidays at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.holidays.is_empty()
+    }
+
+    /// Names of the holidays on `day`.
+    #[must_use]
+    pub fn on(&self, day: Date) -> Vec<&str> {
+        self.holidays
+            .iter()
+            .filter(|h| h.when.in_year(day.year()) == Some(day))
+            .map(|h| h.name.as_str())
+            .collect()
+    }
+
+    /// Holidays from `first` to `last`, both included, in date order.
+    #[must_use]
+    pub fn between(&self, first: Date, last: Date) -> Vec<(Date, &str)> {
+        let mut out: Vec<(Date, &str)> = (first.year()..=last.year())
+            .flat_map(|year| {
+                self.holidays
+                    .iter()
+                    .filter_map(move |h| Some((h.when.in_year(year)?, h.name.as_str())))
+            })
+            .filter(|(d, _)| (first..=last).contains(d))
+            .collect();
+        out.sort();
+        out
+    }
+
+    /// The first holiday on or after `from`, looking a few years ahead.
+    #[must_use]
+    pub fn next(&self, from: Date) -> Option<(Date, &str)> {
+        let last = date_in(from.year().saturating_add(8), 12, 31)?;
+        self.between(from, last).into_iter().next()
+    }
+}
+
+/// Read `"MM-DD"` or `"--MM-DD"` as a month and day, without checking that
+/// the day exists.
+#[must_use]
+pub fn parse_month_day(s: &str) -> Option<(i8, i8)> {
+    let (month, day) = s.trim().trim_start_matches("--").split_once('-')?;
+    Some((month.parse().ok()?, day.parse().ok()?))
+}
+
+fn date_in(year: i16, month: i8, day: i8) -> Option<Date> {
+    Date::new(year, month, day).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use jiff::civil::date;
+    use pretty_assertions::assert_eq;
+
+    fn calendar() -> Calendar {
+        let now: Zoned = "2025-01-15T10:30:00+00:00[UTC]".parse().unwrap();
+        let table = HashMap::from([
+            ("Christmas".to_string(), "12-25".to_string()),
+            ("Good Friday".to_string(), "2025-04-18".to_string()),
+            ("Leap Day".to_string(), "--02-29".to_string()),
+        ]);
+        Calendar::from_table(&table, &now).unwrap()
+    }
+
+    #[test]
+    fn yearly_and_one_off_holidays() {
+        let cal = calendar();
+        assert_eq!(cal.on(date(2031, 12, 25)), ["Christmas"]);
+        assert_eq!(cal.on(date(2026, 4, 18)), Vec::<&str>::new());
+        assert_eq!(
+            cal.next(date(2025, 1, 15)),
+            Some((date(2025, 4, 18), "Good Friday"))
+        );
+        assert_eq!(
+            cal.between(date(2025, 1, 1), date(2028, 3, 1)),
+            [
+                (date(2025, 4, 18), "Good Friday"),
+                (date(2025, 12, 25), "Christmas"),
+                (date(2026, 12, 25), "Christmas"),
+                (date(2027, 12, 25), "Christmas"),
+                (date(2028, 2, 29), "Leap Day"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_impossible_days() {
+        let now: Zoned = "2025-01-15T10:30:00+00:00[UTC]".parse().unwrap();
+        assert!(When::parse("02-30", &now).is_err());
+        assert!(When::parse("whenever", &now).is_err());
+        assert_eq!(parse_month_day("--06-24"), Some((6, 24)));
+    }
+}
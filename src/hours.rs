@@ -0,0 +1,304 @@
+//! Opening hours for **TARDIS**.
+//!
+//! A [`Schedule`] is built from the `[hours]` table of the config file,
+//! which maps days to the shifts worked on them: `mon-fri = "09:00-18:00"`,
+//! `sat = "10:00-12:00, 13:00-16:00"` or `sun = "closed"`.  A key naming
+//! fewer days wins over one naming more, so `fri` can shorten `mon-fri`.
+//! A key that is a date instead, `"12-24"` every year or `"2025-12-31"`
+//! once, is an exception to its weekday.  Days of the holiday calendar are
+//! closed unless an exception names them.  A shift whose end is not after
+//! its start runs past midnight, as in [`crate::meet`].
+
+use std::collections::HashMap;
+
+use jiff::{
+    Span, Zoned,
+    civil::{Date, Weekday},
+};
+
+use crate::{
+    Result,
+    holiday::{Calendar, When},
+    meet::Hours,
+    user_input_error,
+};
+
+/// How far ahead [`Schedule::next_open`] looks for a shift.
+const LOOKAHEAD_DAYS: i64 = 400;
+
+/// Working hours by weekday, with exceptions for given days.
+#[must_use]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schedule {
+    /// Shifts by weekday, Monday first.
+    weekly: [Vec<Hours>; 7],
+    exceptions: Vec<(When, Vec<Hours>)>,
+    holidays: Calendar,
+}
+
+/// One stretch of open time.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shift {
+    pub start: Zoned,
+    pub end: Zoned,
+}
+
+impl Schedule {
+    /// Build the schedule from the `[hours]` table, resolving one-off
+    /// exceptions against `now`.  An empty table opens `workdays` from
+    /// 09:00 to 17:00.
+    pub fn from_table(
+        table: &HashMap<String, String>,
+        workdays: &[Weekday],
+        holidays: Calendar,
+        now: &Zoned,
+    ) -> Result<Self> {
+        if table.is_empty() {
+            return Ok(Self {
+                holidays,
+                ..Self::weekly(Hours::default(), workdays)
+            });
+        }
+        let mut schedule = Self {
+            holidays,
+            ..Self::default()
+        };
+
+        // The number of days named by the key that set each weekday.
+        let mut set_by: [Option<(usize, &str)>; 7] = [None; 7];
+        let mut names: Vec<&String> = table.keys().collect();
+        names.sort();
+        for name in names {
+            let invalid = |message: String| {
+                user_input_error!(
+                    InvalidConfig,
+                    "invalid value for 'hours.{}': {}",
+                    name,
+                    message
+                )
+            };
+            let shifts = read_shifts(&table[name]).map_err(invalid)?;
+            let Some(days) = read_days(name) else {
+                let when = When::parse(name, now).map_err(|_| {
+                    invalid("expected days such as 'mon-fri' or a date such as '12-24'".into())
+                })?;
+                schedule.exceptions.push((when, shifts));
+                continue;
+            };
+            for day in &days {
+                let slot = &mut set_by[index(*day)];
+                match *slot {
+                    Some((n, other)) if n == days.len() => {
+                        return Err(invalid(format!(
+                            "'{}' also sets the hours for {:?}",
+                            other, day
+                        )));
+                    }
+                    Some((n, _)) if n < days.len() => continue,
+                    _ => {}
+                }
+                *slot = Some((days.len(), name));
+                schedule.weekly[index(*day)].clone_from(&shifts);
+            }
+        }
+        Ok(schedule)
+    }
+
+    /// The same `hours` on each of `workdays`, with no exceptions and no
+    /// holidays.
+    pub fn weekly(hours: Hours, workdays: &[Weekday]) -> Self {
+        let mut schedule = Self::default();
+        for day in workdays {
+            schedule.weekly[index(*day)] = vec![hours];
+        }
+        schedule
+    }
+
+    /// The shifts starting on `day`, earliest first.
+    #[must_use]
+    pub fn shifts_on(&self, day: Date) -> &[Hours] {
+        let exception = self
+            .exceptions
+            .iter()
+            .find(|(when, _)| when.in_year(day.year()) == Some(day));
+        match exception {
+            Some((_, shifts)) => shifts,
+            None if !self.holidays.on(day).is_empty() => &[],
+            None => &self.weekly[index(day.weekday())],
+        }
+    }
+
+    /// Whether `at` falls inside a shift.
+    pub fn is_open(&self, at: &Zoned) -> Result<bool> {
+        Ok(self.next_open(at)?.is_some_and(|shift| shift.start <= *at))
+    }
+
+    /// The shift holding `at`, or else the next one to start, looking a
+    /// little over a year ahead.
+    pub fn next_open(&self, at: &Zoned) -> Result<Option<Shift>> {
+        let day = at.date();
+        for offset in -1..=LOOKAHEAD_DAYS {
+            let date = day
+                .checked_add(Span::new().days(offset))
+                .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+            for hours in self.shifts_on(date) {
+                let shift = shift(date, *hours, at)?;
+                if shift.end > *at {
+                    return Ok(Some(shift));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// `hours` worked from `day`, in the time zone of `at`.
+fn shift(day: Date, hours: Hours, at: &Zoned) -> Result<Shift> {
+    let zoned = |day: Date, time| {
+        day.to_datetime(time)
+            .to_zoned(at.time_zone().clone())
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))
+    };
+    let end_day = if hours.end > hours.start {
+        day
+    } else {
+        day.tomorrow()
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))?
+    };
+    Ok(Shift {
+        start: zoned(day, hours.start)?,
+        end: zoned(end_day, hours.end)?,
+    })
+}
+
+/// `"mon-fri"`, `"sat"`, `"mon, wed, fri"` or `"fri-mon"`, wrapping past
+/// Sunday; `None` when `key` names no days.
+fn read_days(key: &str) -> Option<Vec<Weekday>> {
+    let mut days = Vec::new();
+    for part in key.split(',') {
+        let day = |s: &str| crate::recur::parse_weekday(s);
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (day(first)?, day(last)?);
+                let mut d = first;
+                days.push(d);
+                while d != last {
+                    d = d.next();
+                    days.push(d);
+                }
+            }
+            None => days.push(day(part)?),
+        }
+    }
+    days.sort_by_key(|d| index(*d));
+    days.dedup();
+    Some(days)
+}
+
+/// `"09:00-18:00"`, several separated by commas, or `"closed"`.
+fn read_shifts(value: &str) -> std::result::Result<Vec<Hours>, String> {
+    if value.trim().eq_ignore_ascii_case("closed") {
+        return Ok(Vec::new());
+    }
+    let mut shifts = value
+        .split(',')
+        .map(|s| {
+            s.parse::<Hours>().map_err(|_| {
+                format!(
+                    "invalid shift '{}' (expected HH:MM-HH:MM or \"closed\")",
+                    s.trim()
+                )
+            })
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    shifts.sort_by_key(|h| h.start);
+    Ok(shifts)
+}
+
+fn index(day: Weekday) -> usize {
+    day.to_monday_zero_offset().unsigned_abs().into()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use jiff::civil::{date, time};
+    use pretty_assertions::assert_eq;
+
+    fn now() -> Zoned {
+        "2025-01-15T10:30:00+00:00[UTC]".parse().unwrap()
+    }
+
+    fn schedule(entries: &[(&str, &str)]) -> Result<Schedule> {
+        let table = entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let holidays = HashMap::from([("MLK".to_string(), "2025-01-20".to_string())]);
+        let holidays = Calendar::from_table(&holidays, &now()).unwrap();
+        Schedule::from_table(&table, &[Weekday::Monday], holidays, &now())
+    }
+
+    fn at(s: &str) -> Zoned {
+        format!("{s}+00:00[UTC]").parse().unwrap()
+    }
+
+    #[test]
+    fn narrower_keys_and_exceptions_win() {
+        let s = schedule(&[
+            ("mon-fri", "09:00-18:00"),
+            ("fri", "09:00-12:00, 13:00-15:00"),
+            ("sat-sun", "closed"),
+            ("12-24", "10:00-12:00"),
+        ])
+        .unwrap();
+        let h = |a: (i8, i8), b: (i8, i8)| Hours {
+            start: time(a.0, a.1, 0, 0),
+            end: time(b.0, b.1, 0, 0),
+        };
+        assert_eq!(s.shifts_on(date(2025, 1, 15)), [h((9, 0), (18, 0))]);
+        assert_eq!(
+            s.shifts_on(date(2025, 1, 17)),
+            [h((9, 0), (12, 0)), h((13, 0), (15, 0))]
+        );
+        assert_eq!(s.shifts_on(date(2025, 1, 18)), []);
+        assert_eq!(s.shifts_on(date(2025, 1, 20)), []);
+        assert_eq!(s.shifts_on(date(2025, 12, 24)), [h((10, 0), (12, 0))]);
+
+        assert!(s.is_open(&at("2025-01-15T17:59:59")).unwrap());
+        assert!(!s.is_open(&at("2025-01-17T12:30:00")).unwrap());
+        let next = s.next_open(&at("2025-01-17T15:00:00")).unwrap().unwrap();
+        assert_eq!(next.start, at("2025-01-21T09:00:00"));
+    }
+
+    #[test]
+    fn overnight_shifts_and_defaults() {
+        let s = schedule(&[("sat", "22:00-06:00")]).unwrap();
+        assert!(s.is_open(&at("2025-01-19T05:00:00")).unwrap());
+        let shift = s.next_open(&at("2025-01-19T05:00:00")).unwrap().unwrap();
+        assert_eq!(shift.end, at("2025-01-19T06:00:00"));
+
+        let s = schedule(&[]).unwrap();
+        assert!(s.is_open(&at("2025-01-13T16:59:00")).unwrap());
+        assert!(!s.is_open(&at("2025-01-14T10:00:00")).unwrap());
+        assert_eq!(
+            s.next_open(&at("2025-01-14T10:00:00"))
+                .unwrap()
+                .unwrap()
+                .start,
+            at("2025-01-27T09:00:00")
+        );
+    }
+
+    #[test]
+    fn rejects_bad_keys_and_shifts() {
+        assert!(schedule(&[("mon-fri", "9 to 5")]).is_err());
+        assert!(schedule(&[("someday", "09:00-17:00")]).is_err());
+        let err = schedule(&[("mon-wed", "09:00-17:00"), ("wed-fri", "10:00-16:00")])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("also sets the hours for Wednesday"), "{err}");
+    }
+}
@@ -0,0 +1,248 @@
+//! Relative descriptions of a moment, for `td humanize` and `--relative`.
+//!
+//! A [`Humanizer`] says how far a moment is from now in calendar units,
+//! largest first: `in 1 week 6 days`, `3 hours ago`.  It keeps as many
+//! units as its granularity allows and rounds the last one.  Moments within
+//! `now_within` of now read `now`, and moments on the adjacent calendar day
+//! that are at least `named_day_after` away read `yesterday` or `tomorrow`.
+//! Every description parses back as a `td` expression.
+
+use jiff::{RoundMode, SignedDuration, Span, SpanRound, Unit, Zoned};
+
+use crate::{Result, config::HumanizeConfig, duration, user_input_error};
+
+/// Units in the order they are printed.
+const UNITS: [(Unit, &str); 7] = [
+    (Unit::Year, "year"),
+    (Unit::Month, "month"),
+    (Unit::Week, "week"),
+    (Unit::Day, "day"),
+    (Unit::Hour, "hour"),
+    (Unit::Minute, "minute"),
+    (Unit::Second, "second"),
+];
+
+/// Describes moments relative to now.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Humanizer {
+    granularity: usize,
+    now_within: SignedDuration,
+    named_day_after: SignedDuration,
+}
+
+impl Default for Humanizer {
+    /// One unit, `now` within 10 seconds, named days from 12 hours away.
+    fn default() -> Self {
+        Self {
+            granularity: 1,
+            now_within: SignedDuration::from_secs(10),
+            named_day_after: SignedDuration::from_hours(12),
+        }
+    }
+}
+
+impl Humanizer {
+    /// The defaults, overridden by the `[humanize]` config table.
+    pub fn from_config(cfg: &HumanizeConfig) -> Result<Self> {
+        let threshold = |key: &str, value: &Option<String>, default| match value {
+            Some(text) => duration::parse(text).map_err(|_| {
+                user_input_error!(
+                    InvalidDate,
+                    "[humanize] {} = '{}' is not a duration",
+                    key,
+                    text
+                )
+            }),
+            None => Ok(default),
+        };
+        let defaults = Self::default();
+        let humanizer = Self {
+            granularity: defaults.granularity,
+            now_within: threshold("now_within", &cfg.now_within, defaults.now_within)?,
+            named_day_after: threshold(
+                "named_day_after",
+                &cfg.named_day_after,
+                defaults.named_day_after,
+            )?,
+        };
+        match cfg.granularity {
+            Some(n) => humanizer.granularity(n),
+            None => Ok(humanizer),
+        }
+    }
+
+    /// Show at most `n` units (1 to 7).
+    pub fn granularity(self, n: usize) -> Result<Self> {
+        if !(1..=UNITS.len()).contains(&n) {
+            return Err(user_input_error!(
+                InvalidDate,
+                "granularity must be between 1 and {}, got {}",
+                UNITS.len(),
+                n
+            ));
+        }
+        Ok(Self {
+            granularity: n,
+            ..self
+        })
+    }
+
+    /// Describe `target` as seen from `now`.
+    pub fn describe(&self, target: &Zoned, now: &Zoned) -> Result<String> {
+        let gap = now.duration_until(target).abs();
+        if gap < self.now_within {
+            return Ok("now".to_string());
+        }
+        if gap >= self.named_day_after {
+            let day = target.date();
+            if now.date().yesterday().is_ok_and(|d| d == day) {
+                return Ok("yesterday".to_string());
+            }
+            if now.date().tomorrow().is_ok_and(|d| d == day) {
+                return Ok("tomorrow".to_string());
+            }
+        }
+
+        let words = self.distance(target, now)?;
+        Ok(if target > now {
+            format!("in {words}")
+        } else {
+            format!("{words} ago")
+        })
+    }
+
+    /// How far `target` is from `now`, without "in" or "ago" and without
+    /// the "now" / "yesterday" / "tomorrow" shortcuts: `1 week 6 days`.
+    pub fn distance(&self, target: &Zoned, now: &Zoned) -> Result<String> {
+        let span = now.until((Unit::Year, target)).map_err(jiff_error)?;
+        let Some(first) = amounts(&span).iter().position(|&n| n != 0) else {
+            return Ok("0 seconds".to_string());
+        };
+        let last = (first + self.granularity - 1).min(UNITS.len() - 1);
+        let mut amounts = amounts(&round(span, UNITS[last].0, now)?);
+        if UNITS[last].0 == Unit::Week && amounts[3] >= 4 {
+            amounts[2] += 1;
+        }
+
+        let words: Vec<String> = UNITS
+            .iter()
+            .zip(amounts)
+            .take(last + 1)
+            .filter(|&(_, n)| n != 0)
+            .map(|((_, name), n)| format!("{n} {name}{}", if n == 1 { "" } else { "s" }))
+            .collect();
+        Ok(words.join(" "))
+    }
+}
+
+/// Round `span` to `unit`, or to days when `unit` is weeks (weeks are
+/// counted out of the days afterwards).
+fn round(span: Span, unit: Unit, now: &Zoned) -> Result<Span> {
+    let smallest = if unit == Unit::Week { Unit::Day } else { unit };
+    span.round(
+        SpanRound::new()
+            .smallest(smallest)
+            .largest(Unit::Year)
+            .relative(now)
+            .mode(RoundMode::HalfExpand),
+    )
+    .map_err(jiff_error)
+}
+
+/// The magnitude of each of [`UNITS`] in `span`, with days split into
+/// weeks and days.
+fn amounts(span: &Span) -> [i64; 7] {
+    let span = span.abs();
+    let days = i64::from(span.get_days());
+    [
+        i64::from(span.get_years()),
+        i64::from(span.get_months()),
+        days / 7,
+        days % 7,
+        i64::from(span.get_hours()),
+        span.get_minutes(),
+        span.get_seconds(),
+    ]
+}
+
+fn jiff_error(e: jiff::Error) -> crate::Error {
+    user_input_error!(InvalidDate, "{}", e)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn zoned(s: &str) -> Zoned {
+        format!("{s}[UTC]").parse().unwrap()
+    }
+
+    fn describe(granularity: usize, target: &str) -> String {
+        Humanizer::default()
+            .granularity(granularity)
+            .unwrap()
+            .describe(&zoned(target), &zoned("2025-06-27T14:00:00"))
+            .unwrap()
+    }
+
+    #[test]
+    fn keeps_granularity_units_and_rounds_the_last() {
+        assert_eq!(describe(2, "2025-07-10T14:00:00"), "in 1 week 6 days");
+        assert_eq!(describe(1, "2025-07-10T14:00:00"), "in 2 weeks");
+        assert_eq!(
+            describe(3, "2025-07-10T18:30:00"),
+            "in 1 week 6 days 5 hours"
+        );
+        assert_eq!(describe(2, "2024-03-20T09:00:00"), "1 year 3 months ago");
+        assert_eq!(describe(1, "2025-06-27T11:20:00"), "3 hours ago");
+    }
+
+    #[test]
+    fn close_moments_read_now_and_adjacent_days_get_names() {
+        assert_eq!(describe(1, "2025-06-27T14:00:05"), "now");
+        assert_eq!(describe(1, "2025-06-26T09:00:00"), "yesterday");
+        assert_eq!(describe(1, "2025-06-28T08:00:00"), "tomorrow");
+        assert_eq!(describe(1, "2025-06-28T01:00:00"), "in 11 hours");
+        assert_eq!(
+            Humanizer::default()
+                .distance(&zoned("2025-06-26T09:00:00"), &zoned("2025-06-27T14:00:00"))
+                .unwrap(),
+            "1 day"
+        );
+    }
+
+    #[test]
+    fn config_overrides_thresholds() {
+        let cfg = HumanizeConfig {
+            granularity: Some(2),
+            now_within: Some("1m".into()),
+            named_day_after: Some("48h".into()),
+            ..HumanizeConfig::default()
+        };
+        let humanizer = Humanizer::from_config(&cfg).unwrap();
+        let now = zoned("2025-06-27T14:00:00");
+        assert_eq!(
+            humanizer
+                .describe(&zoned("2025-06-26T15:00:00"), &now)
+                .unwrap(),
+            "23 hours ago"
+        );
+        assert_eq!(
+            humanizer
+                .describe(&zoned("2025-06-27T14:00:40"), &now)
+                .unwrap(),
+            "now"
+        );
+        assert!(Humanizer::default().granularity(0).is_err());
+        assert!(
+            Humanizer::from_config(&HumanizeConfig {
+                now_within: Some("soon".into()),
+                ..HumanizeConfig::default()
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,1052 @@
+//! Localized `--help` text for the top-level `td` command.
+//!
+//! The English strings stay next to the argument definitions in
+//! `cli_defs.rs`, which `build.rs` also reads to render the man pages and
+//! completions.  [`localize`] swaps in a catalog's strings at runtime when
+//! the locale named by `LC_ALL`, `LC_MESSAGES` or `LANG` has one; anything
+//! a catalog lacks keeps its English text.  A catalog also carries the
+//! month and weekday [`Names`] a preset's `locale` prints dates with.
+
+use std::env;
+
+use clap::Command;
+use color_print::cstr;
+
+/// A language with a message catalog.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    /// English, the text written in `cli_defs.rs`.
+    En,
+    /// Brazilian Portuguese.
+    PtBr,
+}
+
+/// The language of the message locale: the first non-empty of `LC_ALL`,
+/// `LC_MESSAGES` and `LANG`, as POSIX orders them.
+#[must_use]
+pub fn detect() -> Lang {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|v| !v.is_empty()))
+        .map_or(Lang::En, |locale| lang_from(&locale))
+}
+
+/// The language of a locale name such as `pt_BR.UTF-8`.  Only the language
+/// part counts, so every Portuguese locale gets the Brazilian catalog.
+#[must_use]
+pub fn lang_from(locale: &str) -> Lang {
+    catalog_lang(locale).unwrap_or(Lang::En)
+}
+
+/// Like [`lang_from`], but `None` for a language without a catalog.
+#[must_use]
+pub fn catalog_lang(locale: &str) -> Option<Lang> {
+    let language = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default();
+    match language.to_ascii_lowercase().as_str() {
+        "pt" => Some(Lang::PtBr),
+        "en" => Some(Lang::En),
+        _ => None,
+    }
+}
+
+/// Month and weekday names, full and abbreviated, as printed by `%B`, `%b`
+/// (and `%h`), `%A` and `%a`.  Weekdays start on Monday.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Names {
+    pub months: [&'static str; 12],
+    pub months_abbr: [&'static str; 12],
+    pub weekdays: [&'static str; 7],
+    pub weekdays_abbr: [&'static str; 7],
+}
+
+impl Lang {
+    /// The names dates are printed with in this language, or `None` for
+    /// English, which strftime already prints.
+    #[must_use]
+    pub fn names(self) -> Option<&'static Names> {
+        match self {
+            Lang::PtBr => Some(&PT_BR_NAMES),
+            Lang::En => None,
+        }
+    }
+}
+
+static PT_BR_NAMES: Names = Names {
+    months: [
+        "janeiro",
+        "fevereiro",
+        "março",
+        "abril",
+        "maio",
+        "junho",
+        "julho",
+        "agosto",
+        "setembro",
+        "outubro",
+        "novembro",
+        "dezembro",
+    ],
+    months_abbr: [
+        "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+    ],
+    weekdays: [
+        "segunda-feira",
+        "terça-feira",
+        "quarta-feira",
+        "quinta-feira",
+        "sexta-feira",
+        "sábado",
+        "domingo",
+    ],
+    weekdays_abbr: ["seg", "ter", "qua", "qui", "sex", "sáb", "dom"],
+};
+
+/// `cmd` with its help text in `lang`.
+#[must_use]
+pub fn localize(cmd: Command, lang: Lang) -> Command {
+    let catalog = match lang {
+        Lang::PtBr => &PT_BR,
+        Lang::En => return cmd,
+    };
+
+    let mut cmd = cmd
+        .about(catalog.about)
+        .long_about(catalog.long_about)
+        .after_help(catalog.after_help)
+        .after_long_help(catalog.after_long_help)
+        .help_template(catalog.template)
+        .subcommand_help_heading(catalog.commands_heading)
+        .subcommand_value_name(catalog.command_value_name);
+    // `help` and `version` only exist once the command is built.  From then
+    // on clap's argument index is fixed, so everything below edits in place:
+    // `mut_arg` would move the argument and break positional parsing.
+    cmd.build();
+
+    cmd.mut_subcommands(|sub| {
+        match catalog
+            .subcommands
+            .iter()
+            .find(|(name, _)| sub.get_name() == *name)
+        {
+            Some(&(_, about)) => sub.about(about),
+            None => sub,
+        }
+    })
+    .mut_args(|arg| {
+        let heading = if arg.is_positional() {
+            catalog.arguments_heading
+        } else {
+            catalog.options_heading
+        };
+        let arg = arg.help_heading(heading);
+        match catalog.args.iter().find(|(id, ..)| arg.get_id() == *id) {
+            Some(&(_, help, Some(long_help))) => arg.help(help).long_help(long_help),
+            Some(&(_, help, None)) => arg.help(help),
+            None => arg,
+        }
+    })
+}
+
+/// The strings of one language.
+struct Catalog {
+    about: &'static str,
+    long_about: &'static str,
+    after_help: &'static str,
+    after_long_help: &'static str,
+    /// clap's default template with the `Usage:` heading translated.
+    template: &'static str,
+    arguments_heading: &'static str,
+    options_heading: &'static str,
+    commands_heading: &'static str,
+    command_value_name: &'static str,
+    /// Argument id, short help and, where the English has one, long help.
+    args: &'static [(&'static str, &'static str, Option<&'static str>)],
+    /// Subcommand name and one-line description.
+    subcommands: &'static [(&'static str, &'static str)],
+}
+
+const PT_BR: Catalog = Catalog {
+    about: "TARDIS - Converte datas em linguagem natural para formatos legíveis por máquina",
+    long_about: cstr!(
+        r#"
+<magenta>TARDIS — Time And Relative Date Input Simplifier</magenta>
+
+Converte expressões de tempo em linguagem natural em datas formatadas.
+
+Uma ferramenta de linha de comando leve para transformar frases de data e hora
+como <bold>"next Friday at 2:00"</bold> ou <bold>"in 3 days"</bold> em saída utilizável por máquinas.
+"#
+    ),
+    after_help: cstr!(
+        "Para mais informações, visite <underline>https://github.com/hvpaiva/tardis-cli</underline>"
+    ),
+    after_long_help: cstr!(
+        r#"
+<green><bold>Variáveis de Ambiente:</bold></green>
+  <bold><blue>TARDIS_FORMAT</blue></bold>     Formato de saída ou nome de preset padrão.
+  <bold><blue>TARDIS_TIMEZONE</blue></bold>   Fuso horário IANA padrão (ex.: America/Sao_Paulo).
+  <bold><blue>TARDIS_NOW</blue></bold>        Substitui "agora" (RFC 3339). O mesmo que --now.
+  <bold><blue>TARDIS_CONFIG_DIR</blue></bold> Diretório do config.toml, no lugar do padrão.
+
+<green><bold>Arquivo de Configuração:</bold></green>
+  <blue><bold>$TARDIS_CONFIG_DIR</bold>/config.toml</blue>, senão
+  <blue><bold>$XDG_CONFIG_HOME</bold>/tardis/config.toml</blue>
+
+  se nenhuma delas estiver definida:
+        • Linux:   ~/.config/tardis/config.toml
+        • macOS:   ~/Library/Application Support/tardis/config.toml
+        • Windows: %APPDATA%\tardis\config.toml
+
+  O arquivo é criado automaticamente na primeira execução e traz exemplos
+  comentados de cada campo.
+
+<green><bold>Precedência:</bold></green>
+  opções da linha de comando → variáveis de ambiente → arquivo de configuração
+
+Para mais informações, visite <underline>https://github.com/hvpaiva/tardis-cli</underline>
+"#
+    ),
+    template: cstr!(
+        "{before-help}{about-with-newline}\n<green><bold>Uso:</bold></green> {usage}\n\n{all-args}{after-help}"
+    ),
+    arguments_heading: "Argumentos",
+    options_heading: "Opções",
+    commands_heading: "Comandos",
+    command_value_name: "COMANDO",
+    args: &[
+        (
+            "input",
+            cstr!(
+                r#"
+<bold>Uma expressão em linguagem natural</bold> como <underline>"next Friday at 9:30"</underline>.
+Se omitida e STDIN for um pipe, lê dele. Se omitida em um terminal, usa <bold>"now"</bold>.
+
+Aceita a sintaxe <bold>@<<epoch>></bold> para timestamps Unix (ex.: <bold>@1719244800</bold>).
+Precisão automática: segundos, milissegundos, microssegundos e nanossegundos.
+
+Aceita aritmética (<bold>"tomorrow + 3 hours"</bold>), períodos (<bold>"this week"</bold>) e limites (<bold>"eod"</bold>, <bold>"sow"</bold>).
+"#
+            ),
+            None,
+        ),
+        (
+            "format",
+            "Formato de saída.",
+            Some(cstr!(
+                r#"
+<bold>Formato de saída.</bold>
+
+Aceita padrões strftime (ex.: <bold>"%Y‑%m‑%d"</bold>) ou o nome de um preset
+definido no arquivo de configuração.
+
+Valores especiais: <bold>"epoch"</bold> ou <bold>"unix"</bold> geram um timestamp Unix (segundos),
+<bold>"unix_ns"</bold> um em nanossegundos.
+
+Tokens de fuso: <bold>{tzid}</bold> (ID IANA, ex.: America/Sao_Paulo), <bold>{tzabbr}</bold>
+(abreviação, como <bold>%Z</bold>) e <bold>{tzoffset}</bold> (deslocamento UTC em segundos).
+<bold>{beats}</bold> é o Swatch Internet Time; o formato <bold>"beats"</bold> o mostra como @347.
+<bold>{kind}</bold> é date, time ou datetime, como em <bold>--require</bold>.
+
+Referência:
+<underline>https://github.com/hvpaiva/tardis-cli/blob/main/docs/FORMAT-SPECIFIERS.md</underline>
+
+Se omitido, tenta ler de <bold><blue>TARDIS_FORMAT</blue></bold> e, por fim, usa o
+formato padrão definido no arquivo de configuração.
+"#
+            )),
+        ),
+        (
+            "rfc_email",
+            "Mostra datas RFC 5322, como em \"Wed, 15 Jan 2025 10:30:00 +0000\".",
+            None,
+        ),
+        (
+            "iso_8601",
+            "Mostra ISO 8601 na precisão dada (date, hours, minutes, seconds, ns).",
+            Some(cstr!(
+                r#"
+<bold>Mostra ISO 8601 na precisão dada.</bold>
+
+  <bold>date</bold>      2025-01-15 (o padrão)
+  <bold>hours</bold>     2025-01-15T10+00:00
+  <bold>minutes</bold>   2025-01-15T10:30+00:00
+  <bold>seconds</bold>   2025-01-15T10:30:00+00:00
+  <bold>ns</bold>        2025-01-15T10:30:00,000000000+00:00
+
+Escreva a precisão logo após a opção, como em <bold>-Iseconds</bold> ou
+<bold>--iso-8601=minutes</bold>, como no GNU date.  Um <bold>-I</bold> sozinho vai depois da
+expressão, já que a palavra seguinte é lida como a precisão.
+"#
+            )),
+        ),
+        (
+            "timezone",
+            "Fuso horário a aplicar (ID IANA/Olson). Se omitido, usa a hora local do sistema.",
+            Some(cstr!(
+                r#"
+<bold>Fuso horário a aplicar</bold> (ID IANA/Olson). Se omitido, usa a hora local do sistema.
+
+Exemplos: <italic>"UTC", "America/Sao_Paulo", "Europe/London".</italic>
+Nomes de cidades como <italic>"Tokyo"</italic> ou <italic>"Portland, US"</italic> também funcionam.
+
+Referência:
+<underline>https://www.iana.org/time-zones</underline>
+
+Se omitido, tenta ler de <bold><blue>TARDIS_TIMEZONE</blue></bold> e, por fim, usa o
+fuso horário padrão definido no arquivo de configuração.
+"#
+            )),
+        ),
+        (
+            "now",
+            "Substitui \"agora\". Formato **RFC 3339**, ex.: 2025-06-24T09:00:00Z.",
+            Some(cstr!(
+                r#"
+Substitui "agora". Formato <bold>RFC 3339</bold>, ex.: <italic>2025‑06‑24T09:00:00Z</italic>.
+"#
+            )),
+        ),
+        (
+            "params",
+            "Preenche um marcador do preset (NOME=VALOR). Pode ser repetida.",
+            Some(cstr!(
+                r#"
+<bold>Preenche um marcador do preset.</bold> Pode ser repetida.
+
+Um preset como <bold>meeting = "%Y-%m-%d %H:%M ({label})"</bold> tem o marcador <bold>{label}</bold>;
+<bold>-f meeting --param label=standup</bold> o preenche. Todo marcador precisa ser
+informado, e um nome que o preset não usa é um erro.
+"#
+            )),
+        ),
+        (
+            "defines",
+            "Declara ou substitui um preset nesta execução (NOME=FMT). Pode ser repetida.",
+            Some(cstr!(
+                r#"
+<bold>Declara ou substitui um preset só nesta execução.</bold> Pode ser repetida.
+
+<bold>--define day=%F -f day</bold> funciona seja qual for o arquivo de configuração. Um
+nome que já existe em <bold>[formats]</bold> é substituído, inclusive os padrões da
+tabela, e os presets que o referenciam passam a ver o novo formato. Nomes de
+grupo como <bold>work.iso</bold> são aceitos. Um formato inválido é um erro.
+"#
+            )),
+        ),
+        (
+            "no_presets",
+            "Ignora os presets do arquivo de configuração; -f é um formato ou nome embutido.",
+            Some(cstr!(
+                r#"
+<bold>Ignora todos os presets do arquivo de configuração.</bold>
+
+<bold>-f</bold> passa a ser lido como formato ou nome embutido, como <bold>iso</bold>, seja
+qual for o conteúdo de <bold>[formats]</bold> e <bold>preset_group</bold> nesta máquina, e os
+avisos deles não são mostrados. Presets dados com <bold>--define</bold> continuam
+valendo, então um script compartilhado conta exatamente com os que declara.
+"#
+            )),
+        ),
+        (
+            "preset_group",
+            "Grupo de presets consultado primeiro para nomes de formato.",
+            Some(cstr!(
+                r#"
+<bold>Grupo de presets consultado primeiro.</bold>
+
+Presets de uma tabela <bold>[formats.work]</bold> se chamam <bold>work.iso</bold>, <bold>work.short</bold>, ...
+Com <bold>--preset-group work</bold>, <bold>-f iso</bold> escolhe <bold>work.iso</bold> quando existe e
+recorre ao <bold>iso</bold> de nível superior caso contrário. Substitui <bold>preset_group</bold>
+do arquivo de configuração.
+"#
+            )),
+        ),
+        (
+            "adjust",
+            "Ajusta o resultado como o date -v do BSD (ex.: +3d, -1H, +fri). Repetível.",
+            Some(cstr!(
+                r#"
+<bold>Ajusta o resultado como o date -v do BSD.</bold>  Repetível; aplicado em ordem.
+
+  <bold>+N</bold>UNID, <bold>-N</bold>UNID   desloca N anos (y), meses (m), semanas (w), dias (d),
+                 horas (H), minutos (M) ou segundos (S)
+  <bold>N</bold>UNID         define esse campo; para w, de 0 (domingo) a 6
+  <bold>+</bold>NOME, <bold>-</bold>NOME   vai ao próximo ou anterior dia da semana ou mês com esse
+                 nome, sem mover se já estiver nele
+  NOME          define o dia da semana dentro da semana, ou o mês
+
+<bold>-v</bold> com o ajuste colado é a forma curta desta opção, então cadeias do BSD
+funcionam sem mudanças, enquanto <bold>-v</bold> sozinho continua sendo --verbose:
+
+  td now -v+1m -v1d -v-1d -f %F     # último dia deste mês
+"#
+            )),
+        ),
+        (
+            "keep_offset",
+            "Mostra o resultado no deslocamento UTC escrito na entrada, se houver.",
+            Some(cstr!(
+                r#"
+<bold>Mantém o deslocamento escrito na entrada</bold> em vez de converter para <bold>-t</bold>.
+
+Uma entrada pode trazer um deslocamento UTC: um timestamp RFC 3339 como
+<bold>2025-06-24T15:00:00-03:00</bold>, ou uma expressão que termina com um, como
+<bold>"today 3pm UTC+2"</bold> ou <bold>"tomorrow 9:00 -03:00"</bold>. Ela é sempre lida nesse
+deslocamento; com esta opção o resultado também é mostrado nele. Entradas
+sem deslocamento não são afetadas.
+"#
+            )),
+        ),
+        (
+            "convert",
+            "Converte apenas entradas legíveis por máquina, sem o analisador de expressões.",
+            Some(cstr!(
+                r#"
+<bold>Converte apenas entradas legíveis por máquina</bold>, sem o analisador de expressões.
+
+A entrada deve ser um timestamp RFC 3339 (<bold>2025-06-24T15:00:00-03:00</bold>), uma
+data RFC 2822 (<bold>"Tue, 24 Jun 2025 15:00:00 -0300"</bold>) ou um timestamp Unix
+(<bold>1750788000</bold>, <bold>@1750788000123ms</bold>; números sem prefixo usam a mesma
+precisão automática de <bold>@</bold>). Qualquer outra coisa é um erro, então um erro de
+digitação nunca é lido como expressão relativa. Combine com <bold>-t</bold> e <bold>-f</bold>
+para reformatar dados.
+"#
+            )),
+        ),
+        (
+            "allow_extreme",
+            "Lê valores de epoch grandes demais para 64 bits.",
+            Some(cstr!(
+                r#"
+<bold>Lê valores de epoch grandes demais para 64 bits.</bold>
+
+As datas precisam ficar entre os anos <bold>-9999</bold> e <bold>9999</bold>; fora disso é um
+erro que cita esse intervalo. Valores de epoch normalmente precisam caber em
+64 bits, o que limita timestamps em nanossegundos a 2262. Com esta opção eles
+são lidos como números de 128 bits, então <bold>@99999999999999999999</bold>
+(nanossegundos) resolve para o ano 5138.
+"#
+            )),
+        ),
+        (
+            "require",
+            "Falha se a entrada não for deste tipo (date, time, datetime).",
+            Some(cstr!(
+                r#"
+<bold>Falha se a entrada não escrever o tipo de data pedido:</bold>
+
+  <bold>date</bold>      um dia sem horário: "tomorrow", "next friday", "2025-03-01"
+  <bold>time</bold>      um horário sem dia
+  <bold>datetime</bold>  ambos, ou um instante exato: "tomorrow 9am", "now", "in 2 hours"
+
+Períodos e limites como "next month" e "eod" são datas; epochs, timestamps
+RFC 3339 e deslocamentos a partir de agora são datetimes. Qualquer outro tipo
+termina com 64, então um script pode recusar "friday" onde precisa de
+"friday 9am":
+
+  td "$WHEN" --require datetime -f touch --exec 'at -t {fmt}'
+
+O tipo também é o token <bold>{kind}</bold> de <bold>-f</bold> e <bold>--exec</bold> e o campo "kind" de <bold>--json</bold>.
+"#
+            )),
+        ),
+        (
+            "default_time",
+            "Horário para datas dadas sem um (ex.: 09:00, end-of-day).",
+            Some(cstr!(
+                r#"
+<bold>Resolve datas dadas sem horário para TIME</bold> em vez de meia-noite.
+
+TIME é escrito como depois de uma data (<bold>09:00</bold>, <bold>9am</bold>, <bold>18h</bold>), ou é <bold>midnight</bold>
+(o padrão) ou <bold>end-of-day</bold>, o último instante do dia como em <bold>eod</bold>:
+
+  td "next friday" --default-time 9am        # 2025-01-17T09:00:00
+  td "2025-03-01" --default-time end-of-day  # 2025-03-01T23:59:59
+
+Vale para "today", "next friday", "2025-03-01" e similares, e para
+aritmética sobre eles; períodos e limites como "next month" e "sod" mantêm
+seus próprios horários. Sobrepõe <bold>default_time</bold> do arquivo de configuração.
+"#
+            )),
+        ),
+        (
+            "scale",
+            "Escala de tempo da saída (utc, tai, gps).",
+            Some(cstr!(
+                r#"
+<bold>Mostra o resultado em outra escala de tempo.</bold>
+
+  <bold>utc</bold>  Tempo Universal Coordenado (padrão)
+  <bold>tai</bold>  Tempo Atômico Internacional: UTC mais os segundos bissextos desde 1972 (37 hoje)
+  <bold>gps</bold>  Tempo GPS: TAI menos 19 segundos (UTC mais 18 hoje)
+
+O deslocamento vem de uma tabela embutida de segundos bissextos. Datas após o
+fim da tabela geram um aviso, pois um segundo bissexto mais novo pode faltar.
+"#
+            )),
+        ),
+        ("json", "Saída em JSON em vez de texto simples.", None),
+        ("no_newline", "Omite a quebra de linha final.", None),
+        (
+            "line_ending",
+            "Terminador de linha dos resultados (lf, crlf, auto).",
+            Some(cstr!(
+                r#"
+<bold>Terminador de linha dos resultados.</bold>
+
+  <bold>auto</bold>  CRLF no Windows, LF nos demais (padrão)
+  <bold>lf</bold>    \n, para ferramentas Unix
+  <bold>crlf</bold>  \r\n, para scripts batch do Windows e ferramentas antigas
+
+Vale para cada linha de resultado, também no modo em lote, e para a linha em
+branco que <bold>--skip-errors</bold> escreve para uma entrada com falha. <bold>-n</bold> ainda
+omite a última.
+"#
+            )),
+        ),
+        (
+            "verbose",
+            "Mostra diagnósticos detalhados no stderr (configuração, etapas de análise, tempos).",
+            None,
+        ),
+        (
+            "skip_errors",
+            "Ignora linhas que não puderem ser analisadas no modo em lote.",
+            Some(cstr!(
+                r#"
+<bold>Ignora linhas que não puderem ser analisadas no modo em lote</bold> em vez de abortar.
+
+Os erros vão para o stderr. O stdout recebe uma linha vazia para cada entrada
+com falha, para <bold>preservar o alinhamento</bold> com a entrada original.
+
+O código de saída é <bold>1</bold> se alguma linha falhou, <bold>0</bold> se todas deram certo.
+"#
+            )),
+        ),
+        (
+            "on_error",
+            "O que o modo em lote faz com uma linha que falha (abort, skip, record).",
+            Some(ON_ERROR_HELP),
+        ),
+        (
+            "strict",
+            "Sai com 1 quando alguma linha do lote falhou.",
+            Some(ON_ERROR_HELP),
+        ),
+        (
+            "month_overflow",
+            "O que somar meses ou anos faz ao passar do fim do mês (clamp, overflow, error).",
+            Some(MONTH_OVERFLOW_HELP),
+        ),
+        (
+            "output",
+            "Layout da saída (text, git-env).",
+            Some(cstr!(
+                r#"
+<bold>Layout da saída.</bold>
+
+  <bold>text</bold>     A data formatada (padrão).
+  <bold>git-env</bold>  <bold>GIT_AUTHOR_DATE=… GIT_COMMITTER_DATE=…</bold> no formato interno do git
+           <bold>@<<epoch>><<offset>></bold>, pronto para <bold>env $(td … -o git-env) git commit</bold>.
+           A opção <bold>--format</bold> é ignorada neste modo.
+"#
+            )),
+        ),
+        (
+            "exec",
+            "Executa um comando com {iso}, {epoch} e {fmt} preenchidos.",
+            Some(cstr!(
+                r#"
+<bold>Executa um comando com a data resolvida</bold> em vez de mostrá-la.
+
+O modelo é dividido em palavras como uma linha de comando do shell, e então
+estes tokens são substituídos dentro de cada palavra:
+
+  <bold>{iso}</bold>    data RFC 3339, ex.: 2025-01-17T00:00:00+00:00
+  <bold>{epoch}</bold>  timestamp Unix em segundos
+  <bold>{fmt}</bold>    a saída como teria sido mostrada (respeita <bold>-f</bold>)
+  <bold>{tzid}</bold>   ID IANA do fuso horário, ex.: America/Sao_Paulo
+  <bold>{tzabbr}</bold> abreviação do fuso horário, como <bold>%Z</bold>
+  <bold>{tzoffset}</bold> deslocamento UTC em segundos, ex.: -10800
+  <bold>{beats}</bold>  Swatch Internet Time, ex.: 347
+  <bold>{kind}</bold>   o que a entrada escreveu: date, time ou datetime
+
+Escreva <bold>{{</bold> e <bold>}}</bold> para chaves literais. O comando roda diretamente, não
+por um shell, então um valor com espaços continua sendo um só argumento. td
+termina com o código de saída do comando quando ele falha.
+
+  td "next friday" --exec 'gh milestone create --due {iso}'
+"#
+            )),
+        ),
+        (
+            "dry_run",
+            "Com --exec, mostra o comando em vez de executá-lo.",
+            None,
+        ),
+        (
+            "relative",
+            "Mostra o resultado relativo a agora, ex.: \"in 3 days\" ou \"yesterday\".",
+            Some(cstr!(
+                r#"
+<bold>Mostra a distância entre o resultado e agora</bold> em vez de formatá-lo:
+"in 1 week 6 days", "3 hours ago", "yesterday", "now".
+
+O número de unidades e os limites para "now", "yesterday" e "tomorrow" vêm da
+tabela <bold>[humanize]</bold> da configuração, compartilhada com <bold>td humanize</bold>.
+A descrição pode ser lida de volta como uma expressão do td.
+"#
+            )),
+        ),
+        (
+            "if_past",
+            "Mostra TEXTO no lugar quando o resultado está no passado.",
+            Some(IF_PAST_HELP),
+        ),
+        (
+            "if_future",
+            "Mostra TEXTO no lugar quando o resultado é agora ou no futuro.",
+            Some(IF_PAST_HELP),
+        ),
+        (
+            "count",
+            "Mostra as próximas N ocorrências quando a entrada nomeia uma recorrência.",
+            Some(COUNT_HELP),
+        ),
+        (
+            "compat",
+            "Lê a linha de comando como outra ferramenta (date).",
+            Some(cstr!(
+                r#"
+<bold>Lê o resto da linha de comando como outra ferramenta leria.</bold>
+
+<bold>date</bold> aceita as opções mais comuns do GNU date, para que scripts existentes
+possam migrar com um alias como <bold>alias date='td --compat date'</bold>:
+
+  -d, --date TEXTO         a expressão de entrada
+  -u, --utc, --universal   mostra em UTC
+  +FORMATO                 o formato de saída
+  -R, --rfc-email          data e hora RFC 5322
+  -I[SPEC], --iso-8601[=SPEC]
+                           ISO 8601; SPEC é date (padrão), hours, minutes,
+                           seconds ou ns
+
+Sem um formato, o resultado é mostrado como o <bold>date</bold> mostraria, como em
+<bold>Wed Jan 15 10:30:00 UTC 2025</bold>.  Outras opções, como --now ou --json,
+mantêm o significado do td.
+
+  td --compat date -u -d tomorrow +%F
+"#
+            )),
+        ),
+        ("quiet", "Omite os avisos no stderr.", None),
+        (
+            "deny_warnings",
+            "Trata avisos como erros.",
+            Some(cstr!(
+                r#"
+<bold>Trata avisos como erros.</bold>
+
+Avisos como um preset que encobre um formato embutido ou uma abreviação de
+fuso horário ambígua abortam o comando com código de saída <bold>64</bold> antes
+que qualquer saída seja escrita.
+"#
+            )),
+        ),
+        (
+            "classic_exit_codes",
+            "Sai com 1 em toda falha em vez dos códigos sysexits.",
+            Some(cstr!(
+                r#"
+<bold>Sai com 1 em toda falha</bold> em vez dos códigos sysexits: 64 para entrada
+inválida, 74 para erros de E/S e 78 para erros de configuração.  Erros de
+uso também saem com 1, em vez de 2.
+
+Para sistemas de CI que tratam alguns códigos como falhas de infraestrutura.
+A tabela <bold>[exit_codes]</bold> do arquivo de configuração define cada código; esta
+opção vence a tabela, e vale também para um arquivo que não pode ser lido.
+"#
+            )),
+        ),
+        (
+            "trace_format",
+            "Formato dos diagnósticos de --verbose (text, json).",
+            Some(cstr!(
+                r#"
+<bold>Como os diagnósticos de --verbose são escritos.</bold>
+
+<bold>text</bold> (padrão) mostra uma linha marcada por etapa.  <bold>json</bold> mostra um objeto
+JSON por linha, com os campos <bold>timestamp</bold>, <bold>target</bold> (config, parse,
+resolve ou timing) e <bold>message</bold>, para coletores de logs em CI.
+
+Não tem efeito sem <bold>-v</bold>.  Vale também para os subcomandos.
+"#
+            )),
+        ),
+        (
+            "plain",
+            "Saída simples: sem cores, colunas separadas por tab, apenas ASCII.",
+            Some(cstr!(
+                r#"
+<bold>Saída simples</bold> para leitores de tela, terminais burros e scripts.
+
+Sem cores ANSI nem movimento de cursor, mesmo em um terminal. Tabelas e
+listas de rótulo e valor separam suas colunas com um único tab em vez de
+espaços, e <bold>td hist</bold> desenha suas barras com <bold>#</bold>. A saída JSON é
+compacta. Vale também para os subcomandos.
+"#
+            )),
+        ),
+        (
+            "ascii",
+            "Desenha apenas com ASCII, para terminais ou fontes sem Unicode.",
+            Some(cstr!(
+                r#"
+<bold>Desenha apenas com ASCII</bold>, para terminais ou fontes sem Unicode.
+
+Decorações como as barras de <bold>td hist</bold> usam <bold>#</bold> em vez de caracteres de
+bloco. Ativada sozinha quando o locale (<bold>LC_ALL</bold>, <bold>LC_CTYPE</bold> ou <bold>LANG</bold>)
+indica um conjunto de caracteres diferente de UTF-8, como <bold>C</bold> ou
+<bold>en_US.ISO-8859-1</bold>. Implícita em <bold>--plain</bold>. Vale também para os subcomandos.
+"#
+            )),
+        ),
+        (
+            "line_buffered",
+            "Descarrega a saída a cada linha, mesmo num pipeline.",
+            Some(BUFFERING_HELP),
+        ),
+        (
+            "block_buffered",
+            "Escreve a saída em blocos, mesmo nos comandos de fluxo.",
+            Some(BUFFERING_HELP),
+        ),
+        (
+            "columns",
+            "Colunas das tabelas a mostrar, por nome e em ordem, ex.: zone,offset.",
+            Some(COLUMNS_HELP),
+        ),
+        (
+            "max_width",
+            "Corta as células das tabelas para que cada linha caiba em N caracteres.",
+            Some(COLUMNS_HELP),
+        ),
+        (
+            "no_pager",
+            "Nunca pagina listagens longas pelo $PAGER.",
+            None,
+        ),
+        (
+            "print_man_dir",
+            "Instala as páginas de manual embutidas e mostra o diretório delas no MANPATH.",
+            Some(cstr!(
+                r#"
+<bold>Instala as páginas de manual embutidas e mostra onde estão.</bold>
+
+Escreve as páginas do <bold>td</bold> e de cada subcomando em
+<bold>$XDG_DATA_HOME/tardis/man</bold> (páginas já atualizadas não são tocadas)
+e mostra esse diretório, pronto para o <bold>MANPATH</bold>:
+
+  MANPATH="$(td --print-man-dir):" man td-diff
+
+Empacotadores podem copiar <bold>man1/</bold> dali para o diretório de manuais do sistema.
+"#
+            )),
+        ),
+        (
+            "help",
+            "Mostra a ajuda (veja mais com '--help')",
+            Some("Mostra a ajuda (veja um resumo com '-h')"),
+        ),
+        ("version", "Mostra a versão", None),
+    ],
+    subcommands: &[
+        ("config", "Gerencia o arquivo de configuração."),
+        ("cache", "Gerencia o cache em disco."),
+        ("history", "Gerencia o histórico do prompt interativo."),
+        ("completions", "Gera ou instala completions para o shell."),
+        ("diff", "Calcula a diferença entre duas datas."),
+        ("convert", "Converte uma data entre formatos."),
+        ("tz", "Converte uma data para outro fuso horário."),
+        ("info", "Mostra metadados de calendário de uma data."),
+        (
+            "range",
+            "Expande uma expressão de data em um intervalo de início e fim.",
+        ),
+        (
+            "split",
+            "Divide um intervalo em partes iguais e mostra o início e o fim de cada uma.",
+        ),
+        (
+            "overlaps",
+            "Sai com 0 quando dois intervalos se sobrepõem, mostrando onde se pedido.",
+        ),
+        (
+            "workweek",
+            "Mostra os dias úteis da semana que contém uma data.",
+        ),
+        (
+            "quarter",
+            "Mostra o início e o fim do trimestre civil ou fiscal que contém uma data.",
+        ),
+        (
+            "next-occurrence",
+            "Mostra a próxima vez em que um mês e dia se repetem, para aniversários.",
+        ),
+        (
+            "holiday",
+            "Consulta os feriados da tabela [holidays] do arquivo de configuração.",
+        ),
+        (
+            "is",
+            "Sai com 0 quando a data é fim de semana, dia útil, feriado ou o dia dado.",
+        ),
+        (
+            "is-open",
+            "Sai com 0 quando um instante está dentro do horário de trabalho configurado.",
+        ),
+        (
+            "next-open",
+            "Mostra quando o horário de trabalho começa de novo.",
+        ),
+        (
+            "lint-format",
+            "Explica uma string de formato e avisa sobre prováveis erros.",
+        ),
+        (
+            "prompt",
+            "Mostra uma linha para o prompt do shell, sem nunca falhar.",
+        ),
+        (
+            "meet",
+            "Encontra horários em que todos estão no expediente.",
+        ),
+        (
+            "worldclock",
+            "Mostra um horário em uma lista de fusos favoritos.",
+        ),
+        (
+            "epoch",
+            "Converte entre timestamps Unix e datas, na direção que couber.",
+        ),
+        (
+            "span",
+            "Mostra um instante em UTC e no fuso de destino, lado a lado.",
+        ),
+        (
+            "sleep-until",
+            "Espera até que uma expressão de data seja alcançada.",
+        ),
+        ("every", "Executa um comando em um agendamento recorrente."),
+        (
+            "stamp",
+            "Prefixa cada linha lida do stdin com a hora atual.",
+        ),
+        (
+            "delta",
+            "Mostra o tempo entre linhas consecutivas com timestamp do stdin.",
+        ),
+        (
+            "rewrite",
+            "Normaliza o timestamp de cada linha de log lida do stdin.",
+        ),
+        ("sum", "Soma durações lidas do stdin, uma por linha."),
+        ("stats", "Resume os timestamps das linhas lidas do stdin."),
+        (
+            "hist",
+            "Mostra em gráfico como os timestamps do stdin se distribuem no tempo.",
+        ),
+        (
+            "group-by",
+            "Agrupa linhas do stdin pelo dia, semana ou mês do seu timestamp.",
+        ),
+        (
+            "humanize",
+            "Diz a distância entre uma data e agora, ex.: \"in 1 week 6 days\".",
+        ),
+        (
+            "until",
+            "Verifica a proximidade de uma data, saindo com 0/1/2 como um plugin Nagios.",
+        ),
+        (
+            "clamp",
+            "Move uma data para o intervalo dado por --min e --max.",
+        ),
+        ("min", "Mostra a mais antiga de várias datas."),
+        ("max", "Mostra a mais recente de várias datas."),
+        (
+            "floor",
+            "Recua datas até a última ocorrência de um agendamento.",
+        ),
+        (
+            "ceil",
+            "Avança datas até a próxima ocorrência de um agendamento.",
+        ),
+        (
+            "help",
+            "Mostra esta mensagem ou a ajuda do(s) subcomando(s) informado(s)",
+        ),
+    ],
+};
+
+const COLUMNS_HELP: &str = cstr!(
+    r#"
+<bold>Escolhe as colunas das tabelas e sua largura.</bold>
+
+Comandos que mostram tabelas (<bold>td tz list</bold>, <bold>td worldclock</bold>, <bold>td meet</bold>,
+<bold>td stats</bold>, <bold>td hist</bold>, <bold>td group-by</bold>, <bold>td sum --group-by</bold>) dão nome às
+colunas. <bold>--columns</bold> lista as que aparecem, em ordem; um nome desconhecido é
+um erro que lista as opções. <bold>--max-width</bold> estreita as colunas mais largas,
+cortando suas células com <bold>…</bold>, até que cada linha caiba.
+
+  td tz list --columns zone,offset,abbr --max-width 40
+"#
+);
+
+const BUFFERING_HELP: &str = cstr!(
+    r#"
+<bold>Escolhe quando a saída é descarregada.</bold>
+
+Por padrão cada linha é escrita assim que fica pronta num terminal e em
+<bold>td stamp</bold>, <bold>td delta</bold> e <bold>td rewrite</bold>, que repassam o stdin conforme ele
+chega. Nos demais casos a saída é escrita em blocos, o que é bem mais rápido
+para um lote grande passado pelo <bold>td</bold>. <bold>--line-buffered</bold> descarrega cada linha,
+para quem lê os resultados conforme chegam; <bold>--block-buffered</bold> usa blocos até
+nos comandos de fluxo, pela vazão.
+
+  cat app.log | td rewrite --block-buffered > app-iso.log
+"#
+);
+
+const ON_ERROR_HELP: &str = cstr!(
+    r#"
+<bold>Escolhe o que o modo em lote faz com uma linha que falha.</bold>
+
+  <bold>abort</bold>   Para na primeira falha (padrão).
+  <bold>skip</bold>    Mostra o erro no stderr e uma linha vazia no lugar.
+  <bold>record</bold>  Escreve um registro JSON no lugar, para pipelines de logs:
+          {"error":"...","input":"...","line":2}
+
+Em ambos os casos a execução continua, a contagem de linhas com falha vai para
+o stderr no final e o código de saída é <bold>0</bold>. <bold>--strict</bold> sai com <bold>1</bold> quando
+alguma linha falhou. <bold>--skip-errors</bold> equivale a <bold>--on-error skip --strict</bold>.
+"#
+);
+
+const MONTH_OVERFLOW_HELP: &str = cstr!(
+    r#"
+<bold>Escolhe o que somar meses ou anos faz depois do fim do mês.</bold>
+
+31 de janeiro + 1 mês e 29 de fevereiro + 1 ano caem em dias que não existem:
+
+  <bold>clamp</bold>     Usa o último dia do mês: 28 de fev., 28 de fev. (padrão).
+  <bold>overflow</bold>  Leva os dias que sobram adiante: 3 de mar., 1 de mar.
+  <bold>error</bold>     Falha, indicando a data que não existe.
+
+Vale para expressões como <bold>"2025-01-31 + 1 month"</bold> e <bold>"in 1 year"</bold>,
+para <bold>--adjust +1m</bold> e para todos os subcomandos.
+"#
+);
+
+const COUNT_HELP: &str = cstr!(
+    r#"
+<bold>Mostra as próximas N ocorrências de uma recorrência nomeada.</bold>
+
+Quando a entrada é um nome da tabela <bold>[recurrences]</bold> do arquivo de
+configuração, o td mostra a próxima ocorrência, ou as próximas N com esta
+opção, uma por linha:
+
+  [recurrences]
+  sprint-start = "every other monday starting 2025-01-06"
+
+  td sprint-start --count 3 -f %F
+"#
+);
+
+const IF_PAST_HELP: &str = cstr!(
+    r#"
+<bold>Mostra um texto escolhido conforme o resultado já passou ou não.</bold>
+
+<bold>--if-past</bold> vale quando o resultado é anterior a agora, <bold>--if-future</bold> caso
+contrário. Quando só um é dado, nada é mostrado no outro caso. O texto pode usar
+<bold>{iso}</bold>, <bold>{epoch}</bold> e <bold>{fmt}</bold> como em <bold>--exec</bold>, e <bold>{relative}</bold> para a
+distância até agora, ex.: "2 days":
+
+  td "deadline" --if-past "OVERDUE" --if-future "{relative} left"
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use clap::CommandFactory;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reads_the_language_of_a_locale() {
+        assert_eq!(lang_from("pt_BR.UTF-8"), Lang::PtBr);
+        assert_eq!(lang_from("pt_PT"), Lang::PtBr);
+        assert_eq!(lang_from("pt-BR"), Lang::PtBr);
+        assert_eq!(lang_from("en_US.UTF-8"), Lang::En);
+        assert_eq!(lang_from("C"), Lang::En);
+        assert_eq!(lang_from("POSIX"), Lang::En);
+        assert_eq!(lang_from(""), Lang::En);
+
+        assert_eq!(catalog_lang("pt-BR"), Some(Lang::PtBr));
+        assert_eq!(catalog_lang("en_GB"), Some(Lang::En));
+        assert_eq!(catalog_lang("ja"), None);
+    }
+
+    #[test]
+    fn catalog_names_existing_arguments_and_subcommands() {
+        let mut cmd = crate::cli::Cli::command();
+        cmd.build();
+        for (id, _, _) in PT_BR.args {
+            assert!(
+                cmd.get_arguments().any(|a| a.get_id() == *id),
+                "unknown argument {id}"
+            );
+        }
+        for (name, _) in PT_BR.subcommands {
+            assert!(
+                cmd.find_subcommand(name).is_some(),
+                "unknown subcommand {name}"
+            );
+        }
+        assert_eq!(PT_BR.subcommands.len(), cmd.get_subcommands().count());
+    }
+
+    #[test]
+    fn catalog_covers_every_argument() {
+        let mut cmd = crate::cli::Cli::command();
+        cmd.build();
+        for arg in cmd.get_arguments() {
+            assert!(
+                PT_BR.args.iter().any(|(id, ..)| arg.get_id() == *id),
+                "no pt-BR help for {}",
+                arg.get_id()
+            );
+        }
+    }
+
+    #[test]
+    fn localized_help_is_portuguese() {
+        let mut cmd = localize(crate::cli::Cli::command(), Lang::PtBr);
+        let help = cmd.render_long_help().to_string();
+        assert!(help.contains("Uso: td"), "{help}");
+        assert!(help.contains("Opções:"), "{help}");
+        assert!(help.contains("Formato de saída."), "{help}");
+        assert!(
+            help.contains("Mostra a mais antiga de várias datas."),
+            "{help}"
+        );
+
+        let mut cmd = localize(crate::cli::Cli::command(), Lang::En);
+        assert!(cmd.render_long_help().to_string().contains("Usage: td"));
+    }
+}
@@ -1,14 +1,45 @@
 //! **TARDIS** -- Time And Relative Date Input Simplifier.
 //!
-//! Library crate exposing the CLI argument types, configuration loader,
-//! core transformation pipeline, natural-language parser, and error types.
+//! Library crate exposing the CLI argument types, configuration loader and
+//! migrations, calendar buckets, disk cache, shell completions, GNU `date`
+//! compatibility, BSD `date -v` adjustments, core transformation pipeline,
+//! duration parsing, format linter, relative-time humanizer, localized help
+//! text, meeting-window finder, pager, `--exec` command templates, log
+//! timestamp extraction, bundled man pages, warnings sink, natural-language
+//! parser, recurring schedules, table rendering, terminal styling, TAI/GPS time
+//! scales, time-zone search, and error types.
 
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
+pub mod adjust;
+pub mod bucket;
+pub mod cache;
 pub mod cli;
+pub mod compat;
+pub mod completions;
 pub mod config;
 pub mod core;
+pub mod diagnostics;
+pub mod duration;
 pub mod errors;
+pub mod exec;
+pub mod history;
+pub mod holiday;
+pub mod hours;
+pub mod humanize;
+pub mod i18n;
+pub mod lint;
+pub mod logtime;
+pub mod man;
+pub mod meet;
+pub mod migrate;
+pub mod pager;
 pub mod parser;
+pub mod recur;
+pub mod signal;
+pub mod table;
+pub mod term;
+pub mod timescale;
+pub mod zones;
 
 pub use errors::{Error, Result};
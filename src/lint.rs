@@ -0,0 +1,281 @@
+//! Static checks for strftime format strings.
+//!
+//! [`lint`] splits a format into specifiers and literal text, explains each
+//! specifier, and flags patterns that are valid but rarely intended, such
+//! as `%M` (minute) in a date-only format or `%c` in machine-readable
+//! output.
+
+use crate::{Result, core::validate_format};
+
+/// One piece of a format string.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Piece {
+    /// A conversion such as `%Y` or `%-d`, with its human-readable meaning.
+    Specifier { spec: String, meaning: String },
+    /// Text copied to the output verbatim.
+    Literal(String),
+}
+
+/// Result of linting a format string.
+#[must_use]
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct FormatLint {
+    /// Pieces in the order they appear in the format.
+    pub pieces: Vec<Piece>,
+    /// Likely mistakes; the format is still valid.
+    pub warnings: Vec<String>,
+}
+
+/// Lint `fmt`, failing only if it contains an unsupported specifier.
+pub fn lint(fmt: &str) -> Result<FormatLint> {
+    validate_format(fmt)?;
+    let pieces = split(fmt);
+    let warnings = check(&pieces);
+    Ok(FormatLint { pieces, warnings })
+}
+
+/// Meaning of a conversion character, without padding flags.
+fn describe(conv: &str) -> &'static str {
+    match conv {
+        "A" => "full weekday name",
+        "a" => "abbreviated weekday name",
+        "B" => "full month name",
+        "b" | "h" => "abbreviated month name",
+        "C" => "century (00-99)",
+        "c" => "date and time (implementation-defined layout)",
+        "D" => "date as %m/%d/%y",
+        "d" => "day of month (01-31)",
+        "e" => "day of month, space-padded ( 1-31)",
+        "F" => "date as %Y-%m-%d",
+        "f" | "N" => "fractional seconds",
+        "G" => "ISO 8601 week-based year",
+        "g" => "ISO 8601 week-based year, two digits",
+        "H" => "hour, 24-hour clock (00-23)",
+        "I" => "hour, 12-hour clock (01-12)",
+        "j" => "day of year (001-366)",
+        "k" => "hour, 24-hour clock, space-padded ( 0-23)",
+        "l" => "hour, 12-hour clock, space-padded ( 1-12)",
+        "M" => "minute (00-59)",
+        "m" => "month (01-12)",
+        "n" => "newline",
+        "P" => "am/pm",
+        "p" => "AM/PM",
+        "R" => "time as %H:%M",
+        "r" => "time as %I:%M:%S %p",
+        "S" => "second (00-60)",
+        "s" => "Unix timestamp (seconds)",
+        "T" => "time as %H:%M:%S",
+        "t" => "tab",
+        "U" => "week of year, Sunday first (00-53)",
+        "u" => "weekday, Monday = 1 (1-7)",
+        "V" => "ISO 8601 week number (01-53)",
+        "v" => "date as %e-%b-%Y",
+        "W" => "week of year, Monday first (00-53)",
+        "w" => "weekday, Sunday = 0 (0-6)",
+        "X" => "time (implementation-defined layout)",
+        "x" => "date (implementation-defined layout)",
+        "Y" => "year",
+        "y" => "year, two digits (00-99)",
+        "Z" => "time-zone abbreviation",
+        "z" => "UTC offset (+hhmm)",
+        ":z" => "UTC offset (+hh:mm)",
+        "::z" => "UTC offset (+hh:mm:ss)",
+        "%" => "literal %",
+        _ => "unknown",
+    }
+}
+
+/// `meaning` without the padding of its range: `(01-31)` and `( 1-31)`
+/// become `(1-31)`.
+fn unpadded(meaning: &str) -> String {
+    let meaning = meaning.replace(", space-padded", "");
+    let Some((text, range)) = meaning.rsplit_once('(') else {
+        return meaning;
+    };
+    match range.strip_suffix(')').and_then(|r| r.split_once('-')) {
+        Some((low, high)) if low.trim().chars().all(|c| c.is_ascii_digit()) => {
+            let low = low.trim().trim_start_matches('0');
+            format!("{text}({}-{high})", if low.is_empty() { "0" } else { low })
+        }
+        _ => meaning,
+    }
+}
+
+/// Split an already-validated format into pieces.
+fn split(fmt: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = fmt.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            literal.push(c);
+            continue;
+        }
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+        }
+
+        let mut flag = None;
+        while let Some(&f @ ('-' | '0' | '_')) = chars.peek() {
+            flag = Some(f);
+            chars.next();
+        }
+        let mut conv = String::new();
+        while let Some(&':') = chars.peek() {
+            conv.push(':');
+            chars.next();
+        }
+        if let Some(c) = chars.next() {
+            conv.push(c);
+        }
+
+        let mut meaning = describe(&conv).to_string();
+        match flag {
+            Some('-') => meaning = unpadded(&meaning) + ", no padding",
+            Some('0') => meaning.push_str(", zero-padded"),
+            Some('_') => meaning.push_str(", space-padded"),
+            _ => {}
+        }
+        let spec = match flag {
+            Some(f) => format!("%{f}{conv}"),
+            None => format!("%{conv}"),
+        };
+        pieces.push(Piece::Specifier { spec, meaning });
+    }
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+    pieces
+}
+
+/// Conversion character of a specifier, with padding flags stripped.
+fn conv(spec: &str) -> &str {
+    spec.trim_start_matches('%')
+        .trim_start_matches(['-', '0', '_'])
+}
+
+fn check(pieces: &[Piece]) -> Vec<String> {
+    let convs: Vec<&str> = pieces
+        .iter()
+        .filter_map(|p| match p {
+            Piece::Specifier { spec, .. } => Some(conv(spec)),
+            Piece::Literal(_) => None,
+        })
+        .collect();
+    let has = |c: &str| convs.contains(&c);
+    let has_hour = ["H", "I", "k", "l", "R", "r", "T"].iter().any(|c| has(c));
+    let has_date = ["Y", "y", "d", "e", "F", "D", "j"].iter().any(|c| has(c));
+
+    let mut warnings = Vec::new();
+    if has("M") && !has_hour && has_date {
+        warnings
+            .push("%M is the minute, but the format has no hour; did you mean %m (month)?".into());
+    }
+    if has("m") && has_hour && !has_date && has("S") {
+        warnings.push(
+            "%m is the month, but the format looks like a time; did you mean %M (minute)?".into(),
+        );
+    }
+    for c in ["c", "x", "X"] {
+        if has(c) {
+            warnings.push(format!(
+                "%{c} has an implementation-defined layout; spell the fields out for machine-readable output"
+            ));
+        }
+    }
+    if (has("I") || has("l")) && !has("p") && !has("P") {
+        warnings
+            .push("12-hour clock without %p; morning and evening times will look the same".into());
+    }
+    if has("y") || has("D") {
+        warnings.push("two-digit year is ambiguous; prefer %Y".into());
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn spec(spec: &str, meaning: &str) -> Piece {
+        Piece::Specifier {
+            spec: spec.into(),
+            meaning: meaning.into(),
+        }
+    }
+
+    #[test]
+    fn splits_specifiers_and_literals() {
+        let out = lint("%Y-%m-%dT%H:%M:%S%:z").unwrap();
+        assert_eq!(out.pieces.len(), 12);
+        assert_eq!(out.pieces[0], spec("%Y", "year"));
+        assert_eq!(out.pieces[1], Piece::Literal("-".into()));
+        assert_eq!(out.pieces[11], spec("%:z", "UTC offset (+hh:mm)"));
+        assert!(out.warnings.is_empty());
+    }
+
+    #[test]
+    fn padding_flags_are_described() {
+        let out = lint("%-d").unwrap();
+        assert_eq!(
+            out.pieces,
+            [spec("%-d", "day of month (1-31), no padding")]
+        );
+        let out = lint("%-H %-j %-e %-z").unwrap();
+        let meanings: Vec<_> = out
+            .pieces
+            .iter()
+            .filter_map(|p| match p {
+                Piece::Specifier { meaning, .. } => Some(meaning.as_str()),
+                Piece::Literal(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            meanings,
+            [
+                "hour, 24-hour clock (0-23), no padding",
+                "day of year (1-366), no padding",
+                "day of month (1-31), no padding",
+                "UTC offset (+hhmm), no padding",
+            ]
+        );
+    }
+
+    #[test]
+    fn minute_in_date_is_flagged() {
+        let out = lint("%Y-%M-%d").unwrap();
+        assert_eq!(
+            out.warnings,
+            ["%M is the minute, but the format has no hour; did you mean %m (month)?"]
+        );
+    }
+
+    #[test]
+    fn month_in_time_is_flagged() {
+        let out = lint("%H:%m:%S").unwrap();
+        assert_eq!(out.warnings.len(), 1);
+        assert!(out.warnings[0].contains("did you mean %M"));
+    }
+
+    #[test]
+    fn locale_dependent_specifiers_are_flagged() {
+        let out = lint("%c").unwrap();
+        assert!(out.warnings[0].starts_with("%c has an implementation-defined layout"));
+    }
+
+    #[test]
+    fn twelve_hour_clock_needs_meridiem() {
+        assert_eq!(lint("%I:%M").unwrap().warnings.len(), 1);
+        assert!(lint("%I:%M %p").unwrap().warnings.is_empty());
+    }
+
+    #[test]
+    fn unknown_specifier_is_error() {
+        assert!(lint("%Q").is_err());
+    }
+}
@@ -0,0 +1,423 @@
+//! Timestamps found inside log lines.
+//!
+//! An [`Extractor`] scans a line for the first timestamp of its
+//! [`LogProfile`] and reports where it sits and which instant it names:
+//!
+//! * `iso` -- `2025-01-15T10:30:00Z`, `2025-01-15 10:30:00.250+01:00`, ...
+//! * `syslog` -- `Jan 15 10:30:00`, in the extractor's year
+//! * `nginx` -- `15/Jan/2025:10:30:00 +0000` (common/combined log format)
+//! * `java` -- `2025-01-15 10:30:00,250` (log4j/logback) or
+//!   `Jan 15, 2025 10:30:00 AM` (java.util.logging)
+//! * `epoch` -- `1736937000`, `1736937000.250` (seconds) or `1736937000250`
+//!   (milliseconds)
+//!
+//! A timestamp without an offset is read in the extractor's zone.
+
+use std::ops::Range;
+
+use jiff::{
+    Timestamp, Zoned,
+    civil::{Date, DateTime, Time},
+    tz::{Offset, TimeZone},
+};
+
+use crate::cli::LogProfile;
+
+const MONTHS: [&[u8; 3]; 12] = [
+    b"Jan", b"Feb", b"Mar", b"Apr", b"May", b"Jun", b"Jul", b"Aug", b"Sep", b"Oct", b"Nov", b"Dec",
+];
+
+/// A timestamp located in a line.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found {
+    /// Byte range of the timestamp text within the line.
+    pub span: Range<usize>,
+    /// The instant it names.
+    pub at: Timestamp,
+}
+
+/// Finds timestamps of one [`LogProfile`] in log lines.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Extractor {
+    profile: LogProfile,
+    tz: TimeZone,
+    year: i16,
+}
+
+impl Extractor {
+    /// Read offset-less timestamps in `now`'s zone and year-less ones in
+    /// `now`'s year.
+    pub fn new(profile: LogProfile, now: &Zoned) -> Self {
+        Self {
+            profile,
+            tz: now.time_zone().clone(),
+            year: now.year(),
+        }
+    }
+
+    /// Find the first timestamp in `line`.
+    pub fn find(&self, line: &str) -> Option<Found> {
+        let bytes = line.as_bytes();
+        (0..bytes.len())
+            .filter(|&i| i == 0 || !bytes[i - 1].is_ascii_alphanumeric())
+            .find_map(|i| self.at(bytes, i))
+    }
+
+    fn at(&self, bytes: &[u8], start: usize) -> Option<Found> {
+        let mut cur = Cursor { bytes, pos: start };
+        if self.profile == LogProfile::Epoch {
+            let at = cur.epoch()?;
+            return Some(Found {
+                span: start..cur.pos,
+                at,
+            });
+        }
+        let (dt, offset) = match self.profile {
+            LogProfile::Syslog => (cur.syslog(self.year)?, None),
+            LogProfile::Nginx => cur.nginx()?,
+            LogProfile::Java => match cur.java_util_logging() {
+                Some(dt) => (dt, None),
+                None => {
+                    cur.pos = start;
+                    cur.log4j()?
+                }
+            },
+            _ => cur.iso()?,
+        };
+        let at = match offset {
+            Some(offset) => offset.to_timestamp(dt).ok()?,
+            None => self.tz.to_ambiguous_timestamp(dt).compatible().ok()?,
+        };
+        Some(Found {
+            span: start..cur.pos,
+            at,
+        })
+    }
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    /// `2025-01-15T10:30:00[.fff][Z|+HH:MM]`, with `T` or a space.
+    fn iso(&mut self) -> Option<(DateTime, Option<Offset>)> {
+        let date = self.ymd()?;
+        self.byte(b'T').or_else(|| self.byte(b' '))?;
+        let time = self.hms()?;
+        Some((date.to_datetime(time), self.offset()))
+    }
+
+    /// `2025-01-15 10:30:00,250`, the log4j and logback default.
+    fn log4j(&mut self) -> Option<(DateTime, Option<Offset>)> {
+        let date = self.ymd()?;
+        self.byte(b' ')?;
+        let time = self.hms()?;
+        Some((date.to_datetime(time), None))
+    }
+
+    /// `Jan 15, 2025 10:30:00 AM`, the java.util.logging default.
+    fn java_util_logging(&mut self) -> Option<DateTime> {
+        let month = self.month()?;
+        self.byte(b' ')?;
+        let day = self.number(2)?;
+        self.byte(b',')?;
+        self.byte(b' ')?;
+        let year = self.digits(4)?;
+        self.byte(b' ')?;
+        let hour = self.number(2)?;
+        self.byte(b':')?;
+        let minute = self.digits(2)?;
+        self.byte(b':')?;
+        let second = self.digits(2)?;
+        self.byte(b' ')?;
+        let pm = match self.bytes.get(self.pos..self.pos + 2)? {
+            b"AM" => false,
+            b"PM" => true,
+            _ => return None,
+        };
+        self.pos += 2;
+        if !(1..=12).contains(&hour) {
+            return None;
+        }
+        let hour = hour % 12 + if pm { 12 } else { 0 };
+        let date = Date::new(year.try_into().ok()?, month, day.try_into().ok()?).ok()?;
+        let time = Time::new(
+            hour.try_into().ok()?,
+            minute.try_into().ok()?,
+            second.try_into().ok()?,
+            0,
+        )
+        .ok()?;
+        Some(date.to_datetime(time))
+    }
+
+    /// `Jan 15 10:30:00` or `Jan  5 10:30:00` (RFC 3164 syslog).
+    fn syslog(&mut self, year: i16) -> Option<DateTime> {
+        let month = self.month()?;
+        self.byte(b' ')?;
+        let _ = self.byte(b' ');
+        let day = self.number(2)?;
+        self.byte(b' ')?;
+        let time = self.hms()?;
+        let date = Date::new(year, month, day.try_into().ok()?).ok()?;
+        Some(date.to_datetime(time))
+    }
+
+    /// Ten digits of Unix seconds with an optional `.fff` fraction, or
+    /// thirteen of milliseconds, not running into a longer number or word.
+    fn epoch(&mut self) -> Option<Timestamp> {
+        let len = self.bytes[self.pos..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        let value: i64 = std::str::from_utf8(&self.bytes[self.pos..self.pos + len])
+            .ok()?
+            .parse()
+            .ok()?;
+        self.pos += len;
+        let at = match len {
+            13 => Timestamp::from_millisecond(value).ok()?,
+            10 => Timestamp::new(value, self.fraction(b".")).ok()?,
+            _ => return None,
+        };
+        if self.peek().is_some_and(|b| b.is_ascii_alphanumeric()) {
+            return None;
+        }
+        Some(at)
+    }
+
+    /// `15/Jan/2025:10:30:00 +0000` (nginx and Apache access logs).
+    fn nginx(&mut self) -> Option<(DateTime, Option<Offset>)> {
+        let day = self.digits(2)?;
+        self.byte(b'/')?;
+        let month = self.month()?;
+        self.byte(b'/')?;
+        let year = self.digits(4)?;
+        self.byte(b':')?;
+        let time = self.hms()?;
+        self.byte(b' ')?;
+        let offset = self.offset()?;
+        let date = Date::new(year.try_into().ok()?, month, day.try_into().ok()?).ok()?;
+        Some((date.to_datetime(time), Some(offset)))
+    }
+
+    fn ymd(&mut self) -> Option<Date> {
+        let year = self.digits(4)?;
+        self.byte(b'-')?;
+        let month = self.digits(2)?;
+        self.byte(b'-')?;
+        let day = self.digits(2)?;
+        Date::new(
+            year.try_into().ok()?,
+            month.try_into().ok()?,
+            day.try_into().ok()?,
+        )
+        .ok()
+    }
+
+    /// `HH:MM:SS` with an optional `.fff` or `,fff` fraction.
+    fn hms(&mut self) -> Option<Time> {
+        let hour = self.digits(2)?;
+        self.byte(b':')?;
+        let minute = self.digits(2)?;
+        self.byte(b':')?;
+        let second = self.digits(2)?;
+
+        let nanos = self.fraction(b".,");
+        Time::new(
+            hour.try_into().ok()?,
+            minute.try_into().ok()?,
+            second.try_into().ok()?,
+            nanos,
+        )
+        .ok()
+    }
+
+    /// Nanoseconds of a fraction such as `.250` led by one of `separators`,
+    /// or 0 (leaving the cursor alone) when there is none.
+    fn fraction(&mut self, separators: &[u8]) -> i32 {
+        let start = self.pos;
+        if !self.peek().is_some_and(|b| separators.contains(&b)) {
+            return 0;
+        }
+        self.pos += 1;
+        let from = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let fraction = &self.bytes[from..self.pos];
+        if fraction.is_empty() {
+            self.pos = start;
+            return 0;
+        }
+        fraction
+            .iter()
+            .chain(std::iter::repeat(&b'0'))
+            .take(9)
+            .fold(0, |n, d| n * 10 + i32::from(d - b'0'))
+    }
+
+    /// A three-letter English month abbreviation.
+    fn month(&mut self) -> Option<i8> {
+        let name = self.bytes.get(self.pos..self.pos + 3)?;
+        let index = MONTHS.iter().position(|m| m.as_slice() == name)?;
+        self.pos += 3;
+        (index + 1).try_into().ok()
+    }
+
+    /// One to `max` digits.
+    fn number(&mut self, max: usize) -> Option<i32> {
+        let len = self.bytes[self.pos..]
+            .iter()
+            .take(max)
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if len == 0 {
+            return None;
+        }
+        self.digits(len)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn byte(&mut self, want: u8) -> Option<()> {
+        (self.peek()? == want).then(|| self.pos += 1)
+    }
+
+    fn digits(&mut self, count: usize) -> Option<i32> {
+        let digits = self.bytes.get(self.pos..self.pos + count)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.pos += count;
+        Some(digits.iter().fold(0, |n, d| n * 10 + i32::from(d - b'0')))
+    }
+
+    /// `Z`, `+HH:MM` or `+HHMM`; leaves the cursor alone when absent.
+    fn offset(&mut self) -> Option<Offset> {
+        let start = self.pos;
+        if self.byte(b'Z').is_some() {
+            return Some(Offset::UTC);
+        }
+        let sign = match self.peek()? {
+            b'+' => 1,
+            b'-' => -1,
+            _ => return None,
+        };
+        self.pos += 1;
+        let parsed = (|| {
+            let hours = self.digits(2)?;
+            let _ = self.byte(b':');
+            let minutes = self.digits(2)?;
+            Offset::from_seconds(sign * (hours * 3600 + minutes * 60)).ok()
+        })();
+        if parsed.is_none() {
+            self.pos = start;
+        }
+        parsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn at(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    fn extractor(profile: LogProfile, zone: &str) -> Extractor {
+        let now = at("2025-06-01T00:00:00Z").to_zoned(TimeZone::get(zone).unwrap());
+        Extractor::new(profile, &now)
+    }
+
+    fn find(profile: LogProfile, line: &str) -> Option<(&str, Timestamp)> {
+        let found = extractor(profile, "UTC").find(line)?;
+        Some((&line[found.span], found.at))
+    }
+
+    #[test]
+    fn finds_rfc3339_with_offset() {
+        assert_eq!(
+            find(LogProfile::Iso, "[2025-01-15T10:30:00.250+01:00] GET /"),
+            Some((
+                "2025-01-15T10:30:00.250+01:00",
+                at("2025-01-15T09:30:00.25Z")
+            ))
+        );
+    }
+
+    #[test]
+    fn offsetless_times_use_the_given_zone() {
+        let found = extractor(LogProfile::Iso, "America/Sao_Paulo")
+            .find("2025-01-15 10:30:00,5 INFO started")
+            .unwrap();
+        assert_eq!(found.span, 0..21);
+        assert_eq!(found.at, at("2025-01-15T13:30:00.5Z"));
+    }
+
+    #[test]
+    fn skips_things_that_only_look_like_dates() {
+        assert_eq!(find(LogProfile::Iso, "build 12025-01-15 10:30:00"), None);
+        assert_eq!(find(LogProfile::Iso, "2025-13-01 10:30:00"), None);
+        assert_eq!(find(LogProfile::Iso, "no time here"), None);
+        assert_eq!(
+            find(LogProfile::Iso, "2025-01-15T10:30:00-x").unwrap().0,
+            "2025-01-15T10:30:00"
+        );
+    }
+
+    #[test]
+    fn syslog_uses_the_extractor_year() {
+        assert_eq!(
+            find(LogProfile::Syslog, "Jan  5 10:30:00 host sshd[42]: ok"),
+            Some(("Jan  5 10:30:00", at("2025-01-05T10:30:00Z")))
+        );
+        assert_eq!(find(LogProfile::Syslog, "XJan 15 10:30:00"), None);
+    }
+
+    #[test]
+    fn nginx_reads_the_bracketed_time() {
+        let line = r#"127.0.0.1 - - [15/Jan/2025:10:30:00 -0300] "GET / HTTP/1.1" 200"#;
+        assert_eq!(
+            find(LogProfile::Nginx, line),
+            Some(("15/Jan/2025:10:30:00 -0300", at("2025-01-15T13:30:00Z")))
+        );
+    }
+
+    #[test]
+    fn java_reads_log4j_and_java_util_logging() {
+        assert_eq!(
+            find(LogProfile::Java, "2025-01-15 10:30:00,250 INFO Main - up"),
+            Some(("2025-01-15 10:30:00,250", at("2025-01-15T10:30:00.25Z")))
+        );
+        assert_eq!(
+            find(LogProfile::Java, "Jan 5, 2025 1:02:03 PM org.Main run"),
+            Some(("Jan 5, 2025 1:02:03 PM", at("2025-01-05T13:02:03Z")))
+        );
+        assert_eq!(find(LogProfile::Java, "2025-01-15T10:30:00Z"), None);
+    }
+
+    #[test]
+    fn epoch_reads_seconds_and_milliseconds() {
+        assert_eq!(
+            find(LogProfile::Epoch, "ts=1736937000.25 GET /"),
+            Some(("1736937000.25", at("2025-01-15T10:30:00.25Z")))
+        );
+        assert_eq!(
+            find(LogProfile::Epoch, "1736937000250,INFO,up"),
+            Some(("1736937000250", at("2025-01-15T10:30:00.25Z")))
+        );
+        assert_eq!(find(LogProfile::Epoch, "id 17369370001 x"), None);
+        assert_eq!(find(LogProfile::Epoch, "v1736937000"), None);
+        assert_eq!(find(LogProfile::Epoch, "1736937000ms"), None);
+    }
+}
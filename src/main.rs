@@ -1,28 +1,57 @@
 //! TARDIS binary entry-point.
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
-use std::io::{self, IsTerminal};
+use std::io::{self, IsTerminal, Write};
 
 use tardis_cli::{
     Result,
     cli::{
-        Cli, Command, ConfigAction, ConvertArgs, DiffArgs, DiffOutput, InfoArgs, RangeArgs,
-        ShellType, SubCmd, TzArgs,
+        CacheAction, ClampArgs, Cli, Command, CompletionsAction, CompletionsArgs, ConfigAction,
+        ConvertArgs, DeltaArgs, DiffArgs, DiffOutput, EpochArgs, EpochUnit, EveryArgs, ExtremeArgs,
+        GroupByArgs, HistArgs, HistoryAction, HolidayAction, HumanizeArgs, InfoArgs, InputKind,
+        IsArgs, IsOpenArgs, LeapDay, LintFormatArgs, MeetArgs, NextOccurrenceArgs, NextOpenArgs,
+        OnError, OutputMode, OverlapsArgs, PromptArgs, QuarterArgs, RangeArgs, RewriteArgs,
+        SleepUntilArgs, SnapArgs, SpanArgs, SplitArgs, StampArgs, StatsArgs, SubCmd, SumArgs,
+        TimeScale, TraceFormat, TzAction, TzArgs, TzAtArgs, TzCompareArgs, TzListArgs,
+        TzNextChangeArgs, TzPickArgs, TzSort, TzTransitionsArgs, UntilArgs, WorkweekArgs,
+        WorldclockArgs,
     },
     config::Config,
     core::{self, App},
-    parser, user_input_error,
+    diagnostics::Diagnostics,
+    errors::ExitCodes,
+    humanize::Humanizer,
+    i18n, parser, table, term, timescale, user_input_error,
 };
 
-/// Check if stderr supports color output.
-fn stderr_use_color() -> bool {
-    io::stderr().is_terminal() && std::env::var("NO_COLOR").is_err()
+/// Whether `--trace-format json` was given; set once at startup.
+static TRACE_JSON: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+
+/// The `--columns` and `--max-width` layout for tables; set once at startup.
+static TABLE_LAYOUT: std::sync::OnceLock<table::Layout> = std::sync::OnceLock::new();
+
+/// The `--month-overflow` policy subcommands parse dates with; set once at
+/// startup.
+static PARSE_OPTIONS: std::sync::OnceLock<parser::ParseOptions> = std::sync::OnceLock::new();
+
+fn parse_options() -> &'static parser::ParseOptions {
+    PARSE_OPTIONS.get_or_init(parser::ParseOptions::default)
 }
 
-/// Print a colored verbose diagnostic line to stderr.
+/// Print a verbose diagnostic line to stderr: colored text, or a JSON
+/// object under `--trace-format json`.
 macro_rules! verbose {
     ($tag:expr, $($arg:tt)*) => {{
-        if stderr_use_color() {
+        if TRACE_JSON.get().copied().unwrap_or(false) {
+            eprintln!(
+                "{}",
+                serde_json::json!({
+                    "timestamp": jiff::Timestamp::now().to_string(),
+                    "target": $tag,
+                    "message": format!($($arg)*),
+                })
+            );
+        } else if term::stderr_color() {
             let color = match $tag {
                 "config" => "\x1b[36m",
                 "parse" => "\x1b[34m",
@@ -38,21 +67,92 @@ macro_rules! verbose {
 }
 
 fn main() {
+    tardis_cli::signal::restore_sigpipe();
     if let Err(err) = run() {
         err.exit();
     }
 }
 
 fn run() -> Result<()> {
-    let cli = <Cli as clap::Parser>::parse();
+    let cli = {
+        use clap::{CommandFactory, FromArgMatches};
+        let cmd = i18n::localize(Cli::command(), i18n::detect());
+        // Usage errors come from parsing, before `cli.classic_exit_codes`
+        // can be read.
+        let classic = std::env::args_os().any(|arg| arg == "--classic-exit-codes");
+        if classic {
+            ExitCodes::use_classic();
+        }
+        let args = tardis_cli::compat::rewrite(std::env::args_os().collect())?;
+        let args = tardis_cli::adjust::rewrite(args);
+        // Help and usage errors are printed while parsing, before `cli.plain`
+        // can be read.
+        #[cfg(feature = "color")]
+        let cmd = if args.iter().any(|arg| arg == "--plain") {
+            cmd.color(clap::ColorChoice::Never)
+        } else {
+            cmd
+        };
+        let matches = cmd.try_get_matches_from(args).unwrap_or_else(|e| {
+            let code = if classic && e.use_stderr() {
+                1
+            } else {
+                e.exit_code()
+            };
+            exit_clap(&e, code)
+        });
+        Cli::from_arg_matches(&matches).unwrap_or_else(|e| exit_clap(&e, e.exit_code()))
+    };
+    term::set_plain(cli.plain);
+    PARSE_OPTIONS.get_or_init(|| parser::ParseOptions {
+        month_overflow: cli.month_overflow,
+        ..Default::default()
+    });
+    term::set_ascii(cli.ascii || term::ascii_locale());
+    term::set_buffering(
+        cli.line_buffered
+            .then_some(term::Buffering::Line)
+            .or(cli.block_buffered.then_some(term::Buffering::Block)),
+    );
+    TABLE_LAYOUT.get_or_init(|| table::Layout {
+        columns: cli.columns.clone(),
+        max_width: cli.max_width,
+        plain: term::plain(),
+        ascii: term::ascii(),
+    });
+    let mut diag = Diagnostics::new(cli.quiet, cli.deny_warnings);
+    TRACE_JSON.get_or_init(|| cli.trace_format == TraceFormat::Json);
+
+    if cli.print_man_dir {
+        println!("{}", tardis_cli::man::install()?.display());
+        return Ok(());
+    }
 
     if let Some(subcmd) = cli.subcmd {
-        return handle_subcmd(subcmd);
+        return handle_subcmd(subcmd, !cli.no_pager, &mut diag);
     }
 
     let is_terminal = io::stdin().is_terminal();
-    let cmd = Command::from_raw_cli(cli, io::stdin(), is_terminal)?;
-    let cfg = Config::load()?;
+    let mut cfg = if cli.no_presets {
+        installed(Config::load_without_presets(&mut diag)?, &mut diag)?
+    } else {
+        load_config(&mut diag)?
+    };
+    // `--default-time` is read with the rest of the flags; the config key
+    // only applies without it.
+    let config_default_time = match cli.default_time {
+        Some(_) => None,
+        None => cfg.default_time()?,
+    };
+    let mut cli = cli;
+    if cli.input.is_none() && is_terminal && cfg.interactive && io::stderr().is_terminal() {
+        cli.input = Some(read_interactive_input()?);
+    }
+    let mut cmd = Command::from_raw_cli(cli, io::stdin(), is_terminal)?;
+    if let Some(time) = config_default_time {
+        cmd.parse_options.default_time = time;
+    }
+    cfg.define_presets(&cmd.defines)?;
 
     if cmd.verbose {
         verbose!(
@@ -65,41 +165,121 @@ fn run() -> Result<()> {
         verbose!("config", "format={} timezone={}", cfg.format, cfg.timezone);
     }
 
+    let mut out = term::stdout(false);
+    if let Some(expr) = cfg.recurrences.get(cmd.input.trim()) {
+        print_recurrence(&cmd, expr, &cfg, &mut diag, &mut out)?;
+        out.flush()?;
+        return Ok(());
+    }
+    if cmd.count.is_some() {
+        return Err(user_input_error!(
+            InvalidDateFormat,
+            "--count needs the name of a recurrence from [recurrences], got '{}'",
+            cmd.input
+        ));
+    }
+
     let lines: Vec<&str> = cmd.input.lines().collect();
     if lines.len() > 1 {
-        let mut had_error = false;
-        for line in lines {
+        let (mut total, mut failed) = (0, 0);
+        for (number, line) in lines.into_iter().enumerate() {
             let line = line.trim();
             if line.is_empty() {
                 continue;
             }
+            total += 1;
             let single_cmd = cmd.with_input(line.to_owned());
-            let result = process_and_print(&single_cmd, &cfg);
-            if let Err(e) = result {
-                if cmd.skip_errors {
+            let result = process_and_print(&single_cmd, &cfg, &mut diag, &mut out);
+            let Err(e) = result else {
+                continue;
+            };
+            failed += 1;
+            match cmd.on_error {
+                OnError::Skip => {
                     eprintln!("{e}");
                     if !io::stdout().is_terminal() {
-                        println!();
+                        write!(out, "{}", cmd.line_ending.terminator())?;
                     }
-                    had_error = true;
-                } else {
-                    return Err(e);
                 }
+                OnError::Record => {
+                    let record = serde_json::json!({
+                        "error": e.to_string(),
+                        "input": line,
+                        "line": number + 1,
+                    });
+                    write!(out, "{record}{}", cmd.line_ending.terminator())?;
+                }
+                _ => return Err(e),
             }
         }
-        if had_error {
-            std::process::exit(1);
+        out.flush()?;
+        if failed > 0 {
+            diag.note(format!("{failed} of {total} lines failed"));
+            diag.flush()?;
+            if cmd.strict {
+                std::process::exit(1);
+            }
         }
     } else {
-        process_and_print(&cmd, &cfg)?;
+        process_and_print(&cmd, &cfg, &mut diag, &mut out)?;
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// Print a clap error, or the help or version it stands for, and exit with
+/// `code`.  Without the `color` feature clap cannot strip the styling the
+/// help is written with, so it is stripped here.
+fn exit_clap(e: &clap::Error, code: i32) -> ! {
+    #[cfg(feature = "color")]
+    let _ = e.print();
+    #[cfg(not(feature = "color"))]
+    {
+        let text = term::strip_ansi(&e.render().to_string());
+        if e.use_stderr() {
+            eprint!("{text}");
+        } else {
+            print!("{text}");
+        }
     }
+    let _ = io::stdout().flush();
+    std::process::exit(code)
+}
 
+/// Print the next `--count` occurrences (default 1) of the recurrence
+/// `expr`, each as if its instant had been given as input.
+fn print_recurrence(
+    cmd: &Command,
+    expr: &str,
+    cfg: &Config,
+    diag: &mut Diagnostics,
+    out: &mut term::Output,
+) -> Result<()> {
+    let app = App::from_cli_with(cmd, cfg, diag)?;
+    let now = app
+        .now
+        .unwrap_or_else(|| jiff::Zoned::now().with_time_zone(app.timezone.clone()));
+    let recurrence = tardis_cli::recur::Recurrence::parse(expr, &now)?;
+    for at in recurrence.upcoming(&now, cmd.count.unwrap_or(1))? {
+        let input = format!("@{}", at.timestamp().as_second());
+        process_and_print(&cmd.with_input(input), cfg, diag, out)?;
+    }
     Ok(())
 }
 
-fn process_and_print(cmd: &Command, cfg: &Config) -> Result<()> {
+fn process_and_print(
+    cmd: &Command,
+    cfg: &Config,
+    diag: &mut Diagnostics,
+    out: &mut term::Output,
+) -> Result<()> {
     let start = std::time::Instant::now();
-    let app = App::from_cli(cmd, cfg)?;
+    let mut app = App::from_cli_with(cmd, cfg, diag)?;
+    diag.flush()?;
+    if cmd.output == OutputMode::GitEnv {
+        app.format = core::GIT_DATE_FORMAT.to_string();
+    }
 
     if cmd.verbose {
         verbose!("parse", "input={:?}", cmd.input);
@@ -111,7 +291,16 @@ fn process_and_print(cmd: &Command, cfg: &Config) -> Result<()> {
         );
     }
 
-    let result = core::process(&app, &cfg.presets())?;
+    let result = core::process(&app, cfg.presets())?;
+    if app.scale != TimeScale::Utc
+        && jiff::Timestamp::from_second(result.epoch).is_ok_and(timescale::beyond_table)
+    {
+        diag.warn(format!(
+            "the leap-second table ends on {}; --scale may miss a leap second announced after it",
+            timescale::TABLE_VALID_UNTIL
+        ));
+        diag.flush()?;
+    }
 
     if cmd.verbose {
         let elapsed = start.elapsed();
@@ -124,36 +313,154 @@ fn process_and_print(cmd: &Command, cfg: &Config) -> Result<()> {
         verbose!("timing", "{:.3}ms", elapsed.as_secs_f64() * 1000.0);
     }
 
-    if cmd.json {
+    if let Some(template) = &cmd.exec {
+        return run_exec(template, &result, cmd, out);
+    }
+
+    if cmd.relative || cmd.if_past.is_some() || cmd.if_future.is_some() {
+        let now = app
+            .now
+            .clone()
+            .unwrap_or_else(|| jiff::Zoned::now().with_time_zone(app.timezone.clone()));
+        let target = jiff::Timestamp::from_second(result.epoch)
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))?
+            .to_zoned(app.timezone.clone());
+        let humanizer = Humanizer::from_config(&cfg.humanize)?;
+        if cmd.relative {
+            print_result(&humanizer.describe(&target, &now)?, cmd, out)?;
+        } else {
+            // Fill both texts so a bad token fails either way.
+            let relative = humanizer.distance(&target, &now)?;
+            let fill = |text: &Option<String>| {
+                text.as_deref()
+                    .map(|t| tardis_cli::exec::fill(t, &result, &relative))
+                    .transpose()
+            };
+            let (past, future) = (fill(&cmd.if_past)?, fill(&cmd.if_future)?);
+            if let Some(text) = if target < now { past } else { future } {
+                print_result(&text, cmd, out)?;
+            }
+        }
+    } else if cmd.json {
         let json = serde_json::json!({
             "input": cmd.input,
             "output": result.formatted,
             "epoch": result.epoch,
+            "epoch_ns": json_integer(result.epoch_ns),
+            "kind": result.kind.as_str(),
             "timezone": app.timezone.iana_name().unwrap_or("Unknown"),
             "format": app.format,
         });
-        emit_json(&json, cmd.no_newline);
-    } else if cmd.no_newline {
-        print!("{}", result.formatted);
+        print_result(&json_text(&json), cmd, out)?;
+    } else if cmd.output == OutputMode::GitEnv {
+        print_result(
+            &format!(
+                "GIT_AUTHOR_DATE={0} GIT_COMMITTER_DATE={0}",
+                result.formatted
+            ),
+            cmd,
+            out,
+        )?;
     } else {
-        println!("{}", result.formatted);
+        print_result(&result.formatted, cmd, out)?;
     }
 
     Ok(())
 }
 
-fn handle_subcmd(subcmd: SubCmd) -> Result<()> {
+/// Fill an `--exec` template and run it, or print it with `--dry-run`.
+/// A failing command is an error carrying its exit status, so a batch
+/// handles it like any failing line.
+fn run_exec(
+    template: &str,
+    result: &core::ProcessOutput,
+    cmd: &Command,
+    out: &mut term::Output,
+) -> Result<()> {
+    let argv = tardis_cli::exec::expand(template, result)?;
+    if cmd.dry_run {
+        print_result(&tardis_cli::exec::quote(&argv), cmd, out)?;
+        return Ok(());
+    }
+    // The command writes to the same stdout, after what is buffered.
+    out.flush()?;
+    let status = std::process::Command::new(&argv[0])
+        .args(&argv[1..])
+        .status()
+        .map_err(|e| io::Error::new(e.kind(), format!("failed to run '{}': {e}", argv[0])))?;
+    if !status.success() {
+        return Err(tardis_cli::errors::SystemError::Command {
+            program: argv[0].clone(),
+            status,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Prompt `date> ` on stderr and read one expression from the terminal,
+/// recording it in the history.  An empty line (or end of input) means
+/// "now".
+fn read_interactive_input() -> Result<String> {
+    use std::io::Write;
+
+    eprint!("date> ");
+    io::stderr().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok("now".to_string());
+    }
+    let _ = tardis_cli::history::record(line);
+    Ok(line.to_string())
+}
+
+fn handle_subcmd(subcmd: SubCmd, paging: bool, diag: &mut Diagnostics) -> Result<()> {
     match subcmd {
-        SubCmd::Config { action } => handle_config(action),
-        SubCmd::Completions { shell } => {
-            handle_completions(shell);
+        SubCmd::Config { action } => handle_config(action, diag),
+        SubCmd::Cache { action } => handle_cache(action),
+        SubCmd::History { action } => handle_history(action, paging, diag),
+        SubCmd::Completions(args) => handle_completions(args),
+        SubCmd::Diff(args) => handle_diff(args, diag),
+        SubCmd::Convert(args) => handle_convert(args, diag),
+        SubCmd::Tz(args) => handle_tz(args, paging, diag),
+        SubCmd::Info(args) => handle_info(args, diag),
+        SubCmd::Range(args) => handle_range(args, diag),
+        SubCmd::Split(args) => handle_split(args, diag),
+        SubCmd::Overlaps(args) => handle_overlaps(args, diag),
+        SubCmd::Workweek(args) => handle_workweek(args, diag),
+        SubCmd::Quarter(args) => handle_quarter(args, diag),
+        SubCmd::NextOccurrence(args) => handle_next_occurrence(args, diag),
+        SubCmd::Is(args) => handle_is(args, diag),
+        SubCmd::Holiday { action } => handle_holiday(action, diag),
+        SubCmd::IsOpen(args) => handle_is_open(args, diag),
+        SubCmd::NextOpen(args) => handle_next_open(args, diag),
+        SubCmd::LintFormat(args) => handle_lint_format(args, diag),
+        SubCmd::Meet(args) => handle_meet(args, diag),
+        SubCmd::Worldclock(args) => handle_worldclock(args, diag),
+        SubCmd::Epoch(args) => handle_epoch(args, diag),
+        SubCmd::Span(args) => handle_span(args, diag),
+        SubCmd::SleepUntil(args) => handle_sleep_until(args, diag),
+        SubCmd::Every(args) => handle_every(args, diag),
+        SubCmd::Stamp(args) => handle_stamp(args, diag),
+        SubCmd::Delta(args) => handle_delta(args, diag),
+        SubCmd::Rewrite(args) => handle_rewrite(args, diag),
+        SubCmd::Sum(args) => handle_sum(args, diag),
+        SubCmd::Stats(args) => handle_stats(args, diag),
+        SubCmd::Hist(args) => handle_hist(args, diag),
+        SubCmd::GroupBy(args) => handle_group_by(args, diag),
+        SubCmd::Humanize(args) => handle_humanize(args, diag),
+        SubCmd::Until(args) => handle_until(args, diag),
+        SubCmd::Clamp(args) => handle_clamp(args, diag),
+        SubCmd::Min(args) => handle_extreme(args, false, diag),
+        SubCmd::Max(args) => handle_extreme(args, true, diag),
+        SubCmd::Floor(args) => handle_snap(args, false, diag),
+        SubCmd::Ceil(args) => handle_snap(args, true, diag),
+        SubCmd::Prompt(args) => {
+            handle_prompt(args);
             Ok(())
         }
-        SubCmd::Diff(args) => handle_diff(args),
-        SubCmd::Convert(args) => handle_convert(args),
-        SubCmd::Tz(args) => handle_tz(args),
-        SubCmd::Info(args) => handle_info(args),
-        SubCmd::Range(args) => handle_range(args),
         _ => unreachable!(),
     }
 }
@@ -171,14 +478,54 @@ fn resolve_now(now_arg: &Option<String>) -> Result<Option<jiff::Timestamp>> {
 }
 
 /// Resolve a timezone argument or fall back to the system timezone.
-fn resolve_timezone(tz_arg: &Option<String>) -> Result<jiff::tz::TimeZone> {
+fn resolve_timezone(tz_arg: &Option<String>, diag: &mut Diagnostics) -> Result<jiff::tz::TimeZone> {
     match tz_arg {
-        Some(name) => jiff::tz::TimeZone::get(name)
-            .map_err(|e| user_input_error!(UnsupportedTimezone, "{}", e)),
+        Some(name) => lookup_timezone(name, diag),
         None => Ok(jiff::tz::TimeZone::system()),
     }
 }
 
+/// Resolve a timezone name, flushing any abbreviation warning it raised.
+fn lookup_timezone(name: &str, diag: &mut Diagnostics) -> Result<jiff::tz::TimeZone> {
+    let tz = core::lookup_timezone(name, diag)?;
+    diag.flush()?;
+    Ok(tz)
+}
+
+/// The zone whose calendar decides where days (and hours, weeks and
+/// months) begin when timestamps are bucketed: `--day-boundary`, else `tz`.
+fn day_boundary(
+    arg: &Option<String>,
+    tz: &jiff::tz::TimeZone,
+    diag: &mut Diagnostics,
+) -> Result<jiff::tz::TimeZone> {
+    match arg {
+        Some(name) => lookup_timezone(name, diag),
+        None => Ok(tz.clone()),
+    }
+}
+
+/// The zone set in the config file, or the system zone when it is empty.
+fn configured_timezone(cfg: &Config, diag: &mut Diagnostics) -> Result<jiff::tz::TimeZone> {
+    match cfg.timezone.trim() {
+        "" => Ok(jiff::tz::TimeZone::system()),
+        name => lookup_timezone(name, diag),
+    }
+}
+
+/// Load the configuration, flushing any warnings raised while reading it.
+fn load_config(diag: &mut Diagnostics) -> Result<Config> {
+    installed(Config::load_with(diag)?, diag)
+}
+
+/// Install the exit codes `cfg` sets and flush the warnings raised while
+/// reading it.
+fn installed(cfg: Config, diag: &mut Diagnostics) -> Result<Config> {
+    cfg.exit_codes.codes().install();
+    diag.flush()?;
+    Ok(cfg)
+}
+
 /// Resolve a `Zoned` "now" reference from the `--now` arg and timezone.
 fn resolve_now_zoned(now_arg: &Option<String>, tz: &jiff::tz::TimeZone) -> Result<jiff::Zoned> {
     match resolve_now(now_arg)? {
@@ -189,15 +536,15 @@ fn resolve_now_zoned(now_arg: &Option<String>, tz: &jiff::tz::TimeZone) -> Resul
 
 /// Resolve a builtin format name to a strftime pattern.
 ///
-/// Case-insensitive lookup for well-known names (iso8601, rfc3339, etc.);
+/// Case-insensitive lookup for well-known names (see [`core::BUILTIN_FORMATS`]);
 /// custom strftime patterns are returned verbatim (preserving case of `%Y` etc.).
 fn resolve_builtin_format(name: &str) -> String {
     match name.to_lowercase().as_str() {
-        "iso8601" | "iso" => "%Y-%m-%dT%H:%M:%S%:z".to_string(),
-        "rfc3339" => "%Y-%m-%dT%H:%M:%S%:z".to_string(),
-        "rfc2822" => "%a, %d %b %Y %H:%M:%S %z".to_string(),
         "epoch" | "unix" => "epoch".to_string(),
-        _ => name.to_string(),
+        "unix_ns" => "unix_ns".to_string(),
+        _ => core::builtin_format(name)
+            .map(str::to_string)
+            .unwrap_or_else(|| name.to_string()),
     }
 }
 
@@ -210,18 +557,51 @@ fn output_value(value: &str, no_newline: bool) {
     }
 }
 
+/// Print a main-command result ending in the `--line-ending` terminator,
+/// which also replaces the line breaks inside it, unless `--no-newline`.
+fn print_result(value: &str, cmd: &Command, out: &mut term::Output) -> Result<()> {
+    let eol = cmd.line_ending.terminator();
+    let value = if eol == "\n" {
+        value.to_string()
+    } else {
+        value.replace('\n', eol)
+    };
+    if cmd.no_newline {
+        write!(out, "{value}")?;
+    } else {
+        write!(out, "{value}{eol}")?;
+    }
+    Ok(())
+}
+
+/// Print a long listing, through the pager unless `--no-pager` or
+/// `pager = false` in the config file turn it off.
+fn print_listing(text: &str, paging: bool, diag: &mut Diagnostics) -> Result<()> {
+    let paging = paging && io::stdout().is_terminal() && load_config(diag)?.pager;
+    tardis_cli::pager::page(text, paging)
+}
+
+/// Print rows under the column `names`, laid out by `--columns`,
+/// `--max-width` and `--plain`.
+fn print_table(names: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    print!("{}", render_table(names, rows)?);
+    Ok(())
+}
+
+fn render_table(names: &[&str], rows: &[Vec<String>]) -> Result<String> {
+    table::render(
+        names,
+        rows,
+        TABLE_LAYOUT.get_or_init(table::Layout::default),
+    )
+}
+
 /// Emit a JSON value to stdout with TTY-aware formatting.
 ///
-/// Pretty-prints with syntax colors when stdout is a TTY and `NO_COLOR` is unset;
+/// Pretty-prints with syntax colors when [`term::stdout_color`] allows it;
 /// emits compact single-line JSON otherwise.
 fn emit_json(value: &serde_json::Value, no_newline: bool) {
-    let text = if io::stdout().is_terminal() && std::env::var("NO_COLOR").is_err() {
-        colored_json::to_colored_json_auto(value)
-            .unwrap_or_else(|_| serde_json::to_string_pretty(value).unwrap_or_default())
-    } else {
-        value.to_string()
-    };
-
+    let text = json_text(value);
     if no_newline {
         print!("{text}");
     } else {
@@ -229,12 +609,22 @@ fn emit_json(value: &serde_json::Value, no_newline: bool) {
     }
 }
 
+/// Render a JSON value the way [`emit_json`] prints it.
+fn json_text(value: &serde_json::Value) -> String {
+    #[cfg(feature = "color")]
+    if term::stdout_color() {
+        return colored_json::to_colored_json_auto(value)
+            .unwrap_or_else(|_| serde_json::to_string_pretty(value).unwrap_or_default());
+    }
+    value.to_string()
+}
+
 /// Handle `td range <expression>` -- expand expression to start/end pair.
-fn handle_range(args: RangeArgs) -> Result<()> {
+fn handle_range(args: RangeArgs, diag: &mut Diagnostics) -> Result<()> {
     let start_instant = std::time::Instant::now();
-    let tz = resolve_timezone(&args.timezone)?;
+    let tz = resolve_timezone(&args.timezone, diag)?;
     let now = resolve_now_zoned(&args.now, &tz)?;
-    let cfg = Config::load()?;
+    let cfg = load_config(diag)?;
     let fmt = args
         .format
         .as_deref()
@@ -246,11 +636,13 @@ fn handle_range(args: RangeArgs) -> Result<()> {
         verbose!("parse", "timezone={}", tz.iana_name().unwrap_or("system"));
     }
 
-    let (start, end) = parser::parse_range_with_granularity(&args.input, &now)
+    let (start, end) = parser::parse_range_with_granularity(&args.input, &now, parse_options())
         .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
 
-    let start_str = start.strftime(&fmt).to_string();
-    let end_str = end.strftime(&fmt).to_string();
+    let show = |z: &jiff::Zoned| {
+        core::epoch_output(z.timestamp(), &fmt).unwrap_or_else(|| z.strftime(&fmt).to_string())
+    };
+    let (start_str, end_str) = (show(&start), show(&end));
 
     if args.verbose {
         verbose!("resolve", "start={} end={}", start_str, end_str);
@@ -271,18 +663,1691 @@ fn handle_range(args: RangeArgs) -> Result<()> {
         });
         emit_json(&json, args.no_newline);
     } else {
-        output_value(
-            &format!("{start_str}{}{end_str}", args.delimiter),
-            args.no_newline,
-        );
+        output_value(
+            &format!("{start_str}{}{end_str}", args.delimiter),
+            args.no_newline,
+        );
+    }
+    Ok(())
+}
+
+/// Most slots `td split` prints, so that a tiny `--by` fails at once
+/// instead of running until it is killed.
+const MAX_SPLIT_SLOTS: i128 = 1_000_000;
+
+/// Handle `td split <range> --by <duration>` -- print equal slots.
+fn handle_split(args: SplitArgs, diag: &mut Diagnostics) -> Result<()> {
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let cfg = load_config(diag)?;
+    let fmt = core::compile_format(args.format.as_deref().unwrap_or(&cfg.format), cfg.presets())?;
+    let by = match args.by.trim().parse::<jiff::Span>() {
+        Ok(span) => span,
+        Err(_) => jiff::Span::try_from(tardis_cli::duration::parse(&args.by)?)
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))?,
+    };
+    if !by.is_positive() {
+        return Err(user_input_error!(
+            InvalidDate,
+            "--by must be longer than zero, got '{}'",
+            args.by
+        ));
+    }
+    let (start, end) = parse_interval(&args.input, &now)?;
+
+    // Months and years vary in length; the first slot stands for them all.
+    let step = by
+        .to_duration(&start)
+        .map_err(|e| user_input_error!(InvalidDate, "{}", e))?
+        .as_nanos();
+    let total = start.duration_until(&end).as_nanos();
+    if total / step >= MAX_SPLIT_SLOTS {
+        return Err(user_input_error!(
+            InvalidDate,
+            "--by '{}' splits '{}' into more than {} slots",
+            args.by,
+            args.input,
+            MAX_SPLIT_SLOTS
+        ));
+    }
+
+    let show = |z: &jiff::Zoned| fmt.render(z, InputKind::Datetime);
+    let mut json = Vec::new();
+    let mut stdout = io::stdout().lock();
+    let mut from = start;
+    while from < end {
+        let next = from
+            .checked_add(by)
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+        if next > end && args.full {
+            break;
+        }
+        let until = if next > end { end.clone() } else { next };
+        if args.json {
+            json.push(serde_json::json!({
+                "start": show(&from),
+                "end": show(&until),
+                "start_epoch": from.timestamp().as_second(),
+                "end_epoch": until.timestamp().as_second(),
+            }));
+        } else {
+            writeln!(stdout, "{}{}{}", show(&from), args.delimiter, show(&until))?;
+        }
+        from = until;
+    }
+    drop(stdout);
+
+    if args.json {
+        emit_json(&serde_json::Value::Array(json), false);
+    }
+    Ok(())
+}
+
+/// Handle `td overlaps RANGE RANGE` -- exit 0 when the ranges share an
+/// instant, printing the intersection with `--print` or `--json`.
+fn handle_overlaps(args: OverlapsArgs, diag: &mut Diagnostics) -> Result<()> {
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let cfg = load_config(diag)?;
+    let fmt = core::compile_format(args.format.as_deref().unwrap_or(&cfg.format), cfg.presets())?;
+
+    // Two arguments are a range each and four are two START END pairs; of
+    // three, the one written "START to END" stands alone.
+    let is_range = |s: &str| split_interval(s).is_some();
+    let ranges = match args.ranges.as_slice() {
+        [a, b] => [a.clone(), b.clone()],
+        [a, b, c, d] => [format!("{a} to {b}"), format!("{c} to {d}")],
+        [a, b, c] if is_range(a) && !is_range(b) => [a.clone(), format!("{b} to {c}")],
+        [a, b, c] if is_range(c) && !is_range(b) => [format!("{a} to {b}"), c.clone()],
+        _ => {
+            return Err(user_input_error!(
+                InvalidDateFormat,
+                "cannot tell the two ranges apart in '{}'; write one as \"START to END\"",
+                args.ranges.join(" ")
+            ));
+        }
+    };
+    let (a_start, a_end) = parse_interval(&ranges[0], &now)?;
+    let (b_start, b_end) = parse_interval(&ranges[1], &now)?;
+    let start = a_start.max(b_start);
+    let end = a_end.min(b_end);
+    let overlap = (start < end).then_some((start, end));
+    diag.flush()?;
+
+    let show = |z: &jiff::Zoned| fmt.render(z, InputKind::Datetime);
+    if args.json {
+        let json = match &overlap {
+            Some((start, end)) => serde_json::json!({
+                "overlaps": true,
+                "start": show(start),
+                "end": show(end),
+                "start_epoch": start.timestamp().as_second(),
+                "end_epoch": end.timestamp().as_second(),
+            }),
+            None => serde_json::json!({ "overlaps": false }),
+        };
+        emit_json(&json, false);
+    } else if let Some((start, end)) = overlap.as_ref().filter(|_| args.print) {
+        println!("{}{}{}", show(start), args.delimiter, show(end));
+    }
+    if overlap.is_none() {
+        io::stdout().flush()?;
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Handle `td workweek [date]` -- the working days of the week holding it.
+fn handle_workweek(args: WorkweekArgs, diag: &mut Diagnostics) -> Result<()> {
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let cfg = load_config(diag)?;
+    let fmt = args
+        .format
+        .as_deref()
+        .map_or_else(|| "%Y-%m-%d".to_string(), resolve_builtin_format);
+    core::validate_format(&fmt)?;
+    let week_start = cfg.workweek.week_start(args.week_start.as_deref())?;
+    let workdays = cfg.workweek.days(args.days.as_deref())?;
+
+    let day = parser::parse(&args.input, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?
+        .start_of_day()
+        .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+    let back = day.weekday().since(week_start);
+    let first = day
+        .checked_sub(jiff::Span::new().days(back))
+        .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+    let mut days = Vec::new();
+    for i in 0..7 {
+        let date = first
+            .checked_add(jiff::Span::new().days(i))
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+        if workdays.contains(&date.weekday()) {
+            days.push(date);
+        }
+    }
+    if args.range && days.len() > 1 {
+        days.drain(1..days.len() - 1);
+    }
+
+    let show = |z: &jiff::Zoned| {
+        core::epoch_output(z.timestamp(), &fmt).unwrap_or_else(|| z.strftime(&fmt).to_string())
+    };
+    if args.json {
+        let json = serde_json::json!({
+            "input": args.input,
+            "week_start": show(&first),
+            "days": days.iter().map(show).collect::<Vec<_>>(),
+        });
+        emit_json(&json, false);
+    } else {
+        let days: Vec<String> = days.iter().map(show).collect();
+        output_value(&days.join(&args.delimiter), false);
+    }
+    Ok(())
+}
+
+/// Handle `td quarter [date]` -- the bounds of the quarter holding it.
+///
+/// Quarters count from the first month of the fiscal year, and a fiscal
+/// year is named after the calendar year it ends in.
+fn handle_quarter(args: QuarterArgs, diag: &mut Diagnostics) -> Result<()> {
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let cfg = load_config(diag)?;
+    let fmt = core::compile_format(args.format.as_deref().unwrap_or(&cfg.format), cfg.presets())?;
+    let year_start = if args.calendar {
+        1
+    } else {
+        cfg.fiscal.year_start(args.fiscal_start.as_deref())?
+    };
+
+    let day = parser::parse(&args.input, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?
+        .start_of_day()
+        .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+    let into_year = (day.month() - year_start).rem_euclid(12);
+    let quarter = into_year / 3 + 1;
+    let start = day
+        .with()
+        .day(1)
+        .build()
+        .and_then(|d| d.checked_sub(jiff::Span::new().months(into_year % 3)))
+        .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+    let end = start
+        .checked_add(jiff::Span::new().months(3))
+        .and_then(|d| d.checked_sub(jiff::SignedDuration::from_nanos(1)))
+        .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+    let fiscal_year = if year_start == 1 || day.month() < year_start {
+        day.year()
+    } else {
+        day.year() + 1
+    };
+    let name = if year_start == 1 {
+        format!("{fiscal_year} Q{quarter}")
+    } else {
+        format!("FY{fiscal_year} Q{quarter}")
+    };
+
+    let show = |z: &jiff::Zoned| fmt.render(z, InputKind::Datetime);
+    if args.json {
+        let json = serde_json::json!({
+            "input": args.input,
+            "name": name,
+            "quarter": quarter,
+            "fiscal_year": fiscal_year,
+            "start": show(&start),
+            "end": show(&end),
+            "start_epoch": start.timestamp().as_second(),
+            "end_epoch": end.timestamp().as_second(),
+        });
+        emit_json(&json, false);
+    } else if args.name {
+        output_value(&name, false);
+    } else {
+        output_value(
+            &format!("{}{}{}", show(&start), args.delimiter, show(&end)),
+            false,
+        );
+    }
+    Ok(())
+}
+
+/// Handle `td next-occurrence MM-DD` -- the next time a month and day come
+/// around, today included.  A full date in an earlier year also counts the
+/// years since it.
+fn handle_next_occurrence(args: NextOccurrenceArgs, diag: &mut Diagnostics) -> Result<()> {
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let fmt = args
+        .format
+        .as_deref()
+        .map_or_else(|| "%Y-%m-%d".to_string(), resolve_builtin_format);
+    core::validate_format(&fmt)?;
+
+    let input = args.input.trim();
+    let (month, day, origin) = match tardis_cli::holiday::parse_month_day(input) {
+        Some((m, d)) => {
+            jiff::civil::Date::new(2000, m, d)
+                .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+            (m, d, None)
+        }
+        None => {
+            let date = parser::parse(input, &now, parse_options())
+                .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?
+                .date();
+            let origin = (date < now.date() && date.year() < now.year()).then_some(date.year());
+            (date.month(), date.day(), origin)
+        }
+    };
+
+    let today = now.date();
+    let mut next = None;
+    for year in today.year()..today.year().saturating_add(9) {
+        let date = match jiff::civil::Date::new(year, month, day) {
+            Ok(date) => date,
+            Err(_) => match args.feb29 {
+                LeapDay::Feb28 => jiff::civil::date(year, 2, 28),
+                LeapDay::Mar1 => jiff::civil::date(year, 3, 1),
+                _ => continue,
+            },
+        };
+        if date >= today {
+            next = Some(date);
+            break;
+        }
+    }
+    let next = next.ok_or_else(|| {
+        user_input_error!(InvalidDate, "'{}' does not come around again", args.input)
+    })?;
+    let at = next
+        .to_zoned(tz)
+        .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+    let days = (next - today).get_days();
+    let years = origin.map(|y| next.year() - y);
+    let shown =
+        core::epoch_output(at.timestamp(), &fmt).unwrap_or_else(|| at.strftime(&fmt).to_string());
+
+    if args.json {
+        let json = serde_json::json!({
+            "input": args.input,
+            "date": shown,
+            "epoch": at.timestamp().as_second(),
+            "days": days,
+            "years": years,
+        });
+        emit_json(&json, false);
+    } else {
+        let when = days_away(days);
+        match years {
+            Some(1) => println!("{shown} ({when}, 1 year)"),
+            Some(n) => println!("{shown} ({when}, {n} years)"),
+            None => println!("{shown} ({when})"),
+        }
+    }
+    Ok(())
+}
+
+/// `days` from today in words: "today", "tomorrow", "in 3 days", "2 days
+/// ago".
+fn days_away(days: i32) -> String {
+    match days {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        n if n < 0 => format!("{} days ago", -n),
+        n => format!("in {n} days"),
+    }
+}
+
+/// Handle `td is <predicate> [date]` -- exit 0 when the predicate holds
+/// for the date and 1 when it does not, printing nothing.
+fn handle_is(args: IsArgs, diag: &mut Diagnostics) -> Result<()> {
+    use jiff::civil::Weekday;
+    use tardis_cli::holiday::Calendar;
+
+    let cfg = load_config(diag)?;
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let day = parser::parse(&args.input, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?
+        .date();
+    let weekend = matches!(day.weekday(), Weekday::Saturday | Weekday::Sunday);
+    let holiday = || {
+        Ok::<_, tardis_cli::Error>(
+            !Calendar::from_table(&cfg.holidays, &now)?
+                .on(day)
+                .is_empty(),
+        )
+    };
+
+    let holds = match args.predicate.trim().to_ascii_lowercase().as_str() {
+        "weekend" => weekend,
+        "weekday" => !weekend,
+        "workday" => cfg.workweek.days(None)?.contains(&day.weekday()) && !holiday()?,
+        "holiday" => holiday()?,
+        name => match tardis_cli::recur::parse_weekday(name) {
+            Some(weekday) => day.weekday() == weekday,
+            None => {
+                return Err(user_input_error!(
+                    InvalidDateFormat,
+                    "unknown predicate '{}'; expected weekend, weekday, workday, holiday or a day name",
+                    args.predicate
+                ));
+            }
+        },
+    };
+    diag.flush()?;
+    if !holds {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Handle `td holiday <action>` -- query the `[holidays]` table.
+fn handle_holiday(action: HolidayAction, diag: &mut Diagnostics) -> Result<()> {
+    use tardis_cli::holiday::Calendar;
+
+    let cfg = load_config(diag)?;
+    let setup = |timezone: &Option<String>, now: &Option<String>, diag: &mut Diagnostics| {
+        let tz = resolve_timezone(timezone, diag)?;
+        let now = resolve_now_zoned(now, &tz)?;
+        let calendar = Calendar::from_table(&cfg.holidays, &now)?;
+        if calendar.is_empty() {
+            diag.note("no holidays are defined; add some to the [holidays] table");
+        }
+        Ok::<_, tardis_cli::Error>((now, calendar))
+    };
+    let day_of = |input: &str, now: &jiff::Zoned| {
+        parser::parse(input, now, parse_options())
+            .map(|z| z.date())
+            .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))
+    };
+    let show = |day: jiff::civil::Date, now: &jiff::Zoned, fmt: &Option<String>| {
+        let at = day
+            .to_zoned(now.time_zone().clone())
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+        let fmt = fmt
+            .as_deref()
+            .map_or_else(|| "%Y-%m-%d".to_string(), resolve_builtin_format);
+        core::validate_format(&fmt)?;
+        Ok::<_, tardis_cli::Error>(
+            core::epoch_output(at.timestamp(), &fmt)
+                .unwrap_or_else(|| at.strftime(&fmt).to_string()),
+        )
+    };
+
+    match action {
+        HolidayAction::Next(args) => {
+            let (now, calendar) = setup(&args.timezone, &args.now, diag)?;
+            let from = day_of(&args.input, &now)?;
+            let Some((day, name)) = calendar.next(from) else {
+                return diag.flush();
+            };
+            let days = (day - now.date()).get_days();
+            let shown = show(day, &now, &args.format)?;
+            if args.json {
+                let json = serde_json::json!({ "date": shown, "name": name, "days": days });
+                emit_json(&json, false);
+            } else {
+                println!("{shown} {name} ({})", days_away(days));
+            }
+        }
+        HolidayAction::Is(args) => {
+            let (now, calendar) = setup(&args.timezone, &args.now, diag)?;
+            diag.flush()?;
+            let names = calendar.on(day_of(&args.input, &now)?);
+            if names.is_empty() {
+                std::process::exit(1);
+            }
+            println!("{}", names.join(", "));
+        }
+        HolidayAction::List(args) => {
+            let (now, calendar) = setup(&args.timezone, &args.now, diag)?;
+            let (start, end) = parse_interval(&args.range, &now)?;
+            let last = end
+                .checked_sub(jiff::SignedDuration::from_nanos(1))
+                .map_err(|e| user_input_error!(InvalidDate, "{}", e))?
+                .date();
+            let holidays = calendar.between(start.date(), last);
+            let rows = holidays
+                .iter()
+                .map(|&(day, name)| Ok(vec![show(day, &now, &args.format)?, name.to_string()]))
+                .collect::<Result<Vec<_>>>()?;
+            if args.json {
+                let json: Vec<_> = rows
+                    .iter()
+                    .map(|row| serde_json::json!({ "date": row[0], "name": row[1] }))
+                    .collect();
+                emit_json(&serde_json::Value::Array(json), false);
+            } else {
+                print_table(&["date", "name"], &rows)?;
+            }
+        }
+        _ => unreachable!(),
+    }
+    diag.flush()
+}
+
+/// The `[hours]` schedule, and the instant `input` resolves to.
+fn working_hours(
+    cfg: &Config,
+    input: &str,
+    timezone: &Option<String>,
+    now: &Option<String>,
+    diag: &mut Diagnostics,
+) -> Result<(tardis_cli::hours::Schedule, jiff::Zoned)> {
+    use tardis_cli::{holiday::Calendar, hours::Schedule};
+
+    let tz = resolve_timezone(timezone, diag)?;
+    let now = resolve_now_zoned(now, &tz)?;
+    let at = parser::parse(input, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+    let holidays = Calendar::from_table(&cfg.holidays, &now)?;
+    let schedule = Schedule::from_table(&cfg.hours, &cfg.workweek.days(None)?, holidays, &now)?;
+    Ok((schedule, at))
+}
+
+/// Handle `td is-open [date]` -- exit 0 when the instant is within working
+/// hours and 1 when it is not, printing nothing.
+fn handle_is_open(args: IsOpenArgs, diag: &mut Diagnostics) -> Result<()> {
+    let cfg = load_config(diag)?;
+    let (schedule, at) = working_hours(&cfg, &args.input, &args.timezone, &args.now, diag)?;
+    let open = schedule.is_open(&at)?;
+    diag.flush()?;
+    if !open {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Handle `td next-open [date]` -- print when working hours next begin.
+fn handle_next_open(args: NextOpenArgs, diag: &mut Diagnostics) -> Result<()> {
+    let cfg = load_config(diag)?;
+    let fmt = core::compile_format(args.format.as_deref().unwrap_or(&cfg.format), cfg.presets())?;
+    let (schedule, at) = working_hours(&cfg, &args.input, &args.timezone, &args.now, diag)?;
+    let shift = schedule.next_open(&at)?.ok_or_else(|| {
+        user_input_error!(
+            InvalidDate,
+            "no working hours within a year of '{}'",
+            args.input
+        )
+    })?;
+    let open = shift.start <= at;
+    let start = if open { at } else { shift.start };
+    let show = |t: &jiff::Zoned| fmt.render(t, InputKind::Datetime);
+
+    if args.json {
+        let json = serde_json::json!({
+            "input": args.input,
+            "open": open,
+            "start": show(&start),
+            "epoch": start.timestamp().as_second(),
+            "until": show(&shift.end),
+        });
+        emit_json(&json, false);
+    } else {
+        println!("{}", show(&start));
+    }
+    Ok(())
+}
+
+/// Split `[from] START to END` into its two expressions.
+fn split_interval(input: &str) -> Option<(&str, &str)> {
+    let at = input.to_ascii_lowercase().find(" to ")?;
+    let start = input[..at].trim();
+    let start = match start.get(..5) {
+        Some(from) if from.eq_ignore_ascii_case("from ") => start[5..].trim_start(),
+        _ => start,
+    };
+    Some((start, input[at + 4..].trim()))
+}
+
+/// Resolve `[from] START to END` as two expressions, or anything else as a
+/// range with implicit granularity.  Either way the end is exclusive.  An
+/// end that is only a time of day, as in "tomorrow 9am to 5pm", falls on
+/// the start's date.
+fn parse_interval(input: &str, now: &jiff::Zoned) -> Result<(jiff::Zoned, jiff::Zoned)> {
+    let parse = |s: &str, now: &jiff::Zoned| {
+        parser::parse(s, now, parse_options())
+            .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))
+    };
+    let (start, end) = match split_interval(input) {
+        Some((start, end)) => {
+            let start = parse(start, now)?;
+            let end = parse(end, &start)
+                .or_else(|e| parse(&format!("{} {end}", start.date()), &start).map_err(|_| e))?;
+            (start, end)
+        }
+        None => {
+            let (start, last) =
+                parser::parse_range_with_granularity(input, now, parse_options())
+                    .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+            let end = last
+                .checked_add(jiff::SignedDuration::from_nanos(1))
+                .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+            (start, end)
+        }
+    };
+    if end <= start {
+        return Err(user_input_error!(
+            InvalidDate,
+            "range '{}' ends before it starts",
+            input
+        ));
+    }
+    Ok((start, end))
+}
+
+/// Handle `td meet ZONE...` -- print common working-hour windows.
+fn handle_meet(args: MeetArgs, diag: &mut Diagnostics) -> Result<()> {
+    use tardis_cli::{
+        holiday::Calendar,
+        hours::Schedule,
+        meet::{self, Hours, Participant},
+    };
+
+    let cfg = load_config(diag)?;
+    let given_hours: Option<Hours> = match args.hours.as_deref().or(cfg.meet.hours.as_deref()) {
+        Some(hours) => Some(hours.parse()?),
+        None => None,
+    };
+
+    let mut zones = Vec::with_capacity(args.zones.len());
+    for arg in &args.zones {
+        let (label, hours) = match arg.split_once('=') {
+            Some((zone, hours)) => (zone.trim(), Some(hours.parse::<Hours>()?)),
+            None => (arg.trim(), None),
+        };
+        zones.push((label, lookup_timezone(label, diag)?, hours));
+    }
+
+    let first = zones[0].1.clone();
+    let now = resolve_now_zoned(&args.now, &first)?;
+    let day = parser::parse(&args.on, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?
+        .date();
+    // Those without hours of their own keep `--hours` or `[meet] hours` on
+    // the working days, else follow the `[hours]` schedule.
+    let workdays = cfg.workweek.days(None)?;
+    let default = match given_hours {
+        Some(hours) => Schedule::weekly(hours, &workdays),
+        None => {
+            let holidays = Calendar::from_table(&cfg.holidays, &now)?;
+            Schedule::from_table(&cfg.hours, &workdays, holidays, &now)?
+        }
+    };
+    let people: Vec<Participant> = zones
+        .into_iter()
+        .map(|(label, zone, hours)| Participant {
+            label: label.to_string(),
+            zone,
+            schedule: hours.map_or_else(
+                || default.clone(),
+                |hours| Schedule::weekly(hours, &workdays),
+            ),
+        })
+        .collect();
+    let windows = meet::windows(day, &people)?;
+
+    let cell = |w: &meet::Window, zone: &jiff::tz::TimeZone| {
+        let start = w.start.to_zoned(zone.clone());
+        let end = w.end.to_zoned(zone.clone());
+        let span = format!("{}-{}", start.strftime("%H:%M"), end.strftime("%H:%M"));
+        if start.date() == day {
+            span
+        } else {
+            format!("{} {span}", start.strftime("%a"))
+        }
+    };
+
+    if args.json {
+        let json = serde_json::json!({
+            "date": day.to_string(),
+            "windows": windows.iter().map(|w| {
+                serde_json::json!({
+                    "start": w.start.to_zoned(first.clone()).strftime("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                    "end": w.end.to_zoned(first.clone()).strftime("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                    "local": people.iter().map(|p| {
+                        serde_json::json!({ "zone": p.label, "time": cell(w, &p.zone) })
+                    }).collect::<Vec<_>>(),
+                })
+            }).collect::<Vec<_>>(),
+        });
+        emit_json(&json, false);
+        return Ok(());
+    }
+
+    if windows.is_empty() {
+        eprintln!("No common working hours on {day}.");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = std::iter::once(people.iter().map(|p| p.label.clone()).collect())
+        .chain(
+            windows
+                .iter()
+                .map(|w| people.iter().map(|p| cell(w, &p.zone)).collect()),
+        )
+        .collect();
+    let names: Vec<&str> = people.iter().map(|p| p.label.as_str()).collect();
+    print_table(&names, &rows)
+}
+
+/// Handle `td worldclock [EXPR]` -- one time shown in several zones.
+fn handle_worldclock(args: WorldclockArgs, diag: &mut Diagnostics) -> Result<()> {
+    let cfg = load_config(diag)?;
+    let names = if args.zones.is_empty() {
+        cfg.worldclock.zones.clone()
+    } else {
+        args.zones
+    };
+    if names.is_empty() {
+        return Err(user_input_error!(
+            MissingArgument,
+            "no zones to show; pass --zone or set zones in [worldclock]"
+        ));
+    }
+    let fmt = args
+        .format
+        .or_else(|| cfg.worldclock.format.clone())
+        .map_or_else(
+            || "%Y-%m-%d %H:%M".to_string(),
+            |f| resolve_builtin_format(&f),
+        );
+    core::validate_format(&fmt)?;
+
+    let local = jiff::tz::TimeZone::system();
+    let now = resolve_now_zoned(&args.now, &local)?;
+    let instant = parser::parse(&args.input, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+
+    let mut clocks = Vec::with_capacity(names.len());
+    for name in &names {
+        let zoned = instant.with_time_zone(lookup_timezone(name, diag)?);
+        clocks.push((name, zoned));
+    }
+
+    if args.json {
+        let json: Vec<serde_json::Value> = clocks
+            .iter()
+            .map(|(name, z)| {
+                serde_json::json!({
+                    "zone": name,
+                    "time": z.strftime(&fmt).to_string(),
+                    "offset": z.strftime("%:z").to_string(),
+                    "iso": z.strftime("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                })
+            })
+            .collect();
+        emit_json(&serde_json::Value::Array(json), false);
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = clocks
+        .iter()
+        .map(|(name, z)| {
+            vec![
+                name.to_string(),
+                z.strftime(&fmt).to_string(),
+                z.strftime("%:z").to_string(),
+            ]
+        })
+        .collect();
+    print_table(&["zone", "time", "offset"], &rows)
+}
+
+/// Handle `td epoch <timestamp|expr>` -- convert in whichever direction fits.
+fn handle_epoch(args: EpochArgs, diag: &mut Diagnostics) -> Result<()> {
+    use jiff::Timestamp;
+
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let input = args.input.trim();
+    let digits = input.strip_prefix('@').unwrap_or(input);
+
+    if let Ok(value) = digits.parse::<i128>() {
+        let unit = args.unit.unwrap_or_else(|| match value.unsigned_abs() {
+            0..1_000_000_000_000 => EpochUnit::S,
+            1_000_000_000_000..1_000_000_000_000_000 => EpochUnit::Ms,
+            1_000_000_000_000_000..1_000_000_000_000_000_000 => EpochUnit::Us,
+            _ => EpochUnit::Ns,
+        });
+        let nanos_per_unit = match unit {
+            EpochUnit::Ms => 1_000_000,
+            EpochUnit::Us => 1_000,
+            EpochUnit::Ns => 1,
+            _ => 1_000_000_000,
+        };
+        let ts = value
+            .checked_mul(nanos_per_unit)
+            .and_then(|nanos| Timestamp::from_nanosecond(nanos).ok())
+            .ok_or_else(|| {
+                user_input_error!(
+                    InvalidDateFormat,
+                    "invalid epoch: {} is outside the supported range {} to {}",
+                    value,
+                    Timestamp::MIN,
+                    Timestamp::MAX
+                )
+            })?;
+        let iso = ts
+            .to_zoned(tz)
+            .strftime("%Y-%m-%dT%H:%M:%S%.f%:z")
+            .to_string();
+        return print_epoch(&args, value, unit, &iso, &iso);
+    }
+
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let zoned = parser::parse(input, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+    let unit = args.unit.unwrap_or(EpochUnit::S);
+    let ts = zoned.timestamp();
+    let value = match unit {
+        EpochUnit::Ms => i128::from(ts.as_millisecond()),
+        EpochUnit::Us => i128::from(ts.as_microsecond()),
+        EpochUnit::Ns => ts.as_nanosecond(),
+        _ => i128::from(ts.as_second()),
+    };
+    let iso = zoned.strftime("%Y-%m-%dT%H:%M:%S%.f%:z").to_string();
+    print_epoch(&args, value, unit, &value.to_string(), &iso)
+}
+
+/// Handle `td span [expr]` -- the instant in UTC and in the target zone.
+fn handle_span(args: SpanArgs, diag: &mut Diagnostics) -> Result<()> {
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let local = parser::parse(&args.input, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+    let utc = local.with_time_zone(jiff::tz::TimeZone::UTC);
+    let (utc_text, local_text) = (
+        format_zoned(&utc, &args.format)?,
+        format_zoned(&local, &args.format)?,
+    );
+
+    if args.json {
+        let json = serde_json::json!({
+            "utc": utc_text,
+            "local": local_text,
+            "timezone": tz.iana_name().unwrap_or("Unknown"),
+            "epoch": local.timestamp().as_second(),
+        });
+        emit_json(&json, false);
+    } else {
+        println!("{utc_text}{}{local_text}", args.delimiter);
+    }
+    Ok(())
+}
+
+/// A JSON number, or a string when `n` does not fit in 64 bits.
+fn json_integer(n: i128) -> serde_json::Value {
+    i64::try_from(n).map_or_else(|_| n.to_string().into(), serde_json::Value::from)
+}
+
+/// Print the result of `td epoch`: `text` normally, or all fields as JSON.
+fn print_epoch(
+    args: &EpochArgs,
+    epoch: i128,
+    unit: EpochUnit,
+    text: &str,
+    iso: &str,
+) -> Result<()> {
+    if args.json {
+        let unit = match unit {
+            EpochUnit::Ms => "ms",
+            EpochUnit::Us => "us",
+            EpochUnit::Ns => "ns",
+            _ => "s",
+        };
+        let json = serde_json::json!({
+            "input": args.input,
+            "epoch": json_integer(epoch),
+            "unit": unit,
+            "iso": iso,
+        });
+        emit_json(&json, args.no_newline);
+    } else {
+        output_value(text, args.no_newline);
+    }
+    Ok(())
+}
+
+/// Handle `td sleep-until <expr>` -- block until the resolved instant.
+fn handle_sleep_until(args: SleepUntilArgs, diag: &mut Diagnostics) -> Result<()> {
+    use jiff::{SignedDuration, Timestamp};
+    use std::{io::Write, time::Duration};
+
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let target = parser::parse(&args.input, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+
+    let wait = now.duration_until(&target);
+    if wait <= SignedDuration::ZERO {
+        return Ok(());
+    }
+    if let Some(max) = &args.max_wait {
+        let max: SignedDuration = max
+            .parse()
+            .map_err(|e| user_input_error!(InvalidDate, "invalid --max-wait '{}': {}", max, e))?;
+        if wait > max {
+            return Err(user_input_error!(
+                InvalidDate,
+                "{} is {:#} away, beyond --max-wait {:#}",
+                target.strftime("%Y-%m-%dT%H:%M:%S%:z"),
+                wait.round(jiff::Unit::Second).unwrap_or(wait),
+                max
+            ));
+        }
+    }
+
+    // The wall clock, unlike `Instant`, keeps running while the machine
+    // sleeps, so a suspend does not push the deadline back.
+    let deadline = Timestamp::now()
+        .saturating_add(wait)
+        .unwrap_or(Timestamp::MAX);
+    let countdown = io::stderr().is_terminal() && !term::plain();
+    if countdown {
+        tardis_cli::signal::exit_on_interrupt();
+    }
+    loop {
+        let left =
+            Duration::try_from(Timestamp::now().duration_until(deadline)).unwrap_or_default();
+        if left.is_zero() {
+            break;
+        }
+        if countdown {
+            let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+            eprint!(
+                "\r\x1b[Kwaiting {:#} until {}",
+                SignedDuration::from_secs(secs.try_into().unwrap_or(i64::MAX)),
+                target.strftime("%H:%M:%S")
+            );
+            io::stderr().flush()?;
+        }
+        std::thread::sleep(left.min(Duration::from_secs(1)));
+    }
+    if countdown {
+        eprint!("\r\x1b[K");
+    }
+    Ok(())
+}
+
+/// Handle `td every <schedule> -- <command>` -- run a command repeatedly.
+fn handle_every(args: EveryArgs, diag: &mut Diagnostics) -> Result<()> {
+    use jiff::{Timestamp, Zoned};
+    use tardis_cli::recur::Recurrence;
+
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let start = resolve_now_zoned(&args.now, &tz)?;
+    let cfg = load_config(diag)?;
+    let schedule = cfg
+        .recurrences
+        .get(args.schedule.trim())
+        .unwrap_or(&args.schedule);
+    let rec = Recurrence::parse(schedule, &start)?;
+    let iso = |z: &Zoned| z.strftime("%Y-%m-%dT%H:%M:%S%:z").to_string();
+
+    if args.dry_run {
+        for z in rec.upcoming(&start, args.count.unwrap_or(5))? {
+            println!("{}", iso(&z));
+        }
+        return Ok(());
+    }
+    let Some((program, program_args)) = args.command.split_first() else {
+        return Err(user_input_error!(
+            MissingArgument,
+            "td every needs a command after --"
+        ));
+    };
+
+    // `--now` shifts the whole clock, so waits are still real time.
+    let skew = Timestamp::now().duration_until(start.timestamp());
+    let clock = || {
+        Timestamp::now()
+            .checked_add(skew)
+            .unwrap_or_else(|_| Timestamp::now())
+            .to_zoned(tz.clone())
+    };
+
+    let mut next = rec.next_after(&start)?;
+    for run in 1.. {
+        let wait = clock().duration_until(&next);
+        if let Ok(wait) = std::time::Duration::try_from(wait) {
+            std::thread::sleep(wait);
+        }
+
+        let status = std::process::Command::new(program)
+            .args(program_args)
+            .env("TD_RUN", run.to_string())
+            .env("TD_SCHEDULED", iso(&next))
+            .status()?;
+        let outcome = match status.code() {
+            Some(code) => format!("exit {code}"),
+            None => "killed by a signal".to_string(),
+        };
+        diag.note(format!("run {run} at {}: {outcome}", iso(&next)));
+
+        if args.count.is_some_and(|n| run >= n) {
+            diag.flush()?;
+            break;
+        }
+        let now = clock();
+        let mut following = rec.next_after(&next)?;
+        let mut missed = 0;
+        while following <= now {
+            missed += 1;
+            following = rec.next_after(&following)?;
+        }
+        if missed > 0 {
+            diag.note(format!(
+                "skipped {missed} missed run(s); next at {}",
+                iso(&following)
+            ));
+        }
+        diag.flush()?;
+        next = following;
+    }
+    Ok(())
+}
+
+/// Handle `td stamp` -- prefix each stdin line with the time it arrived.
+fn handle_stamp(args: StampArgs, diag: &mut Diagnostics) -> Result<()> {
+    use io::{BufRead, Write};
+    use jiff::{Timestamp, tz::TimeZone};
+
+    let default = if args.elapsed {
+        "%H:%M:%S"
+    } else {
+        "%Y-%m-%d %H:%M:%S"
+    };
+    let fmt = args
+        .format
+        .as_deref()
+        .map_or_else(|| default.to_string(), resolve_builtin_format);
+    core::validate_format(&fmt)?;
+
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let skew = match resolve_now(&args.now)? {
+        Some(now) => Timestamp::now().duration_until(now),
+        None => jiff::SignedDuration::ZERO,
+    };
+    let started = std::time::Instant::now();
+    // Elapsed time is rendered as a time of day on 1970-01-01 UTC, so
+    // `%H:%M:%S` reads as hours, minutes and seconds since the start.
+    let stamp = || -> String {
+        let (ts, zone) = if args.elapsed {
+            let elapsed = jiff::SignedDuration::try_from(started.elapsed()).unwrap_or_default();
+            let ts = Timestamp::UNIX_EPOCH
+                .checked_add(elapsed)
+                .unwrap_or(Timestamp::UNIX_EPOCH);
+            (ts, TimeZone::UTC)
+        } else {
+            let ts = Timestamp::now()
+                .checked_add(skew)
+                .unwrap_or_else(|_| Timestamp::now());
+            (ts, tz.clone())
+        };
+        core::epoch_output(ts, &fmt).unwrap_or_else(|| ts.to_zoned(zone).strftime(&fmt).to_string())
+    };
+
+    let mut input = io::stdin().lock();
+    let mut out = term::stdout(true);
+    let mut line = Vec::new();
+    while input.read_until(b'\n', &mut line)? > 0 {
+        write!(out, "{} ", stamp())?;
+        out.write_all(&line)?;
+        if !line.ends_with(b"\n") {
+            out.write_all(b"\n")?;
+        }
+        line.clear();
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Handle `td delta` -- annotate log lines with the gap since the previous one.
+fn handle_delta(args: DeltaArgs, diag: &mut Diagnostics) -> Result<()> {
+    use io::{BufRead, Write};
+    use jiff::SignedDuration;
+    use tardis_cli::logtime;
+
+    let threshold: Option<SignedDuration> = args
+        .threshold
+        .as_deref()
+        .map(|t| {
+            t.parse()
+                .map_err(|e| user_input_error!(InvalidDate, "invalid --threshold '{}': {}", t, e))
+        })
+        .transpose()?;
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let extractor = logtime::Extractor::new(args.profile, &resolve_now_zoned(&None, &tz)?);
+    let color = term::stdout_color();
+    let plain = term::plain();
+
+    let mut out = term::stdout(true);
+    let mut previous: Option<jiff::Timestamp> = None;
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        let Some(found) = extractor.find(&line) else {
+            if plain && !args.seconds {
+                writeln!(out, "\t{line}")?;
+            } else if !args.seconds {
+                writeln!(out, "{:>14}  {line}", "")?;
+            }
+            continue;
+        };
+        let delta = previous.map_or(SignedDuration::ZERO, |p| p.duration_until(found.at));
+        previous = Some(found.at);
+
+        if args.seconds {
+            writeln!(out, "{}", delta.as_secs_f64())?;
+            continue;
+        }
+        let rounded = delta
+            .round(jiff::SignedDurationRound::new().smallest(jiff::Unit::Millisecond))
+            .unwrap_or(delta);
+        let gap = threshold.is_some_and(|t| delta > t);
+        let cell = format!("{}{rounded:#}", if gap { '!' } else { '+' });
+        if plain {
+            writeln!(out, "{cell}\t{line}")?;
+        } else if gap && color {
+            writeln!(out, "\x1b[1;31m{cell:>14}\x1b[0m  {line}")?;
+        } else {
+            writeln!(out, "{cell:>14}  {line}")?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Handle `td rewrite` -- reformat the timestamp of each log line in place.
+fn handle_rewrite(args: RewriteArgs, diag: &mut Diagnostics) -> Result<()> {
+    use io::{BufRead, Write};
+    use tardis_cli::logtime::Extractor;
+
+    let fmt = args.format.as_deref().map_or_else(
+        || "%Y-%m-%dT%H:%M:%S%:z".to_string(),
+        resolve_builtin_format,
+    );
+    core::validate_format(&fmt)?;
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let extractor = Extractor::new(args.profile, &resolve_now_zoned(&args.now, &tz)?);
+
+    let mut out = term::stdout(true);
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        let Some(found) = extractor.find(&line) else {
+            writeln!(out, "{line}")?;
+            continue;
+        };
+        let stamp = core::epoch_output(found.at, &fmt)
+            .unwrap_or_else(|| found.at.to_zoned(tz.clone()).strftime(&fmt).to_string());
+        writeln!(
+            out,
+            "{}{stamp}{}",
+            &line[..found.span.start],
+            &line[found.span.end..]
+        )?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Handle `td sum` -- total the durations read from stdin.
+fn handle_sum(args: SumArgs, diag: &mut Diagnostics) -> Result<()> {
+    use io::BufRead;
+    use jiff::SignedDuration;
+    use tardis_cli::duration::{self, Decimals};
+
+    let cfg = load_config(diag)?;
+    let decimals = Decimals::from_config(&cfg.humanize, args.decimals, args.rounding)?;
+
+    let mut total = SignedDuration::ZERO;
+    let mut groups: Vec<(String, SignedDuration)> = Vec::new();
+    let mut entries: Vec<(String, SignedDuration)> = Vec::new();
+    for (number, line) in io::stdin().lock().lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (label, text) = if args.group_by {
+            line.split_once(char::is_whitespace)
+                .map_or((line, ""), |(l, d)| (l, d.trim()))
+        } else {
+            ("", line)
+        };
+        let d = duration::parse_entry(text).map_err(|_| {
+            user_input_error!(
+                InvalidDate,
+                "line {}: invalid duration '{}'",
+                number + 1,
+                text
+            )
+        })?;
+        total = total.saturating_add(d);
+        if args.each {
+            entries.push((line.to_string(), d));
+        }
+        if args.group_by {
+            match groups.iter_mut().find(|(l, _)| l == label) {
+                Some((_, sum)) => *sum = sum.saturating_add(d),
+                None => groups.push((label.to_string(), d)),
+            }
+        }
+    }
+
+    let render = |d| duration::render(d, args.output, decimals);
+    if args.json {
+        let mut json = serde_json::json!({
+            "total": render(total),
+            "seconds": total.as_secs(),
+        });
+        if args.group_by {
+            json["groups"] = groups
+                .iter()
+                .map(|(label, d)| {
+                    serde_json::json!({ "label": label, "total": render(*d), "seconds": d.as_secs() })
+                })
+                .collect();
+        }
+        if args.each {
+            json["entries"] = entries
+                .iter()
+                .map(|(entry, d)| {
+                    serde_json::json!({ "entry": entry, "total": render(*d), "seconds": d.as_secs() })
+                })
+                .collect();
+        }
+        emit_json(&json, false);
+    } else if args.group_by || args.each {
+        let mut rows: Vec<Vec<String>> = groups
+            .iter()
+            .chain(&entries)
+            .map(|(label, d)| vec![label.clone(), render(*d)])
+            .collect();
+        rows.push(vec!["total".to_string(), render(total)]);
+        let first = if args.each { "entry" } else { "group" };
+        print_table(&[first, "total"], &rows)?;
+    } else {
+        println!("{}", render(total));
+    }
+    Ok(())
+}
+
+/// Collect the timestamp of every stdin line that has one.
+fn read_timestamps(extractor: &tardis_cli::logtime::Extractor) -> Result<Vec<jiff::Timestamp>> {
+    use io::BufRead;
+
+    let mut stamps = Vec::new();
+    for line in io::stdin().lock().lines() {
+        if let Some(found) = extractor.find(&line?) {
+            stamps.push(found.at);
+        }
+    }
+    Ok(stamps)
+}
+
+/// Handle `td stats` -- count, earliest, latest and span of stdin timestamps.
+fn handle_stats(args: StatsArgs, diag: &mut Diagnostics) -> Result<()> {
+    use jiff::{Timestamp, civil::Date};
+    use tardis_cli::logtime::Extractor;
+
+    let fmt = args.format.as_deref().map_or_else(
+        || "%Y-%m-%dT%H:%M:%S%:z".to_string(),
+        resolve_builtin_format,
+    );
+    core::validate_format(&fmt)?;
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let extractor = Extractor::new(args.profile, &resolve_now_zoned(&args.now, &tz)?);
+    let stamps = read_timestamps(&extractor)?;
+
+    let show = |ts: Timestamp| {
+        core::epoch_output(ts, &fmt)
+            .unwrap_or_else(|| ts.to_zoned(tz.clone()).strftime(&fmt).to_string())
+    };
+    struct Stats {
+        count: usize,
+        earliest: Timestamp,
+        latest: Timestamp,
+    }
+    let summarize = |stamps: &mut dyn Iterator<Item = Timestamp>| {
+        stamps.fold(None, |acc: Option<Stats>, ts| {
+            Some(match acc {
+                None => Stats {
+                    count: 1,
+                    earliest: ts,
+                    latest: ts,
+                },
+                Some(s) => Stats {
+                    count: s.count + 1,
+                    earliest: s.earliest.min(ts),
+                    latest: s.latest.max(ts),
+                },
+            })
+        })
+    };
+    let span = |s: &Stats| {
+        let d = s.earliest.duration_until(s.latest);
+        d.round(jiff::SignedDurationRound::new().smallest(jiff::Unit::Second))
+            .unwrap_or(d)
+    };
+
+    let Some(all) = summarize(&mut stamps.iter().copied()) else {
+        return Err(user_input_error!(
+            InvalidDateFormat,
+            "no timestamps found on stdin"
+        ));
+    };
+    let mut days: Vec<(Date, Stats)> = Vec::new();
+    if args.per_day {
+        let bounds = day_boundary(&args.day_boundary, &tz, diag)?;
+        let mut dates: Vec<Date> = stamps
+            .iter()
+            .map(|ts| ts.to_zoned(bounds.clone()).date())
+            .collect();
+        dates.sort();
+        dates.dedup();
+        for date in dates {
+            let mut same_day = stamps
+                .iter()
+                .copied()
+                .filter(|ts| ts.to_zoned(bounds.clone()).date() == date);
+            if let Some(stats) = summarize(&mut same_day) {
+                days.push((date, stats));
+            }
+        }
+    }
+
+    if args.json {
+        let entry = |s: &Stats| {
+            serde_json::json!({
+                "count": s.count,
+                "earliest": show(s.earliest),
+                "latest": show(s.latest),
+                "span": format!("{:#}", span(s)),
+                "span_seconds": span(s).as_secs(),
+            })
+        };
+        let mut json = entry(&all);
+        if args.per_day {
+            json["days"] = days
+                .iter()
+                .map(|(date, s)| {
+                    let mut day = entry(s);
+                    day["date"] = date.to_string().into();
+                    day
+                })
+                .collect();
+        }
+        emit_json(&json, false);
+    } else if args.per_day {
+        let mut rows = vec![vec![
+            "DATE".to_string(),
+            "COUNT".to_string(),
+            "EARLIEST".to_string(),
+            "LATEST".to_string(),
+            "SPAN".to_string(),
+        ]];
+        rows.extend(days.iter().map(|(date, s)| {
+            vec![
+                date.to_string(),
+                s.count.to_string(),
+                show(s.earliest),
+                show(s.latest),
+                format!("{:#}", span(s)),
+            ]
+        }));
+        print_table(&["date", "count", "earliest", "latest", "span"], &rows)?;
+    } else {
+        let span = span(&all);
+        print_table(
+            &["field", "value"],
+            &[
+                vec!["count".to_string(), all.count.to_string()],
+                vec!["earliest".to_string(), show(all.earliest)],
+                vec!["latest".to_string(), show(all.latest)],
+                vec!["span".to_string(), format!("{span:#}")],
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Handle `td hist` -- bar chart of stdin timestamps per calendar bucket.
+fn handle_hist(args: HistArgs, diag: &mut Diagnostics) -> Result<()> {
+    use tardis_cli::{bucket, logtime::Extractor};
+
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let extractor = Extractor::new(args.profile, &resolve_now_zoned(&args.now, &tz)?);
+    let stamps = read_timestamps(&extractor)?;
+    let bounds = day_boundary(&args.day_boundary, &tz, diag)?;
+    let counts = bucket::counts(&stamps, args.by, &bounds)?;
+
+    if args.csv {
+        println!("bucket,count");
+        for (start, n) in &counts {
+            println!("{},{n}", bucket::label(start, args.by));
+        }
+        return Ok(());
+    }
+
+    let max = counts.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+    let block = if term::ascii() { "#" } else { "█" };
+    let rows: Vec<Vec<String>> = counts
+        .iter()
+        .map(|(start, n)| {
+            let len = (n * args.width).div_ceil(max);
+            let bar = format!("{} {n}", block.repeat(len));
+            vec![bucket::label(start, args.by), bar.trim_start().to_string()]
+        })
+        .collect();
+    print_table(&["bucket", "bar"], &rows)
+}
+
+/// Handle `td group-by <unit>` -- count or regroup stdin lines per bucket.
+///
+/// A line without a timestamp belongs to the group of the line before it,
+/// so stack traces and other continuation lines stay with their entry.
+fn handle_group_by(args: GroupByArgs, diag: &mut Diagnostics) -> Result<()> {
+    use io::BufRead;
+    use std::collections::BTreeMap;
+    use tardis_cli::{bucket, logtime::Extractor};
+
+    let tz = match &args.timezone {
+        Some(name) => lookup_timezone(name, diag)?,
+        None => configured_timezone(&load_config(diag)?, diag)?,
+    };
+    let bounds = day_boundary(&args.day_boundary, &tz, diag)?;
+    let extractor = Extractor::new(args.profile, &resolve_now_zoned(&args.now, &tz)?);
+
+    let mut leading = Vec::new();
+    let mut groups: BTreeMap<jiff::Zoned, (usize, Vec<String>)> = BTreeMap::new();
+    let mut current: Option<jiff::Zoned> = None;
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if let Some(found) = extractor.find(&line) {
+            let start = bucket::start(&found.at.to_zoned(bounds.clone()), args.unit)?;
+            groups.entry(start.clone()).or_default().0 += 1;
+            current = Some(start);
+        }
+        if args.lines {
+            match &current {
+                Some(start) => groups.entry(start.clone()).or_default().1.push(line),
+                None => leading.push(line),
+            }
+        }
+    }
+
+    if !args.lines {
+        let rows: Vec<Vec<String>> = groups
+            .iter()
+            .map(|(start, (n, _))| vec![bucket::label(start, args.unit), n.to_string()])
+            .collect();
+        print_table(&["bucket", "count"], &rows)?;
+        return Ok(());
+    }
+    let mut blocks = Vec::new();
+    if !leading.is_empty() {
+        blocks.push(leading.join("\n"));
+    }
+    for (start, (_, lines)) in &groups {
+        blocks.push(format!(
+            "== {} ==\n{}",
+            bucket::label(start, args.unit),
+            lines.join("\n")
+        ));
+    }
+    if !blocks.is_empty() {
+        println!("{}", blocks.join("\n\n"));
+    }
+    Ok(())
+}
+
+/// Handle `td humanize <date>` -- describe a date relative to now.
+fn handle_humanize(args: HumanizeArgs, diag: &mut Diagnostics) -> Result<()> {
+    let cfg = load_config(diag)?;
+    let tz = match &args.timezone {
+        Some(name) => lookup_timezone(name, diag)?,
+        None => configured_timezone(&cfg, diag)?,
+    };
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let target = parser::parse(&args.input, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+
+    let mut humanizer = Humanizer::from_config(&cfg.humanize)?;
+    if let Some(n) = args.granularity {
+        humanizer = humanizer.granularity(n)?;
+    }
+    println!("{}", humanizer.describe(&target, &now)?);
+    Ok(())
+}
+
+/// Handle `td until <date>` -- print a Nagios-style status line and exit
+/// 0 (OK), 1 (WARNING) or 2 (CRITICAL) depending on how close the date is.
+/// A date already passed is always CRITICAL.
+fn handle_until(args: UntilArgs, diag: &mut Diagnostics) -> Result<()> {
+    let cfg = load_config(diag)?;
+    let tz = match &args.timezone {
+        Some(name) => lookup_timezone(name, diag)?,
+        None => configured_timezone(&cfg, diag)?,
+    };
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let parse = |input: &str| {
+        parser::parse(
+            &core::expand_dates(input, &cfg.dates, &now, parse_options())?,
+            &now,
+            parse_options(),
+        )
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))
+    };
+    let mut events = Vec::new();
+    if args.all {
+        for name in cfg.dates.keys() {
+            events.push((Some(name.clone()), parse(name)?));
+        }
+        events.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        if events.is_empty() {
+            diag.note("no dates to count down to; add some to the [dates] table");
+            return diag.flush();
+        }
+    } else {
+        let input = args.input.clone().unwrap_or_default();
+        // A named date labels itself.
+        let label = args.label.clone().or_else(|| {
+            cfg.dates
+                .keys()
+                .find(|name| name.eq_ignore_ascii_case(input.trim()))
+                .cloned()
+        });
+        events.push((label, parse(&input)?));
+    }
+
+    let humanizer = Humanizer::from_config(&cfg.humanize)?;
+    let mut worst = 0;
+    for (label, target) in &events {
+        let code = print_countdown(label.as_deref(), target, &now, &args, &humanizer)?;
+        worst = worst.max(code);
+    }
+    if worst != 0 {
+        std::process::exit(worst);
+    }
+    Ok(())
+}
+
+/// Print the status line of `td until` for `target`, returning its exit
+/// code.
+fn print_countdown(
+    label: Option<&str>,
+    target: &jiff::Zoned,
+    now: &jiff::Zoned,
+    args: &UntilArgs,
+    humanizer: &Humanizer,
+) -> Result<i32> {
+    // Windows are calendar spans counted back from the target, so "1 month"
+    // before 2025-09-01 starts on 2025-08-01.
+    let within = |flag: &str, window: &Option<String>| -> Result<bool> {
+        let Some(window) = window else {
+            return Ok(false);
+        };
+        let span: jiff::Span = window.parse().map_err(|_| {
+            user_input_error!(InvalidDate, "invalid --{} duration '{}'", flag, window)
+        })?;
+        let from = target
+            .checked_sub(span.abs())
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+        Ok(*now >= from)
+    };
+    let (crit, warn) = (within("crit", &args.crit)?, within("warn", &args.warn)?);
+    let (status, code) = if target <= now || crit {
+        ("CRITICAL", 2)
+    } else if warn {
+        ("WARNING", 1)
+    } else {
+        ("OK", 0)
+    };
+
+    let when = humanizer.describe(target, now)?;
+    let label = label.map(|l| format!("{l} ")).unwrap_or_default();
+    println!(
+        "{status}: {label}{when} ({})",
+        target.strftime("%Y-%m-%dT%H:%M:%S%:z")
+    );
+    Ok(code)
+}
+
+/// Print `zoned` with `--format`, defaulting to RFC 3339.
+fn print_zoned(zoned: &jiff::Zoned, format: &Option<String>) -> Result<()> {
+    println!("{}", format_zoned(zoned, format)?);
+    Ok(())
+}
+
+/// Format `zoned` with `--format`, defaulting to RFC 3339.
+fn format_zoned(zoned: &jiff::Zoned, format: &Option<String>) -> Result<String> {
+    let fmt = format.as_deref().map_or_else(
+        || "%Y-%m-%dT%H:%M:%S%:z".to_string(),
+        resolve_builtin_format,
+    );
+    core::validate_format(&fmt)?;
+    Ok(core::epoch_output(zoned.timestamp(), &fmt)
+        .unwrap_or_else(|| zoned.strftime(&fmt).to_string()))
+}
+
+/// Handle `td clamp <date> --min <date> --max <date>`.
+fn handle_clamp(args: ClampArgs, diag: &mut Diagnostics) -> Result<()> {
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let parse = |input: &str| {
+        parser::parse(input, &now, parse_options())
+            .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))
+    };
+    let zoned = parse(&args.input)?;
+    let min = args.min.as_deref().map(parse).transpose()?;
+    let max = args.max.as_deref().map(parse).transpose()?;
+
+    let clamped = match (min, max) {
+        (Some(min), Some(max)) if min > max => {
+            return Err(user_input_error!(
+                InvalidDate,
+                "--min {} is after --max {}",
+                min.strftime("%Y-%m-%dT%H:%M:%S%:z"),
+                max.strftime("%Y-%m-%dT%H:%M:%S%:z")
+            ));
+        }
+        (Some(min), _) if zoned < min => min,
+        (_, Some(max)) if zoned > max => max,
+        _ => zoned,
+    };
+    print_zoned(&clamped, &args.format)
+}
+
+/// Handle `td min` / `td max` -- print the earliest or latest expression,
+/// reading one per line from stdin when none are given.
+fn handle_extreme(args: ExtremeArgs, latest: bool, diag: &mut Diagnostics) -> Result<()> {
+    use io::BufRead;
+
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let inputs = if args.inputs.is_empty() {
+        io::stdin()
+            .lock()
+            .lines()
+            .filter(|line| line.as_ref().map_or(true, |l| !l.trim().is_empty()))
+            .collect::<io::Result<Vec<_>>>()?
+    } else {
+        args.inputs
+    };
+
+    let mut best: Option<jiff::Zoned> = None;
+    for input in &inputs {
+        let zoned = parser::parse(input.trim(), &now, parse_options())
+            .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+        let better = best
+            .as_ref()
+            .is_none_or(|b| if latest { zoned > *b } else { zoned < *b });
+        if better {
+            best = Some(zoned);
+        }
+    }
+    let Some(best) = best else {
+        return Err(user_input_error!(
+            MissingArgument,
+            "td {} needs at least one date expression",
+            if latest { "max" } else { "min" }
+        ));
+    };
+    print_zoned(&best, &args.format)
+}
+
+/// Handle `td floor|ceil <schedule> [date...]` -- snap each date to the
+/// schedule's occurrence at or before it, or at or after it with `up`.
+fn handle_snap(args: SnapArgs, up: bool, diag: &mut Diagnostics) -> Result<()> {
+    use io::BufRead;
+    use tardis_cli::recur::Recurrence;
+
+    let tz = resolve_timezone(&args.timezone, diag)?;
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let cfg = load_config(diag)?;
+    let schedule = cfg
+        .recurrences
+        .get(args.schedule.trim())
+        .unwrap_or(&args.schedule);
+    // Reject a bad schedule even when there is nothing to snap.
+    let _ = Recurrence::parse_anchored(schedule, &now)?;
+    let inputs = if !args.inputs.is_empty() {
+        args.inputs
+    } else if io::stdin().is_terminal() {
+        vec!["now".to_string()]
+    } else {
+        io::stdin()
+            .lock()
+            .lines()
+            .filter(|line| line.as_ref().map_or(true, |l| !l.trim().is_empty()))
+            .collect::<io::Result<Vec<_>>>()?
+    };
+
+    let mut out = io::stdout().lock();
+    for input in &inputs {
+        let zoned = parser::parse(input.trim(), &now, parse_options())
+            .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+        // Parsed against the date itself, so intervals that divide a day
+        // line up with its midnight.
+        let rec = Recurrence::parse_anchored(schedule, &zoned)?;
+        let snapped = if up {
+            rec.ceil(&zoned)?
+        } else {
+            rec.floor(&zoned)?
+        };
+        writeln!(out, "{}", format_zoned(&snapped, &args.format)?)?;
     }
     Ok(())
 }
 
 /// Handle `td diff <date1> <date2>` -- compute calendar-aware duration.
-fn handle_diff(args: DiffArgs) -> Result<()> {
+fn handle_diff(args: DiffArgs, diag: &mut Diagnostics) -> Result<()> {
     let start_instant = std::time::Instant::now();
-    let tz = resolve_timezone(&args.timezone)?;
+    let tz = resolve_timezone(&args.timezone, diag)?;
     let now = resolve_now_zoned(&args.now, &tz)?;
 
     if args.verbose {
@@ -290,16 +2355,37 @@ fn handle_diff(args: DiffArgs) -> Result<()> {
         verbose!("parse", "timezone={}", tz.iana_name().unwrap_or("system"));
     }
 
-    let z1 = parser::parse(&args.date1, &now)
-        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
-    let z2 = parser::parse(&args.date2, &now)
-        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+    let parse = |input: &str| {
+        parser::parse_with_offset(input, &now, parse_options())
+            .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))
+    };
+    let (z1, offset1) = parse(&args.date1)?;
+    let (z2, offset2) = parse(&args.date2)?;
+
+    if args.visual {
+        // Each date in the offset it was written with, so two readings of
+        // one instant show where they differ.
+        let render = |zoned: &jiff::Zoned, offset: Option<jiff::tz::Offset>| {
+            let zoned = match offset {
+                Some(offset) => zoned.with_time_zone(jiff::tz::TimeZone::fixed(offset)),
+                None => zoned.clone(),
+            };
+            format_zoned(&zoned, &args.format)
+        };
+        print_visual_diff(&render(&z1, offset1)?, &render(&z2, offset2)?);
+    }
 
     let span = z1
         .until(jiff::ZonedDifference::new(&z2).largest(jiff::Unit::Year))
         .map_err(|e| user_input_error!(InvalidDateFormat, "diff failed: {}", e))?;
 
     let total_secs = z2.timestamp().as_second() - z1.timestamp().as_second();
+    let decimals = tardis_cli::duration::Decimals::from_config(
+        &load_config(diag)?.humanize,
+        args.decimals,
+        args.rounding,
+    )?;
+    let decimal = decimals.largest_unit(z1.duration_until(&z2));
 
     if args.verbose {
         verbose!(
@@ -318,6 +2404,7 @@ fn handle_diff(args: DiffArgs) -> Result<()> {
             "human": format!("{:#}", span),
             "seconds": total_secs,
             "iso8601": format!("{}", span),
+            "decimal": decimal,
         });
         emit_json(&json, args.no_newline);
     } else {
@@ -325,16 +2412,79 @@ fn handle_diff(args: DiffArgs) -> Result<()> {
             DiffOutput::Human => format!("{:#}", span),
             DiffOutput::Seconds => total_secs.to_string(),
             DiffOutput::Iso => format!("{}", span),
+            DiffOutput::Decimal => decimal,
         };
         output_value(&text, args.no_newline);
     }
     Ok(())
 }
 
+/// Print `a` above `b` with the components that differ highlighted: in
+/// color on a terminal, else marked with carets on a line below.
+fn print_visual_diff(a: &str, b: &str) {
+    let (a, b) = (date_components(a), date_components(b));
+    let differs = |i: usize| a.get(i) != b.get(i);
+    if term::stdout_color() {
+        let paint = |parts: &[&str], ansi: &str| {
+            parts
+                .iter()
+                .enumerate()
+                .map(|(i, part)| {
+                    if differs(i) {
+                        format!("{ansi}{part}\x1b[0m")
+                    } else {
+                        (*part).to_string()
+                    }
+                })
+                .collect::<String>()
+        };
+        println!("{}", paint(&a, "\x1b[1;31m"));
+        println!("{}", paint(&b, "\x1b[1;32m"));
+        return;
+    }
+    println!("{}\n{}", a.concat(), b.concat());
+    let carets: String = (0..a.len().max(b.len()))
+        .map(|i| {
+            let width = b.get(i).or(a.get(i)).map_or(0, |p| p.chars().count());
+            let mark = if differs(i) { "^" } else { " " };
+            mark.repeat(width)
+        })
+        .collect();
+    if !carets.trim().is_empty() {
+        println!("{}", carets.trim_end());
+    }
+}
+
+/// Split a formatted date into its components: runs of digits, runs of
+/// letters, and each other character on its own.
+fn date_components(text: &str) -> Vec<&str> {
+    let class = |c: char| {
+        if c.is_ascii_digit() {
+            0
+        } else if c.is_alphabetic() {
+            1
+        } else {
+            2
+        }
+    };
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        let end = chars.peek().map_or(text.len(), |&(i, _)| i);
+        let next = chars.peek().map(|&(_, n)| class(n));
+        if class(c) == 2 || next != Some(class(c)) {
+            parts.push(&text[start..end]);
+            start = end;
+        }
+    }
+    parts
+}
+
 /// Handle `td convert <input> --to <format>` -- format conversion.
-fn handle_convert(args: ConvertArgs) -> Result<()> {
+fn handle_convert(args: ConvertArgs, diag: &mut Diagnostics) -> Result<()> {
     let start_instant = std::time::Instant::now();
-    let tz = resolve_timezone(&args.timezone)?;
+    let tz = resolve_timezone(&args.timezone, diag)?;
     let now = resolve_now_zoned(&args.now, &tz)?;
 
     if args.verbose {
@@ -350,42 +2500,28 @@ fn handle_convert(args: ConvertArgs) -> Result<()> {
 
     let zoned = if let Some(ref from_fmt) = args.from {
         let pattern = resolve_builtin_format(from_fmt);
-        jiff::Zoned::strptime(&pattern, &args.input).map_err(|e| {
-            user_input_error!(
-                InvalidDateFormat,
-                "failed to parse with format '{}': {}",
-                from_fmt,
-                e
-            )
-        })?
+        match core::epoch_input(&args.input, &pattern) {
+            Some(ts) => ts?.to_zoned(tz.clone()),
+            None => jiff::Zoned::strptime(&pattern, &args.input).map_err(|e| {
+                user_input_error!(
+                    InvalidDateFormat,
+                    "failed to parse with format '{}': {}",
+                    from_fmt,
+                    e
+                )
+            })?,
+        }
     } else {
-        if let Ok(ts) = args.input.parse::<jiff::Timestamp>() {
-            ts.to_zoned(tz.clone())
-        } else if let Ok(epoch) = args.input.trim().parse::<i64>() {
-            let abs = epoch.unsigned_abs();
-            let ts = if abs < 1_000_000_000_000 {
-                jiff::Timestamp::from_second(epoch)
-            } else if abs < 1_000_000_000_000_000 {
-                jiff::Timestamp::from_millisecond(epoch)
-            } else if abs < 1_000_000_000_000_000_000 {
-                jiff::Timestamp::from_microsecond(epoch)
-            } else {
-                jiff::Timestamp::from_nanosecond(i128::from(epoch))
-            }
-            .map_err(|e| user_input_error!(InvalidDateFormat, "invalid epoch: {}", e))?;
-            ts.to_zoned(tz.clone())
-        } else {
-            parser::parse(&args.input, &now)
-                .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?
+        match parser::parse_machine(&args.input, &tz) {
+            Some((zoned, _)) => zoned,
+            None => parser::parse(&args.input, &now, parse_options())
+                .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?,
         }
     };
 
     let to_fmt = resolve_builtin_format(&args.to);
-    let output = if to_fmt == "epoch" || to_fmt == "unix" {
-        zoned.timestamp().as_second().to_string()
-    } else {
-        zoned.strftime(&to_fmt).to_string()
-    };
+    let output = core::epoch_output(zoned.timestamp(), &to_fmt)
+        .unwrap_or_else(|| zoned.strftime(&to_fmt).to_string());
 
     if args.verbose {
         verbose!("resolve", "output={:?}", output);
@@ -408,26 +2544,42 @@ fn handle_convert(args: ConvertArgs) -> Result<()> {
 }
 
 /// Handle `td tz <datetime> --to <timezone>` -- timezone conversion.
-fn handle_tz(args: TzArgs) -> Result<()> {
+fn handle_tz(args: TzArgs, paging: bool, diag: &mut Diagnostics) -> Result<()> {
+    match args.action {
+        Some(TzAction::Pick(pick)) => return handle_tz_pick(pick),
+        Some(TzAction::At(at)) => return handle_tz_at(at, diag),
+        Some(TzAction::List(list)) => return handle_tz_list(list, paging, diag),
+        Some(TzAction::Transitions(t)) => return handle_tz_transitions(t, diag),
+        Some(TzAction::Compare(c)) => return handle_tz_compare(c, diag),
+        Some(TzAction::NextChange(n)) => return handle_tz_next_change(n, diag),
+        Some(_) => unreachable!(),
+        None => {}
+    }
+    let (Some(input), Some(to)) = (args.input, args.to) else {
+        return Err(user_input_error!(
+            MissingArgument,
+            "td tz needs an expression and --to"
+        ));
+    };
+
     let start_instant = std::time::Instant::now();
-    let from_tz = resolve_timezone(&args.from)?;
+    let from_tz = resolve_timezone(&args.from, diag)?;
     let now = resolve_now_zoned(&args.now, &from_tz)?;
 
     if args.verbose {
         verbose!(
             "parse",
             "input={:?} from={:?} to={:?}",
-            args.input,
+            input,
             args.from,
-            args.to
+            to
         );
     }
 
-    let zoned = parser::parse(&args.input, &now)
+    let zoned = parser::parse(&input, &now, parse_options())
         .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
 
-    let target_tz = jiff::tz::TimeZone::get(&args.to)
-        .map_err(|e| user_input_error!(UnsupportedTimezone, "{}", e))?;
+    let target_tz = lookup_timezone(&to, diag)?;
     let converted = zoned.with_time_zone(target_tz);
 
     if args.verbose {
@@ -443,9 +2595,9 @@ fn handle_tz(args: TzArgs) -> Result<()> {
 
     if args.json {
         let json = serde_json::json!({
-            "input": args.input,
+            "input": input,
             "from_timezone": zoned.time_zone().iana_name().unwrap_or("Unknown"),
-            "to_timezone": args.to,
+            "to_timezone": to,
             "original": zoned.strftime("%Y-%m-%dT%H:%M:%S%:z").to_string(),
             "converted": converted.strftime("%Y-%m-%dT%H:%M:%S%:z").to_string(),
         });
@@ -459,10 +2611,355 @@ fn handle_tz(args: TzArgs) -> Result<()> {
     Ok(())
 }
 
+/// Handle `td tz list` -- every zone, with its offset, abbreviation and
+/// country now when `--columns` asks for them.
+fn handle_tz_list(args: TzListArgs, paging: bool, diag: &mut Diagnostics) -> Result<()> {
+    let now = resolve_now(&args.now)?.unwrap_or_else(jiff::Timestamp::now);
+    let wanted = args
+        .offset
+        .as_deref()
+        .map(|s| {
+            parser::parse_utc_offset(s).ok_or_else(|| {
+                user_input_error!(
+                    UnsupportedTimezone,
+                    "invalid UTC offset '{}'; expected e.g. +05:30, -0300 or UTC+2",
+                    s
+                )
+            })
+        })
+        .transpose()?;
+    let mut zones: Vec<(i32, Vec<String>)> = tardis_cli::zones::all_zones()
+        .into_iter()
+        .filter_map(|name| {
+            let tz = jiff::tz::TimeZone::get(&name).ok()?;
+            let info = tz.to_offset_info(now);
+            if wanted.is_some_and(|w| w != info.offset())
+                || (args.dst_active && !info.dst().is_dst())
+            {
+                return None;
+            }
+            let offset = now.to_zoned(tz.clone()).strftime("%:z").to_string();
+            let country = tardis_cli::zones::zone_country(&name).unwrap_or_default();
+            Some((
+                info.offset().seconds(),
+                vec![
+                    name,
+                    offset,
+                    info.abbreviation().to_string(),
+                    country.to_string(),
+                ],
+            ))
+        })
+        .collect();
+    // `all_zones` is sorted by name, and the sorts are stable, so ties keep
+    // that order.
+    match args.sort {
+        TzSort::Name => {}
+        TzSort::Offset => zones.sort_by_key(|(seconds, _)| *seconds),
+        TzSort::Country => zones.sort_by_key(|(_, cells)| (cells[3].is_empty(), cells[3].clone())),
+        _ => unreachable!(),
+    }
+    if args.reverse {
+        zones.reverse();
+    }
+    let rows: Vec<Vec<String>> = zones.into_iter().map(|(_, cells)| cells).collect();
+    let mut layout = TABLE_LAYOUT.get_or_init(table::Layout::default).clone();
+    layout
+        .columns
+        .get_or_insert_with(|| vec!["zone".to_string()]);
+    let text = table::render(&["zone", "offset", "abbr", "country"], &rows, &layout)?;
+    print_listing(&text, paging, diag)
+}
+
+/// Handle `td tz at` -- show the time in a zone, abbreviation or city.
+fn handle_tz_at(args: TzAtArgs, diag: &mut Diagnostics) -> Result<()> {
+    let tz = lookup_timezone(&args.place, diag)?;
+    if args.id {
+        println!("{}", tz.iana_name().unwrap_or("UTC"));
+        return Ok(());
+    }
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let zoned = parser::parse(&args.input, &now, parse_options())
+        .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
+    output_value(
+        &zoned
+            .with_time_zone(tz)
+            .strftime("%Y-%m-%dT%H:%M:%S%:z")
+            .to_string(),
+        false,
+    );
+    Ok(())
+}
+
+/// Handle `td tz transitions` -- the offset changes of a zone, one row
+/// each, from January 1 of the first year to the end of the last, local
+/// time.
+fn handle_tz_transitions(args: TzTransitionsArgs, diag: &mut Diagnostics) -> Result<()> {
+    let tz = lookup_timezone(&args.place, diag)?;
+    let (first, last) = match args.years.as_deref() {
+        Some(years) => parse_year_range(years)?,
+        None => {
+            let year = resolve_now_zoned(&args.now, &tz)?.year();
+            (year, year)
+        }
+    };
+    let new_year = |year: i16| -> Result<jiff::Timestamp> {
+        jiff::civil::date(year, 1, 1)
+            .to_zoned(tz.clone())
+            .map(|z| z.timestamp())
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))
+    };
+    let start = new_year(first)?;
+    let end = new_year(last + 1)?;
+
+    let second = jiff::SignedDuration::from_secs(1);
+    let mut rows = Vec::new();
+    for t in tz.following(start - second) {
+        let at = t.timestamp();
+        if at >= end {
+            break;
+        }
+        if !changes_offset(&tz, &t) {
+            continue;
+        }
+        let before = tz.to_offset_info(at - second);
+        let (from, to) = (before.offset(), t.offset());
+        rows.push(vec![
+            at.strftime("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            signed_hhmm(from.seconds()),
+            signed_hhmm(to.seconds()),
+            format!(
+                "{} -> {}",
+                from.to_datetime(at).strftime("%Y-%m-%d %H:%M"),
+                to.to_datetime(at).strftime("%H:%M")
+            ),
+            format!("{} -> {}", before.abbreviation(), t.abbreviation()),
+        ]);
+    }
+    if rows.is_empty() {
+        let years = if first == last {
+            first.to_string()
+        } else {
+            format!("{first}..{last}")
+        };
+        diag.note(format!(
+            "{} has no offset changes in {years}",
+            tz.iana_name().unwrap_or("UTC")
+        ));
+    }
+    diag.flush()?;
+    print_table(&["utc", "before", "after", "local", "abbr"], &rows)
+}
+
+/// Handle `td tz compare` -- the offset of zone B from zone A in each
+/// window between their transitions, flagging the windows that differ from
+/// the usual difference.
+fn handle_tz_compare(args: TzCompareArgs, diag: &mut Diagnostics) -> Result<()> {
+    let a = lookup_timezone(&args.a, diag)?;
+    let b = lookup_timezone(&args.b, diag)?;
+    diag.flush()?;
+    let now = resolve_now_zoned(&args.now, &jiff::tz::TimeZone::UTC)?;
+    let start = now.timestamp();
+    let end = now
+        .checked_add(jiff::Span::new().months(args.months))
+        .map_err(|e| user_input_error!(InvalidDate, "{}", e))?
+        .timestamp();
+
+    let mut cuts: Vec<jiff::Timestamp> = [&a, &b]
+        .iter()
+        .flat_map(|tz| tz.following(start).take_while(|t| t.timestamp() < end))
+        .map(|t| t.timestamp())
+        .collect();
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    // (from, until, seconds B is ahead of A), merging windows whose
+    // difference does not change.
+    let mut windows: Vec<(jiff::Timestamp, jiff::Timestamp, i32)> = Vec::new();
+    for (from, until) in std::iter::once(start)
+        .chain(cuts.iter().copied())
+        .zip(cuts.iter().copied().chain(std::iter::once(end)))
+    {
+        let diff = b.to_offset(from).seconds() - a.to_offset(from).seconds();
+        match windows.last_mut() {
+            Some(last) if last.2 == diff => last.1 = until,
+            _ => windows.push((from, until, diff)),
+        }
+    }
+
+    let mut spans: Vec<(i32, i64)> = Vec::new();
+    for (from, until, diff) in &windows {
+        let secs = until.as_second() - from.as_second();
+        match spans.iter_mut().find(|(d, _)| d == diff) {
+            Some((_, total)) => *total += secs,
+            None => spans.push((*diff, secs)),
+        }
+    }
+    let usual = spans
+        .iter()
+        .max_by_key(|(_, total)| *total)
+        .map(|(d, _)| *d);
+
+    let stamp = |t: &jiff::Timestamp| t.strftime("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let rows: Vec<Vec<String>> = windows
+        .iter()
+        .map(|(from, until, diff)| {
+            let note = match usual {
+                Some(u) if u != *diff => format!("shifted {}", signed_hhmm(diff - u)),
+                _ => String::new(),
+            };
+            vec![stamp(from), stamp(until), signed_hhmm(*diff), note]
+        })
+        .collect();
+    print_table(&["from", "until", "difference", "note"], &rows)
+}
+
+/// Handle `td tz next-change` -- the next offset change of a zone, how far
+/// away it is and the abbreviations either side of it.
+fn handle_tz_next_change(args: TzNextChangeArgs, diag: &mut Diagnostics) -> Result<()> {
+    let cfg = load_config(diag)?;
+    let tz = match &args.place {
+        Some(place) => lookup_timezone(place, diag)?,
+        None => configured_timezone(&cfg, diag)?,
+    };
+    let now = resolve_now_zoned(&args.now, &tz)?;
+    let Some(next) = tz
+        .following(now.timestamp())
+        .find(|t| changes_offset(&tz, t))
+    else {
+        diag.note(format!(
+            "{} has no upcoming offset changes",
+            tz.iana_name().unwrap_or("UTC")
+        ));
+        return diag.flush();
+    };
+    let at = next.timestamp().to_zoned(tz.clone());
+    let before = tz.to_offset_info(next.timestamp() - jiff::SignedDuration::from_secs(1));
+    let when = Humanizer::from_config(&cfg.humanize)?.describe(&at, &now)?;
+    println!(
+        "{} ({when}, {} -> {})",
+        format_zoned(&at, &args.format)?,
+        before.abbreviation(),
+        next.abbreviation()
+    );
+    Ok(())
+}
+
+/// Seconds east of UTC, or between two offsets, as `+05:30`.
+fn signed_hhmm(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.unsigned_abs() / 60;
+    format!("{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Whether `t` moves the offset of `tz`.  The tz database also records
+/// transitions that only rename the abbreviation or mark the end of its
+/// table, which are of no interest to a clock.
+fn changes_offset(tz: &jiff::tz::TimeZone, t: &jiff::tz::TimeZoneTransition) -> bool {
+    tz.to_offset(t.timestamp() - jiff::SignedDuration::from_secs(1)) != t.offset()
+}
+
+/// Parse `2025` or the inclusive range `2025..2027`.
+fn parse_year_range(s: &str) -> Result<(i16, i16)> {
+    let invalid = || {
+        user_input_error!(
+            InvalidDate,
+            "invalid years '{}'; expected e.g. 2025 or 2025..2027",
+            s
+        )
+    };
+    let year = |y: &str| {
+        y.trim()
+            .parse::<i16>()
+            .ok()
+            .filter(|y| (1..=9998).contains(y))
+    };
+    let (first, last) = match s.split_once("..") {
+        Some((a, b)) => (year(a).ok_or_else(invalid)?, year(b).ok_or_else(invalid)?),
+        None => {
+            let y = year(s).ok_or_else(invalid)?;
+            (y, y)
+        }
+    };
+    if first > last {
+        return Err(invalid());
+    }
+    Ok((first, last))
+}
+
+/// Handle `td tz pick` -- search zones and print the chosen one.
+fn handle_tz_pick(args: TzPickArgs) -> Result<()> {
+    use std::io::Write;
+    use tardis_cli::{config, zones};
+
+    let interactive = io::stdin().is_terminal() && io::stderr().is_terminal();
+    let read_line = |prompt: &str| -> Result<String> {
+        eprint!("{prompt}");
+        io::stderr().flush()?;
+        let mut line = String::new();
+        io::stdin().read_line(&mut line)?;
+        Ok(line.trim().to_string())
+    };
+
+    let query = match args.query {
+        Some(query) => query,
+        None if interactive && !args.list => read_line("zone> ")?,
+        None => String::new(),
+    };
+    let matches = zones::search(&query);
+    if matches.is_empty() {
+        return Err(user_input_error!(
+            UnsupportedTimezone,
+            "no time zone matches '{}'",
+            query
+        ));
+    }
+
+    if args.list {
+        for m in &matches {
+            println!("{m}");
+        }
+        return Ok(());
+    }
+
+    let offered = &matches[..matches.len().min(args.limit.max(1))];
+    let choice = if interactive && offered.len() > 1 {
+        for (i, m) in offered.iter().enumerate() {
+            eprintln!("{:>3}) {m}", i + 1);
+        }
+        let answer = read_line("pick [1]> ")?;
+        let index = if answer.is_empty() {
+            1
+        } else {
+            answer.parse::<usize>().unwrap_or(0)
+        };
+        offered.get(index.wrapping_sub(1)).ok_or_else(|| {
+            user_input_error!(
+                MissingArgument,
+                "expected a number from 1 to {}, got '{}'",
+                offered.len(),
+                answer
+            )
+        })?
+    } else {
+        &offered[0]
+    };
+
+    println!("{}", choice.zone);
+    if args.save {
+        let path = config::set_default_timezone(&choice.zone)?;
+        eprintln!("Saved timezone = \"{}\" to {}", choice.zone, path.display());
+    }
+    Ok(())
+}
+
 /// Handle `td info <date>` -- calendar metadata card.
-fn handle_info(args: InfoArgs) -> Result<()> {
+fn handle_info(args: InfoArgs, diag: &mut Diagnostics) -> Result<()> {
+    if args.env {
+        return handle_info_env(&args);
+    }
     let start_instant = std::time::Instant::now();
-    let tz = resolve_timezone(&args.timezone)?;
+    let tz = resolve_timezone(&args.timezone, diag)?;
     let now = resolve_now_zoned(&args.now, &tz)?;
 
     if args.verbose {
@@ -470,7 +2967,7 @@ fn handle_info(args: InfoArgs) -> Result<()> {
         verbose!("parse", "timezone={}", tz.iana_name().unwrap_or("system"));
     }
 
-    let zoned = parser::parse(&args.input, &now)
+    let zoned = parser::parse(&args.input, &now, parse_options())
         .map_err(|e| user_input_error!(InvalidDateFormat, "{}", e.format_message()))?;
 
     let iwd = zoned.date().iso_week_date();
@@ -515,9 +3012,7 @@ fn handle_info(args: InfoArgs) -> Result<()> {
         return Ok(());
     }
 
-    let use_color = io::stdout().is_terminal() && std::env::var("NO_COLOR").is_err();
-
-    let (bold, cyan, yellow, green, reset) = if use_color {
+    let (bold, cyan, yellow, green, reset) = if term::stdout_color() {
         ("\x1b[1m", "\x1b[36m", "\x1b[33m", "\x1b[32m", "\x1b[0m")
     } else {
         ("", "", "", "", "")
@@ -531,50 +3026,336 @@ fn handle_info(args: InfoArgs) -> Result<()> {
         "No".to_string()
     };
 
-    let mut lines = Vec::new();
-    lines.push(format!("{bold}{cyan}  Date{reset}         {date_str}"));
-    lines.push(format!("{bold}{cyan}  Time{reset}         {time_str}"));
-    lines.push(format!(
-        "{bold}{cyan}  Week{reset}         W{:02}, {}",
-        iwd.week(),
-        iwd.year()
-    ));
-    lines.push(format!(
-        "{bold}{cyan}  Quarter{reset}      {green}Q{quarter}{reset}"
-    ));
-    lines.push(format!(
-        "{bold}{cyan}  Day of Year{reset}  {day_of_year}/{days_in_year}"
-    ));
-    lines.push(format!("{bold}{cyan}  Leap Year{reset}    {leap_str}"));
-    lines.push(format!("{bold}{cyan}  Unix Epoch{reset}   {epoch_secs}"));
-    lines.push(format!("{bold}{cyan}  Julian Day{reset}   {jdn:.2}"));
+    let fields = [
+        ("Date", date_str),
+        ("Time", time_str),
+        ("Week", format!("W{:02}, {}", iwd.week(), iwd.year())),
+        ("Quarter", format!("{green}Q{quarter}{reset}")),
+        ("Day of Year", format!("{day_of_year}/{days_in_year}")),
+        ("Leap Year", leap_str),
+        ("Unix Epoch", epoch_secs.to_string()),
+        ("Julian Day", format!("{jdn:.2}")),
+    ];
+    let lines: Vec<String> = fields
+        .iter()
+        .map(|(label, value)| {
+            if term::plain() {
+                format!("{label}\t{value}")
+            } else {
+                format!("{bold}{cyan}  {label:<11}{reset}  {value}")
+            }
+        })
+        .collect();
 
     let output = lines.join("\n");
     output_value(&output, args.no_newline);
     Ok(())
 }
 
-fn handle_config(action: ConfigAction) -> Result<()> {
-    use tardis_cli::config;
+/// Handle `td info --env` -- what a bug report needs to know about this td.
+fn handle_info_env(args: &InfoArgs) -> Result<()> {
+    let zoneinfo = std::env::var_os("TZDIR")
+        .map_or_else(|| "/usr/share/zoneinfo".into(), std::path::PathBuf::from);
+    let tzdata = std::fs::read_to_string(zoneinfo.join("tzdata.zi"))
+        .ok()
+        .and_then(|zi| {
+            zi.lines()
+                .find_map(|l| l.strip_prefix("# version "))
+                .map(|v| format!("{} ({})", v.trim(), zoneinfo.display()))
+        })
+        .or_else(|| {
+            std::fs::read_to_string(zoneinfo.join("+VERSION"))
+                .ok()
+                .map(|v| format!("{} ({})", v.trim(), zoneinfo.display()))
+        })
+        .unwrap_or_else(|| {
+            if zoneinfo.is_dir() {
+                format!("unknown version ({})", zoneinfo.display())
+            } else {
+                "bundled".to_string()
+            }
+        });
+    let system = jiff::tz::TimeZone::system();
+    let timezone = match std::env::var("TZ") {
+        Ok(tz) if !tz.is_empty() => {
+            format!("{} (TZ={tz})", system.iana_name().unwrap_or("unknown"))
+        }
+        _ => system.iana_name().unwrap_or("unknown").to_string(),
+    };
+    let locale = ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        .unwrap_or_else(|| "unset".to_string());
+    let config = tardis_cli::config::config_path()
+        .map_or_else(|_| "unknown".to_string(), |p| p.display().to_string());
+
+    let fields = [
+        ("version", "Version", env!("CARGO_PKG_VERSION").to_string()),
+        (
+            "parser",
+            "Parser",
+            format!("td built-in, jiff {}", env!("TD_JIFF_VERSION")),
+        ),
+        ("tzdata", "Tz Database", tzdata),
+        ("config", "Config", config),
+        ("timezone", "Time Zone", timezone),
+        ("locale", "Locale", locale),
+        (
+            "platform",
+            "Platform",
+            format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+        ),
+    ];
+
+    if args.json {
+        let json: serde_json::Map<String, serde_json::Value> = fields
+            .into_iter()
+            .map(|(key, _, value)| (key.to_string(), value.into()))
+            .collect();
+        emit_json(&json.into(), args.no_newline);
+        return Ok(());
+    }
+
+    let (bold, cyan, reset) = if term::stdout_color() {
+        ("\x1b[1m", "\x1b[36m", "\x1b[0m")
+    } else {
+        ("", "", "")
+    };
+    let lines: Vec<String> = fields
+        .iter()
+        .map(|(_, label, value)| {
+            if term::plain() {
+                format!("{label}\t{value}")
+            } else {
+                format!("{bold}{cyan}  {label:<11}{reset}  {value}")
+            }
+        })
+        .collect();
+    output_value(&lines.join("\n"), args.no_newline);
+    Ok(())
+}
+
+/// Handle `td prompt` -- exactly one line of plain output, whatever happens.
+fn handle_prompt(args: PromptArgs) {
+    let line = prompt_line(&args).unwrap_or_else(|_| args.fallback.clone());
+    println!("{}", line.replace(['\r', '\n'], " "));
+}
+
+/// Render the prompt line without creating, migrating or warning about the
+/// config file.
+fn prompt_line(args: &PromptArgs) -> Result<String> {
+    use tardis_cli::cache;
+
+    let (cfg, contents) = match Config::load_readonly()? {
+        Some((cfg, contents)) => (Some(cfg), contents),
+        None => (None, String::new()),
+    };
+
+    let tz_name = args
+        .timezone
+        .clone()
+        .or_else(|| cfg.as_ref().map(|c| c.timezone.clone()))
+        .filter(|tz| !tz.trim().is_empty());
+    let tz = match tz_name {
+        Some(name) => core::lookup_timezone(name.trim(), &mut Diagnostics::default())?,
+        None if args.cache_tz => {
+            let cache = cache::Cache::open(&cache::fingerprint(&contents)).ok();
+            let cached = cache
+                .as_ref()
+                .and_then(|c| c.get(PROMPT_TZ_KEY))
+                .and_then(|name| jiff::tz::TimeZone::get(&name).ok());
+            match cached {
+                Some(tz) => tz,
+                None => {
+                    let tz = jiff::tz::TimeZone::system();
+                    if let (Some(c), Some(name)) = (&cache, tz.iana_name()) {
+                        let _ = c.put(PROMPT_TZ_KEY, name);
+                    }
+                    tz
+                }
+            }
+        }
+        None => jiff::tz::TimeZone::system(),
+    };
+
+    let format = args
+        .format
+        .clone()
+        .or_else(|| cfg.as_ref().map(|c| c.format.clone()))
+        .unwrap_or_else(|| "%H:%M".to_string());
+    let now = resolve_now(&args.now)?.map(|ts| ts.to_zoned(tz.clone()));
+    let app = App::new(args.input.clone(), format, tz, now);
+    let empty = core::Presets::new();
+    let presets = cfg.as_ref().map_or(&empty, Config::presets);
+    Ok(core::process(&app, presets)?.formatted)
+}
+
+/// Cache key for the system time-zone remembered by `td prompt --cache-tz`.
+const PROMPT_TZ_KEY: &str = "prompt-tz";
+
+/// Handle `td lint-format <fmt>` -- explain a format and flag likely mistakes.
+fn handle_lint_format(args: LintFormatArgs, diag: &mut Diagnostics) -> Result<()> {
+    use tardis_cli::lint::{self, Piece};
+
+    let cfg = load_config(diag)?;
+    let presets = cfg.presets();
+    let targets: Vec<(Option<String>, String)> = match args.format {
+        Some(name) => {
+            let fmt = presets
+                .get(&name)
+                .map(|p| p.format.clone())
+                .or_else(|| core::builtin_format(&name).map(str::to_string))
+                .unwrap_or(name);
+            vec![(None, fmt)]
+        }
+        None => {
+            if presets.is_empty() {
+                println!("No presets defined. Add them to [formats] in your config file.");
+                return Ok(());
+            }
+            cfg.preset_names()
+                .into_iter()
+                .map(|name| (Some(name.to_string()), presets[name].format.clone()))
+                .collect()
+        }
+    };
+
+    let mut reports = Vec::new();
+    for (name, fmt) in &targets {
+        let report = lint::lint(fmt).map_err(|e| match name {
+            Some(name) => user_input_error!(UnsupportedFormat, "preset '{}': {}", name, e),
+            None => e,
+        })?;
+        for w in &report.warnings {
+            match name {
+                Some(name) => diag.warn(format!("preset '{name}': {w}")),
+                None => diag.warn(w.clone()),
+            }
+        }
+        reports.push((name, fmt, report));
+    }
+
+    if args.json {
+        let mut items: Vec<_> = reports
+            .iter()
+            .map(|(name, fmt, report)| {
+                let pieces: Vec<_> = report
+                    .pieces
+                    .iter()
+                    .map(|p| match p {
+                        Piece::Specifier { spec, meaning } => {
+                            serde_json::json!({ "specifier": spec, "meaning": meaning })
+                        }
+                        Piece::Literal(text) => serde_json::json!({ "literal": text }),
+                        _ => serde_json::Value::Null,
+                    })
+                    .collect();
+                serde_json::json!({
+                    "name": name,
+                    "format": fmt,
+                    "pieces": pieces,
+                    "warnings": report.warnings,
+                })
+            })
+            .collect();
+        diag.flush()?;
+        let json = if args.presets {
+            serde_json::Value::Array(items)
+        } else {
+            items.swap_remove(0)
+        };
+        emit_json(&json, false);
+        return Ok(());
+    }
+
+    let mut lines = Vec::new();
+    for (name, fmt, report) in &reports {
+        if let Some(name) = name {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("{name} = \"{fmt}\""));
+        }
+        for piece in &report.pieces {
+            match piece {
+                Piece::Specifier { spec, meaning } if term::plain() => {
+                    lines.push(format!("{spec}\t{meaning}"));
+                }
+                Piece::Literal(text) if term::plain() => lines.push(format!("{text:?}\tliteral")),
+                Piece::Specifier { spec, meaning } => lines.push(format!("{spec:<6} {meaning}")),
+                Piece::Literal(text) => lines.push(format!("{:<6} literal", format!("{text:?}"))),
+                _ => {}
+            }
+        }
+    }
+    diag.flush()?;
+    if !lines.is_empty() {
+        println!("{}", lines.join("\n"));
+    }
+    Ok(())
+}
+
+fn handle_config(action: ConfigAction, diag: &mut Diagnostics) -> Result<()> {
+    use tardis_cli::config::{self, Severity};
 
     match action {
         ConfigAction::Path => {
             println!("{}", config::config_path()?.display());
         }
         ConfigAction::Show => {
-            let cfg = Config::load()?;
+            let cfg = load_config(diag)?;
             println!("format   = \"{}\"", cfg.format);
             println!("timezone = \"{}\"", cfg.timezone);
-            if let Some(fmts) = &cfg.formats {
+            if let Some(group) = &cfg.preset_group {
+                println!("preset_group = \"{group}\"");
+            }
+            let defs = cfg.preset_defs();
+            if !defs.is_empty() {
                 println!("\n[formats]");
-                for (name, fmt) in fmts {
-                    println!("{name:<10} = \"{fmt}\"");
+                for name in cfg.preset_names() {
+                    let def = &defs[name];
+                    if def.timezone.is_none() && def.locale.is_none() {
+                        println!("{name:<10} = \"{}\"", def.format);
+                        continue;
+                    }
+                    let mut fields = vec![format!("format = \"{}\"", def.format)];
+                    if let Some(tz) = &def.timezone {
+                        fields.push(format!("timezone = \"{tz}\""));
+                    }
+                    if let Some(locale) = &def.locale {
+                        fields.push(format!("locale = \"{locale}\""));
+                    }
+                    println!("{name:<10} = {{ {} }}", fields.join(", "));
+                }
+            }
+            if let Some(hours) = &cfg.meet.hours {
+                println!("\n[meet]\nhours = \"{hours}\"");
+            }
+            let humanize = &cfg.humanize;
+            if humanize != &Default::default() {
+                println!("\n[humanize]");
+                if let Some(n) = humanize.granularity {
+                    println!("granularity = {n}");
+                }
+                if let Some(within) = &humanize.now_within {
+                    println!("now_within = \"{within}\"");
+                }
+                if let Some(after) = &humanize.named_day_after {
+                    println!("named_day_after = \"{after}\"");
+                }
+            }
+            if !cfg.worldclock.zones.is_empty() || cfg.worldclock.format.is_some() {
+                println!("\n[worldclock]");
+                let zones: toml_edit::Array = cfg.worldclock.zones.iter().collect();
+                println!("zones = {}", toml_edit::value(zones));
+                if let Some(fmt) = &cfg.worldclock.format {
+                    println!("format = {}", toml_edit::value(fmt));
                 }
             }
         }
         ConfigAction::Edit => {
             let path = config::config_path()?;
-            let _ = Config::load()?;
+            let _ = load_config(diag)?;
             let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
             std::process::Command::new(&editor)
                 .arg(&path)
@@ -584,49 +3365,240 @@ fn handle_config(action: ConfigAction) -> Result<()> {
                 })?;
         }
         ConfigAction::Presets => {
-            let cfg = Config::load()?;
+            let cfg = load_config(diag)?;
             let presets = cfg.presets();
             if presets.is_empty() {
                 println!("No presets defined. Add them to [formats] in your config file.");
                 println!("Config: {}", config::config_path()?.display());
+            } else if term::plain() {
+                println!("NAME\tFORMAT");
+                for name in cfg.preset_names() {
+                    println!("{name}\t{}", presets[name].format);
+                }
             } else {
                 println!("{:<12} FORMAT", "NAME");
                 println!("{:<12} ------", "----");
-                for p in &presets {
-                    println!("{:<12} {}", p.name, p.format);
+                for name in cfg.preset_names() {
+                    println!("{:<12} {}", name, presets[name].format);
+                }
+            }
+        }
+        ConfigAction::Schema => print!("{}", config::SCHEMA),
+        ConfigAction::Get { key } => println!("{}", config::get_value(&key)?),
+        ConfigAction::Set { key, value } => {
+            let path = config::set_value(&key, &value)?;
+            println!("Set {key} in {}", path.display());
+        }
+        ConfigAction::Validate(args) => {
+            let path = match args.file {
+                Some(path) => path,
+                None => config::config_path()?,
+            };
+            let findings = config::validate(&path)?;
+            let count = |severity| findings.iter().filter(|f| f.severity == severity).count();
+            let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+            let failed = errors > 0 || (warnings > 0 && diag.denies_warnings());
+            let code = if failed {
+                ExitCodes::current().config
+            } else {
+                0
+            };
+            if args.ci {
+                let items = |severity| {
+                    findings
+                        .iter()
+                        .filter(|f| f.severity == severity)
+                        .map(|f| {
+                            serde_json::json!({
+                                "key": f.key,
+                                "line": f.location.map(|(line, _)| line),
+                                "column": f.location.map(|(_, column)| column),
+                                "message": f.message,
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                };
+                emit_json(
+                    &serde_json::json!({
+                        "path": path.display().to_string(),
+                        "valid": errors == 0,
+                        "errors": items(Severity::Error),
+                        "warnings": items(Severity::Warning),
+                        "exit_code": code,
+                    }),
+                    false,
+                );
+            } else {
+                for f in &findings {
+                    let at = match f.location {
+                        Some((line, column)) => format!("{}:{line}:{column}", path.display()),
+                        None => path.display().to_string(),
+                    };
+                    let label = if f.severity == Severity::Error {
+                        "error"
+                    } else {
+                        "warning"
+                    };
+                    match &f.key {
+                        Some(key) => println!("{at}: {label}: {key}: {}", f.message),
+                        None => println!("{at}: {label}: {}", f.message),
+                    }
+                }
+                if findings.is_empty() {
+                    println!("{} is valid", path.display());
+                } else {
+                    let plural = |n: usize, word: &str| match n {
+                        1 => format!("1 {word}"),
+                        n => format!("{n} {word}s"),
+                    };
+                    println!(
+                        "{}, {}",
+                        plural(errors, "error"),
+                        plural(warnings, "warning")
+                    );
                 }
             }
+            if failed {
+                io::stdout().flush()?;
+                std::process::exit(code);
+            }
+        }
+        ConfigAction::Migrate(args) => {
+            let Some(plan) = config::plan_migration()? else {
+                let path = config::config_path()?;
+                if path.exists() {
+                    println!(
+                        "{} is up to date (version {})",
+                        path.display(),
+                        tardis_cli::migrate::CURRENT_VERSION
+                    );
+                } else {
+                    println!("{} does not exist; nothing to migrate", path.display());
+                }
+                return Ok(());
+            };
+            let changes = plan.report.changes.join("; ");
+            if args.dry_run {
+                print!("{}", plan.diff());
+                eprintln!("would upgrade ({changes}); nothing was written");
+            } else {
+                let backup = plan.apply(args.backup_dir.as_deref())?;
+                println!(
+                    "Upgraded {} from version {} to {} ({changes}); backup saved to {}",
+                    plan.path.display(),
+                    plan.report.from,
+                    plan.report.to,
+                    backup.display()
+                );
+            }
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
+fn handle_cache(action: CacheAction) -> Result<()> {
+    use tardis_cli::cache;
+
+    match action {
+        CacheAction::Path => {
+            println!("{}", cache::cache_dir()?.display());
+        }
+        CacheAction::Clear => {
+            let dir = cache::cache_dir()?;
+            match cache::clear()? {
+                0 => println!("Cache is already empty ({})", dir.display()),
+                1 => println!("Removed 1 cache entry from {}", dir.display()),
+                n => println!("Removed {n} cache entries from {}", dir.display()),
+            }
         }
         _ => unreachable!(),
     }
     Ok(())
 }
 
-fn handle_completions(shell: ShellType) {
-    use clap::CommandFactory;
-    use clap_complete::{Shell, generate};
+fn handle_history(action: HistoryAction, paging: bool, diag: &mut Diagnostics) -> Result<()> {
+    use tardis_cli::history;
 
-    let shell = match shell {
-        ShellType::Bash => Shell::Bash,
-        ShellType::Zsh => Shell::Zsh,
-        ShellType::Fish => Shell::Fish,
-        ShellType::Elvish => Shell::Elvish,
-        ShellType::Powershell => Shell::PowerShell,
+    match action {
+        HistoryAction::Show => {
+            let text: String = history::entries()?
+                .iter()
+                .map(|entry| format!("{entry}\n"))
+                .collect();
+            print_listing(&text, paging, diag)?;
+        }
+        HistoryAction::Path => {
+            println!("{}", history::path()?.display());
+        }
+        HistoryAction::Clear => {
+            let path = history::path()?;
+            match history::clear()? {
+                0 => println!("History is already empty ({})", path.display()),
+                1 => println!("Removed 1 history entry from {}", path.display()),
+                n => println!("Removed {n} history entries from {}", path.display()),
+            }
+        }
         _ => unreachable!(),
+    }
+    Ok(())
+}
+
+fn handle_completions(args: CompletionsArgs) -> Result<()> {
+    use std::io::Write;
+    use tardis_cli::completions::{self, Target};
+
+    let Some(CompletionsAction::Install(install)) = args.action else {
+        let Some(shell) = args.shell else {
+            unreachable!("clap requires a shell without a subcommand")
+        };
+        io::stdout().write_all(&completions::script(shell)?)?;
+        return Ok(());
+    };
+
+    let shell = match install.shell {
+        Some(shell) => shell,
+        None => std::env::var("SHELL")
+            .ok()
+            .and_then(|var| completions::detect(&var))
+            .ok_or_else(|| {
+                user_input_error!(
+                    MissingArgument,
+                    "could not detect your shell from $SHELL; name it, e.g. td completions install zsh"
+                )
+            })?,
     };
+    let name = clap::ValueEnum::to_possible_value(&shell)
+        .map_or_else(String::new, |v| v.get_name().to_string());
 
-    let mut cmd = Cli::command();
-    generate(shell, &mut cmd, "td", &mut io::stdout());
+    match completions::target(shell)? {
+        Target::Snippet { rc, line } => {
+            println!("Add this line to {rc} to enable {name} completions:\n\n    {line}");
+        }
+        Target::File(path) if install.dry_run => {
+            println!("Would install {name} completions to {}", path.display());
+        }
+        Target::File(path) => {
+            if completions::install(&path, &completions::script(shell)?)? {
+                println!(
+                    "Installed {name} completions to {}; open a new shell to use them",
+                    path.display()
+                );
+            } else {
+                println!("{name} completions are up to date in {}", path.display());
+            }
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used, clippy::expect_used)]
     use pretty_assertions::assert_eq;
-    use std::collections::HashMap;
 
     use jiff::{Zoned, tz::TimeZone};
-    use tardis_cli::core::{self, App, Preset};
+    use tardis_cli::core::{self, App, PresetSpec, Presets};
 
     fn utc() -> TimeZone {
         TimeZone::get("UTC").unwrap()
@@ -648,14 +3620,14 @@ mod tests {
         App::new(date.to_string(), fmt.to_string(), tz, now)
     }
 
-    fn run(app: &App, presets: &[Preset]) -> String {
+    fn run(app: &App, presets: &Presets) -> String {
         core::process(app, presets).unwrap().formatted
     }
 
     #[test]
     fn happy_path_basic() {
         let a = app("2025-01-01 12:00", "%Y", utc(), now!());
-        let out = run(&a, &[]);
+        let out = run(&a, &Presets::new());
         assert_eq!(out, "2025");
     }
 
@@ -668,12 +3640,8 @@ mod tests {
             now!("2030-12-31T00:00:00Z"),
         );
 
-        let mut map = HashMap::new();
-        map.insert("br".to_string(), "%d/%m/%Y".to_string());
-        let presets: Vec<_> = map
-            .iter()
-            .map(|(n, f)| Preset::new(n.clone(), f.clone()))
-            .collect();
+        let mut presets = Presets::new();
+        presets.insert("br".to_string(), PresetSpec::new("%d/%m/%Y".to_string()));
 
         let out = run(&a, &presets);
         assert_eq!(out, "31/12/2030");
@@ -682,7 +3650,7 @@ mod tests {
     #[test]
     fn invalid_date_expression() {
         let a = app("$$$", "%Y", utc(), now!());
-        let res = core::process(&a, &[]);
+        let res = core::process(&a, &Presets::new());
         assert!(res.is_err());
     }
 }
@@ -0,0 +1,77 @@
+//! Man pages bundled into the binary, for `td --print-man-dir`.
+//!
+//! `build.rs` embeds every page from `docs/man` as [`PAGES`].  [`install`]
+//! writes them under `$XDG_DATA_HOME/tardis/man` (or the OS data
+//! directory), laid out as a `MANPATH` entry, so `man td-diff` works for
+//! a binary installed without its pages.  Without the `man-pages` feature
+//! nothing is embedded and [`install`] fails.
+
+use std::{env, fs, path::PathBuf};
+
+use crate::{Result, system_error};
+
+include!(concat!(env!("OUT_DIR"), "/man_pages.rs"));
+
+const APP_DIR: &str = "tardis";
+
+/// Resolve the directory the pages are installed under.
+#[must_use = "man_dir returns a PathBuf that should not be discarded"]
+pub fn man_dir() -> Result<PathBuf> {
+    let base_dir = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(dirs::data_dir)
+        .ok_or_else(|| {
+            system_error!(
+                Config,
+                "Could not locate data directory; set $XDG_DATA_HOME or ensure the OS default exists."
+            )
+        })?;
+
+    Ok(base_dir.join(APP_DIR).join("man"))
+}
+
+/// Write [`PAGES`] into `man1` under [`man_dir`], skipping pages that are
+/// already up to date, and return the directory.
+pub fn install() -> Result<PathBuf> {
+    if !cfg!(feature = "man-pages") {
+        return Err(system_error!(
+            Config,
+            "this td was built without man pages (the `man-pages` feature)"
+        ));
+    }
+    let dir = man_dir()?;
+    let section = dir.join("man1");
+    fs::create_dir_all(&section)?;
+    for (name, page) in PAGES {
+        let path = section.join(name);
+        if fs::read_to_string(&path).is_ok_and(|current| current == *page) {
+            continue;
+        }
+        fs::write(path, page)?;
+    }
+    Ok(dir)
+}
+
+#[cfg(all(test, feature = "man-pages"))]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+
+    #[test]
+    fn every_subcommand_has_a_page() {
+        use clap::CommandFactory;
+
+        let cmd = crate::cli::Cli::command();
+        let names: Vec<String> = cmd
+            .get_subcommands()
+            .map(|sub| format!("td-{}.1", sub.get_name()))
+            .chain(["td.1".to_string()])
+            .collect();
+        for name in names {
+            assert!(
+                PAGES.iter().any(|(page, _)| *page == name),
+                "{name} missing"
+            );
+        }
+    }
+}
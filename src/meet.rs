@@ -0,0 +1,247 @@
+//! Overlapping working hours for **TARDIS**.
+//!
+//! [`windows`] takes a day, as seen from the first participant's zone, and
+//! returns the spans of it during which every participant is inside their
+//! working hours.  Each participant's [`Schedule`] is read in their own
+//! zone; a shift whose end is not after its start runs past midnight.
+
+use std::str::FromStr;
+
+use jiff::{
+    Timestamp,
+    civil::{Date, Time},
+    tz::TimeZone,
+};
+
+use crate::{Error, Result, hours::Schedule, user_input_error};
+
+type JiffResult<T> = std::result::Result<T, jiff::Error>;
+
+/// Daily working hours, e.g. `09:00-17:00`.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hours {
+    pub start: Time,
+    pub end: Time,
+}
+
+impl Default for Hours {
+    fn default() -> Self {
+        Self {
+            start: Time::constant(9, 0, 0, 0),
+            end: Time::constant(17, 0, 0, 0),
+        }
+    }
+}
+
+impl FromStr for Hours {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            user_input_error!(
+                InvalidDate,
+                "invalid working hours '{}' (expected HH:MM-HH:MM)",
+                s
+            )
+        };
+        let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+        let parse = |t: &str| match t.trim() {
+            "24:00" => Ok(Time::midnight()),
+            t => t.parse::<Time>().map_err(|_| invalid()),
+        };
+        Ok(Self {
+            start: parse(start)?,
+            end: parse(end)?,
+        })
+    }
+}
+
+/// Someone taking part in the meeting.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Participant {
+    /// Column label, usually the zone name as given.
+    pub label: String,
+    pub zone: TimeZone,
+    /// Working hours, in `zone`.
+    pub schedule: Schedule,
+}
+
+/// A span during which everyone is working.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+/// Common working windows on `day`, which is a date in the first
+/// participant's zone.
+pub fn windows(day: Date, participants: &[Participant]) -> Result<Vec<Window>> {
+    common_windows(day, participants).map_err(|e| user_input_error!(InvalidDate, "{}", e))
+}
+
+fn common_windows(day: Date, participants: &[Participant]) -> JiffResult<Vec<Window>> {
+    let Some(first) = participants.first() else {
+        return Ok(Vec::new());
+    };
+    let bounds = Window {
+        start: day_start(day, &first.zone)?,
+        end: day_start(day.tomorrow()?, &first.zone)?,
+    };
+
+    let mut common = vec![bounds];
+    for p in participants {
+        common = intersect(&common, &shifts(p, bounds)?);
+    }
+    Ok(common)
+}
+
+fn day_start(day: Date, zone: &TimeZone) -> JiffResult<Timestamp> {
+    Ok(day.to_zoned(zone.clone())?.timestamp())
+}
+
+/// `p`'s shifts that touch `bounds`, clipped to it.
+fn shifts(p: &Participant, bounds: Window) -> JiffResult<Vec<Window>> {
+    let local = bounds.start.to_zoned(p.zone.clone()).date();
+    let mut out = Vec::new();
+    for offset in -2..=2 {
+        let date = local.checked_add(jiff::Span::new().days(offset))?;
+        for hours in p.schedule.shifts_on(date) {
+            let end_date = if hours.end > hours.start {
+                date
+            } else {
+                date.tomorrow()?
+            };
+            let start = date.to_datetime(hours.start).to_zoned(p.zone.clone())?;
+            let end = end_date.to_datetime(hours.end).to_zoned(p.zone.clone())?;
+            let shift = Window {
+                start: start.timestamp().max(bounds.start),
+                end: end.timestamp().min(bounds.end),
+            };
+            if shift.start < shift.end {
+                out.push(shift);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Intersection of two sorted lists of disjoint windows.
+fn intersect(a: &[Window], b: &[Window]) -> Vec<Window> {
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let start = a[i].start.max(b[j].start);
+        let end = a[i].end.min(b[j].end);
+        if start < end {
+            out.push(Window { start, end });
+        }
+        if a[i].end < b[j].end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use jiff::civil::{Weekday, date};
+    use pretty_assertions::assert_eq;
+
+    const MON_FRI: [Weekday; 5] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+    ];
+
+    fn who(zone: &str, hours: &str) -> Participant {
+        Participant {
+            label: zone.into(),
+            zone: TimeZone::get(zone).unwrap(),
+            schedule: Schedule::weekly(hours.parse().unwrap(), &MON_FRI),
+        }
+    }
+
+    fn utc(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_hours() {
+        let hours: Hours = "08:30-24:00".parse().unwrap();
+        assert_eq!(hours.start, Time::constant(8, 30, 0, 0));
+        assert_eq!(hours.end, Time::midnight());
+        assert!("9-5".parse::<Hours>().is_err());
+        assert!("09:00".parse::<Hours>().is_err());
+    }
+
+    #[test]
+    fn london_and_new_york_overlap_in_the_afternoon() {
+        let people = [
+            who("Europe/London", "09:00-17:00"),
+            who("America/New_York", "09:00-17:00"),
+        ];
+        assert_eq!(
+            windows(date(2025, 1, 15), &people).unwrap(),
+            [Window {
+                start: utc("2025-01-15T14:00:00Z"),
+                end: utc("2025-01-15T17:00:00Z"),
+            }]
+        );
+    }
+
+    #[test]
+    fn shift_from_the_previous_local_day_counts() {
+        let people = [
+            who("Asia/Tokyo", "09:00-17:00"),
+            who("America/Los_Angeles", "09:00-17:00"),
+        ];
+        assert_eq!(
+            windows(date(2025, 1, 15), &people).unwrap(),
+            [Window {
+                start: utc("2025-01-15T00:00:00Z"),
+                end: utc("2025-01-15T01:00:00Z"),
+            }]
+        );
+    }
+
+    #[test]
+    fn overnight_hours_wrap_midnight() {
+        let people = [
+            who("UTC", "00:00-24:00"),
+            who("Europe/London", "22:00-02:00"),
+        ];
+        let got = windows(date(2025, 1, 15), &people).unwrap();
+        assert_eq!(got.len(), 2);
+        assert_eq!(got[0].end, utc("2025-01-15T02:00:00Z"));
+        assert_eq!(got[1].start, utc("2025-01-15T22:00:00Z"));
+    }
+
+    #[test]
+    fn days_off_have_no_windows() {
+        let people = [who("UTC", "09:00-17:00")];
+        assert!(windows(date(2025, 1, 18), &people).unwrap().is_empty());
+
+        let saturday = Participant {
+            label: "UTC".into(),
+            zone: TimeZone::UTC,
+            schedule: Schedule::weekly("10:00-12:00".parse().unwrap(), &[Weekday::Saturday]),
+        };
+        assert_eq!(
+            windows(date(2025, 1, 18), std::slice::from_ref(&saturday)).unwrap(),
+            [Window {
+                start: utc("2025-01-18T10:00:00Z"),
+                end: utc("2025-01-18T12:00:00Z"),
+            }]
+        );
+        assert!(windows(date(2025, 1, 15), &[saturday]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+//! Schema versioning and migration for `config.toml`.
+//!
+//! Every layout change bumps [`CURRENT_VERSION`] and appends a step to
+//! [`MIGRATIONS`].  On load, a file with an older `version` key (or none at
+//! all, which counts as version 0) is upgraded one step at a time.  Edits go
+//! through `toml_edit` so comments and key order survive the rewrite.
+
+use toml_edit::{DocumentMut, value};
+
+use crate::{Result, system_error};
+
+/// Schema version written by this release.
+pub const CURRENT_VERSION: i64 = 1;
+
+/// A single upgrade step from `from` to `from + 1`.
+struct Migration {
+    from: i64,
+    /// Human-readable summary of what the step changes.
+    describe: &'static str,
+    apply: fn(&mut DocumentMut),
+}
+
+/// Upgrade steps, in order.
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    describe: "added the `version` key",
+    apply: |_| {},
+}];
+
+/// What a migration run changed.
+#[must_use]
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Version found in the file (0 when the key was missing).
+    pub from: i64,
+    /// Version after migration.
+    pub to: i64,
+    /// One entry per applied step.
+    pub changes: Vec<String>,
+}
+
+/// Read the `version` key, treating a missing key as version 0.
+pub fn version_of(doc: &DocumentMut) -> Result<i64> {
+    match doc.get("version") {
+        None => Ok(0),
+        Some(item) => item
+            .as_integer()
+            .ok_or_else(|| system_error!(Config, "`version` must be an integer")),
+    }
+}
+
+/// Upgrade `doc` in place to [`CURRENT_VERSION`].
+///
+/// Returns `None` when the document is already current, and an error when
+/// it was written by a newer `td`.
+pub fn migrate(doc: &mut DocumentMut) -> Result<Option<MigrationReport>> {
+    migrate_with(doc, MIGRATIONS, CURRENT_VERSION)
+}
+
+fn migrate_with(
+    doc: &mut DocumentMut,
+    steps: &[Migration],
+    target: i64,
+) -> Result<Option<MigrationReport>> {
+    let from = version_of(doc)?;
+    if from > target {
+        return Err(system_error!(
+            Config,
+            "config version {} is newer than this td supports (max {}); upgrade td",
+            from,
+            target
+        ));
+    }
+    if from == target {
+        return Ok(None);
+    }
+
+    let mut changes = Vec::new();
+    let mut version = from;
+    while version < target {
+        let step = steps
+            .iter()
+            .find(|m| m.from == version)
+            .ok_or_else(|| system_error!(Config, "no migration from config version {}", version))?;
+        (step.apply)(doc);
+        changes.push(format!(
+            "v{} -> v{}: {}",
+            version,
+            version + 1,
+            step.describe
+        ));
+        version += 1;
+    }
+
+    doc.insert("version", value(target));
+    Ok(Some(MigrationReport {
+        from,
+        to: target,
+        changes,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn doc(src: &str) -> DocumentMut {
+        src.parse().unwrap()
+    }
+
+    #[test]
+    fn missing_version_is_stamped() {
+        let mut d = doc("# keep me\nformat = \"%F\"\n\n[formats]\nbr = \"%d/%m/%Y\"\n");
+        let report = migrate(&mut d).unwrap().unwrap();
+        assert_eq!(report.from, 0);
+        assert_eq!(report.to, CURRENT_VERSION);
+        assert_eq!(report.changes, ["v0 -> v1: added the `version` key"]);
+
+        let out = d.to_string();
+        assert!(out.starts_with("# keep me\nformat = \"%F\"\nversion = 1\n"));
+        assert!(out.contains("[formats]\nbr = \"%d/%m/%Y\""));
+    }
+
+    #[test]
+    fn current_version_is_untouched() {
+        let mut d = doc("version = 1\nformat = \"%F\"\n");
+        assert_eq!(migrate(&mut d).unwrap(), None);
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let mut d = doc("version = 99\n");
+        assert!(migrate(&mut d).is_err());
+    }
+
+    #[test]
+    fn non_integer_version_is_rejected() {
+        let mut d = doc("version = \"one\"\n");
+        assert!(migrate(&mut d).is_err());
+    }
+
+    #[test]
+    fn steps_can_rename_keys_and_move_tables() {
+        let steps = [
+            Migration {
+                from: 0,
+                describe: "stamp",
+                apply: |_| {},
+            },
+            Migration {
+                from: 1,
+                describe: "renamed `tz` to `timezone`, moved [presets] to [formats]",
+                apply: |doc| {
+                    if let Some(tz) = doc.remove("tz") {
+                        doc.insert("timezone", tz);
+                    }
+                    if let Some(presets) = doc.remove("presets") {
+                        doc.insert("formats", presets);
+                    }
+                },
+            },
+        ];
+        let mut d = doc("version = 1\ntz = \"UTC\"\n\n[presets]\nbr = \"%d/%m/%Y\"\n");
+        let report = migrate_with(&mut d, &steps, 2).unwrap().unwrap();
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(d["version"].as_integer(), Some(2));
+        assert_eq!(d["timezone"].as_str(), Some("UTC"));
+        assert_eq!(d["formats"]["br"].as_str(), Some("%d/%m/%Y"));
+        assert!(d.get("presets").is_none());
+    }
+}
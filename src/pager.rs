@@ -0,0 +1,92 @@
+//! Paging long listings, the way git does.
+//!
+//! [`page`] pipes text through `$TARDIS_PAGER`, else `$PAGER`, else
+//! `less`, when stdout is a terminal.  `LESS=FRX` is set unless the user set
+//! `LESS`, so a listing that fits on one screen is printed and left on it.
+//! When the pager cannot be started the text is printed directly.
+
+use std::{
+    env,
+    io::{self, ErrorKind, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+use crate::{Result, exec};
+
+const DEFAULT_PAGER: &str = "less";
+
+/// Print `text`, through the pager when `enabled` and stdout is a terminal.
+pub fn page(text: &str, enabled: bool) -> Result<()> {
+    let argv = if enabled && io::stdout().is_terminal() {
+        pager_command()
+    } else {
+        None
+    };
+    let Some(argv) = argv else {
+        return print(text);
+    };
+
+    let mut command = Command::new(&argv[0]);
+    command.args(&argv[1..]).stdin(Stdio::piped());
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+    if env::var_os("LV").is_none() {
+        command.env("LV", "-c");
+    }
+    let Ok(mut child) = command.spawn() else {
+        return print(text);
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        match stdin.write_all(text.as_bytes()) {
+            Err(e) if e.kind() != ErrorKind::BrokenPipe => return Err(e.into()),
+            _ => {}
+        }
+    }
+    child.wait()?;
+    Ok(())
+}
+
+/// The pager to run, split into words, or `None` for no pager: an empty
+/// or unparsable setting, or `cat`.
+fn pager_command() -> Option<Vec<String>> {
+    let setting = ["TARDIS_PAGER", "PAGER"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|v| !v.trim().is_empty()))
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    parse(&setting)
+}
+
+fn parse(setting: &str) -> Option<Vec<String>> {
+    let argv = exec::split(setting).ok()?;
+    match argv.first().map(String::as_str) {
+        None | Some("cat") => None,
+        Some(_) => Some(argv),
+    }
+}
+
+fn print(text: &str) -> Result<()> {
+    let mut out = io::stdout().lock();
+    match out.write_all(text.as_bytes()) {
+        Err(e) if e.kind() != ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_the_pager_setting() {
+        assert_eq!(parse("less -R"), Some(vec!["less".into(), "-R".into()]));
+        assert_eq!(
+            parse("'/opt/my pager' --quit"),
+            Some(vec!["/opt/my pager".into(), "--quit".into()])
+        );
+        assert_eq!(parse("cat"), None);
+        assert_eq!(parse("   "), None);
+        assert_eq!(parse("less 'oops"), None);
+    }
+}
@@ -3,7 +3,10 @@
 //! The AST separates syntax (what the user typed) from semantics (what datetime
 //! it resolves to). The resolver in `resolver.rs` maps these nodes to `jiff::Zoned`.
 
-use crate::parser::token::{BoundaryKind, EpochPrecision, TemporalUnit};
+use crate::{
+    cli::InputKind,
+    parser::token::{BoundaryKind, EpochPrecision, TemporalUnit},
+};
 
 /// Top-level AST node representing a parsed date expression.
 #[non_exhaustive]
@@ -35,6 +38,43 @@ pub enum DateExpr {
     Boundary(BoundaryKind),
 }
 
+impl DateExpr {
+    /// Whether the expression spells a date, a time of day, or both.
+    ///
+    /// Periods and boundaries are dates; "now", epochs and offsets from now
+    /// name an instant, so they are datetimes.  Arithmetic keeps the kind of
+    /// its base unless it adds hours, minutes or seconds to a date.
+    #[must_use]
+    pub fn kind(&self) -> InputKind {
+        match self {
+            DateExpr::Relative(_, time)
+            | DateExpr::DayRef(_, _, time)
+            | DateExpr::Absolute(_, time) => {
+                if time.is_some() {
+                    InputKind::Datetime
+                } else {
+                    InputKind::Date
+                }
+            }
+            DateExpr::TimeOnly(_) => InputKind::Time,
+            DateExpr::Range(_) | DateExpr::Boundary(_) => InputKind::Date,
+            DateExpr::OffsetFrom(_, comps, base) | DateExpr::Arithmetic(base, _, comps) => {
+                let clock = comps.iter().any(|c| {
+                    matches!(
+                        c.unit,
+                        TemporalUnit::Hour | TemporalUnit::Minute | TemporalUnit::Second
+                    )
+                });
+                match base.kind() {
+                    InputKind::Date if clock => InputKind::Datetime,
+                    kind => kind,
+                }
+            }
+            DateExpr::Now | DateExpr::Epoch(_) | DateExpr::Offset(..) => InputKind::Datetime,
+        }
+    }
+}
+
 /// Named relative date variants.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -90,7 +130,7 @@ pub struct AbsoluteDate {
 #[must_use]
 #[derive(Debug, Clone, PartialEq)]
 pub struct EpochValue {
-    pub raw: i64,
+    pub raw: i128,
     pub precision: EpochPrecision,
 }
 
@@ -177,4 +217,33 @@ mod tests {
         );
         let _ = DateExpr::Range(RangeExpr::LastWeek);
     }
+
+    #[test]
+    fn kind_tells_dates_times_and_datetimes_apart() {
+        use crate::parser::kind;
+        for input in [
+            "tomorrow",
+            "next friday",
+            "2025-03-01",
+            "next month",
+            "eod",
+            "3 days after 2025-03-01",
+        ] {
+            assert_eq!(kind(input), InputKind::Date, "{input}");
+        }
+        assert_eq!(
+            DateExpr::TimeOnly(TimeExpr::HourMinute(15, 30)).kind(),
+            InputKind::Time
+        );
+        for input in [
+            "now",
+            "tomorrow 9am",
+            "in 3 days",
+            "@1735689600",
+            "2025-06-24T15:00:00-03:00",
+            "tomorrow + 3 hours",
+        ] {
+            assert_eq!(kind(input), InputKind::Datetime, "{input}");
+        }
+    }
 }
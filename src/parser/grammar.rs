@@ -229,12 +229,15 @@ impl<'a> Parser<'a> {
             return Ok(None);
         }
 
-        if !self.match_token(&Token::Number(0)) {
-            self.restore(saved);
-            return Ok(None);
-        }
-
-        let raw = self.last_number();
+        let raw = match self.peek() {
+            Some(Token::Number(n)) => i128::from(*n),
+            Some(Token::WideNumber(n)) => *n,
+            _ => {
+                self.restore(saved);
+                return Ok(None);
+            }
+        };
+        self.pos += 1;
 
         let precision = if self.match_token(&Token::EpochSuffix(EpochPrecision::Seconds)) {
             match &self.tokens[self.pos - 1].kind {
@@ -347,29 +350,26 @@ impl<'a> Parser<'a> {
     fn try_duration_components(&mut self) -> Option<Vec<DurationComponent>> {
         let mut comps = Vec::new();
 
-        if let Some(comp) = self.try_single_duration() {
-            if comp.unit == TemporalUnit::Hour {
-                let saved_after_hour = self.save();
-                if self.match_token(&Token::Number(0)) {
-                    let minutes = self.last_number();
-                    if !self.peek_is_unit() {
-                        comps.push(comp);
-                        comps.push(DurationComponent {
-                            count: minutes,
-                            unit: TemporalUnit::Minute,
-                        });
-                    } else {
-                        self.restore(saved_after_hour);
-                        comps.push(comp);
-                    }
+        let comp = self.try_single_duration()?;
+        if comp.unit == TemporalUnit::Hour {
+            let saved_after_hour = self.save();
+            if self.match_token(&Token::Number(0)) {
+                let minutes = self.last_number();
+                if !self.peek_is_unit() {
+                    comps.push(comp);
+                    comps.push(DurationComponent {
+                        count: minutes,
+                        unit: TemporalUnit::Minute,
+                    });
                 } else {
+                    self.restore(saved_after_hour);
                     comps.push(comp);
                 }
             } else {
                 comps.push(comp);
             }
         } else {
-            return None;
+            comps.push(comp);
         }
 
         loop {
@@ -508,6 +508,7 @@ impl<'a> Parser<'a> {
     }
 
     /// ISO date: `Number Dash Number Dash Number [time]`
+    /// EXIF date: `Number(year) Colon Number Colon Number [time]`
     /// Day-month: `Number Month [Number] [time]`
     fn try_absolute_datetime(&mut self) -> Result<Option<DateExpr>, ParseError> {
         let saved = self.save();
@@ -532,6 +533,30 @@ impl<'a> Parser<'a> {
                 }
             }
 
+            self.restore(saved);
+            self.advance();
+
+            // A year can never be a valid hour, so `YYYY:MM:DD` is unambiguous.
+            if first >= 1000
+                && self.match_token(&Token::Colon)
+                && self.match_token(&Token::Number(0))
+            {
+                let second = self.last_number();
+
+                if self.match_token(&Token::Colon) && self.match_token(&Token::Number(0)) {
+                    let third = self.last_number();
+
+                    let abs = AbsoluteDate {
+                        year: first as i16,
+                        month: second as i8,
+                        day: third as i8,
+                    };
+
+                    let time = self.try_time_suffix();
+                    return Ok(Some(DateExpr::Absolute(abs, time)));
+                }
+            }
+
             self.restore(saved);
             self.advance();
             let day = first;
@@ -838,7 +863,7 @@ impl<'a> Parser<'a> {
 /// - `1e12 <= |value| < 1e15` -> milliseconds
 /// - `1e15 <= |value| < 1e18` -> microseconds
 /// - `|value| >= 1e18` -> nanoseconds
-pub(crate) fn detect_epoch_precision(value: i64) -> EpochPrecision {
+pub(crate) fn detect_epoch_precision(value: i128) -> EpochPrecision {
     let abs = value.unsigned_abs();
     if abs < 1_000_000_000_000 {
         EpochPrecision::Seconds
@@ -1020,6 +1045,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exif_datetime_absolute() {
+        let tokens = vec![
+            st(Token::Number(2025)),
+            st(Token::Colon),
+            st(Token::Number(6)),
+            st(Token::Colon),
+            st(Token::Number(27)),
+            st(Token::Number(9)),
+            st(Token::Colon),
+            st(Token::Number(0)),
+            st(Token::Colon),
+            st(Token::Number(0)),
+        ];
+        let result = parse_tokens(&tokens).unwrap();
+        assert_eq!(
+            result,
+            DateExpr::Absolute(
+                AbsoluteDate {
+                    year: 2025,
+                    month: 6,
+                    day: 27
+                },
+                Some(TimeExpr::HourMinuteSecond(9, 0, 0))
+            )
+        );
+    }
+
     #[test]
     fn bare_weekday_future_biased() {
         let tokens = vec![st(Token::Weekday(Weekday::Friday))];
@@ -140,6 +140,14 @@ pub(crate) const KEYWORD_LIST: &[(&str, Token)] = &[
     ("eony", Token::Boundary(BoundaryKind::Eony)),
 ];
 
+/// Month number of an English month name or abbreviation, any case.
+pub(crate) fn month(word: &str) -> Option<i8> {
+    match match_keyword(&word.trim().to_ascii_lowercase()) {
+        Some(Token::Month(m)) => Some(m),
+        _ => None,
+    }
+}
+
 /// Match a lowercased word against the known keyword table.
 ///
 /// Returns `Some(Token)` for a recognized keyword, `None` otherwise.
@@ -301,9 +309,8 @@ pub(crate) fn tokenize(input: &str) -> Vec<SpannedToken> {
                         pos += 1;
                     }
                     let num_str = &input[start + 1..pos];
-                    let value: i64 = num_str.parse().unwrap_or(0);
                     tokens.push(SpannedToken {
-                        kind: Token::Number(value),
+                        kind: number_token(num_str),
                         span: ByteSpan { start, end: pos },
                     });
                     try_epoch_suffix(input, &mut pos, &mut tokens);
@@ -326,14 +333,12 @@ pub(crate) fn tokenize(input: &str) -> Vec<SpannedToken> {
                 if pos + 1 < len && bytes[pos + 1].is_ascii_digit() && is_sign_position {
                     let start = pos;
                     pos += 1;
-                    let num_start = pos;
                     while pos < len && bytes[pos].is_ascii_digit() {
                         pos += 1;
                     }
-                    let num_str = &input[num_start..pos];
-                    let value: i64 = num_str.parse().unwrap_or(0);
+                    let num_str = &input[start..pos];
                     tokens.push(SpannedToken {
-                        kind: Token::Number(-value),
+                        kind: number_token(num_str),
                         span: ByteSpan { start, end: pos },
                     });
                     try_epoch_suffix(input, &mut pos, &mut tokens);
@@ -358,9 +363,8 @@ pub(crate) fn tokenize(input: &str) -> Vec<SpannedToken> {
                 pos += 1;
             }
             let num_str = &input[start..pos];
-            let value: i64 = num_str.parse().unwrap_or(0);
             tokens.push(SpannedToken {
-                kind: Token::Number(value),
+                kind: number_token(num_str),
                 span: ByteSpan { start, end: pos },
             });
             try_epoch_suffix(input, &mut pos, &mut tokens);
@@ -476,6 +480,18 @@ fn try_epoch_suffix(input: &str, pos: &mut usize, tokens: &mut Vec<SpannedToken>
     }
 }
 
+/// An optionally negative run of digits: a `Number` when it fits in `i64`,
+/// a `WideNumber` when it needs `i128`, and a `Word` beyond that.
+fn number_token(digits: &str) -> Token {
+    if let Ok(n) = digits.parse() {
+        Token::Number(n)
+    } else if let Ok(n) = digits.parse() {
+        Token::WideNumber(n)
+    } else {
+        Token::Word(digits.to_string())
+    }
+}
+
 /// Try to match a quarter pattern like "q1", "q2", "q3", "q4".
 fn match_quarter(lower: &str) -> Option<Token> {
     match lower {
@@ -630,6 +646,24 @@ mod tests {
         assert_eq!(kinds("@-86400"), vec![Token::AtSign, Token::Number(-86400)]);
     }
 
+    #[test]
+    fn numbers_past_i64_stay_whole() {
+        assert_eq!(
+            kinds("@-99999999999999999999"),
+            vec![
+                Token::AtSign,
+                Token::WideNumber(-99_999_999_999_999_999_999)
+            ]
+        );
+        assert_eq!(
+            kinds("@1234567890123456789012345678901234567890"),
+            vec![
+                Token::AtSign,
+                Token::Word("1234567890123456789012345678901234567890".into())
+            ]
+        );
+    }
+
     #[test]
     fn case_insensitive_keywords() {
         assert_eq!(
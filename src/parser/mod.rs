@@ -13,36 +13,283 @@ pub(crate) mod suggest;
 pub mod token;
 
 pub use error::ParseError;
+use token::EpochPrecision;
+
+use crate::cli::{InputKind, MonthOverflow};
+
+use jiff::{
+    civil::Time,
+    fmt::temporal::Pieces,
+    tz::{Offset, TimeZone},
+};
 
 /// Maximum input length in bytes. Inputs longer than this are rejected
 /// before tokenization to prevent abuse.
 const MAX_INPUT_LEN: usize = 1024;
 
+/// Policies for what an expression leaves open, passed to every parse
+/// along with "now".  The default clamps to the end of the month and puts
+/// a date given without a time at midnight.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// What adding months or years does past the end of a month
+    /// (`--month-overflow`).
+    pub month_overflow: MonthOverflow,
+    /// The time of day a date given without one, such as "next friday",
+    /// resolves to (`--default-time`).
+    pub default_time: Time,
+}
+
+/// Read a `--default-time` value: a time of day as written after a date
+/// (`09:00`, `9am`, `18h`), `midnight`, or `end-of-day` for the last
+/// nanosecond of the day, as `eod` resolves.
+#[must_use]
+pub fn parse_default_time(value: &str) -> Option<Time> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("midnight") {
+        return Some(Time::midnight());
+    }
+    if value.eq_ignore_ascii_case("end-of-day") {
+        return Some(Time::MAX);
+    }
+    let input = format!("today {value}");
+    let tokens = lexer::tokenize(&input);
+    match grammar::Parser::new(&tokens, &input).parse_expression() {
+        // The grammar lets an out-of-range hour such as `25:00` through, so
+        // build the time checked rather than with `apply_time`.
+        Ok(ast::DateExpr::Relative(ast::RelativeDate::Today, Some(time))) => match time {
+            ast::TimeExpr::HourOnly(h) => Time::new(h, 0, 0, 0).ok(),
+            ast::TimeExpr::HourMinute(h, m) => Time::new(h, m, 0, 0).ok(),
+            ast::TimeExpr::HourMinuteSecond(h, m, s) => Time::new(h, m, s, 0).ok(),
+            ast::TimeExpr::SameTime => None,
+        },
+        _ => None,
+    }
+}
+
 /// Parse a natural-language date expression into a [`jiff::Zoned`] datetime.
 ///
 /// * `input` -- the raw expression (e.g. `"next friday"`, `"@1735689600"`, `"in 3 days"`)
 /// * `now` -- reference "now" for relative resolution
+/// * `opts` -- month-overflow and default-time policies
 ///
 /// Returns the resolved datetime or a [`ParseError`] with span-based diagnostics.
 #[must_use = "parse returns a Result that should not be discarded"]
-pub fn parse(input: &str, now: &jiff::Zoned) -> std::result::Result<jiff::Zoned, ParseError> {
+pub fn parse(
+    input: &str,
+    now: &jiff::Zoned,
+    opts: &ParseOptions,
+) -> std::result::Result<jiff::Zoned, ParseError> {
+    parse_with_offset(input, now, opts).map(|(zoned, _)| zoned)
+}
+
+/// Like [`parse`], also returning the UTC offset written in the input.
+///
+/// An RFC 3339 timestamp carries its own offset (`2025-06-24T15:00:00-03:00`),
+/// and any other expression may end with one: `3pm UTC+2`, `tomorrow 9:00
+/// -03:00`, `noon GMT`, `15:00 Z`.  Such an expression is resolved as a
+/// wall-clock time in that offset -- "today" and "tomorrow" included -- and
+/// the result is then converted to `now`'s time zone.
+#[must_use = "parse_with_offset returns a Result that should not be discarded"]
+pub fn parse_with_offset(
+    input: &str,
+    now: &jiff::Zoned,
+    opts: &ParseOptions,
+) -> std::result::Result<(jiff::Zoned, Option<Offset>), ParseError> {
+    parse_in(input, now, opts, false)
+}
+
+/// Like [`parse_with_offset`], also reading epoch values too large for 64
+/// bits (`@99999999999999999999` nanoseconds) instead of rejecting them.
+#[must_use = "parse_extreme returns a Result that should not be discarded"]
+pub fn parse_extreme(
+    input: &str,
+    now: &jiff::Zoned,
+    opts: &ParseOptions,
+) -> std::result::Result<(jiff::Zoned, Option<Offset>), ParseError> {
+    parse_in(input, now, opts, true)
+}
+
+fn parse_in(
+    input: &str,
+    now: &jiff::Zoned,
+    opts: &ParseOptions,
+    wide_epochs: bool,
+) -> std::result::Result<(jiff::Zoned, Option<Offset>), ParseError> {
     if input.len() > MAX_INPUT_LEN {
         return Err(ParseError::input_too_long(input.len(), MAX_INPUT_LEN));
     }
 
     let trimmed = input.trim();
     if trimmed.is_empty() {
-        return resolver::resolve(&ast::DateExpr::Now, now);
+        return Ok((resolver::resolve(&ast::DateExpr::Now, now, opts)?, None));
     }
 
     if let Ok(ts) = trimmed.parse::<jiff::Timestamp>() {
-        return Ok(ts.to_zoned(now.time_zone().clone()));
+        let offset = Pieces::parse(trimmed)
+            .ok()
+            .and_then(|p| p.to_numeric_offset());
+        return Ok((ts.to_zoned(now.time_zone().clone()), offset));
     }
 
+    if let Some((rest, offset)) = split_offset_suffix(trimmed) {
+        let local_now = now.with_time_zone(TimeZone::fixed(offset));
+        let zoned = parse_expression(rest, &local_now, opts, wide_epochs)?;
+        return Ok((zoned.with_time_zone(now.time_zone().clone()), Some(offset)));
+    }
+
+    Ok((parse_expression(trimmed, now, opts, wide_epochs)?, None))
+}
+
+/// Whether `input` spells a date, a time of day, or both (see
+/// [`ast::DateExpr::kind`]).  RFC 3339 timestamps are datetimes, and a
+/// trailing UTC offset does not change the kind.  Input that does not parse
+/// counts as a datetime.
+#[must_use]
+pub fn kind(input: &str) -> InputKind {
+    let trimmed = input.trim();
+    if trimmed.is_empty() || trimmed.parse::<jiff::Timestamp>().is_ok() {
+        return InputKind::Datetime;
+    }
+    let expr = split_offset_suffix(trimmed).map_or(trimmed, |(rest, _)| rest);
+    let tokens = lexer::tokenize(expr);
+    grammar::Parser::new(&tokens, expr)
+        .parse_expression()
+        .map_or(InputKind::Datetime, |e| e.kind())
+}
+
+/// Read machine-readable input without the natural-language parser: an
+/// RFC 3339 or RFC 2822 datetime, or a Unix timestamp (`@1735689600`,
+/// `@1735689600123ms`, or bare digits with the same smart precision).
+///
+/// Returns `None` for anything else, including out-of-range epochs.  The
+/// result is in `tz`; the offset is the one written in the input, if any.
+#[must_use]
+pub fn parse_machine(input: &str, tz: &TimeZone) -> Option<(jiff::Zoned, Option<Offset>)> {
+    let trimmed = input.trim();
+    if let Ok(ts) = trimmed.parse::<jiff::Timestamp>() {
+        let offset = Pieces::parse(trimmed)
+            .ok()
+            .and_then(|p| p.to_numeric_offset());
+        return Some((ts.to_zoned(tz.clone()), offset));
+    }
+    if let Ok(zoned) = jiff::fmt::rfc2822::parse(trimmed) {
+        return Some((zoned.with_time_zone(tz.clone()), Some(zoned.offset())));
+    }
+
+    let (digits, precision) = match trimmed.strip_prefix('@') {
+        Some(rest) => {
+            let end = rest.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            let precision = match rest[end.len()..].to_ascii_lowercase().as_str() {
+                "" => None,
+                "s" => Some(EpochPrecision::Seconds),
+                "ms" => Some(EpochPrecision::Milliseconds),
+                "us" => Some(EpochPrecision::Microseconds),
+                "ns" => Some(EpochPrecision::Nanoseconds),
+                _ => return None,
+            };
+            (end.strip_prefix('+').unwrap_or(end), precision)
+        }
+        None => (trimmed, None),
+    };
+    if digits.is_empty()
+        || !digits
+            .trim_start_matches('-')
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let raw = i128::from(digits.parse::<i64>().ok()?);
+    let epoch = ast::EpochValue {
+        raw,
+        precision: precision.unwrap_or_else(|| grammar::detect_epoch_precision(raw)),
+    };
+    Some((resolver::resolve_epoch(&epoch, tz).ok()?, None))
+}
+
+fn parse_expression(
+    trimmed: &str,
+    now: &jiff::Zoned,
+    opts: &ParseOptions,
+    wide_epochs: bool,
+) -> std::result::Result<jiff::Zoned, ParseError> {
     let tokens = lexer::tokenize(trimmed);
+    if !wide_epochs {
+        if let Some(wide) = tokens
+            .iter()
+            .find(|t| matches!(t.kind, token::Token::WideNumber(_)))
+        {
+            return Err(ParseError::resolution(format!(
+                "{} does not fit in 64 bits; --allow-extreme reads it as a 128-bit epoch",
+                &trimmed[wide.span.start..wide.span.end]
+            )));
+        }
+    }
     let mut parser = grammar::Parser::new(&tokens, trimmed);
-    let expr = parser.parse_expression()?;
-    resolver::resolve(&expr, now)
+    let expr = parser
+        .parse_expression()
+        .map_err(|e| with_phrase_suggestion(e, trimmed))?;
+    resolver::resolve(&expr, now, opts)
+}
+
+/// Split a trailing UTC offset off `input`: `Z`, `UTC`, `GMT`, `UTC+2`,
+/// `GMT-03:30`, `+02:00` or `-0300`, separated from the rest by whitespace.
+fn split_offset_suffix(input: &str) -> Option<(&str, Offset)> {
+    let (rest, word) = input.rsplit_once(char::is_whitespace)?;
+    let rest = rest.trim_end();
+    if rest.is_empty() {
+        return None;
+    }
+    let upper = word.to_ascii_uppercase();
+    // A bare offset needs its minutes, so `+2` stays arithmetic.
+    let named = upper == "Z" || upper.starts_with("UTC") || upper.starts_with("GMT");
+    if !named && word.len() < 5 {
+        return None;
+    }
+    Some((rest, parse_utc_offset(word)?))
+}
+
+/// Parse a UTC offset: `Z`, `UTC`, `GMT`, `UTC+2`, `GMT-03:30`, `+02:00`,
+/// `+5:30`, `-0300` or `+2`.  Offsets beyond 18 hours are rejected.
+#[must_use]
+pub fn parse_utc_offset(word: &str) -> Option<Offset> {
+    let upper = word.trim().to_ascii_uppercase();
+    let signed = match upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+    {
+        Some("") => return Some(Offset::UTC),
+        Some(signed) => signed,
+        None if upper == "Z" => return Some(Offset::UTC),
+        None => &upper,
+    };
+
+    let (sign, digits) = match signed.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) if m.len() == 2 => (h, m),
+        Some(_) => return None,
+        None if digits.len() == 4 => digits.split_at(2),
+        None => (digits, "0"),
+    };
+    if !(1..=2).contains(&hours.len())
+        || !hours
+            .bytes()
+            .chain(minutes.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 18 || minutes > 59 {
+        return None;
+    }
+    Offset::from_seconds(sign * (hours * 3600 + minutes * 60)).ok()
 }
 
 /// Parse any expression and resolve it as a range with implicit granularity.
@@ -60,6 +307,7 @@ pub fn parse(input: &str, now: &jiff::Zoned) -> std::result::Result<jiff::Zoned,
 pub fn parse_range_with_granularity(
     input: &str,
     now: &jiff::Zoned,
+    opts: &ParseOptions,
 ) -> std::result::Result<(jiff::Zoned, jiff::Zoned), ParseError> {
     if input.len() > MAX_INPUT_LEN {
         return Err(ParseError::input_too_long(input.len(), MAX_INPUT_LEN));
@@ -78,6 +326,17 @@ pub fn parse_range_with_granularity(
 
     let tokens = lexer::tokenize(trimmed);
     let mut parser = grammar::Parser::new(&tokens, trimmed);
-    let expr = parser.parse_expression()?;
-    resolver::resolve_range_with_granularity(&expr, now)
+    let expr = parser
+        .parse_expression()
+        .map_err(|e| with_phrase_suggestion(e, trimmed))?;
+    resolver::resolve_range_with_granularity(&expr, now, opts)
+}
+
+/// Replace a single-word suggestion with a corrected whole phrase when one
+/// parses.
+fn with_phrase_suggestion(err: ParseError, input: &str) -> ParseError {
+    match suggest::suggest_phrase(input) {
+        Some(phrase) => err.with_suggestion(phrase),
+        None => err,
+    }
 }
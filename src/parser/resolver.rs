@@ -3,45 +3,56 @@
 //! This module is a pure function of AST + reference time. No parsing logic.
 //! All datetime arithmetic uses jiff's native calendar-aware operations.
 //!
-//! ## Clamping policy
+//! ## Month overflow
 //!
-//! jiff's `checked_add`/`checked_sub` clamps to end-of-month for calendar
-//! unit arithmetic (e.g., Jan 31 + 1 month = Feb 28). This is intentional
-//! and matches Python dateutil, JS Temporal, and Go `time.AddDate` behavior.
-//! Non-reversibility is inherent: `Jan 31 + 1 month = Feb 28`, but
-//! `Feb 28 - 1 month = Jan 28` (not Jan 31).
+//! Adding months or years can land on a day the target month lacks
+//! (Jan 31 + 1 month, Feb 29 + 1 year).  [`add_span`] settles it by the
+//! [`MonthOverflow`] policy of [`ParseOptions`] (`--month-overflow`): `clamp` (the default,
+//! like Python dateutil, JS Temporal and jiff) uses the month's last day,
+//! `overflow` carries the extra days into the next month (like Go
+//! `time.AddDate`), and `error` fails.  Clamping is not reversible:
+//! `Jan 31 + 1 month = Feb 28`, but `Feb 28 - 1 month = Jan 28`.
 
 use jiff::{Span, Zoned, civil};
 
-use crate::parser::{
-    ast::*,
-    error::ParseError,
-    token::{BoundaryKind, EpochPrecision, TemporalUnit},
+use crate::{
+    cli::MonthOverflow,
+    parser::{
+        ParseOptions,
+        ast::*,
+        error::ParseError,
+        token::{BoundaryKind, EpochPrecision, TemporalUnit},
+    },
 };
 
 /// Resolve an AST node to a concrete `jiff::Zoned` datetime.
-pub(crate) fn resolve(expr: &DateExpr, now: &Zoned) -> Result<Zoned, ParseError> {
+pub(crate) fn resolve(
+    expr: &DateExpr,
+    now: &Zoned,
+    opts: &ParseOptions,
+) -> Result<Zoned, ParseError> {
     match expr {
         DateExpr::Now => Ok(now.clone()),
-        DateExpr::Relative(rel, time) => resolve_relative(rel, time, now),
-        DateExpr::DayRef(dir, weekday, time) => resolve_day_ref(dir, weekday, time, now),
-        DateExpr::Absolute(abs, time) => resolve_absolute(abs, time, now),
+        DateExpr::Relative(rel, time) => resolve_relative(rel, time, now, opts),
+        DateExpr::DayRef(dir, weekday, time) => resolve_day_ref(dir, weekday, time, now, opts),
+        DateExpr::Absolute(abs, time) => resolve_absolute(abs, time, now, opts),
         DateExpr::TimeOnly(time) => resolve_time_only(time, now),
         DateExpr::Epoch(epoch) => resolve_epoch(epoch, now.time_zone()),
-        DateExpr::Offset(dir, comps) => resolve_offset(dir, comps, now),
-        DateExpr::OffsetFrom(dir, comps, base) => resolve_offset_from(dir, comps, base, now),
-        DateExpr::Arithmetic(base, op, comps) => resolve_arithmetic(base, op, comps, now),
+        DateExpr::Offset(dir, comps) => resolve_offset(dir, comps, now, opts),
+        DateExpr::OffsetFrom(dir, comps, base) => resolve_offset_from(dir, comps, base, now, opts),
+        DateExpr::Arithmetic(base, op, comps) => resolve_arithmetic(base, op, comps, now, opts),
         DateExpr::Range(range) => resolve_range_start(range, now),
         DateExpr::Boundary(kind) => resolve_boundary(kind, now),
     }
 }
 
-/// Resolve relative dates: today/tomorrow/yesterday/overmorrow at midnight,
+/// Resolve relative dates: today/tomorrow/yesterday/overmorrow at the default time,
 /// or at the specified time if provided.
 fn resolve_relative(
     rel: &RelativeDate,
     time: &Option<TimeExpr>,
     now: &Zoned,
+    opts: &ParseOptions,
 ) -> Result<Zoned, ParseError> {
     let today = now.date();
 
@@ -49,26 +60,26 @@ fn resolve_relative(
         RelativeDate::Today => today,
         RelativeDate::Tomorrow => today
             .checked_add(Span::new().days(1))
-            .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?,
+            .map_err(out_of_range)?,
         RelativeDate::Yesterday => today
             .checked_sub(Span::new().days(1))
-            .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?,
+            .map_err(out_of_range)?,
         RelativeDate::Overmorrow => today
             .checked_add(Span::new().days(2))
-            .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?,
+            .map_err(out_of_range)?,
         RelativeDate::Ereyesterday => today
             .checked_sub(Span::new().days(2))
-            .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?,
+            .map_err(out_of_range)?,
     };
 
-    let civil_dt = apply_time_or_midnight(target_date, time, now);
+    let civil_dt = apply_time_or_default(target_date, time, now, opts);
     let tz = now.time_zone().clone();
     tz.to_ambiguous_zoned(civil_dt)
         .compatible()
-        .map_err(|e| ParseError::resolution(format!("ambiguous datetime: {e}")))
+        .map_err(out_of_range)
 }
 
-/// Resolve day references: next/last/this <weekday> at midnight or given time.
+/// Resolve day references: next/last/this <weekday> at the default time or given time.
 ///
 /// Weekday delta computation:
 /// - Next: advance forward, skip today -> `(target - current + 7) % 7; if 0 then 7`
@@ -79,6 +90,7 @@ fn resolve_day_ref(
     weekday: &jiff::civil::Weekday,
     time: &Option<TimeExpr>,
     now: &Zoned,
+    opts: &ParseOptions,
 ) -> Result<Zoned, ParseError> {
     let today = now.date();
     let current_wd = today.weekday();
@@ -105,13 +117,13 @@ fn resolve_day_ref(
 
     let target_date = today
         .checked_add(Span::new().days(i64::from(delta_days)))
-        .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+        .map_err(out_of_range)?;
 
-    let civil_dt = apply_time_or_midnight(target_date, time, now);
+    let civil_dt = apply_time_or_default(target_date, time, now, opts);
     let tz = now.time_zone().clone();
     tz.to_ambiguous_zoned(civil_dt)
         .compatible()
-        .map_err(|e| ParseError::resolution(format!("ambiguous datetime: {e}")))
+        .map_err(out_of_range)
 }
 
 /// Resolve absolute dates with optional time.
@@ -120,6 +132,7 @@ fn resolve_absolute(
     abs: &AbsoluteDate,
     time: &Option<TimeExpr>,
     now: &Zoned,
+    opts: &ParseOptions,
 ) -> Result<Zoned, ParseError> {
     let year = if abs.year == 0 {
         now.date().year()
@@ -127,12 +140,12 @@ fn resolve_absolute(
         abs.year
     };
 
-    let date = civil::date(year, abs.month, abs.day);
-    let civil_dt = apply_time_or_midnight(date, time, now);
+    let date = date(year, abs.month, abs.day)?;
+    let civil_dt = apply_time_or_default(date, time, now, opts);
     let tz = now.time_zone().clone();
     tz.to_ambiguous_zoned(civil_dt)
         .compatible()
-        .map_err(|e| ParseError::resolution(format!("ambiguous datetime: {e}")))
+        .map_err(out_of_range)
 }
 
 /// Resolve time-only expressions against today's date from `now`.
@@ -142,19 +155,55 @@ fn resolve_time_only(time: &TimeExpr, now: &Zoned) -> Result<Zoned, ParseError>
     let tz = now.time_zone().clone();
     tz.to_ambiguous_zoned(civil_dt)
         .compatible()
-        .map_err(|e| ParseError::resolution(format!("ambiguous datetime: {e}")))
+        .map_err(out_of_range)
+}
+
+/// A date from its parts, which may lie outside jiff's range (`year 10000`)
+/// or not exist (`2025-02-30`).
+fn date(year: i16, month: i8, day: i8) -> Result<civil::Date, ParseError> {
+    civil::Date::new(year, month, day).map_err(|_| {
+        if (-9999..=9999).contains(&year) {
+            ParseError::resolution(format!("{year:04}-{month:02}-{day:02} is not a valid date"))
+        } else {
+            out_of_range(())
+        }
+    })
+}
+
+/// The error for a result jiff cannot represent.  Date arithmetic and zone
+/// conversion only fail here when the result leaves the supported range.
+fn out_of_range<E>(_: E) -> ParseError {
+    ParseError::resolution(format!(
+        "date out of range: td handles {} to {}",
+        jiff::Timestamp::MIN.strftime("%Y-%m-%d"),
+        jiff::Timestamp::MAX.strftime("%Y-%m-%d")
+    ))
 }
 
 /// Resolve epoch timestamps, dispatching on precision.
-fn resolve_epoch(epoch: &EpochValue, tz: &jiff::tz::TimeZone) -> Result<Zoned, ParseError> {
-    let timestamp = match epoch.precision {
-        EpochPrecision::Seconds => jiff::Timestamp::from_second(epoch.raw),
-        EpochPrecision::Milliseconds => jiff::Timestamp::from_millisecond(epoch.raw),
-        EpochPrecision::Microseconds => jiff::Timestamp::from_microsecond(epoch.raw),
-        EpochPrecision::Nanoseconds => jiff::Timestamp::from_nanosecond(epoch.raw as i128),
+pub(crate) fn resolve_epoch(
+    epoch: &EpochValue,
+    tz: &jiff::tz::TimeZone,
+) -> Result<Zoned, ParseError> {
+    let (unit, per_second): (&str, i128) = match epoch.precision {
+        EpochPrecision::Seconds => ("seconds", 1),
+        EpochPrecision::Milliseconds => ("milliseconds", 1_000),
+        EpochPrecision::Microseconds => ("microseconds", 1_000_000),
+        EpochPrecision::Nanoseconds => ("nanoseconds", 1_000_000_000),
     };
-    let ts = timestamp
-        .map_err(|e| ParseError::resolution(format!("epoch timestamp out of range: {e}")))?;
+    let ts = epoch
+        .raw
+        .checked_mul(1_000_000_000 / per_second)
+        .and_then(|nanos| jiff::Timestamp::from_nanosecond(nanos).ok())
+        .ok_or_else(|| {
+            ParseError::resolution(format!(
+                "epoch {} is out of range: {} must be between {} and {}",
+                epoch.raw,
+                unit,
+                i128::from(jiff::Timestamp::MIN.as_second()) * per_second,
+                i128::from(jiff::Timestamp::MAX.as_second()) * per_second
+            ))
+        })?;
     Ok(ts.to_zoned(tz.clone()))
 }
 
@@ -164,15 +213,12 @@ fn resolve_offset(
     dir: &Direction,
     comps: &[DurationComponent],
     now: &Zoned,
+    opts: &ParseOptions,
 ) -> Result<Zoned, ParseError> {
-    let span = build_span(comps);
+    let span = build_span(comps)?;
     match dir {
-        Direction::Future => now
-            .checked_add(span)
-            .map_err(|e| ParseError::resolution(format!("overflow: {e}"))),
-        Direction::Past => now
-            .checked_sub(span)
-            .map_err(|e| ParseError::resolution(format!("overflow: {e}"))),
+        Direction::Future => add_span(now, span, opts.month_overflow),
+        Direction::Past => add_span(now, span.negate(), opts.month_overflow),
         _ => Err(ParseError::resolution(format!(
             "unexpected direction {dir:?} for offset"
         ))),
@@ -187,19 +233,50 @@ fn resolve_arithmetic(
     op: &ArithOp,
     comps: &[DurationComponent],
     now: &Zoned,
+    opts: &ParseOptions,
 ) -> Result<Zoned, ParseError> {
-    let base_zoned = resolve(base, now)?;
-    let span = build_span(comps);
+    let base_zoned = resolve(base, now, opts)?;
+    let span = build_span(comps)?;
     match op {
-        ArithOp::Add => base_zoned
-            .checked_add(span)
-            .map_err(|e| ParseError::resolution(format!("overflow: {e}"))),
-        ArithOp::Sub => base_zoned
-            .checked_sub(span)
-            .map_err(|e| ParseError::resolution(format!("overflow: {e}"))),
+        ArithOp::Add => add_span(&base_zoned, span, opts.month_overflow),
+        ArithOp::Sub => add_span(&base_zoned, span.negate(), opts.month_overflow),
     }
 }
 
+/// `at` moved by `span`, with `policy` settling a years-and-months step
+/// that lands past the end of the target month (see the module docs).
+pub(crate) fn add_span(at: &Zoned, span: Span, policy: MonthOverflow) -> Result<Zoned, ParseError> {
+    let months = i64::from(span.get_years()) * 12 + i64::from(span.get_months());
+    if months == 0 || policy == MonthOverflow::Clamp {
+        return at.checked_add(span).map_err(out_of_range);
+    }
+    let index = i64::from(at.year()) * 12 + i64::from(at.month()) - 1 + months;
+    let year = i16::try_from(index.div_euclid(12)).map_err(out_of_range)?;
+    let month = i8::try_from(index.rem_euclid(12) + 1).map_err(out_of_range)?;
+    let last = date(year, month, 1)?.days_in_month();
+    if at.day() <= last {
+        return at.checked_add(span).map_err(out_of_range);
+    }
+    if policy == MonthOverflow::Error {
+        return Err(ParseError::resolution(format!(
+            "{} {} {:#} falls on {year:04}-{month:02}-{:02}, which does not exist \
+             (--month-overflow clamp or overflow picks a date)",
+            at.date(),
+            if months < 0 { "-" } else { "+" },
+            span.abs().years(0).months(months.abs()),
+            at.day()
+        )));
+    }
+    at.with()
+        .year(year)
+        .month(month)
+        .day(last)
+        .build()
+        .and_then(|end| end.checked_add(Span::new().days(at.day() - last)))
+        .and_then(|landed| landed.checked_add(span.years(0).months(0)))
+        .map_err(out_of_range)
+}
+
 /// Resolve range expressions to (start, end) datetime pairs.
 ///
 /// - Monday as week start (ISO 8601)
@@ -214,13 +291,13 @@ pub(crate) fn resolve_range(range: &RangeExpr, now: &Zoned) -> Result<(Zoned, Zo
             let current_wd = today.weekday().to_monday_zero_offset() as i32;
             let this_monday = today
                 .checked_sub(Span::new().days(i64::from(current_wd)))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let last_monday = this_monday
                 .checked_sub(Span::new().weeks(1))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let last_sunday = last_monday
                 .checked_add(Span::new().days(6))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             Ok((
                 zoned_midnight(last_monday, &tz)?,
                 zoned_end_of_day(last_sunday, &tz)?,
@@ -230,10 +307,10 @@ pub(crate) fn resolve_range(range: &RangeExpr, now: &Zoned) -> Result<(Zoned, Zo
             let current_wd = today.weekday().to_monday_zero_offset() as i32;
             let this_monday = today
                 .checked_sub(Span::new().days(i64::from(current_wd)))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let this_sunday = this_monday
                 .checked_add(Span::new().days(6))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             Ok((
                 zoned_midnight(this_monday, &tz)?,
                 zoned_end_of_day(this_sunday, &tz)?,
@@ -243,13 +320,13 @@ pub(crate) fn resolve_range(range: &RangeExpr, now: &Zoned) -> Result<(Zoned, Zo
             let current_wd = today.weekday().to_monday_zero_offset() as i32;
             let this_monday = today
                 .checked_sub(Span::new().days(i64::from(current_wd)))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let next_monday = this_monday
                 .checked_add(Span::new().weeks(1))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let next_sunday = next_monday
                 .checked_add(Span::new().days(6))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             Ok((
                 zoned_midnight(next_monday, &tz)?,
                 zoned_end_of_day(next_sunday, &tz)?,
@@ -300,6 +377,7 @@ fn resolve_range_start(range: &RangeExpr, now: &Zoned) -> Result<Zoned, ParseErr
 pub(crate) fn resolve_range_with_granularity(
     expr: &DateExpr,
     now: &Zoned,
+    opts: &ParseOptions,
 ) -> Result<(Zoned, Zoned), ParseError> {
     match expr {
         DateExpr::Now => {
@@ -308,19 +386,19 @@ pub(crate) fn resolve_range_with_granularity(
         }
         DateExpr::Range(range) => resolve_range(range, now),
         DateExpr::Relative(_, time) | DateExpr::DayRef(_, _, time) => {
-            let z = resolve(expr, now)?;
+            let z = resolve(expr, now, opts)?;
             expand_by_time_granularity(z, time)
         }
         DateExpr::Absolute(_, time) => {
-            let z = resolve(expr, now)?;
+            let z = resolve(expr, now, opts)?;
             expand_by_time_granularity(z, time)
         }
         DateExpr::Boundary(_) => {
-            let z = resolve(expr, now)?;
+            let z = resolve(expr, now, opts)?;
             Ok((z.clone(), z))
         }
         _ => {
-            let z = resolve(expr, now)?;
+            let z = resolve(expr, now, opts)?;
             Ok((z.clone(), z))
         }
     }
@@ -347,11 +425,11 @@ fn expand_by_time_granularity(
             let start = tz
                 .to_ambiguous_zoned(dt_start)
                 .compatible()
-                .map_err(|e| ParseError::resolution(format!("ambiguous: {e}")))?;
+                .map_err(out_of_range)?;
             let end = tz
                 .to_ambiguous_zoned(dt_end)
                 .compatible()
-                .map_err(|e| ParseError::resolution(format!("ambiguous: {e}")))?;
+                .map_err(out_of_range)?;
             Ok((start, end))
         }
         Some(TimeExpr::HourMinute(h, m)) => {
@@ -360,11 +438,11 @@ fn expand_by_time_granularity(
             let start = tz
                 .to_ambiguous_zoned(dt_start)
                 .compatible()
-                .map_err(|e| ParseError::resolution(format!("ambiguous: {e}")))?;
+                .map_err(out_of_range)?;
             let end = tz
                 .to_ambiguous_zoned(dt_end)
                 .compatible()
-                .map_err(|e| ParseError::resolution(format!("ambiguous: {e}")))?;
+                .map_err(out_of_range)?;
             Ok((start, end))
         }
         Some(TimeExpr::HourMinuteSecond(..)) => Ok((base.clone(), base)),
@@ -392,106 +470,106 @@ fn resolve_boundary(kind: &BoundaryKind, now: &Zoned) -> Result<Zoned, ParseErro
         BoundaryKind::Sow => {
             let monday = today
                 .checked_sub(Span::new().days(current_wd))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_midnight(monday, &tz)
         }
         BoundaryKind::Eow => {
             let monday = today
                 .checked_sub(Span::new().days(current_wd))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let sunday = monday
                 .checked_add(Span::new().days(6))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_end_of_day(sunday, &tz)
         }
         BoundaryKind::Soww => {
             let monday = today
                 .checked_sub(Span::new().days(current_wd))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_midnight(monday, &tz)
         }
         BoundaryKind::Eoww => {
             let monday = today
                 .checked_sub(Span::new().days(current_wd))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let friday = monday
                 .checked_add(Span::new().days(4))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_end_of_day(friday, &tz)
         }
         BoundaryKind::Som => {
-            let first = civil::date(today.year(), today.month(), 1);
+            let first = date(today.year(), today.month(), 1)?;
             zoned_midnight(first, &tz)
         }
         BoundaryKind::Eom => {
-            let first = civil::date(today.year(), today.month(), 1);
+            let first = date(today.year(), today.month(), 1)?;
             let last_day = first.days_in_month();
-            let last = civil::date(today.year(), today.month(), last_day);
+            let last = date(today.year(), today.month(), last_day)?;
             zoned_end_of_day(last, &tz)
         }
         BoundaryKind::Soq => {
             let q = (today.month() - 1) / 3 + 1;
             let start_month = (q - 1) * 3 + 1;
-            let first = civil::date(today.year(), start_month, 1);
+            let first = date(today.year(), start_month, 1)?;
             zoned_midnight(first, &tz)
         }
         BoundaryKind::Eoq => {
             let q = (today.month() - 1) / 3 + 1;
             let end_month = q * 3;
-            let end_date = civil::date(today.year(), end_month, 1);
+            let end_date = date(today.year(), end_month, 1)?;
             let last_day = end_date.days_in_month();
-            let last = civil::date(today.year(), end_month, last_day);
+            let last = date(today.year(), end_month, last_day)?;
             zoned_end_of_day(last, &tz)
         }
         BoundaryKind::Soy => {
-            let first = civil::date(today.year(), 1, 1);
+            let first = date(today.year(), 1, 1)?;
             zoned_midnight(first, &tz)
         }
         BoundaryKind::Eoy => {
-            let last = civil::date(today.year(), 12, 31);
+            let last = date(today.year(), 12, 31)?;
             zoned_end_of_day(last, &tz)
         }
 
         BoundaryKind::Sopd => {
             let yesterday = today
                 .checked_sub(Span::new().days(1))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_midnight(yesterday, &tz)
         }
         BoundaryKind::Eopd => {
             let yesterday = today
                 .checked_sub(Span::new().days(1))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_end_of_day(yesterday, &tz)
         }
         BoundaryKind::Sopw => {
             let this_monday = today
                 .checked_sub(Span::new().days(current_wd))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let last_monday = this_monday
                 .checked_sub(Span::new().weeks(1))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_midnight(last_monday, &tz)
         }
         BoundaryKind::Eopw => {
             let this_monday = today
                 .checked_sub(Span::new().days(current_wd))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let last_sunday = this_monday
                 .checked_sub(Span::new().days(1))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_end_of_day(last_sunday, &tz)
         }
         BoundaryKind::Sopm => {
             let (year, month) = prev_month(today.year(), today.month());
-            let first = civil::date(year, month, 1);
+            let first = date(year, month, 1)?;
             zoned_midnight(first, &tz)
         }
         BoundaryKind::Eopm => {
             let (year, month) = prev_month(today.year(), today.month());
-            let first = civil::date(year, month, 1);
+            let first = date(year, month, 1)?;
             let last_day = first.days_in_month();
-            let last = civil::date(year, month, last_day);
+            let last = date(year, month, last_day)?;
             zoned_end_of_day(last, &tz)
         }
         BoundaryKind::Sopq => {
@@ -502,7 +580,7 @@ fn resolve_boundary(kind: &BoundaryKind, now: &Zoned) -> Result<Zoned, ParseErro
                 (today.year(), q - 1)
             };
             let start_month = (prev_q - 1) * 3 + 1;
-            let first = civil::date(year, start_month, 1);
+            let first = date(year, start_month, 1)?;
             zoned_midnight(first, &tz)
         }
         BoundaryKind::Eopq => {
@@ -513,62 +591,62 @@ fn resolve_boundary(kind: &BoundaryKind, now: &Zoned) -> Result<Zoned, ParseErro
                 (today.year(), q - 1)
             };
             let end_month = prev_q * 3;
-            let end_date = civil::date(year, end_month, 1);
+            let end_date = date(year, end_month, 1)?;
             let last_day = end_date.days_in_month();
-            let last = civil::date(year, end_month, last_day);
+            let last = date(year, end_month, last_day)?;
             zoned_end_of_day(last, &tz)
         }
         BoundaryKind::Sopy => {
-            let first = civil::date(today.year() - 1, 1, 1);
+            let first = date(today.year() - 1, 1, 1)?;
             zoned_midnight(first, &tz)
         }
         BoundaryKind::Eopy => {
-            let last = civil::date(today.year() - 1, 12, 31);
+            let last = date(today.year() - 1, 12, 31)?;
             zoned_end_of_day(last, &tz)
         }
 
         BoundaryKind::Sond => {
             let tomorrow = today
                 .checked_add(Span::new().days(1))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_midnight(tomorrow, &tz)
         }
         BoundaryKind::Eond => {
             let tomorrow = today
                 .checked_add(Span::new().days(1))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_end_of_day(tomorrow, &tz)
         }
         BoundaryKind::Sonw => {
             let this_monday = today
                 .checked_sub(Span::new().days(current_wd))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let next_monday = this_monday
                 .checked_add(Span::new().weeks(1))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_midnight(next_monday, &tz)
         }
         BoundaryKind::Eonw => {
             let this_monday = today
                 .checked_sub(Span::new().days(current_wd))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             let next_sunday = this_monday
                 .checked_add(Span::new().weeks(1))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?
+                .map_err(out_of_range)?
                 .checked_add(Span::new().days(6))
-                .map_err(|e| ParseError::resolution(format!("overflow: {e}")))?;
+                .map_err(out_of_range)?;
             zoned_end_of_day(next_sunday, &tz)
         }
         BoundaryKind::Sonm => {
             let (year, month) = next_month(today.year(), today.month());
-            let first = civil::date(year, month, 1);
+            let first = date(year, month, 1)?;
             zoned_midnight(first, &tz)
         }
         BoundaryKind::Eonm => {
             let (year, month) = next_month(today.year(), today.month());
-            let first = civil::date(year, month, 1);
+            let first = date(year, month, 1)?;
             let last_day = first.days_in_month();
-            let last = civil::date(year, month, last_day);
+            let last = date(year, month, last_day)?;
             zoned_end_of_day(last, &tz)
         }
         BoundaryKind::Sonq => {
@@ -579,7 +657,7 @@ fn resolve_boundary(kind: &BoundaryKind, now: &Zoned) -> Result<Zoned, ParseErro
                 (today.year(), q + 1)
             };
             let start_month = (next_q - 1) * 3 + 1;
-            let first = civil::date(year, start_month, 1);
+            let first = date(year, start_month, 1)?;
             zoned_midnight(first, &tz)
         }
         BoundaryKind::Eonq => {
@@ -590,17 +668,17 @@ fn resolve_boundary(kind: &BoundaryKind, now: &Zoned) -> Result<Zoned, ParseErro
                 (today.year(), q + 1)
             };
             let end_month = next_q * 3;
-            let end_date = civil::date(year, end_month, 1);
+            let end_date = date(year, end_month, 1)?;
             let last_day = end_date.days_in_month();
-            let last = civil::date(year, end_month, last_day);
+            let last = date(year, end_month, last_day)?;
             zoned_end_of_day(last, &tz)
         }
         BoundaryKind::Sony => {
-            let first = civil::date(today.year() + 1, 1, 1);
+            let first = date(today.year() + 1, 1, 1)?;
             zoned_midnight(first, &tz)
         }
         BoundaryKind::Eony => {
-            let last = civil::date(today.year() + 1, 12, 31);
+            let last = date(today.year() + 1, 12, 31)?;
             zoned_end_of_day(last, &tz)
         }
     }
@@ -609,17 +687,13 @@ fn resolve_boundary(kind: &BoundaryKind, now: &Zoned) -> Result<Zoned, ParseErro
 /// Create a Zoned at midnight (00:00:00.000000000) for the given date.
 fn zoned_midnight(date: civil::Date, tz: &jiff::tz::TimeZone) -> Result<Zoned, ParseError> {
     let dt = date.at(0, 0, 0, 0);
-    tz.to_ambiguous_zoned(dt)
-        .compatible()
-        .map_err(|e| ParseError::resolution(format!("ambiguous datetime: {e}")))
+    tz.to_ambiguous_zoned(dt).compatible().map_err(out_of_range)
 }
 
 /// Create a Zoned at end of day (23:59:59.999999999) for the given date.
 fn zoned_end_of_day(date: civil::Date, tz: &jiff::tz::TimeZone) -> Result<Zoned, ParseError> {
     let dt = date.at(23, 59, 59, 999_999_999);
-    tz.to_ambiguous_zoned(dt)
-        .compatible()
-        .map_err(|e| ParseError::resolution(format!("ambiguous datetime: {e}")))
+    tz.to_ambiguous_zoned(dt).compatible().map_err(out_of_range)
 }
 
 /// Compute month range: first day at midnight to last day at end of day.
@@ -628,16 +702,16 @@ fn month_range(
     month: i8,
     tz: &jiff::tz::TimeZone,
 ) -> Result<(Zoned, Zoned), ParseError> {
-    let first = civil::date(year, month, 1);
+    let first = date(year, month, 1)?;
     let days = first.days_in_month();
-    let last = civil::date(year, month, days);
+    let last = date(year, month, days)?;
     Ok((zoned_midnight(first, tz)?, zoned_end_of_day(last, tz)?))
 }
 
 /// Compute year range: Jan 1 at midnight to Dec 31 at end of day.
 fn year_range(year: i16, tz: &jiff::tz::TimeZone) -> Result<(Zoned, Zoned), ParseError> {
-    let first = civil::date(year, 1, 1);
-    let last = civil::date(year, 12, 31);
+    let first = date(year, 1, 1)?;
+    let last = date(year, 12, 31)?;
     Ok((zoned_midnight(first, tz)?, zoned_end_of_day(last, tz)?))
 }
 
@@ -655,9 +729,9 @@ fn quarter_range(year: i16, q: i8, tz: &jiff::tz::TimeZone) -> Result<(Zoned, Zo
             )));
         }
     };
-    let first = civil::date(year, start_month, 1);
-    let end_date = civil::date(year, end_month, 1);
-    let last = civil::date(year, end_month, end_date.days_in_month());
+    let first = date(year, start_month, 1)?;
+    let end_date = date(year, end_month, 1)?;
+    let last = date(year, end_month, end_date.days_in_month())?;
     Ok((zoned_midnight(first, tz)?, zoned_end_of_day(last, tz)?))
 }
 
@@ -685,38 +759,41 @@ fn resolve_offset_from(
     comps: &[DurationComponent],
     base: &DateExpr,
     now: &Zoned,
+    opts: &ParseOptions,
 ) -> Result<Zoned, ParseError> {
-    let base_zoned = resolve(base, now)?;
-    resolve_offset(dir, comps, &base_zoned)
+    let base_zoned = resolve(base, now, opts)?;
+    resolve_offset(dir, comps, &base_zoned, opts)
 }
 
-/// Build a `jiff::Span` from a list of duration components.
-fn build_span(comps: &[DurationComponent]) -> Span {
-    let mut span = Span::new();
-    for comp in comps {
-        span = match comp.unit {
-            TemporalUnit::Year => span.years(comp.count),
-            TemporalUnit::Month => span.months(comp.count),
-            TemporalUnit::Week => span.weeks(comp.count),
-            TemporalUnit::Day => span.days(comp.count),
-            TemporalUnit::Hour => span.hours(comp.count),
-            TemporalUnit::Minute => span.minutes(comp.count),
-            TemporalUnit::Second => span.seconds(comp.count),
-        };
-    }
-    span
+/// Build a `jiff::Span` from a list of duration components.  A count too
+/// large for a span (`in 20000 years`) is out of range like its result.
+fn build_span(comps: &[DurationComponent]) -> Result<Span, ParseError> {
+    comps.iter().try_fold(Span::new(), |span, comp| {
+        match comp.unit {
+            TemporalUnit::Year => span.try_years(comp.count),
+            TemporalUnit::Month => span.try_months(comp.count),
+            TemporalUnit::Week => span.try_weeks(comp.count),
+            TemporalUnit::Day => span.try_days(comp.count),
+            TemporalUnit::Hour => span.try_hours(comp.count),
+            TemporalUnit::Minute => span.try_minutes(comp.count),
+            TemporalUnit::Second => span.try_seconds(comp.count),
+        }
+        .map_err(out_of_range)
+    })
 }
 
-/// Apply a TimeExpr to a date, or use midnight.
+/// Apply a TimeExpr to a date, or use the
+/// [default time](ParseOptions::default_time) of `opts`.
 /// Accepts a `now` reference so that `SameTime` can extract the current time.
-fn apply_time_or_midnight(
+fn apply_time_or_default(
     date: civil::Date,
     time: &Option<TimeExpr>,
     now: &Zoned,
+    opts: &ParseOptions,
 ) -> civil::DateTime {
     match time {
         Some(t) => apply_time(date, t, Some(now)),
-        None => date.at(0, 0, 0, 0),
+        None => date.to_datetime(opts.default_time),
     }
 }
 
@@ -760,35 +837,55 @@ mod tests {
     #[test]
     fn resolve_now() {
         let now = make_now();
-        let result = resolve(&DateExpr::Now, &now).unwrap();
+        let result = resolve(&DateExpr::Now, &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T12:00:00");
     }
 
     #[test]
     fn resolve_today_midnight() {
         let now = make_now();
-        let result = resolve(&DateExpr::Relative(RelativeDate::Today, None), &now).unwrap();
+        let result = resolve(
+            &DateExpr::Relative(RelativeDate::Today, None),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T00:00:00");
     }
 
     #[test]
     fn resolve_tomorrow() {
         let now = make_now();
-        let result = resolve(&DateExpr::Relative(RelativeDate::Tomorrow, None), &now).unwrap();
+        let result = resolve(
+            &DateExpr::Relative(RelativeDate::Tomorrow, None),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T00:00:00");
     }
 
     #[test]
     fn resolve_yesterday() {
         let now = make_now();
-        let result = resolve(&DateExpr::Relative(RelativeDate::Yesterday, None), &now).unwrap();
+        let result = resolve(
+            &DateExpr::Relative(RelativeDate::Yesterday, None),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-14T00:00:00");
     }
 
     #[test]
     fn resolve_overmorrow() {
         let now = make_now();
-        let result = resolve(&DateExpr::Relative(RelativeDate::Overmorrow, None), &now).unwrap();
+        let result = resolve(
+            &DateExpr::Relative(RelativeDate::Overmorrow, None),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-17T00:00:00");
     }
 
@@ -798,6 +895,7 @@ mod tests {
         let result = resolve(
             &DateExpr::Relative(RelativeDate::Today, Some(TimeExpr::HourMinute(18, 30))),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T18:30:00");
@@ -809,6 +907,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::Next, Weekday::Friday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-20T00:00:00");
@@ -820,6 +919,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::Next, Weekday::Sunday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-22T00:00:00");
@@ -831,6 +931,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::This, Weekday::Sunday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T00:00:00");
@@ -842,6 +943,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::Last, Weekday::Sunday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-08T00:00:00");
@@ -859,6 +961,7 @@ mod tests {
                 }],
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-18T12:00:00");
@@ -876,6 +979,7 @@ mod tests {
                 }],
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T11:00:00");
@@ -890,6 +994,7 @@ mod tests {
                 precision: EpochPrecision::Seconds,
             }),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-01-01T00:00:00");
@@ -904,6 +1009,7 @@ mod tests {
                 precision: EpochPrecision::Seconds,
             }),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "1969-12-31T00:00:00");
@@ -922,6 +1028,7 @@ mod tests {
                 None,
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-01-01T00:00:00");
@@ -940,6 +1047,7 @@ mod tests {
                 Some(TimeExpr::HourMinuteSecond(13, 25, 30)),
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2022-11-07T13:25:30");
@@ -958,6 +1066,7 @@ mod tests {
                 None,
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-03-24T00:00:00");
@@ -966,7 +1075,12 @@ mod tests {
     #[test]
     fn resolve_time_only() {
         let now = make_now();
-        let result = resolve(&DateExpr::TimeOnly(TimeExpr::HourMinute(15, 30)), &now).unwrap();
+        let result = resolve(
+            &DateExpr::TimeOnly(TimeExpr::HourMinute(15, 30)),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T15:30:00");
     }
 
@@ -982,6 +1096,7 @@ mod tests {
                 }],
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-08T12:00:00");
@@ -999,6 +1114,7 @@ mod tests {
                 }],
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-05-15T12:00:00");
@@ -1016,6 +1132,7 @@ mod tests {
                 }],
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2024-06-15T12:00:00");
@@ -1034,6 +1151,7 @@ mod tests {
                 Box::new(DateExpr::Relative(RelativeDate::Tomorrow, None)),
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T21:00:00");
@@ -1052,6 +1170,7 @@ mod tests {
                 }],
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T03:00:00");
@@ -1084,6 +1203,7 @@ mod tests {
                 }],
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T14:30:00");
@@ -1105,11 +1225,57 @@ mod tests {
                 }],
             ),
             &jan31,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-02-28T12:00:00");
     }
 
+    #[test]
+    fn add_span_month_overflow_policies() {
+        let at = |y, m, d| {
+            let dt = civil::date(y, m, d).at(12, 0, 0, 0);
+            utc().to_ambiguous_zoned(dt).compatible().unwrap()
+        };
+        let add =
+            |z: &Zoned, span: Span, policy| add_span(z, span, policy).map(|r| format_zoned(&r));
+        let jan31 = at(2025, 1, 31);
+        let leap = at(2024, 2, 29);
+        let month = Span::new().months(1);
+
+        assert_eq!(
+            add(&jan31, month, MonthOverflow::Clamp).unwrap(),
+            "2025-02-28T12:00:00"
+        );
+        assert_eq!(
+            add(&jan31, month, MonthOverflow::Overflow).unwrap(),
+            "2025-03-03T12:00:00"
+        );
+        assert_eq!(
+            add(&leap, Span::new().years(1), MonthOverflow::Overflow).unwrap(),
+            "2025-03-01T12:00:00"
+        );
+        assert_eq!(
+            add(
+                &at(2025, 3, 31),
+                month.negate().hours(2),
+                MonthOverflow::Overflow
+            )
+            .unwrap(),
+            "2025-03-03T10:00:00"
+        );
+        assert_eq!(
+            add(&at(2025, 1, 15), month, MonthOverflow::Error).unwrap(),
+            "2025-02-15T12:00:00"
+        );
+        let err = add(&jan31, month, MonthOverflow::Error).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("2025-01-31 + 1mo falls on 2025-02-31, which does not exist"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn resolve_arithmetic_next_friday_minus_1_week() {
         let now = make_now();
@@ -1123,6 +1289,7 @@ mod tests {
                 }],
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-13T00:00:00");
@@ -1131,14 +1298,24 @@ mod tests {
     #[test]
     fn resolve_range_returns_start_of_period() {
         let now = make_wednesday();
-        let result = resolve(&DateExpr::Range(RangeExpr::ThisWeek), &now).unwrap();
+        let result = resolve(
+            &DateExpr::Range(RangeExpr::ThisWeek),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T00:00:00");
     }
 
     #[test]
     fn resolve_range_start_next_month() {
         let now = make_wednesday();
-        let result = resolve(&DateExpr::Range(RangeExpr::NextMonth), &now).unwrap();
+        let result = resolve(
+            &DateExpr::Range(RangeExpr::NextMonth),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-07-01T00:00:00");
     }
 
@@ -1148,6 +1325,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::This, Weekday::Monday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T00:00:00");
@@ -1159,6 +1337,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::This, Weekday::Friday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-20T00:00:00");
@@ -1170,6 +1349,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::This, Weekday::Saturday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-21T00:00:00");
@@ -1181,6 +1361,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::Last, Weekday::Saturday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-14T00:00:00");
@@ -1192,6 +1373,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::Last, Weekday::Monday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-09T00:00:00");
@@ -1203,6 +1385,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::Next, Weekday::Monday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T00:00:00");
@@ -1214,6 +1397,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::Next, Weekday::Saturday, None),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-21T00:00:00");
@@ -1222,105 +1406,124 @@ mod tests {
     #[test]
     fn parse_now_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("now", &now).unwrap();
+        let result = crate::parser::parse("now", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T12:00:00");
     }
 
     #[test]
     fn parse_tomorrow_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("tomorrow", &now).unwrap();
+        let result = crate::parser::parse("tomorrow", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T00:00:00");
     }
 
     #[test]
     fn parse_in_3_days_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("in 3 days", &now).unwrap();
+        let result = crate::parser::parse("in 3 days", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-18T12:00:00");
     }
 
     #[test]
     fn parse_epoch_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("@1735689600", &now).unwrap();
+        let result = crate::parser::parse("@1735689600", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-01-01T00:00:00");
     }
 
     #[test]
     fn parse_empty_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("", &now).unwrap();
+        let result = crate::parser::parse("", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T12:00:00");
     }
 
+    #[test]
+    fn parse_options_apply_per_call() {
+        let now = make_now();
+        let opts = ParseOptions {
+            month_overflow: MonthOverflow::Overflow,
+            default_time: civil::time(9, 0, 0, 0),
+        };
+        let parse = |input: &str, opts: &ParseOptions| {
+            format_zoned(&crate::parser::parse(input, &now, opts).unwrap())
+        };
+        assert_eq!(parse("tomorrow", &opts), "2025-06-16T09:00:00");
+        assert_eq!(parse("tomorrow 18:00", &opts), "2025-06-16T18:00:00");
+        assert_eq!(parse("2025-01-31 + 1 month", &opts), "2025-03-03T09:00:00");
+        let clamp = ParseOptions::default();
+        assert_eq!(parse("2025-01-31 + 1 month", &clamp), "2025-02-28T00:00:00");
+    }
+
     #[test]
     fn parse_error_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("???", &now);
+        let result = crate::parser::parse("???", &now, &ParseOptions::default());
         assert!(result.is_err());
     }
 
     #[test]
     fn parse_whitespace_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("   ", &now).unwrap();
+        let result = crate::parser::parse("   ", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T12:00:00");
     }
 
     #[test]
     fn parse_next_friday_17_00_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("next friday 17:00", &now).unwrap();
+        let result =
+            crate::parser::parse("next friday 17:00", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-20T17:00:00");
     }
 
     #[test]
     fn parse_a_week_ago_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("a week ago", &now).unwrap();
+        let result = crate::parser::parse("a week ago", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-08T12:00:00");
     }
 
     #[test]
     fn parse_an_hour_ago_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("an hour ago", &now).unwrap();
+        let result = crate::parser::parse("an hour ago", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T11:00:00");
     }
 
     #[test]
     fn parse_iso_date_with_time_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("2022-11-07 13:25:30", &now).unwrap();
+        let result =
+            crate::parser::parse("2022-11-07 13:25:30", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2022-11-07T13:25:30");
     }
 
     #[test]
     fn parse_today_18_30_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("today 18:30", &now).unwrap();
+        let result = crate::parser::parse("today 18:30", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T18:30:00");
     }
 
     #[test]
     fn parse_this_sunday_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("this sunday", &now).unwrap();
+        let result = crate::parser::parse("this sunday", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T00:00:00");
     }
 
     #[test]
     fn parse_next_sunday_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("next sunday", &now).unwrap();
+        let result = crate::parser::parse("next sunday", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-22T00:00:00");
     }
 
     #[test]
     fn parse_last_sunday_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("last sunday", &now).unwrap();
+        let result = crate::parser::parse("last sunday", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-08T00:00:00");
     }
 
@@ -1435,57 +1638,74 @@ mod tests {
     #[test]
     fn parse_tomorrow_plus_3_hours_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("tomorrow + 3 hours", &now).unwrap();
+        let result =
+            crate::parser::parse("tomorrow + 3 hours", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T03:00:00");
     }
 
     #[test]
     fn parse_now_plus_1_day_plus_3_hours_minus_30_minutes_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("now + 1 day + 3 hours - 30 minutes", &now).unwrap();
+        let result = crate::parser::parse(
+            "now + 1 day + 3 hours - 30 minutes",
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T14:30:00");
     }
 
     #[test]
     fn parse_3_hours_after_tomorrow_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("3 hours after tomorrow", &now).unwrap();
+        let result =
+            crate::parser::parse("3 hours after tomorrow", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T03:00:00");
     }
 
     #[test]
     fn parse_2_days_before_next_friday_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("2 days before next friday", &now).unwrap();
+        let result =
+            crate::parser::parse("2 days before next friday", &now, &ParseOptions::default())
+                .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-18T00:00:00");
     }
 
     #[test]
     fn parse_next_friday_minus_1_week_e2e() {
         let now = make_now();
-        let result = crate::parser::parse("next friday - 1 week", &now).unwrap();
+        let result =
+            crate::parser::parse("next friday - 1 week", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-13T00:00:00");
     }
 
     #[test]
     fn parse_verbal_and_infix_same_result() {
         let now = make_now();
-        let verbal = crate::parser::parse("3 hours after tomorrow", &now).unwrap();
-        let infix = crate::parser::parse("tomorrow + 3 hours", &now).unwrap();
+        let verbal =
+            crate::parser::parse("3 hours after tomorrow", &now, &ParseOptions::default()).unwrap();
+        let infix =
+            crate::parser::parse("tomorrow + 3 hours", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&verbal), format_zoned(&infix));
     }
 
     #[test]
     fn parse_last_week_returns_period_start_e2e() {
         let now = make_wednesday();
-        let result = crate::parser::parse("last week", &now).unwrap();
+        let result = crate::parser::parse("last week", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-09T00:00:00");
     }
 
     #[test]
     fn parse_range_last_week_produces_range_e2e() {
         let now = make_wednesday();
-        let (start, end) = crate::parser::parse_range_with_granularity("last week", &now).unwrap();
+        let (start, end) = crate::parser::parse_range_with_granularity(
+            "last week",
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&start), "2025-06-09T00:00:00");
         assert_eq!(format_zoned(&end), "2025-06-15T23:59:59");
     }
@@ -1493,7 +1713,12 @@ mod tests {
     #[test]
     fn parse_range_this_month_e2e() {
         let now = make_wednesday();
-        let (start, end) = crate::parser::parse_range_with_granularity("this month", &now).unwrap();
+        let (start, end) = crate::parser::parse_range_with_granularity(
+            "this month",
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&start), "2025-06-01T00:00:00");
         assert_eq!(format_zoned(&end), "2025-06-30T23:59:59");
     }
@@ -1501,7 +1726,9 @@ mod tests {
     #[test]
     fn parse_range_q3_2025_e2e() {
         let now = make_wednesday();
-        let (start, end) = crate::parser::parse_range_with_granularity("Q3 2025", &now).unwrap();
+        let (start, end) =
+            crate::parser::parse_range_with_granularity("Q3 2025", &now, &ParseOptions::default())
+                .unwrap();
         assert_eq!(format_zoned(&start), "2025-07-01T00:00:00");
         assert_eq!(format_zoned(&end), "2025-09-30T23:59:59");
     }
@@ -1509,7 +1736,9 @@ mod tests {
     #[test]
     fn parse_range_with_granularity_resolves_non_range_as_day() {
         let now = make_now();
-        let (start, end) = crate::parser::parse_range_with_granularity("tomorrow", &now).unwrap();
+        let (start, end) =
+            crate::parser::parse_range_with_granularity("tomorrow", &now, &ParseOptions::default())
+                .unwrap();
         let start_str = format_zoned(&start);
         let end_str = format_zoned(&end);
         assert!(
@@ -1530,154 +1759,314 @@ mod tests {
     #[test]
     fn resolve_boundary_sod() {
         let now = boundary_now();
-        let result = resolve(&DateExpr::Boundary(BoundaryKind::Sod), &now).unwrap();
+        let result = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sod),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-18T00:00:00");
     }
 
     #[test]
     fn resolve_boundary_eod() {
         let now = boundary_now();
-        let result = resolve(&DateExpr::Boundary(BoundaryKind::Eod), &now).unwrap();
+        let result = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eod),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-18T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sow() {
         let now = boundary_now();
-        let result = resolve(&DateExpr::Boundary(BoundaryKind::Sow), &now).unwrap();
+        let result = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sow),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T00:00:00");
     }
 
     #[test]
     fn resolve_boundary_eow() {
         let now = boundary_now();
-        let result = resolve(&DateExpr::Boundary(BoundaryKind::Eow), &now).unwrap();
+        let result = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eow),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-22T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_soww_eoww() {
         let now = boundary_now();
-        let soww = resolve(&DateExpr::Boundary(BoundaryKind::Soww), &now).unwrap();
+        let soww = resolve(
+            &DateExpr::Boundary(BoundaryKind::Soww),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&soww), "2025-06-16T00:00:00");
-        let eoww = resolve(&DateExpr::Boundary(BoundaryKind::Eoww), &now).unwrap();
+        let eoww = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eoww),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eoww), "2025-06-20T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_som_eom() {
         let now = boundary_now();
-        let som = resolve(&DateExpr::Boundary(BoundaryKind::Som), &now).unwrap();
+        let som = resolve(
+            &DateExpr::Boundary(BoundaryKind::Som),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&som), "2025-06-01T00:00:00");
-        let eom = resolve(&DateExpr::Boundary(BoundaryKind::Eom), &now).unwrap();
+        let eom = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eom),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eom), "2025-06-30T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_soq_eoq() {
         let now = boundary_now();
-        let soq = resolve(&DateExpr::Boundary(BoundaryKind::Soq), &now).unwrap();
+        let soq = resolve(
+            &DateExpr::Boundary(BoundaryKind::Soq),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&soq), "2025-04-01T00:00:00");
-        let eoq = resolve(&DateExpr::Boundary(BoundaryKind::Eoq), &now).unwrap();
+        let eoq = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eoq),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eoq), "2025-06-30T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_soy_eoy() {
         let now = boundary_now();
-        let soy = resolve(&DateExpr::Boundary(BoundaryKind::Soy), &now).unwrap();
+        let soy = resolve(
+            &DateExpr::Boundary(BoundaryKind::Soy),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&soy), "2025-01-01T00:00:00");
-        let eoy = resolve(&DateExpr::Boundary(BoundaryKind::Eoy), &now).unwrap();
+        let eoy = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eoy),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eoy), "2025-12-31T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sopd_eopd() {
         let now = boundary_now();
-        let sopd = resolve(&DateExpr::Boundary(BoundaryKind::Sopd), &now).unwrap();
+        let sopd = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sopd),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&sopd), "2025-06-17T00:00:00");
-        let eopd = resolve(&DateExpr::Boundary(BoundaryKind::Eopd), &now).unwrap();
+        let eopd = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eopd),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eopd), "2025-06-17T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sonw_eonw() {
         let now = boundary_now();
-        let sonw = resolve(&DateExpr::Boundary(BoundaryKind::Sonw), &now).unwrap();
+        let sonw = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sonw),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&sonw), "2025-06-23T00:00:00");
-        let eonw = resolve(&DateExpr::Boundary(BoundaryKind::Eonw), &now).unwrap();
+        let eonw = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eonw),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eonw), "2025-06-29T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sopw_eopw() {
         let now = boundary_now();
-        let sopw = resolve(&DateExpr::Boundary(BoundaryKind::Sopw), &now).unwrap();
+        let sopw = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sopw),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&sopw), "2025-06-09T00:00:00");
-        let eopw = resolve(&DateExpr::Boundary(BoundaryKind::Eopw), &now).unwrap();
+        let eopw = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eopw),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eopw), "2025-06-15T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sopm_eopm() {
         let now = boundary_now();
-        let sopm = resolve(&DateExpr::Boundary(BoundaryKind::Sopm), &now).unwrap();
+        let sopm = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sopm),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&sopm), "2025-05-01T00:00:00");
-        let eopm = resolve(&DateExpr::Boundary(BoundaryKind::Eopm), &now).unwrap();
+        let eopm = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eopm),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eopm), "2025-05-31T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sonm_eonm() {
         let now = boundary_now();
-        let sonm = resolve(&DateExpr::Boundary(BoundaryKind::Sonm), &now).unwrap();
+        let sonm = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sonm),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&sonm), "2025-07-01T00:00:00");
-        let eonm = resolve(&DateExpr::Boundary(BoundaryKind::Eonm), &now).unwrap();
+        let eonm = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eonm),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eonm), "2025-07-31T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sopq_eopq() {
         let now = boundary_now();
-        let sopq = resolve(&DateExpr::Boundary(BoundaryKind::Sopq), &now).unwrap();
+        let sopq = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sopq),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&sopq), "2025-01-01T00:00:00");
-        let eopq = resolve(&DateExpr::Boundary(BoundaryKind::Eopq), &now).unwrap();
+        let eopq = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eopq),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eopq), "2025-03-31T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sonq_eonq() {
         let now = boundary_now();
-        let sonq = resolve(&DateExpr::Boundary(BoundaryKind::Sonq), &now).unwrap();
+        let sonq = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sonq),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&sonq), "2025-07-01T00:00:00");
-        let eonq = resolve(&DateExpr::Boundary(BoundaryKind::Eonq), &now).unwrap();
+        let eonq = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eonq),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eonq), "2025-09-30T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sopy_eopy() {
         let now = boundary_now();
-        let sopy = resolve(&DateExpr::Boundary(BoundaryKind::Sopy), &now).unwrap();
+        let sopy = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sopy),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&sopy), "2024-01-01T00:00:00");
-        let eopy = resolve(&DateExpr::Boundary(BoundaryKind::Eopy), &now).unwrap();
+        let eopy = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eopy),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eopy), "2024-12-31T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sony_eony() {
         let now = boundary_now();
-        let sony = resolve(&DateExpr::Boundary(BoundaryKind::Sony), &now).unwrap();
+        let sony = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sony),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&sony), "2026-01-01T00:00:00");
-        let eony = resolve(&DateExpr::Boundary(BoundaryKind::Eony), &now).unwrap();
+        let eony = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eony),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eony), "2026-12-31T23:59:59");
     }
 
     #[test]
     fn resolve_boundary_sond_eond() {
         let now = boundary_now();
-        let sond = resolve(&DateExpr::Boundary(BoundaryKind::Sond), &now).unwrap();
+        let sond = resolve(
+            &DateExpr::Boundary(BoundaryKind::Sond),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&sond), "2025-06-19T00:00:00");
-        let eond = resolve(&DateExpr::Boundary(BoundaryKind::Eond), &now).unwrap();
+        let eond = resolve(
+            &DateExpr::Boundary(BoundaryKind::Eond),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&eond), "2025-06-19T23:59:59");
     }
 
@@ -1687,6 +2076,7 @@ mod tests {
         let result = resolve(
             &DateExpr::Relative(RelativeDate::Today, Some(TimeExpr::HourOnly(18))),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-18T18:00:00");
@@ -1695,21 +2085,21 @@ mod tests {
     #[test]
     fn parse_eod_e2e() {
         let now = boundary_now();
-        let result = crate::parser::parse("eod", &now).unwrap();
+        let result = crate::parser::parse("eod", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-18T23:59:59");
     }
 
     #[test]
     fn parse_sod_e2e() {
         let now = boundary_now();
-        let result = crate::parser::parse("sod", &now).unwrap();
+        let result = crate::parser::parse("sod", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-18T00:00:00");
     }
 
     #[test]
     fn parse_eod_plus_1h_e2e() {
         let now = boundary_now();
-        let result = crate::parser::parse("eod + 1h", &now).unwrap();
+        let result = crate::parser::parse("eod + 1h", &now, &ParseOptions::default()).unwrap();
         let formatted = result.strftime("%Y-%m-%dT%H:%M").to_string();
         assert_eq!(formatted, "2025-06-19T00:59");
     }
@@ -1717,44 +2107,47 @@ mod tests {
     #[test]
     fn parse_plus_3h_e2e() {
         let now = boundary_now();
-        let result = crate::parser::parse("+3h", &now).unwrap();
+        let result = crate::parser::parse("+3h", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-18T17:30:00");
     }
 
     #[test]
     fn parse_minus_1d_e2e() {
         let now = boundary_now();
-        let result = crate::parser::parse("-1d", &now).unwrap();
+        let result = crate::parser::parse("-1d", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-17T14:30:00");
     }
 
     #[test]
     fn parse_today_18h_e2e() {
         let now = boundary_now();
-        let result = crate::parser::parse("today 18h", &now).unwrap();
+        let result = crate::parser::parse("today 18h", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-18T18:00:00");
     }
 
     #[test]
     fn parse_now_plus_13h30_e2e() {
         let now = boundary_now();
-        let result = crate::parser::parse("now+13h30", &now).unwrap();
+        let result = crate::parser::parse("now+13h30", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-19T04:00:00");
     }
 
     #[test]
     fn parse_now_plus_colon_duration_e2e() {
         let now = boundary_now();
-        let result = crate::parser::parse("now+13:30", &now).unwrap();
+        let result = crate::parser::parse("now+13:30", &now, &ParseOptions::default()).unwrap();
         assert_eq!(format_zoned(&result), "2025-06-19T04:00:00");
     }
 
     #[test]
     fn range_granularity_day() {
         let now = make_now();
-        let (start, end) =
-            resolve_range_with_granularity(&DateExpr::Relative(RelativeDate::Tomorrow, None), &now)
-                .unwrap();
+        let (start, end) = resolve_range_with_granularity(
+            &DateExpr::Relative(RelativeDate::Tomorrow, None),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         let (s, e) = format_range(&start, &end);
         assert_eq!(s, "2025-06-16T00:00:00");
         assert_eq!(e, "2025-06-16T23:59:59");
@@ -1766,6 +2159,7 @@ mod tests {
         let (start, end) = resolve_range_with_granularity(
             &DateExpr::Relative(RelativeDate::Tomorrow, Some(TimeExpr::HourOnly(18))),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         let (s, e) = format_range(&start, &end);
@@ -1779,6 +2173,7 @@ mod tests {
         let (start, end) = resolve_range_with_granularity(
             &DateExpr::Relative(RelativeDate::Tomorrow, Some(TimeExpr::HourMinute(18, 30))),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         let (s, e) = format_range(&start, &end);
@@ -1795,6 +2190,7 @@ mod tests {
                 Some(TimeExpr::HourMinuteSecond(18, 30, 45)),
             ),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         let (s, e) = format_range(&start, &end);
@@ -1805,7 +2201,8 @@ mod tests {
     #[test]
     fn range_granularity_now_is_instant() {
         let now = make_now();
-        let (start, end) = resolve_range_with_granularity(&DateExpr::Now, &now).unwrap();
+        let (start, end) =
+            resolve_range_with_granularity(&DateExpr::Now, &now, &ParseOptions::default()).unwrap();
         assert_eq!(start, end);
         assert_eq!(format_zoned(&start), "2025-06-15T12:00:00");
     }
@@ -1813,8 +2210,12 @@ mod tests {
     #[test]
     fn range_granularity_this_week_uses_resolve_range() {
         let now = make_wednesday();
-        let (start, end) =
-            resolve_range_with_granularity(&DateExpr::Range(RangeExpr::ThisWeek), &now).unwrap();
+        let (start, end) = resolve_range_with_granularity(
+            &DateExpr::Range(RangeExpr::ThisWeek),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         let (s, e) = format_range(&start, &end);
         assert_eq!(s, "2025-06-16T00:00:00");
         assert_eq!(e, "2025-06-22T23:59:59");
@@ -1823,8 +2224,12 @@ mod tests {
     #[test]
     fn range_granularity_boundary_is_instant() {
         let now = make_wednesday();
-        let (start, end) =
-            resolve_range_with_granularity(&DateExpr::Boundary(BoundaryKind::Eod), &now).unwrap();
+        let (start, end) = resolve_range_with_granularity(
+            &DateExpr::Boundary(BoundaryKind::Eod),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(start, end);
         assert_eq!(format_zoned(&start), "2025-06-18T23:59:59");
     }
@@ -1835,6 +2240,7 @@ mod tests {
         let result = resolve(
             &DateExpr::Relative(RelativeDate::Tomorrow, Some(TimeExpr::SameTime)),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T12:00:00");
@@ -1846,6 +2252,7 @@ mod tests {
         let result = resolve(
             &DateExpr::Relative(RelativeDate::Yesterday, Some(TimeExpr::SameTime)),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-14T12:00:00");
@@ -1857,6 +2264,7 @@ mod tests {
         let result = resolve(
             &DateExpr::DayRef(Direction::Next, Weekday::Friday, Some(TimeExpr::SameTime)),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-20T12:00:00");
@@ -1869,6 +2277,7 @@ mod tests {
         let result = resolve(
             &DateExpr::Relative(RelativeDate::Tomorrow, Some(TimeExpr::SameTime)),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T12:34:56");
@@ -1877,14 +2286,24 @@ mod tests {
     #[test]
     fn resolve_3pm_time_only() {
         let now = make_now();
-        let result = resolve(&DateExpr::TimeOnly(TimeExpr::HourMinute(15, 0)), &now).unwrap();
+        let result = resolve(
+            &DateExpr::TimeOnly(TimeExpr::HourMinute(15, 0)),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T15:00:00");
     }
 
     #[test]
     fn resolve_12am_time_only() {
         let now = make_now();
-        let result = resolve(&DateExpr::TimeOnly(TimeExpr::HourMinute(0, 0)), &now).unwrap();
+        let result = resolve(
+            &DateExpr::TimeOnly(TimeExpr::HourMinute(0, 0)),
+            &now,
+            &ParseOptions::default(),
+        )
+        .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-15T00:00:00");
     }
 
@@ -1894,6 +2313,7 @@ mod tests {
         let result = resolve(
             &DateExpr::Relative(RelativeDate::Tomorrow, Some(TimeExpr::HourMinute(15, 0))),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(format_zoned(&result), "2025-06-16T15:00:00");
@@ -1905,6 +2325,7 @@ mod tests {
         let (start, end) = resolve_range_with_granularity(
             &DateExpr::Relative(RelativeDate::Tomorrow, Some(TimeExpr::SameTime)),
             &now,
+            &ParseOptions::default(),
         )
         .unwrap();
         assert_eq!(start, end);
@@ -1,9 +1,10 @@
 //! Typo correction via Levenshtein distance for near-miss keyword suggestions.
 //!
 //! Uses the compile-time [`KEYWORD_LIST`](super::lexer::KEYWORD_LIST) for
-//! near-miss suggestions. Hand-rolled implementation (~20 lines DP algorithm)
-//! instead of adding a `strsim` dependency, per the project's "fewer is better"
-//! dependency philosophy.
+//! near-miss suggestions, either for a single word or for a whole phrase
+//! whose corrected form is checked against the grammar. Hand-rolled
+//! implementation (~20 lines DP algorithm) instead of adding a `strsim`
+//! dependency, per the project's "fewer is better" dependency philosophy.
 
 /// Compute Levenshtein edit distance between two strings.
 pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
@@ -64,6 +65,51 @@ pub(crate) fn suggest_keyword(word: &str, max_distance: usize) -> Option<String>
     best.map(|(kw, _)| kw)
 }
 
+/// Suggest a corrected version of a whole expression that failed to parse.
+///
+/// Every unrecognized word is replaced by its nearest keyword, or split into
+/// two keywords when it looks like a missing space (`nextfriday`).  The
+/// result is only returned if the corrected phrase parses.
+pub(crate) fn suggest_phrase(input: &str) -> Option<String> {
+    use super::{grammar, lexer, token::Token};
+
+    let tokens = lexer::tokenize(input);
+    let mut out = String::with_capacity(input.len());
+    let mut last = 0;
+
+    for tok in &tokens {
+        let Token::Word(word) = &tok.kind else {
+            continue;
+        };
+        let max_distance = if word.len() <= 3 { 1 } else { 2 };
+        let fix = suggest_keyword(word, max_distance).or_else(|| split_word(word))?;
+        out.push_str(&input[last..tok.span.start]);
+        out.push_str(&fix);
+        last = tok.span.end;
+    }
+    if last == 0 {
+        return None;
+    }
+    out.push_str(&input[last..]);
+
+    let fixed = lexer::tokenize(&out);
+    grammar::Parser::new(&fixed, &out)
+        .parse_expression()
+        .ok()
+        .map(|_| out)
+}
+
+/// Split a word into two exact keywords, e.g. `nextfriday` -> `next friday`.
+fn split_word(word: &str) -> Option<String> {
+    let lower = word.to_ascii_lowercase();
+    let is_keyword = |w: &str| super::lexer::KEYWORD_LIST.iter().any(|&(kw, _)| kw == w);
+    (2..lower.len().saturating_sub(1))
+        .filter(|&i| lower.is_char_boundary(i))
+        .map(|i| lower.split_at(i))
+        .find(|(head, tail)| is_keyword(head) && is_keyword(tail))
+        .map(|(head, tail)| format!("{head} {tail}"))
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used, clippy::expect_used)]
@@ -119,4 +165,27 @@ mod tests {
         let suggestion = suggest_keyword("wednesday", 2);
         assert!(suggestion.is_none());
     }
+
+    #[test]
+    fn phrase_with_several_typos_is_corrected() {
+        assert_eq!(
+            suggest_phrase("nxet fridya at 9:30").as_deref(),
+            Some("next friday at 9:30")
+        );
+    }
+
+    #[test]
+    fn phrase_with_missing_space_is_split() {
+        assert_eq!(suggest_phrase("nextfriday").as_deref(), Some("next friday"));
+    }
+
+    #[test]
+    fn phrase_without_unknown_words_has_no_suggestion() {
+        assert_eq!(suggest_phrase("next next"), None);
+    }
+
+    #[test]
+    fn phrase_that_still_fails_has_no_suggestion() {
+        assert_eq!(suggest_phrase("xyzzy friday"), None);
+    }
 }
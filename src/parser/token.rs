@@ -109,6 +109,8 @@ pub enum Token {
     Weekday(jiff::civil::Weekday),
     Month(i8),
     Number(i64),
+    /// An integer too large for `i64`, only valid as an epoch value.
+    WideNumber(i128),
     Colon,
     Dash,
     Slash,
@@ -0,0 +1,696 @@
+//! Recurring schedules for **TARDIS**.
+//!
+//! [`Recurrence::parse`] understands a small language of repeating times:
+//!
+//! * fixed intervals -- `every 15 minutes`, `every 2h`, `hourly`;
+//! * days at a time -- `every day at 9`, `every weekday at 9:30am`,
+//!   `every mon, wed and fri at 18:00`, `every weekend`;
+//! * every other week or unit -- `every other monday`, `every other day`;
+//! * five-field cron expressions -- `*/15 * * * *`, `0 9 * * 1-5`, with
+//!   cron's rule that a day matching either a restricted day of the month
+//!   or a restricted day of the week fires.
+//!
+//! A trailing `starting DATE` clause anchors a schedule: intervals count
+//! from `DATE` and days-of-week schedules never fire before it, with
+//! `every other` weeks counted from the week holding it.
+//!
+//! Intervals that divide a day evenly are aligned to local midnight, so
+//! `every 15 minutes` fires at `:00`, `:15`, `:30` and `:45`; other
+//! intervals count from the moment the schedule was parsed.  Occurrences
+//! are always computed from the schedule itself rather than from the
+//! previous run, so a slow command never makes later runs drift.
+
+use jiff::{
+    SignedDuration, Span, Timestamp, Zoned,
+    civil::{Date, Time, Weekday},
+};
+
+use crate::{Result, user_input_error};
+
+const WEEKDAYS: [Weekday; 5] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+];
+const WEEKEND: [Weekday; 2] = [Weekday::Saturday, Weekday::Sunday];
+/// How far a cron expression is searched: long enough for the 29th of
+/// February, which can be eight years away.
+const CRON_SEARCH_DAYS: i32 = 366 * 9;
+const ALL_DAYS: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+/// A parsed recurring schedule.
+#[must_use]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recurrence {
+    /// Every `every`, counted from `anchor`.
+    Interval {
+        every: SignedDuration,
+        anchor: Timestamp,
+    },
+    /// At `time` on each of `days`, in the zone of the reference time.
+    Days { days: Vec<Weekday>, time: Time },
+    /// At `time` on each of `days` of every `weeks`-th week, counting from
+    /// the week holding `start` and never before it.
+    Weeks {
+        days: Vec<Weekday>,
+        time: Time,
+        weeks: i32,
+        start: Date,
+    },
+    /// A cron expression, in the zone of the reference time.
+    Cron(Cron),
+}
+
+/// A five-field cron expression, each field the set of values it allows.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cron {
+    minutes: u64,
+    hours: u32,
+    /// Bit `d` for day `d` of the month.
+    days: u32,
+    /// Bit `m` for month `m`.
+    months: u16,
+    /// Bit 0 for Sunday through bit 6 for Saturday.
+    weekdays: u8,
+    /// Whether both the day-of-month and day-of-week fields are
+    /// restricted, so that a day matching either one fires.
+    either_day: bool,
+}
+
+impl Cron {
+    /// Parse `minute hour day-of-month month day-of-week`, each field `*`,
+    /// a number, a range `a-b`, a step `*/n` or `a-b/n`, or a list of these.
+    /// Day of week 0 and 7 are both Sunday.
+    fn parse(expr: &str) -> Option<Self> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields.as_slice() else {
+            return None;
+        };
+        let weekdays = cron_field(weekday, 0, 7)?;
+        Some(Self {
+            minutes: cron_field(minute, 0, 59)?,
+            hours: u32::try_from(cron_field(hour, 0, 23)?).ok()?,
+            days: u32::try_from(cron_field(day, 1, 31)?).ok()?,
+            months: u16::try_from(cron_field(month, 1, 12)?).ok()?,
+            weekdays: u8::try_from((weekdays | weekdays >> 7) & 0x7f).ok()?,
+            either_day: !day.starts_with('*') && !weekday.starts_with('*'),
+        })
+    }
+
+    /// Whether the expression fires at some time on `date`.
+    fn fires_on(&self, date: Date) -> bool {
+        let bit = |set: u32, n: i8| set & (1 << n) != 0;
+        let day = bit(self.days, date.day());
+        let weekday = bit(self.weekdays.into(), date.weekday().to_sunday_zero_offset());
+        bit(self.months.into(), date.month())
+            && if self.either_day {
+                day || weekday
+            } else {
+                day && weekday
+            }
+    }
+
+    /// The times of day the expression fires at, earliest first.
+    fn times(&self) -> Vec<Time> {
+        (0..24)
+            .filter(|h| self.hours & (1 << h) != 0)
+            .flat_map(|h| {
+                (0..60)
+                    .filter(|m| self.minutes & (1 << m) != 0)
+                    .filter_map(move |m| Time::new(h, m, 0, 0).ok())
+            })
+            .collect()
+    }
+}
+
+/// Whether `expr` is written like a cron expression: five fields of
+/// digits, `*`, `,`, `-` and `/`.
+fn looks_like_cron(expr: &str) -> bool {
+    let fields: Vec<&str> = expr.split_whitespace().collect();
+    fields.len() == 5
+        && fields
+            .iter()
+            .all(|f| f.chars().all(|c| c.is_ascii_digit() || "*,-/".contains(c)))
+}
+
+/// The values one cron field allows between `min` and `max`, as bits.
+fn cron_field(field: &str, min: u8, max: u8) -> Option<u64> {
+    let mut bits = 0;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, step.parse::<usize>().ok().filter(|&s| s > 0)?),
+            None => (item, 1),
+        };
+        let (lo, hi) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((lo, hi)) => (lo.parse().ok()?, hi.parse().ok()?),
+            // `5/15` runs from 5 to the end, as in cron.
+            None if step > 1 => (range.parse().ok()?, max),
+            None => {
+                let n = range.parse().ok()?;
+                (n, n)
+            }
+        };
+        if lo < min || hi > max || lo > hi {
+            return None;
+        }
+        for n in (lo..=hi).step_by(step) {
+            bits |= 1 << n;
+        }
+    }
+    Some(bits)
+}
+
+impl Recurrence {
+    /// Parse `expr`, anchoring intervals relative to `now`.
+    pub fn parse(expr: &str, now: &Zoned) -> Result<Self> {
+        Self::parse_inner(expr, now, false)
+    }
+
+    /// Like [`Recurrence::parse`], but refuse a schedule that would count
+    /// from `now` itself -- an interval that does not divide a day, or
+    /// `every other` weeks -- unless it says `starting DATE`.  For snapping
+    /// a date to a schedule, where anchoring to the date would return it
+    /// unchanged.
+    pub fn parse_anchored(expr: &str, now: &Zoned) -> Result<Self> {
+        Self::parse_inner(expr, now, true)
+    }
+
+    fn parse_inner(expr: &str, now: &Zoned, anchored: bool) -> Result<Self> {
+        if looks_like_cron(expr) {
+            return Cron::parse(expr).map(Self::Cron).ok_or_else(|| {
+                user_input_error!(
+                    InvalidDateFormat,
+                    "invalid cron expression '{}' (fields: minute hour day-of-month month day-of-week)",
+                    expr.trim()
+                )
+            });
+        }
+        let lower = expr.trim().to_lowercase();
+        let (lower, start) = match lower.split_once(" starting ") {
+            Some((rest, date)) => {
+                let start =
+                    crate::parser::parse(date.trim(), now, &crate::parser::ParseOptions::default())
+                        .map_err(|e| {
+                            user_input_error!(InvalidDateFormat, "{}", e.format_message())
+                        })?;
+                (rest, Some(start))
+            }
+            None => (lower.as_str(), None),
+        };
+        let text = lower.strip_prefix("every ").unwrap_or(lower).trim();
+        let (text, other) = match text.strip_prefix("other ") {
+            Some(text) => (text.trim(), true),
+            None => (text, false),
+        };
+        let invalid = || {
+            user_input_error!(
+                InvalidDateFormat,
+                "unrecognized schedule '{}' (try \"every 15 minutes\" or \"every weekday at 9\")",
+                expr
+            )
+        };
+        let unanchored = || {
+            user_input_error!(
+                InvalidDateFormat,
+                "schedule '{}' has no fixed start; add 'starting DATE' to anchor it",
+                expr.trim()
+            )
+        };
+
+        let (head, time) = match text.split_once(" at ") {
+            Some((head, time)) => (head.trim(), Some(parse_time(time).ok_or_else(invalid)?)),
+            None => (text, None),
+        };
+
+        // "every other day" is an interval; "every other monday" skips weeks.
+        let days = parse_days(head).filter(|_| !(other && parse_interval(head).is_some()));
+        if let Some(days) = days {
+            let time = time.unwrap_or(Time::midnight());
+            return Ok(match (other, start) {
+                (false, None) => Self::Days { days, time },
+                (true, None) if anchored => return Err(unanchored()),
+                (other, start) => Self::Weeks {
+                    days,
+                    time,
+                    weeks: if other { 2 } else { 1 },
+                    start: start.map_or_else(|| now.date(), |s| s.date()),
+                },
+            });
+        }
+        if time.is_some() {
+            return Err(invalid());
+        }
+
+        let mut every = parse_interval(head).ok_or_else(invalid)?;
+        if every <= SignedDuration::ZERO {
+            return Err(invalid());
+        }
+        if other {
+            every = every.checked_mul(2).ok_or_else(invalid)?;
+        }
+        let anchor = if let Some(start) = start {
+            start.timestamp()
+        } else if SignedDuration::from_hours(24).as_secs() % every.as_secs() == 0 {
+            now.start_of_day()
+                .map_err(|e| user_input_error!(InvalidDate, "{}", e))?
+                .timestamp()
+        } else if anchored {
+            return Err(unanchored());
+        } else {
+            now.timestamp()
+        };
+        Ok(Self::Interval { every, anchor })
+    }
+
+    /// First occurrence strictly after `t`, in `t`'s zone.
+    pub fn next_after(&self, t: &Zoned) -> Result<Zoned> {
+        self.nearest(t, true)?
+            .ok_or_else(|| user_input_error!(InvalidDate, "schedule has no days"))
+    }
+
+    /// Last occurrence strictly before `t`, in `t`'s zone.  Schedules never
+    /// fire before their `starting` date, nor intervals before their anchor.
+    pub fn prev_before(&self, t: &Zoned) -> Result<Zoned> {
+        self.nearest(t, false)?.ok_or_else(|| {
+            user_input_error!(
+                InvalidDate,
+                "schedule has no occurrence before {}",
+                t.strftime("%Y-%m-%dT%H:%M:%S%:z")
+            )
+        })
+    }
+
+    /// Latest occurrence at or before `t`.
+    pub fn floor(&self, t: &Zoned) -> Result<Zoned> {
+        let just_after = t
+            .checked_add(SignedDuration::from_nanos(1))
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+        self.prev_before(&just_after)
+    }
+
+    /// Earliest occurrence at or after `t`.
+    pub fn ceil(&self, t: &Zoned) -> Result<Zoned> {
+        let just_before = t
+            .checked_sub(SignedDuration::from_nanos(1))
+            .map_err(|e| user_input_error!(InvalidDate, "{}", e))?;
+        self.next_after(&just_before)
+    }
+
+    /// The occurrence closest to `t` on its `forward` side, or `None` when
+    /// the schedule has none there.
+    fn nearest(&self, t: &Zoned, forward: bool) -> Result<Option<Zoned>> {
+        let out_of_range = |e: jiff::Error| user_input_error!(InvalidDate, "{}", e);
+        match self {
+            Self::Interval { every, anchor } => {
+                let elapsed = t.timestamp().duration_since(*anchor).as_nanos();
+                let every = every.as_nanos();
+                let steps = match forward {
+                    true if elapsed < 0 => 0,
+                    true => elapsed / every + 1,
+                    false if elapsed <= 0 => return Ok(None),
+                    false => (elapsed - 1) / every,
+                };
+                let offset =
+                    SignedDuration::from_nanos(i64::try_from(steps * every).unwrap_or(i64::MAX));
+                let at = anchor.checked_add(offset).map_err(out_of_range)?;
+                Ok(Some(at.to_zoned(t.time_zone().clone())))
+            }
+            Self::Days { days, time } => scan(t, t.date(), forward, 8, &[*time], |date| {
+                Ok(days.contains(&date.weekday()))
+            }),
+            Self::Weeks {
+                days,
+                time,
+                weeks,
+                start,
+            } => {
+                let monday =
+                    |d: Date| d.checked_sub(Span::new().days(d.weekday().to_monday_zero_offset()));
+                let first = monday(*start).map_err(out_of_range)?;
+                let from = if forward {
+                    t.date().max(*start)
+                } else {
+                    t.date()
+                };
+                scan(t, from, forward, 7 * weeks + 1, &[*time], |date| {
+                    let week = (monday(date).map_err(out_of_range)? - first).get_days() / 7;
+                    Ok(date >= *start && week % weeks == 0 && days.contains(&date.weekday()))
+                })
+            }
+            Self::Cron(cron) => scan(
+                t,
+                t.date(),
+                forward,
+                CRON_SEARCH_DAYS,
+                &cron.times(),
+                |date| Ok(cron.fires_on(date)),
+            ),
+        }
+    }
+
+    /// The next `n` occurrences after `t`.
+    pub fn upcoming(&self, t: &Zoned, n: usize) -> Result<Vec<Zoned>> {
+        let mut out = Vec::with_capacity(n);
+        let mut cursor = t.clone();
+        for _ in 0..n {
+            cursor = self.next_after(&cursor)?;
+            out.push(cursor.clone());
+        }
+        Ok(out)
+    }
+}
+
+/// Walk up to `days` days from `from`, forward or back, for the first of
+/// `times` (earliest first) on a day `fires` accepts that lies strictly
+/// after `t`, or strictly before it.
+fn scan(
+    t: &Zoned,
+    from: Date,
+    forward: bool,
+    days: i32,
+    times: &[Time],
+    fires: impl Fn(Date) -> Result<bool>,
+) -> Result<Option<Zoned>> {
+    let out_of_range = |e: jiff::Error| user_input_error!(InvalidDate, "{}", e);
+    let mut date = from;
+    for _ in 0..days {
+        if fires(date)? {
+            let ordered: Box<dyn Iterator<Item = &Time>> = if forward {
+                Box::new(times.iter())
+            } else {
+                Box::new(times.iter().rev())
+            };
+            for time in ordered {
+                let candidate = date
+                    .to_datetime(*time)
+                    .to_zoned(t.time_zone().clone())
+                    .map_err(out_of_range)?;
+                if (forward && candidate > *t) || (!forward && candidate < *t) {
+                    return Ok(Some(candidate));
+                }
+            }
+        }
+        date = if forward {
+            date.tomorrow()
+        } else {
+            date.yesterday()
+        }
+        .map_err(out_of_range)?;
+    }
+    Ok(None)
+}
+
+/// `"day"`, `"weekday"`, `"mon, wed and fri"`, ...
+fn parse_days(s: &str) -> Option<Vec<Weekday>> {
+    match s {
+        "day" | "daily" => return Some(ALL_DAYS.to_vec()),
+        "weekday" | "weekdays" => return Some(WEEKDAYS.to_vec()),
+        "weekend" | "weekends" => return Some(WEEKEND.to_vec()),
+        _ => {}
+    }
+    let mut days = Vec::new();
+    for word in s.replace(" and ", ",").split(',') {
+        let day = weekday(word.trim())?;
+        if !days.contains(&day) {
+            days.push(day);
+        }
+    }
+    Some(days)
+}
+
+/// Parse a weekday name such as `"Monday"`, `"mon"` or `"Tues"`.
+#[must_use]
+pub fn parse_weekday(s: &str) -> Option<Weekday> {
+    weekday(&s.trim().to_ascii_lowercase())
+}
+
+fn weekday(s: &str) -> Option<Weekday> {
+    let s = s
+        .strip_suffix('s')
+        .filter(|s| s.ends_with("day"))
+        .unwrap_or(s);
+    Some(match s {
+        "monday" | "mon" => Weekday::Monday,
+        "tuesday" | "tue" | "tues" => Weekday::Tuesday,
+        "wednesday" | "wed" => Weekday::Wednesday,
+        "thursday" | "thu" | "thurs" => Weekday::Thursday,
+        "friday" | "fri" => Weekday::Friday,
+        "saturday" | "sat" => Weekday::Saturday,
+        "sunday" | "sun" => Weekday::Sunday,
+        _ => return None,
+    })
+}
+
+/// `"15 minutes"`, `"15m"`, `"hour"`, `"hourly"`, ...
+fn parse_interval(s: &str) -> Option<SignedDuration> {
+    match s {
+        "hourly" => return Some(SignedDuration::from_hours(1)),
+        "minutely" => return Some(SignedDuration::from_mins(1)),
+        _ => {}
+    }
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (count, unit) = s.split_at(split);
+    let count: i64 = if count.is_empty() {
+        1
+    } else {
+        count.parse().ok()?
+    };
+    let secs = match unit.trim() {
+        "s" | "sec" | "secs" | "second" | "seconds" => 1,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 3600,
+        "d" | "day" | "days" => 86_400,
+        "w" | "week" | "weeks" => 7 * 86_400,
+        _ => return None,
+    };
+    Some(SignedDuration::from_secs(count.checked_mul(secs)?))
+}
+
+/// `"9"`, `"9am"`, `"9:30pm"`, `"18:00"`, `"noon"`, `"midnight"`.
+fn parse_time(s: &str) -> Option<Time> {
+    let s = s.trim();
+    match s {
+        "noon" => return Some(Time::constant(12, 0, 0, 0)),
+        "midnight" => return Some(Time::midnight()),
+        _ => {}
+    }
+    let (clock, pm) = match (s.strip_suffix("am"), s.strip_suffix("pm")) {
+        (Some(c), _) => (c.trim(), Some(false)),
+        (_, Some(c)) => (c.trim(), Some(true)),
+        _ => (s, None),
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) => (h.parse::<i8>().ok()?, m.parse::<i8>().ok()?),
+        None => (clock.parse::<i8>().ok()?, 0),
+    };
+    let hour = match pm {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(true) => hour % 12 + 12,
+        Some(false) => hour % 12,
+        None => hour,
+    };
+    Time::new(hour, minute, 0, 0).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn at(s: &str) -> Zoned {
+        s.parse().unwrap()
+    }
+
+    fn next(expr: &str, now: &str) -> String {
+        let now = at(now);
+        Recurrence::parse(expr, &now)
+            .unwrap()
+            .next_after(&now)
+            .unwrap()
+            .strftime("%a %Y-%m-%d %H:%M:%S")
+            .to_string()
+    }
+
+    #[test]
+    fn intervals_dividing_a_day_align_to_midnight() {
+        assert_eq!(
+            next("every 15 minutes", "2025-01-15T10:31:07+00:00[UTC]"),
+            "Wed 2025-01-15 10:45:00"
+        );
+        assert_eq!(
+            next("hourly", "2025-01-15T23:59:00+00:00[UTC]"),
+            "Thu 2025-01-16 00:00:00"
+        );
+    }
+
+    #[test]
+    fn other_intervals_count_from_now() {
+        assert_eq!(
+            next("every 7m", "2025-01-15T10:31:00+00:00[UTC]"),
+            "Wed 2025-01-15 10:38:00"
+        );
+    }
+
+    #[test]
+    fn interval_occurrences_do_not_drift() {
+        let now = at("2025-01-15T10:00:00+00:00[UTC]");
+        let rec = Recurrence::parse("every 10 minutes", &now).unwrap();
+        let late = at("2025-01-15T10:23:59+00:00[UTC]");
+        assert_eq!(
+            rec.next_after(&late).unwrap().strftime("%H:%M").to_string(),
+            "10:30"
+        );
+    }
+
+    #[test]
+    fn weekdays_at_a_time() {
+        assert_eq!(
+            next("every weekday at 9", "2025-01-17T10:00:00+00:00[UTC]"),
+            "Mon 2025-01-20 09:00:00"
+        );
+        assert_eq!(
+            next(
+                "mon, wed and fri at 6:30pm",
+                "2025-01-15T10:00:00+00:00[UTC]"
+            ),
+            "Wed 2025-01-15 18:30:00"
+        );
+        assert_eq!(
+            next("every sunday", "2025-01-15T10:00:00+00:00[UTC]"),
+            "Sun 2025-01-19 00:00:00"
+        );
+    }
+
+    #[test]
+    fn every_other_week_counts_from_the_start() {
+        let now = at("2025-01-15T10:00:00+00:00[UTC]");
+        let rec = Recurrence::parse("every other monday starting 2025-01-06", &now).unwrap();
+        let got: Vec<String> = rec
+            .upcoming(&now, 3)
+            .unwrap()
+            .iter()
+            .map(|z| z.strftime("%a %F").to_string())
+            .collect();
+        assert_eq!(got, ["Mon 2025-01-20", "Mon 2025-02-03", "Mon 2025-02-17"]);
+
+        assert_eq!(
+            next(
+                "every weekday at 9 starting 2025-02-01",
+                "2025-01-15T10:00:00+00:00[UTC]"
+            ),
+            "Mon 2025-02-03 09:00:00"
+        );
+        assert_eq!(
+            next(
+                "every other day starting 2025-01-14",
+                "2025-01-15T10:00:00+00:00[UTC]"
+            ),
+            "Thu 2025-01-16 00:00:00"
+        );
+    }
+
+    #[test]
+    fn upcoming_lists_in_order() {
+        let now = at("2025-01-15T10:00:00+00:00[UTC]");
+        let rec = Recurrence::parse("every day at noon", &now).unwrap();
+        let got: Vec<String> = rec
+            .upcoming(&now, 2)
+            .unwrap()
+            .iter()
+            .map(|z| z.strftime("%d %H:%M").to_string())
+            .collect();
+        assert_eq!(got, ["15 12:00", "16 12:00"]);
+    }
+
+    #[test]
+    fn cron_expressions_follow_cron_rules() {
+        assert_eq!(
+            next("*/15 * * * *", "2025-01-15T10:31:07+00:00[UTC]"),
+            "Wed 2025-01-15 10:45:00"
+        );
+        assert_eq!(
+            next("0 9 * * 1-5", "2025-01-17T10:00:00+00:00[UTC]"),
+            "Mon 2025-01-20 09:00:00"
+        );
+        // Both day fields restricted: either one fires.
+        assert_eq!(
+            next("0 0 1 * 0", "2025-01-15T10:00:00+00:00[UTC]"),
+            "Sun 2025-01-19 00:00:00"
+        );
+        assert_eq!(
+            next("30 12 29 2 *", "2025-01-15T10:00:00+00:00[UTC]"),
+            "Tue 2028-02-29 12:30:00"
+        );
+        assert_eq!(
+            next("0 0 * * 7", "2025-01-15T10:00:00+00:00[UTC]"),
+            "Sun 2025-01-19 00:00:00"
+        );
+        let now = at("2025-01-15T10:00:00+00:00[UTC]");
+        for bad in ["60 * * * *", "* * 0 * *", "*/0 * * * *", "1-2-3 * * * *"] {
+            assert!(Recurrence::parse(bad, &now).is_err(), "{bad}");
+        }
+        let never = Recurrence::parse("0 0 31 2 *", &now).unwrap();
+        assert!(never.next_after(&now).is_err());
+    }
+
+    #[test]
+    fn floor_and_ceil_snap_to_occurrences() {
+        let snap = |expr: &str, t: &str| {
+            let t = at(t);
+            let rec = Recurrence::parse(expr, &t).unwrap();
+            let show = |z: Zoned| z.strftime("%a %H:%M:%S").to_string();
+            (show(rec.floor(&t).unwrap()), show(rec.ceil(&t).unwrap()))
+        };
+        assert_eq!(
+            snap("every 15 minutes", "2025-01-15T10:31:07+00:00[UTC]"),
+            ("Wed 10:30:00".into(), "Wed 10:45:00".into())
+        );
+        assert_eq!(
+            snap("every 15 minutes", "2025-01-15T10:30:00+00:00[UTC]"),
+            ("Wed 10:30:00".into(), "Wed 10:30:00".into())
+        );
+        assert_eq!(
+            snap("0 9 * * 1-5", "2025-01-18T10:00:00+00:00[UTC]"),
+            ("Fri 09:00:00".into(), "Mon 09:00:00".into())
+        );
+        assert_eq!(
+            snap("every weekday at 9", "2025-01-15T09:00:00+00:00[UTC]"),
+            ("Wed 09:00:00".into(), "Wed 09:00:00".into())
+        );
+
+        let now = at("2025-01-15T10:00:00+00:00[UTC]");
+        let rec = Recurrence::parse("every other monday starting 2025-01-06", &now).unwrap();
+        assert_eq!(
+            rec.floor(&now).unwrap().strftime("%a %F").to_string(),
+            "Mon 2025-01-06"
+        );
+        assert!(rec.floor(&at("2025-01-05T10:00:00+00:00[UTC]")).is_err());
+    }
+
+    #[test]
+    fn rejects_nonsense() {
+        let now = at("2025-01-15T10:00:00+00:00[UTC]");
+        for bad in [
+            "every fortnight",
+            "every 0 minutes",
+            "every 5m at 9",
+            "daily at 25",
+        ] {
+            assert!(Recurrence::parse(bad, &now).is_err(), "{bad}");
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Signal handling for **TARDIS**.
+//!
+//! Rust ignores `SIGPIPE`, so writing to a closed pipe, as in
+//! `td range ... | head -1`, would panic with a backtrace instead of
+//! stopping quietly; [`restore_sigpipe`] puts back the default action, which
+//! ends the process the way `cat` or `date` would.  [`exit_on_interrupt`]
+//! makes `Ctrl-C` during a countdown clear its line and show the cursor
+//! before exiting with the conventional 130.  Both do nothing off Unix.
+
+/// Exit status of a process stopped by `SIGINT`, 128 + 2.
+pub const INTERRUPTED: i32 = 130;
+
+/// Let `SIGPIPE` end the process, as it does for other command-line tools.
+pub fn restore_sigpipe() {
+    #[cfg(unix)]
+    // SAFETY: called at startup, before any thread is spawned; `SIG_DFL` is
+    // a valid action for `SIGPIPE`.
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+}
+
+/// On `SIGINT`, clear the current line of stderr, show the cursor and exit
+/// with [`INTERRUPTED`].
+pub fn exit_on_interrupt() {
+    #[cfg(unix)]
+    // SAFETY: `on_interrupt` only calls the async-signal-safe `write` and
+    // `_exit`.
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+}
+
+#[cfg(unix)]
+extern "C" fn on_interrupt(_: libc::c_int) {
+    const RESTORE: &[u8] = b"\r\x1b[K\x1b[?25h";
+    // SAFETY: `RESTORE` is valid for its length, and neither call touches
+    // state that the interrupted code might hold.
+    unsafe {
+        libc::write(libc::STDERR_FILENO, RESTORE.as_ptr().cast(), RESTORE.len());
+        libc::_exit(INTERRUPTED);
+    }
+}
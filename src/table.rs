@@ -0,0 +1,173 @@
+//! The renderer shared by every command that prints a table.
+//!
+//! A table is a list of named columns and rows of cells.  [`Layout`] carries
+//! the global `--columns` and `--max-width` options: the first picks and
+//! orders the columns shown, the second narrows the widest columns, cutting
+//! their cells short, until each line fits.  Columns are left-aligned and
+//! separated by two spaces, or by a tab under `--plain`.
+
+use crate::{Result, user_input_error};
+
+/// How tables are laid out, from the global options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Layout {
+    /// Columns to show, by name and in order; all of them when `None`.
+    pub columns: Option<Vec<String>>,
+    /// Longest line, in characters.
+    pub max_width: Option<usize>,
+    /// Separate columns with a tab instead of padding.
+    pub plain: bool,
+    /// Mark cut cells with `~` instead of `…`.
+    pub ascii: bool,
+}
+
+/// Render `rows` under the column `names`, one line per row.
+///
+/// Fails when `--columns` names a column the table does not have.
+pub fn render(names: &[&str], rows: &[Vec<String>], layout: &Layout) -> Result<String> {
+    let picked: Vec<usize> = match &layout.columns {
+        None => (0..names.len()).collect(),
+        Some(wanted) => wanted
+            .iter()
+            .map(|w| {
+                names
+                    .iter()
+                    .position(|n| n.eq_ignore_ascii_case(w))
+                    .ok_or_else(|| {
+                        user_input_error!(
+                            UnsupportedFormat,
+                            "unknown column '{}'; choose from {}",
+                            w,
+                            names.join(", ")
+                        )
+                    })
+            })
+            .collect::<Result<_>>()?,
+    };
+    let rows: Vec<Vec<&str>> = rows
+        .iter()
+        .map(|row| {
+            picked
+                .iter()
+                .map(|&i| row.get(i).map_or("", String::as_str))
+                .collect()
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = (0..picked.len())
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0))
+        .collect();
+    let sep = if layout.plain { "\t" } else { "  " };
+    if let Some(max) = layout.max_width {
+        shrink(&mut widths, max, sep.len());
+    }
+    let mark = if layout.ascii { '~' } else { '…' };
+
+    let mut out = String::new();
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &w)| {
+                let cell = cut(cell, w, mark);
+                if layout.plain {
+                    cell
+                } else {
+                    format!("{cell:<w$}")
+                }
+            })
+            .collect();
+        out.push_str(cells.join(sep).trim_end());
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Narrow the widest column, one character at a time, until a line of
+/// `widths` separated by `sep` characters fits in `max`.  A column is never
+/// narrowed below one character.
+fn shrink(widths: &mut [usize], max: usize, sep: usize) {
+    let total =
+        |widths: &[usize]| widths.iter().sum::<usize>() + sep * widths.len().saturating_sub(1);
+    while total(widths) > max {
+        match widths.iter_mut().filter(|w| **w > 1).max_by_key(|w| **w) {
+            Some(widest) => *widest -= 1,
+            None => break,
+        }
+    }
+}
+
+/// `cell` cut to `width` characters, ending in `mark` when shortened.
+fn cut(cell: &str, width: usize, mark: char) -> String {
+    if cell.chars().count() <= width {
+        return cell.to_string();
+    }
+    let mut cut: String = cell.chars().take(width.saturating_sub(1)).collect();
+    cut.push(mark);
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn rows() -> Vec<Vec<String>> {
+        [
+            ["America/Sao_Paulo", "14:30", "-03:00"],
+            ["UTC", "17:30", "+00:00"],
+        ]
+        .iter()
+        .map(|r| r.iter().map(|c| c.to_string()).collect())
+        .collect()
+    }
+
+    const NAMES: [&str; 3] = ["zone", "time", "offset"];
+
+    #[test]
+    fn pads_columns_and_picks_them_by_name() {
+        let all = render(&NAMES, &rows(), &Layout::default()).unwrap();
+        assert_eq!(
+            all,
+            "America/Sao_Paulo  14:30  -03:00\nUTC                17:30  +00:00\n"
+        );
+
+        let layout = Layout {
+            columns: Some(vec!["offset".into(), "Zone".into()]),
+            plain: true,
+            ..Layout::default()
+        };
+        let picked = render(&NAMES, &rows(), &layout).unwrap();
+        assert_eq!(picked, "-03:00\tAmerica/Sao_Paulo\n+00:00\tUTC\n");
+
+        let layout = Layout {
+            columns: Some(vec!["abbr".into()]),
+            ..Layout::default()
+        };
+        let err = render(&NAMES, &rows(), &layout).unwrap_err();
+        assert!(err.to_string().contains("choose from zone, time, offset"));
+    }
+
+    #[test]
+    fn max_width_cuts_the_widest_column() {
+        let layout = Layout {
+            max_width: Some(26),
+            ..Layout::default()
+        };
+        assert_eq!(
+            render(&NAMES, &rows(), &layout).unwrap(),
+            "America/Sa…  14:30  -03:00\nUTC          17:30  +00:00\n"
+        );
+
+        let layout = Layout {
+            max_width: Some(5),
+            ascii: true,
+            ..Layout::default()
+        };
+        assert_eq!(
+            render(&NAMES, &rows(), &layout).unwrap(),
+            "~  ~  ~\n~  ~  ~\n"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! How output is dressed up for a terminal.
+//!
+//! Color is used only when the stream is a terminal, `NO_COLOR` is unset and
+//! `--plain` was not given.  [`plain`] also tells commands to separate
+//! columns with tabs instead of padding, so the output reads well in screen
+//! readers and dumb terminals.  [`ascii`] asks for ASCII where a command
+//! would otherwise draw with Unicode, for `--ascii`, `--plain` and locales
+//! whose character set is not UTF-8.  [`stdout`] hands out a buffered
+//! stdout that flushes each line only when someone is likely to be watching.
+
+use std::{
+    env,
+    io::{self, BufWriter, IsTerminal, StdoutLock, Write},
+    sync::atomic::{AtomicBool, AtomicU8, Ordering},
+};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static ASCII: AtomicBool = AtomicBool::new(false);
+static BUFFERING: AtomicU8 = AtomicU8::new(0);
+
+/// How often [`stdout`] flushes, for `--line-buffered` and `--block-buffered`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Buffering {
+    /// After every line.
+    Line,
+    /// When the buffer fills, and at the end.
+    Block,
+}
+
+/// Turn plain output on or off for the rest of the process.
+pub fn set_plain(on: bool) {
+    PLAIN.store(on, Ordering::Relaxed);
+}
+
+/// Whether `--plain` was given.
+#[must_use]
+pub fn plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Turn ASCII-only drawing on or off for the rest of the process.
+pub fn set_ascii(on: bool) {
+    ASCII.store(on, Ordering::Relaxed);
+}
+
+/// Whether to draw with ASCII only.
+#[must_use]
+pub fn ascii() -> bool {
+    ASCII.load(Ordering::Relaxed) || plain()
+}
+
+/// Choose the buffering of [`stdout`] for the rest of the process; `None`
+/// lets each command decide.
+pub fn set_buffering(choice: Option<Buffering>) {
+    let code = match choice {
+        None => 0,
+        Some(Buffering::Line) => 1,
+        Some(Buffering::Block) => 2,
+    };
+    BUFFERING.store(code, Ordering::Relaxed);
+}
+
+/// The buffering chosen with [`set_buffering`].  Otherwise output is
+/// line-buffered when stdout is a terminal or when `streaming`, for
+/// commands that pass stdin through as it arrives, and block-buffered
+/// in a pipeline.
+#[must_use]
+pub fn buffering(streaming: bool) -> Buffering {
+    match BUFFERING.load(Ordering::Relaxed) {
+        1 => Buffering::Line,
+        2 => Buffering::Block,
+        _ if streaming || io::stdout().is_terminal() => Buffering::Line,
+        _ => Buffering::Block,
+    }
+}
+
+/// Stdout, locked and buffered as [`buffering`] says.  Call
+/// [`Write::flush`] when done: dropping it flushes too, but loses errors.
+#[must_use]
+pub fn stdout(streaming: bool) -> Output {
+    Output {
+        inner: BufWriter::new(io::stdout().lock()),
+        line: buffering(streaming) == Buffering::Line,
+    }
+}
+
+/// A locked, buffered stdout; see [`stdout`].
+#[derive(Debug)]
+pub struct Output {
+    inner: BufWriter<StdoutLock<'static>>,
+    line: bool,
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        if self.line && buf[..n].contains(&b'\n') {
+            self.inner.flush()?;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Whether the character-set locale, the first non-empty of `LC_ALL`,
+/// `LC_CTYPE` and `LANG`, rules out Unicode.  An unset locale does not.
+#[must_use]
+pub fn ascii_locale() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|v| !v.is_empty()))
+        .is_some_and(|locale| is_ascii_locale(&locale))
+}
+
+/// Whether `locale` names a character set other than UTF-8.  `C` and
+/// `POSIX` are ASCII; a locale without a character set, such as `en_US`,
+/// is assumed to be UTF-8.
+fn is_ascii_locale(locale: &str) -> bool {
+    let locale = locale.split('@').next().unwrap_or_default();
+    match locale.split_once('.') {
+        Some((_, codeset)) => {
+            !codeset.eq_ignore_ascii_case("utf-8") && !codeset.eq_ignore_ascii_case("utf8")
+        }
+        None => locale == "C" || locale == "POSIX",
+    }
+}
+
+/// Whether to color what goes to stdout.
+#[must_use]
+pub fn stdout_color() -> bool {
+    color(io::stdout().is_terminal())
+}
+
+/// Whether to color what goes to stderr.
+#[must_use]
+pub fn stderr_color() -> bool {
+    color(io::stderr().is_terminal())
+}
+
+fn color(is_terminal: bool) -> bool {
+    cfg!(feature = "color") && is_terminal && !plain() && env::var_os("NO_COLOR").is_none()
+}
+
+/// `text` without its ANSI escape sequences.
+#[must_use]
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.clone().next() == Some('[') {
+            chars.by_ref().skip(1).find(|c| ('@'..='~').contains(c));
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_character_set_of_a_locale() {
+        assert!(is_ascii_locale("C"));
+        assert!(is_ascii_locale("POSIX"));
+        assert!(is_ascii_locale("en_US.ISO-8859-1"));
+        assert!(is_ascii_locale("de_DE.ISO-8859-15@euro"));
+        assert!(!is_ascii_locale("C.UTF-8"));
+        assert!(!is_ascii_locale("pt_BR.utf8"));
+        assert!(!is_ascii_locale("en_US.UTF-8@calendar=gregorian"));
+        assert!(!is_ascii_locale("en_US"));
+    }
+
+    #[test]
+    fn strips_ansi_escapes() {
+        assert_eq!(
+            strip_ansi("\x1b[1m\x1b[32mUsage:\x1b[0m td [OPTIONS]"),
+            "Usage: td [OPTIONS]"
+        );
+        assert_eq!(strip_ansi("plain \x1b text"), "plain \x1b text");
+    }
+}
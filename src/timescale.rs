@@ -0,0 +1,142 @@
+//! Atomic time scales for `--scale`.
+//!
+//! TAI runs ahead of UTC by the leap seconds inserted since 1972 (37 since
+//! 2017), and GPS time runs a fixed 19 seconds behind TAI (so 18 ahead of
+//! UTC today).  [`convert`] shifts a UTC instant so its clock reads on the
+//! chosen scale, using the bundled leap-second table.  The table only
+//! knows leap seconds announced up to [`TABLE_VALID_UNTIL`]; [`beyond_table`]
+//! tells when a date is past it and the offset may be stale.
+
+use jiff::{
+    SignedDuration, Timestamp, Zoned,
+    civil::{Date, date},
+    tz::TimeZone,
+};
+
+use crate::{Result, cli::TimeScale, user_input_error};
+
+/// TAI − UTC, in seconds, from each date (00:00 UTC) on.
+const LEAP_SECONDS: [(Date, i64); 28] = [
+    (date(1972, 1, 1), 10),
+    (date(1972, 7, 1), 11),
+    (date(1973, 1, 1), 12),
+    (date(1974, 1, 1), 13),
+    (date(1975, 1, 1), 14),
+    (date(1976, 1, 1), 15),
+    (date(1977, 1, 1), 16),
+    (date(1978, 1, 1), 17),
+    (date(1979, 1, 1), 18),
+    (date(1980, 1, 1), 19),
+    (date(1981, 7, 1), 20),
+    (date(1982, 7, 1), 21),
+    (date(1983, 7, 1), 22),
+    (date(1985, 7, 1), 23),
+    (date(1988, 1, 1), 24),
+    (date(1990, 1, 1), 25),
+    (date(1991, 1, 1), 26),
+    (date(1992, 7, 1), 27),
+    (date(1993, 7, 1), 28),
+    (date(1994, 7, 1), 29),
+    (date(1996, 1, 1), 30),
+    (date(1997, 7, 1), 31),
+    (date(1999, 1, 1), 32),
+    (date(2006, 1, 1), 33),
+    (date(2009, 1, 1), 34),
+    (date(2012, 7, 1), 35),
+    (date(2015, 7, 1), 36),
+    (date(2017, 1, 1), 37),
+];
+
+/// Last day the bundled table is known to be complete: no leap second was
+/// announced before it beyond those listed.
+pub const TABLE_VALID_UNTIL: Date = date(2026, 6, 28);
+
+/// TAI − GPS, in seconds; fixed since the GPS epoch.
+const GPS_BEHIND_TAI: i64 = 19;
+
+/// Shift `zoned` so its clock reads on `scale` instead of UTC.
+pub fn convert(zoned: &Zoned, scale: TimeScale) -> Result<Zoned> {
+    let seconds = match scale {
+        TimeScale::Tai => tai_minus_utc(zoned.timestamp())?,
+        TimeScale::Gps => tai_minus_utc(zoned.timestamp())? - GPS_BEHIND_TAI,
+        _ => return Ok(zoned.clone()),
+    };
+    zoned
+        .checked_add(SignedDuration::from_secs(seconds))
+        .map_err(|e| user_input_error!(InvalidDate, "{}", e))
+}
+
+/// Whether `ts` falls after [`TABLE_VALID_UNTIL`], where a leap second
+/// unknown to the table may apply.
+#[must_use]
+pub fn beyond_table(ts: Timestamp) -> bool {
+    utc_date(ts) > TABLE_VALID_UNTIL
+}
+
+/// TAI − UTC at `ts`, in seconds.
+fn tai_minus_utc(ts: Timestamp) -> Result<i64> {
+    let day = utc_date(ts);
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|(from, _)| day >= *from)
+        .map(|&(_, seconds)| seconds)
+        .ok_or_else(|| {
+            user_input_error!(
+                InvalidDate,
+                "time scales start on {}; {} is too early",
+                LEAP_SECONDS[0].0,
+                day
+            )
+        })
+}
+
+fn utc_date(ts: Timestamp) -> Date {
+    TimeZone::UTC.to_datetime(ts).date()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn at(s: &str) -> Zoned {
+        s.parse::<Timestamp>().unwrap().to_zoned(TimeZone::UTC)
+    }
+
+    fn on(s: &str, scale: TimeScale) -> String {
+        convert(&at(s), scale).unwrap().timestamp().to_string()
+    }
+
+    #[test]
+    fn applies_the_leap_seconds_in_force() {
+        assert_eq!(
+            on("2025-01-15T10:30:00Z", TimeScale::Tai),
+            "2025-01-15T10:30:37Z"
+        );
+        assert_eq!(
+            on("2025-01-15T10:30:00Z", TimeScale::Gps),
+            "2025-01-15T10:30:18Z"
+        );
+        assert_eq!(
+            on("2025-01-15T10:30:00Z", TimeScale::Utc),
+            "2025-01-15T10:30:00Z"
+        );
+        assert_eq!(
+            on("2016-12-31T23:59:59Z", TimeScale::Tai),
+            "2017-01-01T00:00:35Z"
+        );
+        assert_eq!(
+            on("1980-01-06T00:00:00Z", TimeScale::Gps),
+            "1980-01-06T00:00:00Z"
+        );
+        assert!(convert(&at("1971-12-31T00:00:00Z"), TimeScale::Tai).is_err());
+    }
+
+    #[test]
+    fn dates_past_the_table_are_flagged() {
+        assert!(!beyond_table(at("2026-06-28T23:59:59Z").timestamp()));
+        assert!(beyond_table(at("2026-06-29T00:00:00Z").timestamp()));
+    }
+}
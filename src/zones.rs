@@ -0,0 +1,350 @@
+//! Time-zone discovery for **TARDIS**.
+//!
+//! [`search`] ranks every IANA zone known to the time-zone database, plus
+//! the abbreviations in [`TZ_ABBREVIATIONS`], against a loose query such as
+//! `"sao paulo"` or `"nyc"`.  Matching is case-insensitive, treats `_` as a
+//! space, and accepts the query as a subsequence, so `"ny"` still finds
+//! `America/New_York`.
+//!
+//! [`find_city`] resolves a city name through a small bundled table
+//! (`assets/cities.tsv`), using population to settle names shared by
+//! cities in different zones.
+
+use std::fmt;
+
+use crate::core::TZ_ABBREVIATIONS;
+
+const CITIES: &str = include_str!("../assets/cities.tsv");
+
+/// A larger city wins a shared name only when it is this many times the
+/// population of the next one in a different zone.
+const POPULATION_MARGIN: u64 = 3;
+
+/// One search result.
+#[must_use]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneMatch {
+    /// IANA zone identifier.
+    pub zone: String,
+    /// Abbreviation that matched, when the hit came from an alias.
+    pub alias: Option<&'static str>,
+    score: i64,
+}
+
+impl fmt::Display for ZoneMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.alias {
+            Some(alias) => write!(f, "{alias} ({})", self.zone),
+            None => f.write_str(&self.zone),
+        }
+    }
+}
+
+/// A row of the bundled city table.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct City {
+    pub name: &'static str,
+    /// ISO 3166 alpha-2 country code.
+    pub country: &'static str,
+    /// IANA zone identifier.
+    pub zone: &'static str,
+    pub population: u64,
+}
+
+impl fmt::Display for City {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {} ({})", self.name, self.country, self.zone)
+    }
+}
+
+/// Outcome of [`find_city`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CityMatch {
+    /// No city by that name.
+    None,
+    /// Exactly one zone fits.
+    Unique(City),
+    /// Several zones fit, but this city is much larger than the rest.
+    Likely { city: City, others: Vec<City> },
+    /// Several zones fit and none clearly dominates; largest first.
+    Ambiguous(Vec<City>),
+}
+
+/// Look up a city by name, optionally qualified with a country code
+/// (`"Portland, US"`).  Case, accents, `_` and `-` are ignored.
+pub fn find_city(query: &str) -> CityMatch {
+    let (name, country) = match query.rsplit_once(',') {
+        Some((name, cc)) if cc.trim().len() == 2 => (name, Some(cc.trim())),
+        _ => (query, None),
+    };
+    let name = fold(name);
+
+    let mut found: Vec<City> = cities()
+        .filter(|c| fold(c.name) == name)
+        .filter(|c| country.is_none_or(|cc| c.country.eq_ignore_ascii_case(cc)))
+        .collect();
+    found.sort_by_key(|c| std::cmp::Reverse(c.population));
+
+    let Some(&best) = found.first() else {
+        return CityMatch::None;
+    };
+    let others: Vec<City> = found
+        .iter()
+        .copied()
+        .filter(|c| c.zone != best.zone)
+        .collect();
+    match others.first() {
+        None => CityMatch::Unique(best),
+        Some(next) if best.population >= next.population * POPULATION_MARGIN => {
+            CityMatch::Likely { city: best, others }
+        }
+        Some(_) => {
+            let mut seen = Vec::new();
+            found.retain(|c| {
+                let fresh = !seen.contains(&c.zone);
+                seen.push(c.zone);
+                fresh
+            });
+            CityMatch::Ambiguous(found)
+        }
+    }
+}
+
+fn cities() -> impl Iterator<Item = City> {
+    CITIES
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut cols = line.split('\t');
+            Some(City {
+                name: cols.next()?,
+                country: cols.next()?,
+                zone: cols.next()?,
+                population: cols.next()?.parse().ok()?,
+            })
+        })
+}
+
+/// Lowercase, strip common Latin accents and treat `_`/`-` as spaces.
+fn fold(s: &str) -> String {
+    s.trim()
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            'ñ' => 'n',
+            'ý' | 'ÿ' => 'y',
+            '_' | '-' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+/// ISO 3166 country of `zone`, taken from its largest city in the bundled
+/// table; `None` for zones without one.
+#[must_use]
+pub fn zone_country(zone: &str) -> Option<&'static str> {
+    cities()
+        .filter(|c| c.zone == zone)
+        .max_by_key(|c| c.population)
+        .map(|c| c.country)
+}
+
+/// All zone identifiers in the time-zone database, sorted.
+#[must_use]
+pub fn all_zones() -> Vec<String> {
+    let mut zones: Vec<String> = jiff::tz::db()
+        .available()
+        .map(|name| name.as_str().to_string())
+        .collect();
+    zones.sort_unstable();
+    zones
+}
+
+/// Rank zones and abbreviations against `query`, best match first.
+///
+/// An empty query matches everything, in alphabetical order.
+pub fn search(query: &str) -> Vec<ZoneMatch> {
+    search_in(query, &all_zones())
+}
+
+fn search_in(query: &str, zones: &[String]) -> Vec<ZoneMatch> {
+    let query = normalize(query);
+    let mut matches: Vec<ZoneMatch> = zones
+        .iter()
+        .filter_map(|zone| {
+            score(&query, &normalize(zone)).map(|score| ZoneMatch {
+                zone: zone.clone(),
+                alias: None,
+                score,
+            })
+        })
+        .collect();
+
+    if !query.is_empty() {
+        for (abbr, zone) in TZ_ABBREVIATIONS {
+            if abbr.eq_ignore_ascii_case(&query) {
+                matches.push(ZoneMatch {
+                    zone: (*zone).to_string(),
+                    alias: Some(abbr),
+                    score: i64::MAX,
+                });
+            }
+        }
+    }
+
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.zone.len().cmp(&b.zone.len()))
+            .then(a.zone.cmp(&b.zone))
+    });
+    matches
+}
+
+/// Lowercase and turn `_` into spaces, so `"sao paulo"` matches `Sao_Paulo`.
+fn normalize(s: &str) -> String {
+    s.trim().to_lowercase().replace('_', " ")
+}
+
+/// Higher is better; `None` when `query` is not even a subsequence.
+fn score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let len = candidate.len() as i64;
+
+    if let Some(pos) = candidate.find(query) {
+        let at_boundary = pos == 0 || matches!(candidate.as_bytes()[pos - 1], b'/' | b' ' | b'-');
+        let whole_city = candidate.rsplit('/').next() == Some(query);
+        let mut score = 1000 - len;
+        if at_boundary {
+            score += 200;
+        }
+        if whole_city {
+            score += 500;
+        }
+        return Some(score);
+    }
+
+    let candidate: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut prev: Option<usize> = None;
+    for q in query.chars() {
+        let i = (next..candidate.len()).find(|&i| candidate[i] == q)?;
+        score += 10;
+        if prev.is_some_and(|p| p + 1 == i) {
+            score += 5;
+        }
+        if i == 0 || matches!(candidate[i - 1], '/' | ' ' | '-') {
+            score += 8;
+        }
+        score -= (i - next) as i64;
+        prev = Some(i);
+        next = i + 1;
+    }
+    Some(score - len / 4)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn zones() -> Vec<String> {
+        [
+            "America/New_York",
+            "America/Sao_Paulo",
+            "Asia/Tokyo",
+            "Europe/London",
+            "Europe/Paris",
+            "America/Los_Angeles",
+        ]
+        .iter()
+        .map(|z| z.to_string())
+        .collect()
+    }
+
+    fn top(query: &str) -> String {
+        search_in(query, &zones())[0].to_string()
+    }
+
+    #[test]
+    fn substring_and_spaces_match() {
+        assert_eq!(top("tokyo"), "Asia/Tokyo");
+        assert_eq!(top("sao paulo"), "America/Sao_Paulo");
+        assert_eq!(top("PARIS"), "Europe/Paris");
+    }
+
+    #[test]
+    fn subsequence_matches() {
+        assert_eq!(top("nwyrk"), "America/New_York");
+        assert!(search_in("qqq", &zones()).is_empty());
+    }
+
+    #[test]
+    fn abbreviation_is_listed_first() {
+        assert_eq!(top("pst"), "PST (America/Los_Angeles)");
+    }
+
+    #[test]
+    fn empty_query_lists_everything() {
+        assert_eq!(search_in("", &zones()).len(), zones().len());
+    }
+
+    #[test]
+    fn city_names_ignore_case_and_accents() {
+        let CityMatch::Unique(city) = find_city("são paulo") else {
+            panic!("expected a unique match");
+        };
+        assert_eq!(city.zone, "America/Sao_Paulo");
+        assert!(matches!(find_city("TOKYO"), CityMatch::Unique(c) if c.zone == "Asia/Tokyo"));
+        assert_eq!(find_city("Atlantis"), CityMatch::None);
+    }
+
+    #[test]
+    fn population_settles_shared_names() {
+        let CityMatch::Likely { city, others } = find_city("London") else {
+            panic!("expected a likely match");
+        };
+        assert_eq!(city.zone, "Europe/London");
+        assert_eq!(others.len(), 1);
+    }
+
+    #[test]
+    fn close_populations_are_ambiguous() {
+        let CityMatch::Ambiguous(cands) = find_city("Valencia") else {
+            panic!("expected an ambiguous match");
+        };
+        assert_eq!(cands[0].zone, "America/Caracas");
+        assert_eq!(cands[1].zone, "Europe/Madrid");
+    }
+
+    #[test]
+    fn country_code_qualifies() {
+        assert!(
+            matches!(find_city("Valencia, es"), CityMatch::Unique(c) if c.zone == "Europe/Madrid")
+        );
+    }
+
+    #[test]
+    fn zone_country_comes_from_the_largest_city() {
+        assert_eq!(zone_country("Asia/Kolkata"), Some("IN"));
+        assert_eq!(zone_country("Etc/GMT+12"), None);
+    }
+
+    #[test]
+    fn database_is_not_empty() {
+        assert!(all_zones().iter().any(|z| z == "Asia/Tokyo"));
+    }
+}
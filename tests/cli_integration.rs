@@ -6,7 +6,15 @@ use predicates::prelude::*;
 fn td_cmd(temp_cfg: &TempDir) -> Command {
     let mut cmd = Command::cargo_bin("td").unwrap();
     cmd.env("XDG_CONFIG_HOME", temp_cfg.path());
+    cmd.env_remove("TARDIS_CONFIG_DIR");
+    cmd.env("XDG_CACHE_HOME", temp_cfg.path().join("cache"));
+    cmd.env_remove("TARDIS_CACHE_DIR");
+    cmd.env("XDG_STATE_HOME", temp_cfg.path().join("state"));
+    cmd.env_remove("TARDIS_STATE_DIR");
     cmd.env("LANG", "en_US.UTF-8");
+    cmd.env_remove("LC_ALL");
+    cmd.env_remove("LC_MESSAGES");
+    cmd.env_remove("LC_CTYPE");
     cmd.env_remove("LC_TIME");
     cmd
 }
@@ -605,6 +613,56 @@ fn completions_powershell() {
         .success();
 }
 
+#[test]
+fn completions_install_is_idempotent() {
+    let tmp = TempDir::new().unwrap();
+    let data = tmp.path().join("data");
+
+    td_cmd(&tmp)
+        .args(["completions", "install"])
+        .env("SHELL", "/bin/bash")
+        .env("XDG_DATA_HOME", &data)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Installed bash completions"));
+    let script = data.join("bash-completion/completions/td");
+    assert!(std::fs::read_to_string(&script).unwrap().contains("_td()"));
+    td_cmd(&tmp)
+        .args(["completions", "install", "bash"])
+        .env("XDG_DATA_HOME", &data)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("up to date"));
+
+    td_cmd(&tmp)
+        .args(["completions", "install", "zsh"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("source <(td completions zsh)"));
+    td_cmd(&tmp)
+        .args(["completions", "install"])
+        .env("SHELL", "/bin/tcsh")
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("could not detect your shell"));
+}
+
+#[test]
+fn print_man_dir_installs_every_page() {
+    let tmp = TempDir::new().unwrap();
+    let data = tmp.path().join("data");
+
+    td_cmd(&tmp)
+        .arg("--print-man-dir")
+        .env("XDG_DATA_HOME", &data)
+        .assert()
+        .success()
+        .stdout(format!("{}\n", data.join("tardis/man").display()));
+    let page = std::fs::read_to_string(data.join("tardis/man/man1/td-diff.1")).unwrap();
+    assert!(page.starts_with(".\\\" Automatically generated by Pandoc"));
+    assert!(data.join("tardis/man/man1/td.1").exists());
+}
+
 #[test]
 fn version_flag() {
     let tmp = TempDir::new().unwrap();
@@ -826,6 +884,190 @@ fn config_presets_empty() {
         .stdout(predicate::str::contains("No presets defined"));
 }
 
+#[test]
+fn config_migrate_dry_run_then_apply() {
+    let tmp = TempDir::new().unwrap();
+    let original = "# mine\nformat = \"%Y\"\ntimezone = \"UTC\"\n";
+    write_config(&tmp, original);
+    let file = tmp.path().join("tardis/config.toml");
+
+    td_cmd(&tmp)
+        .args(["config", "migrate", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("@@ -1,3 +1,4 @@"))
+        .stdout(predicate::str::contains("+version = 1\n"));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), original);
+
+    let backups = tmp.path().join("backups");
+    td_cmd(&tmp)
+        .args(["config", "migrate", "--backup-dir"])
+        .arg(&backups)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from version 0 to 1"));
+    assert_eq!(
+        std::fs::read_to_string(backups.join("config.toml.v0.bak")).unwrap(),
+        original
+    );
+    assert!(
+        std::fs::read_to_string(&file)
+            .unwrap()
+            .contains("version = 1")
+    );
+
+    td_cmd(&tmp)
+        .args(["config", "migrate", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is up to date (version 1)"));
+}
+
+#[test]
+fn config_schema_is_json() {
+    let tmp = TempDir::new().unwrap();
+
+    let out = td_cmd(&tmp).args(["config", "schema"]).output().unwrap();
+    assert!(out.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        schema["required"],
+        serde_json::json!(["format", "timezone"])
+    );
+    assert!(schema["$defs"]["presetTable"]["properties"]["timezone"].is_object());
+}
+
+#[test]
+fn config_set_and_get() {
+    let tmp = TempDir::new().unwrap();
+    write_config(&tmp, "# my zone\nformat = \"%Y\"\ntimezone = \"UTC\"\n");
+
+    td_cmd(&tmp)
+        .args(["config", "set", "timezone", "Asia/Tokyo"])
+        .assert()
+        .success();
+    td_cmd(&tmp)
+        .args(["config", "set", "humanize.granularity", "2"])
+        .assert()
+        .success();
+    let contents = std::fs::read_to_string(tmp.path().join("tardis/config.toml")).unwrap();
+    assert!(contents.starts_with("# my zone\n"));
+    assert!(contents.contains("timezone = \"Asia/Tokyo\""));
+
+    td_cmd(&tmp)
+        .args(["config", "get", "timezone"])
+        .assert()
+        .success()
+        .stdout("Asia/Tokyo\n");
+    td_cmd(&tmp)
+        .args(["config", "get", "humanize.granularity"])
+        .assert()
+        .success()
+        .stdout("2\n");
+
+    let before = std::fs::read_to_string(tmp.path().join("tardis/config.toml")).unwrap();
+    td_cmd(&tmp)
+        .args(["config", "set", "timezone", "Nowhere/Special"])
+        .assert()
+        .code(64);
+    td_cmd(&tmp)
+        .args(["config", "set", "tz", "UTC"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("unknown config key 'tz'"));
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join("tardis/config.toml")).unwrap(),
+        before
+    );
+}
+
+#[test]
+fn config_when_os_overrides_top_level() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        &format!(
+            "format = \"%Y\"\ntimezone = \"UTC\"\n\n[when.os.{}]\nformat = \"%m\"\n\n[when.os.plan9]\nformat = \"%d\"\n",
+            std::env::consts::OS
+        ),
+    );
+
+    td_cmd(&tmp)
+        .args(["--now", "2025-03-15T00:00:00Z", "now"])
+        .assert()
+        .success()
+        .stdout("03\n");
+    td_cmd(&tmp)
+        .args(["-f", "%d", "--now", "2025-03-15T00:00:00Z", "now"])
+        .assert()
+        .success()
+        .stdout("15\n");
+}
+
+#[test]
+fn tardis_config_dir_overrides_xdg() {
+    let tmp = TempDir::new().unwrap();
+    let dir = tmp.path().join("dotfiles/td");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("config.toml"),
+        "format = \"%Y\"\ntimezone = \"UTC\"\n",
+    )
+    .unwrap();
+
+    td_cmd(&tmp)
+        .env("TARDIS_CONFIG_DIR", &dir)
+        .args(["config", "path"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", dir.join("config.toml").display()));
+    td_cmd(&tmp)
+        .env("TARDIS_CONFIG_DIR", &dir)
+        .args(["--now", "2025-03-15T00:00:00Z", "now"])
+        .assert()
+        .success()
+        .stdout("2025\n");
+}
+
+#[test]
+fn history_lives_in_the_state_dir() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("state/tardis/history");
+
+    td_cmd(&tmp)
+        .args(["history", "path"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", file.display()));
+    td_cmd(&tmp)
+        .args(["history", "show"])
+        .assert()
+        .success()
+        .stdout("");
+
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(&file, "tomorrow\nnext friday\n").unwrap();
+    td_cmd(&tmp)
+        .args(["history", "show"])
+        .assert()
+        .success()
+        .stdout("tomorrow\nnext friday\n");
+    td_cmd(&tmp)
+        .args(["history", "clear"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("Removed 2 history entries"));
+    assert!(!file.exists());
+
+    let dir = tmp.path().join("elsewhere");
+    td_cmd(&tmp)
+        .env("TARDIS_STATE_DIR", &dir)
+        .args(["history", "path"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", dir.join("history").display()));
+}
+
 #[test]
 fn config_edit_with_nonexistent_editor() {
     let tmp = TempDir::new().unwrap();
@@ -3456,6 +3698,34 @@ fn verbose_tz() {
         .stderr(predicate::str::contains("[parse]"));
 }
 
+#[test]
+fn info_env_reports_version_and_config() {
+    let tmp = TempDir::new().unwrap();
+
+    let out = td_cmd(&tmp)
+        .args(["info", "--env", "--json"])
+        .env("LC_ALL", "pt_BR.UTF-8")
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(report["locale"], "pt_BR.UTF-8");
+    assert!(
+        report["config"]
+            .as_str()
+            .unwrap()
+            .starts_with(tmp.path().to_str().unwrap())
+    );
+    assert!(report["parser"].as_str().unwrap().contains("jiff 0."));
+
+    td_cmd(&tmp)
+        .args(["info", "--env"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("  Version      "));
+}
+
 #[test]
 fn verbose_info() {
     let tmp = TempDir::new().unwrap();
@@ -3494,6 +3764,44 @@ fn verbose_range() {
         .stderr(predicate::str::contains("[parse]"));
 }
 
+#[test]
+fn verbose_json_trace_format() {
+    let tmp = TempDir::new().unwrap();
+
+    let out = td_cmd(&tmp)
+        .args([
+            "diff",
+            "2025-01-01",
+            "2025-06-01",
+            "-v",
+            "--trace-format",
+            "json",
+        ])
+        .args(["--now", "2025-01-01T00:00:00Z", "-t", "UTC"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    for line in stderr.lines() {
+        let event: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(event["timestamp"].is_string());
+        assert!(event["message"].is_string());
+    }
+    assert!(stderr.contains(r#""target":"parse""#));
+
+    td_cmd(&tmp)
+        .args([
+            "tomorrow",
+            "--trace-format",
+            "json",
+            "--now",
+            "2025-01-01T00:00:00Z",
+        ])
+        .assert()
+        .success()
+        .stderr("");
+}
+
 #[test]
 fn standalone_time_rejected_3pm() {
     let tmp = TempDir::new().unwrap();
@@ -3663,3 +3971,3154 @@ fn same_time_yesterday() {
         .success()
         .stdout(predicate::str::contains("2025-01-14T10:30:00"));
 }
+
+#[test]
+fn output_git_env() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args([
+            "2024-06-24 14:00",
+            "-t",
+            "Europe/Berlin",
+            "--now",
+            "2024-06-20T00:00:00Z",
+            "--output",
+            "git-env",
+        ])
+        .assert()
+        .success()
+        .stdout("GIT_AUTHOR_DATE=@1719230400+0200 GIT_COMMITTER_DATE=@1719230400+0200\n");
+}
+
+#[test]
+fn output_git_env_conflicts_with_json() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["now", "-o", "git-env", "--json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn warns_when_preset_shadows_builtin() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        r#"
+            version = 1
+            format = "%Y"
+            timezone = "UTC"
+            [formats]
+            org = "%F"
+        "#,
+    );
+
+    td_cmd(&tmp)
+        .args(["2025-01-02", "-f", "org"])
+        .assert()
+        .success()
+        .stdout("2025-01-02\n")
+        .stderr("warning: preset 'org' shadows a built-in format\n");
+}
+
+#[test]
+fn quiet_suppresses_warnings() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["2025-01-02 09:00", "-t", "PST", "-f", "%H:%M", "-q"])
+        .assert()
+        .success()
+        .stdout("09:00\n")
+        .stderr("");
+}
+
+#[test]
+fn deny_warnings_fails_before_output() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["tz", "2025-01-02 09:00", "--to", "PST", "--deny-warnings"])
+        .assert()
+        .code(64)
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "Warning treated as error: timezone abbreviation 'PST' is ambiguous",
+        ));
+}
+
+#[test]
+fn iana_fixed_offset_zones_win_over_abbreviations() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["2025-07-01 12:00", "-t", "EST", "-f", "%H:%M %z"])
+        .args(["--deny-warnings"])
+        .assert()
+        .success()
+        .stdout("12:00 -0500\n")
+        .stderr("");
+    td_cmd(&tmp)
+        .args(["tz", "2025-07-01T12:00:00Z", "--to", "MST"])
+        .assert()
+        .success()
+        .stdout("2025-07-01T05:00:00-07:00\n");
+    td_cmd(&tmp)
+        .args(["2025-07-01 12:00", "-t", "EDT", "-f", "%H:%M %z"])
+        .assert()
+        .success()
+        .stdout("12:00 -0400\n")
+        .stderr(predicate::str::contains("assuming America/New_York"));
+}
+
+#[test]
+fn unversioned_config_is_migrated_with_note() {
+    let tmp = TempDir::new().unwrap();
+    write_config(&tmp, "format = \"%Y\"\ntimezone = \"UTC\"\n");
+
+    td_cmd(&tmp)
+        .args(["2025-01-02"])
+        .assert()
+        .success()
+        .stdout("2025\n")
+        .stderr(predicate::str::contains("note: upgraded"))
+        .stderr(predicate::str::contains("config.toml.v0.bak"));
+
+    let migrated = std::fs::read_to_string(tmp.child("tardis/config.toml").path()).unwrap();
+    assert!(migrated.contains("version = 1"));
+
+    td_cmd(&tmp)
+        .args(["2025-01-02"])
+        .assert()
+        .success()
+        .stderr("");
+}
+
+#[test]
+fn newer_config_version_is_rejected() {
+    let tmp = TempDir::new().unwrap();
+    write_config(&tmp, "version = 99\nformat = \"%Y\"\ntimezone = \"UTC\"\n");
+
+    td_cmd(&tmp)
+        .args(["2025-01-02"])
+        .assert()
+        .code(78)
+        .stderr(predicate::str::contains("newer than this td supports"));
+}
+
+#[test]
+fn invalid_preset_format_warns() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n[formats]\nbad = \"%Q\"\n",
+    );
+
+    for _ in 0..2 {
+        td_cmd(&tmp)
+            .args(["2025-01-02"])
+            .assert()
+            .success()
+            .stderr("warning: preset 'bad' has an invalid format: %Q\n");
+    }
+    assert!(tmp.child("cache/tardis/preset-check").exists());
+}
+
+#[test]
+fn cache_clear_removes_entries() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp).args(["now"]).assert().success();
+    assert!(tmp.child("cache/tardis/preset-check").exists());
+
+    td_cmd(&tmp)
+        .args(["cache", "clear"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("Removed 1 cache entry from "));
+    assert!(!tmp.child("cache/tardis/preset-check").exists());
+
+    td_cmd(&tmp)
+        .args(["cache", "clear"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("Cache is already empty"));
+}
+
+#[test]
+fn cache_path_respects_xdg_cache_home() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["cache", "path"])
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with("cache/tardis\n"));
+}
+
+#[test]
+fn lint_format_explains_and_warns() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["lint-format", "%Y-%M-%d"])
+        .assert()
+        .success()
+        .stdout("%Y     year\n\"-\"    literal\n%M     minute (00-59)\n\"-\"    literal\n%d     day of month (01-31)\n")
+        .stderr(
+            "warning: %M is the minute, but the format has no hour; did you mean %m (month)?\n",
+        );
+}
+
+#[test]
+fn lint_format_deny_warnings_fails() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["lint-format", "%c", "--deny-warnings"])
+        .assert()
+        .code(64)
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "%c has an implementation-defined layout",
+        ));
+}
+
+#[test]
+fn lint_format_resolves_builtin_name_json() {
+    let tmp = TempDir::new().unwrap();
+
+    let out = td_cmd(&tmp)
+        .args(["lint-format", "rfc3339", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(json["format"], "%Y-%m-%dT%H:%M:%S%:z");
+    assert_eq!(json["pieces"][0]["specifier"], "%Y");
+    assert_eq!(json["warnings"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn lint_format_presets_reports_each_preset() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        r#"
+            version = 1
+            format = "%F"
+            timezone = "UTC"
+            [formats]
+            stamp = "%d/%M/%Y"
+            time = "%H:%M"
+        "#,
+    );
+
+    td_cmd(&tmp)
+        .args(["lint-format", "--presets"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("stamp = \"%d/%M/%Y\"\n"))
+        .stdout(predicate::str::contains("\ntime = \"%H:%M\"\n"))
+        .stderr(predicate::str::contains(
+            "warning: preset 'stamp': %M is the minute",
+        ));
+}
+
+#[test]
+fn lint_format_rejects_unknown_specifier() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["lint-format", "%Y-%Q"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("invalid format string"));
+}
+
+#[test]
+fn prompt_prints_one_line_without_creating_config() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["prompt", "-t", "UTC", "--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("10:30\n")
+        .stderr("");
+    assert!(!tmp.child("tardis/config.toml").exists());
+}
+
+#[test]
+fn prompt_prints_fallback_instead_of_failing() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["prompt", "not a date", "--fallback=--:--"])
+        .assert()
+        .success()
+        .stdout("--:--\n")
+        .stderr("");
+
+    td_cmd(&tmp)
+        .args(["prompt", "-t", "Mars/Olympus"])
+        .assert()
+        .success()
+        .stdout("?\n");
+}
+
+#[test]
+fn prompt_keeps_output_on_one_line() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args([
+            "prompt",
+            "-t",
+            "UTC",
+            "--now",
+            "2025-01-15T10:30:00Z",
+            "-f",
+            "%H%n%M",
+        ])
+        .assert()
+        .success()
+        .stdout("10 30\n");
+}
+
+#[test]
+fn prompt_cache_tz_remembers_system_zone() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .env("TZ", "Asia/Tokyo")
+        .args(["prompt", "--cache-tz", "--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("19:30\n");
+
+    let cached = std::fs::read_to_string(tmp.child("cache/tardis/prompt-tz").path()).unwrap();
+    assert_eq!(cached, "Asia/Tokyo");
+
+    td_cmd(&tmp)
+        .env("TZ", "UTC")
+        .args(["prompt", "--cache-tz", "--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("19:30\n");
+}
+
+#[test]
+fn preset_can_reference_other_presets() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n[formats]\nday = \"%d/%m/%Y\"\nlogline = \"{day} {clock} [%Z]\"\nclock = \"%H:%M\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["2025-01-02 09:30", "-f", "logline"])
+        .assert()
+        .success()
+        .stdout("02/01/2025 09:30 [UTC]\n");
+}
+
+#[test]
+fn preset_reference_cycle_warns_and_fails_when_used() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n[formats]\na = \"{b}\"\nb = \"{a}\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["2025-01-02", "-f", "a"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains(
+            "warning: preset 'a' has a reference cycle: a -> b -> a\n",
+        ))
+        .stderr(predicate::str::contains(
+            "preset 'a' has a reference cycle: a -> b -> a",
+        ));
+}
+
+#[test]
+fn preset_params_fill_placeholders() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n[formats]\nmeeting = \"%Y-%m-%d %H:%M ({label})\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args([
+            "2025-01-02 09:30",
+            "-f",
+            "meeting",
+            "--param",
+            "label=standup",
+        ])
+        .assert()
+        .success()
+        .stdout("2025-01-02 09:30 (standup)\n");
+
+    td_cmd(&tmp)
+        .args(["2025-01-02 09:30", "-f", "meeting"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains(
+            "preset 'meeting' needs --param label=VALUE",
+        ));
+}
+
+#[test]
+fn param_without_equals_is_rejected() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["now", "--param", "label"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("expected NAME=VALUE"));
+}
+
+#[test]
+fn preset_group_qualifies_names() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n[formats]\nshort = \"%d/%m\"\n[formats.work]\nshort = \"%m-%d\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["2025-01-02", "-f", "work.short"])
+        .assert()
+        .success()
+        .stdout("01-02\n");
+    td_cmd(&tmp)
+        .args(["2025-01-02", "-f", "short"])
+        .assert()
+        .success()
+        .stdout("02/01\n");
+    td_cmd(&tmp)
+        .args(["2025-01-02", "-f", "short", "--preset-group", "work"])
+        .assert()
+        .success()
+        .stdout("01-02\n");
+}
+
+#[test]
+fn unknown_preset_group_warns() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\npreset_group = \"home\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["2025-01-02"])
+        .assert()
+        .success()
+        .stdout("2025\n")
+        .stderr("warning: preset group 'home' is not defined in [formats]\n");
+}
+
+#[test]
+fn table_preset_applies_its_timezone() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n[formats]\njp = { format = \"%Y年%m月%d日 %H:%M\", timezone = \"Asia/Tokyo\" }\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["now", "--now", "2025-01-02T20:00:00Z", "-f", "jp"])
+        .assert()
+        .success()
+        .stdout("2025年01月03日 05:00\n");
+    td_cmd(&tmp)
+        .args([
+            "now",
+            "--now",
+            "2025-01-02T20:00:00Z",
+            "-f",
+            "jp",
+            "-t",
+            "UTC",
+        ])
+        .assert()
+        .success()
+        .stdout("2025年01月02日 20:00\n");
+}
+
+#[test]
+fn tz_pick_prints_best_match() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["tz", "2025-07-01T12:00:00Z", "--to", "MST"])
+        .assert()
+        .success()
+        .stdout("2025-07-01T05:00:00-07:00\n");
+    td_cmd(&tmp)
+        .args(["tz", "pick", "--list", "pst"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("PST (America/Los_Angeles)\n"));
+}
+
+#[test]
+fn tz_pick_save_updates_config() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\n# keep\nformat = \"%Y\"\ntimezone = \"UTC\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["tz", "pick", "tokyo", "--save"])
+        .assert()
+        .success()
+        .stdout("Asia/Tokyo\n")
+        .stderr(predicate::str::contains("Saved timezone = \"Asia/Tokyo\""));
+
+    let saved = std::fs::read_to_string(tmp.child("tardis/config.toml").path()).unwrap();
+    assert_eq!(
+        saved,
+        "version = 1\n# keep\nformat = \"%Y\"\ntimezone = \"Asia/Tokyo\"\n"
+    );
+}
+
+#[test]
+fn tz_pick_without_match_fails() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["tz", "pick", "qqqq"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("no time zone matches 'qqqq'"));
+}
+
+#[test]
+fn timezone_accepts_city_names() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["now", "-t", "São Paulo", "-f", "%H:%M %Z"])
+        .args(["--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("07:30 -03\n");
+    td_cmd(&tmp)
+        .args(["tz", "at", "tokyo", "--id"])
+        .assert()
+        .success()
+        .stdout("Asia/Tokyo\n");
+}
+
+#[test]
+fn tz_at_prefers_much_larger_city() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["tz", "at", "london", "--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("2025-01-15T10:30:00+00:00\n")
+        .stderr(predicate::str::contains(
+            "assuming London, GB (Europe/London)",
+        ));
+}
+
+#[test]
+fn ambiguous_city_lists_candidates() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["tz", "at", "Valencia"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains(
+            "Valencia, VE (America/Caracas); Valencia, ES (Europe/Madrid)",
+        ));
+    td_cmd(&tmp)
+        .args(["tz", "at", "Valencia, ES", "--id"])
+        .assert()
+        .success()
+        .stdout("Europe/Madrid\n");
+}
+
+#[test]
+fn meet_prints_common_windows() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["meet", "Europe/London", "America/New_York"])
+        .args(["--on", "2025-01-15", "--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("Europe/London  America/New_York\n14:00-17:00    09:00-12:00\n");
+}
+
+#[test]
+fn meet_hours_come_from_config_and_participants() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[meet]\nhours = \"10:00-18:00\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["meet", "UTC", "Asia/Kolkata=09:00-17:00", "--json"])
+        .args(["--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#""start":"2025-01-15T10:00:00+00:00""#,
+        ))
+        .stdout(predicate::str::contains(r#""time":"15:30-17:00""#));
+}
+
+#[test]
+fn meet_falls_back_to_the_hours_schedule() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[hours]\nmon-fri = \"07:00-12:00, 13:00-15:00\"\nwed = \"10:00-14:00\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["meet", "UTC", "--on", "2025-01-14"])
+        .assert()
+        .success()
+        .stdout("UTC\n07:00-12:00\n13:00-15:00\n");
+    td_cmd(&tmp)
+        .args(["meet", "UTC", "--on", "2025-01-15"])
+        .assert()
+        .success()
+        .stdout("UTC\n10:00-14:00\n");
+    td_cmd(&tmp)
+        .args([
+            "meet",
+            "UTC",
+            "--on",
+            "2025-01-15",
+            "--hours",
+            "09:00-10:00",
+        ])
+        .assert()
+        .success()
+        .stdout("UTC\n09:00-10:00\n");
+}
+
+#[test]
+fn meet_follows_the_working_days() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[hours]\nmon-sun = \"10:00-12:00\"\n",
+    );
+    td_cmd(&tmp)
+        .args(["meet", "UTC", "--on", "2026-01-03"])
+        .assert()
+        .success()
+        .stdout("UTC\n10:00-12:00\n");
+
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[workweek]\ndays = [\"sun\", \"mon\", \"tue\", \"wed\", \"thu\"]\n",
+    );
+    for (day, out) in [("2026-01-04", "UTC\n10:00-11:00\n"), ("2026-01-02", "")] {
+        td_cmd(&tmp)
+            .args(["meet", "UTC", "--on", day, "--hours", "10:00-11:00"])
+            .assert()
+            .success()
+            .stdout(out);
+    }
+}
+
+#[test]
+fn meet_finds_no_windows_on_days_the_hours_table_closes() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[hours]\nmon-fri = \"09:00-17:00\"\n\"2025-12-31\" = \"closed\"\n\n[holidays]\nChristmas = \"12-25\"\n",
+    );
+
+    for day in ["2025-12-31", "2025-12-25"] {
+        td_cmd(&tmp)
+            .args(["meet", "UTC", "Europe/London", "--on", day])
+            .assert()
+            .success()
+            .stdout("")
+            .stderr(format!("No common working hours on {day}.\n"));
+    }
+    td_cmd(&tmp)
+        .args(["meet", "UTC", "Europe/London", "--on", "2025-12-31"])
+        .args(["--hours", "10:00-12:00"])
+        .assert()
+        .success()
+        .stdout("UTC          Europe/London\n10:00-12:00  10:00-12:00\n");
+}
+
+#[test]
+fn meet_without_overlap_reports_on_stderr() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["meet", "UTC", "Pacific/Auckland", "--on", "2025-01-15"])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr("No common working hours on 2025-01-15.\n");
+    td_cmd(&tmp)
+        .args(["meet", "UTC", "--hours", "nine-to-five"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("invalid working hours"));
+}
+
+#[test]
+fn worldclock_lists_configured_zones() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[worldclock]\nzones = [\"UTC\", \"Asia/Tokyo\"]\nformat = \"%H:%M\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["worldclock", "--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("UTC         10:30  +00:00\nAsia/Tokyo  19:30  +09:00\n");
+}
+
+#[test]
+fn worldclock_zone_flags_replace_config() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[worldclock]\nzones = [\"UTC\"]\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["worldclock", "tomorrow 9am", "-z", "São Paulo", "--json"])
+        .args(["--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout(
+            r#"[{"iso":"2025-01-16T06:00:00-03:00","offset":"-03:00","time":"2025-01-16 06:00","zone":"São Paulo"}]
+"#,
+        );
+}
+
+#[test]
+fn worldclock_without_zones_fails() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .arg("worldclock")
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("no zones to show"));
+}
+
+#[test]
+fn config_show_quotes_worldclock_values() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[worldclock]\nzones = [\"UTC\", \"Asia/Tokyo\"]\nformat = '%H:%M \"\\%Z\"'\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[worldclock]\nzones = [\"UTC\", \"Asia/Tokyo\"]\nformat = '%H:%M \"\\%Z\"'\n",
+        ));
+}
+
+#[test]
+fn epoch_converts_timestamps_to_dates() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["epoch", "1719230400", "-t", "Asia/Tokyo"])
+        .assert()
+        .success()
+        .stdout("2024-06-24T21:00:00+09:00\n");
+    td_cmd(&tmp)
+        .args(["epoch", "1719230400", "--unit", "ms"])
+        .assert()
+        .success()
+        .stdout("1970-01-20T21:33:50.4+00:00\n");
+}
+
+#[test]
+fn epoch_converts_expressions_to_timestamps() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["epoch", "next friday 9am", "-u", "ms", "-t", "UTC"])
+        .args(["--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("1737104400000\n");
+    td_cmd(&tmp)
+        .args(["epoch", "-86400", "--json", "-t", "UTC"])
+        .assert()
+        .success()
+        .stdout(
+            r#"{"epoch":-86400,"input":"-86400","iso":"1969-12-31T00:00:00+00:00","unit":"s"}
+"#,
+        );
+}
+
+#[test]
+fn unix_ns_reads_and_writes_128_bit_nanoseconds() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["@1735689600123456789ns", "-f", "unix_ns", "-t", "UTC"])
+        .assert()
+        .success()
+        .stdout("1735689600123456789\n");
+    td_cmd(&tmp)
+        .args(["convert", "16725225600000000000", "--from", "unix_ns"])
+        .args(["--to", "%Y-%m-%d", "-t", "UTC"])
+        .assert()
+        .success()
+        .stdout("2500-01-01\n");
+    td_cmd(&tmp)
+        .args(["epoch", "2500-01-01", "-u", "ns", "--json", "-t", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#""epoch":"16725225600000000000""#,
+        ));
+    td_cmd(&tmp)
+        .args(["convert", "12x", "--from", "unix_ns", "--to", "iso8601"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains(
+            "is not a Unix timestamp in nanoseconds",
+        ));
+}
+
+#[test]
+fn sleep_until_waits_for_the_target() {
+    let tmp = TempDir::new().unwrap();
+
+    let started = std::time::Instant::now();
+    td_cmd(&tmp)
+        .args(["sleep-until", "2025-01-15T10:30:01Z"])
+        .args(["--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("");
+    assert!(started.elapsed() >= std::time::Duration::from_millis(900));
+}
+
+#[test]
+fn sleep_until_past_target_returns_at_once() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["sleep-until", "yesterday", "--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn sleep_until_refuses_beyond_max_wait() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args([
+            "sleep-until",
+            "tomorrow 9am",
+            "--max-wait",
+            "1h",
+            "-t",
+            "UTC",
+        ])
+        .args(["--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains(
+            "is 22h 30m away, beyond --max-wait 1h",
+        ));
+}
+
+#[test]
+fn every_dry_run_lists_occurrences() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["every", "every mon and fri at 9am", "--dry-run", "-c", "3"])
+        .args(["-t", "UTC", "--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout(
+            "2025-01-17T09:00:00+00:00\n2025-01-20T09:00:00+00:00\n2025-01-24T09:00:00+00:00\n",
+        );
+}
+
+#[test]
+fn every_runs_the_command_and_logs_it() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["every", "every second", "--count", "1", "--"])
+        .args(["sh", "-c", "echo run $TD_RUN"])
+        .assert()
+        .success()
+        .stdout("run 1\n")
+        .stderr(predicate::str::contains("note: run 1 at "))
+        .stderr(predicate::str::contains(": exit 0"));
+}
+
+#[test]
+fn every_rejects_unknown_schedules_and_missing_commands() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["every", "every fortnight", "--", "true"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("unrecognized schedule"));
+    td_cmd(&tmp)
+        .args(["every", "every minute"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("needs a command after --"));
+}
+
+#[test]
+fn named_dates_resolve_inside_expressions() {
+    let tmp = TempDir::new().unwrap();
+    let now = "2025-01-15T10:30:00Z";
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%F %H:%M\"\ntimezone = \"UTC\"\n\n[dates]\nrelease = \"2025-10-01T12:00:00Z\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["release", "--now", now])
+        .assert()
+        .success()
+        .stdout("2025-10-01 12:00\n");
+    td_cmd(&tmp)
+        .args(["3 days before release", "--now", now])
+        .assert()
+        .success()
+        .stdout("2025-09-28 12:00\n");
+    td_cmd(&tmp)
+        .args(["release", "-t", "Asia/Tokyo", "--json", "--now", now])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"input\":\"release\""))
+        .stdout(predicate::str::contains("\"output\":\"2025-10-01 21:00\""));
+
+    td_cmd(&tmp)
+        .args(["config", "set", "dates.launch", "whenever"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("dates.launch"));
+}
+
+#[test]
+fn until_counts_down_to_named_dates() {
+    let tmp = TempDir::new().unwrap();
+    let now = "2025-01-15T10:30:00Z";
+    td_cmd(&tmp)
+        .args(["until", "--all", "--now", now])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicate::str::contains("no dates to count down to"));
+
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%F\"\ntimezone = \"UTC\"\n\n[dates]\nrelease = \"2025-10-01T12:00:00Z\"\nfreeze = \"2025-01-20\"\n",
+    );
+    td_cmd(&tmp)
+        .args(["until", "release", "--now", now])
+        .assert()
+        .success()
+        .stdout("OK: release in 9 months (2025-10-01T12:00:00+00:00)\n");
+    td_cmd(&tmp)
+        .args(["until", "--all", "--warn", "1w", "--now", now])
+        .assert()
+        .code(1)
+        .stdout(
+            "WARNING: freeze in 5 days (2025-01-20T00:00:00+00:00)\n\
+             OK: release in 9 months (2025-10-01T12:00:00+00:00)\n",
+        );
+}
+
+#[test]
+fn holiday_answers_next_is_and_list() {
+    let tmp = TempDir::new().unwrap();
+    let now = "2025-01-15T10:30:00Z";
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%F\"\ntimezone = \"UTC\"\n\n[holidays]\nChristmas = \"12-25\"\nMLK = \"2025-01-20\"\n",
+    );
+    let holiday = |args: &[&str]| {
+        td_cmd(&tmp)
+            .arg("holiday")
+            .args(args)
+            .args(["--now", now])
+            .assert()
+    };
+
+    holiday(&["next"])
+        .success()
+        .stdout("2025-01-20 MLK (in 5 days)\n");
+    holiday(&["next", "2025-02-01", "--json"])
+        .success()
+        .stdout("{\"date\":\"2025-12-25\",\"days\":344,\"name\":\"Christmas\"}\n");
+    holiday(&["is", "2026-12-25"])
+        .success()
+        .stdout("Christmas\n");
+    holiday(&["is"]).code(1).stdout("");
+    holiday(&["list", "2025-01-01 to 2027-01-01", "-f", "%Y"])
+        .success()
+        .stdout("2025  MLK\n2025  Christmas\n2026  Christmas\n");
+
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%F\"\ntimezone = \"UTC\"\n\n[holidays]\nnope = \"02-30\"\n",
+    );
+    holiday(&["next"])
+        .code(64)
+        .stderr(predicate::str::contains("holidays.nope"));
+}
+
+#[test]
+fn is_exits_by_predicate() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%F\"\ntimezone = \"UTC\"\n\n[holidays]\nMLK = \"2025-01-20\"\n",
+    );
+    let is = |args: &[&str]| {
+        td_cmd(&tmp)
+            .arg("is")
+            .args(args)
+            .args(["--now", "2025-01-15T10:30:00Z"])
+            .assert()
+    };
+    is(&["weekday"]).success().stdout("");
+    is(&["weekend"]).code(1).stdout("");
+    is(&["wed"]).success();
+    is(&["Friday"]).code(1);
+    is(&["weekend", "next saturday"]).success();
+    is(&["holiday", "2025-01-20"]).success();
+    is(&["workday", "2025-01-20"]).code(1);
+    is(&["workday", "2025-01-21"]).success();
+    is(&["funday"])
+        .code(64)
+        .stderr(predicate::str::contains("unknown predicate 'funday'"));
+}
+
+#[test]
+fn is_open_and_next_open_follow_the_hours_table() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%FT%T\"\ntimezone = \"UTC\"\n\n[hours]\nmon-fri = \"09:00-18:00\"\nfri = \"09:00-15:00\"\n\"2025-01-16\" = \"closed\"\n",
+    );
+    let run = |args: &[&str]| {
+        td_cmd(&tmp)
+            .args(args)
+            .args(["--now", "2025-01-15T10:30:00Z"])
+            .assert()
+    };
+    run(&["is-open"]).success().stdout("");
+    run(&["is-open", "today 18:00"]).code(1);
+    run(&["is-open", "tomorrow 10:00"]).code(1);
+    run(&["next-open"])
+        .success()
+        .stdout("2025-01-15T10:30:00\n");
+    run(&["next-open", "today 19:00"])
+        .success()
+        .stdout("2025-01-17T09:00:00\n");
+    run(&["next-open", "2025-01-17 16:00", "--json"])
+        .success()
+        .stdout(predicate::str::contains("\"open\":false"))
+        .stdout(predicate::str::contains(
+            "\"start\":\"2025-01-20T09:00:00\"",
+        ))
+        .stdout(predicate::str::contains(
+            "\"until\":\"2025-01-20T18:00:00\"",
+        ));
+
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%FT%T\"\ntimezone = \"UTC\"\n\n[hours]\nmon-fri = \"9 to 5\"\n",
+    );
+    run(&["is-open"])
+        .code(64)
+        .stderr(predicate::str::contains("hours.mon-fri"));
+
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"br\"\ntimezone = \"UTC\"\n\n[formats]\nbr = \"%d/%m %H:%M\"\n\n[hours]\nmon-fri = \"09:00-18:00\"\n",
+    );
+    run(&["next-open", "today 19:00"])
+        .success()
+        .stdout("16/01 09:00\n");
+}
+
+#[test]
+fn named_recurrences_resolve_as_input_and_schedules() {
+    let tmp = TempDir::new().unwrap();
+    let now = "2025-01-15T10:30:00Z";
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%a %F\"\ntimezone = \"UTC\"\n\n[recurrences]\nsprint-start = \"every other monday starting 2025-01-06\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["sprint-start", "--now", now])
+        .assert()
+        .success()
+        .stdout("Mon 2025-01-20\n");
+    td_cmd(&tmp)
+        .args(["sprint-start", "--count", "3", "-f", "%F", "--now", now])
+        .assert()
+        .success()
+        .stdout("2025-01-20\n2025-02-03\n2025-02-17\n");
+    td_cmd(&tmp)
+        .args([
+            "every",
+            "sprint-start",
+            "--dry-run",
+            "-c",
+            "1",
+            "--now",
+            now,
+        ])
+        .assert()
+        .success()
+        .stdout("2025-01-20T00:00:00+00:00\n");
+
+    td_cmd(&tmp)
+        .args(["tomorrow", "--count", "2", "--now", now])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains(
+            "--count needs the name of a recurrence",
+        ));
+}
+
+#[test]
+fn exec_runs_the_template_with_tokens_filled_in() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["next friday", "-t", "UTC", "--now", "2025-01-15T10:30:00Z"])
+        .args(["-f", "%A %d", "--exec", "echo {iso} {epoch} '<{fmt}>'"])
+        .assert()
+        .success()
+        .stdout("2025-01-17T00:00:00+00:00 1737072000 <Friday 17>\n");
+}
+
+#[test]
+fn exec_dry_run_prints_the_quoted_command() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["next friday", "-t", "UTC", "--now", "2025-01-15T10:30:00Z"])
+        .args(["-f", "%A %d", "--exec", "touch '{fmt}.txt'", "--dry-run"])
+        .assert()
+        .success()
+        .stdout("touch 'Friday 17.txt'\n");
+    assert!(!tmp.path().join("Friday 17.txt").exists());
+}
+
+#[test]
+fn exec_passes_on_failures() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["now", "--exec", "sh -c 'exit 3'"])
+        .assert()
+        .code(3);
+    td_cmd(&tmp)
+        .args(["--exec", "sh -c 'exit 3'", "--on-error", "record"])
+        .write_stdin("today\ntomorrow\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""line":2"#))
+        .stderr(predicate::str::contains("2 of 2 lines failed"));
+    td_cmd(&tmp)
+        .args(["--exec", "sh -c 'exit 3'", "--on-error", "skip", "--strict"])
+        .write_stdin("today\ntomorrow\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("2 of 2 lines failed"));
+    td_cmd(&tmp)
+        .args(["now", "--exec", "echo {date}"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("unknown --exec token '{date}'"));
+}
+
+#[test]
+fn stamp_prefixes_each_line() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["stamp", "-f", "%Y-%m-%d", "-t", "UTC"])
+        .args(["--now", "2025-01-15T10:30:00Z"])
+        .write_stdin("first\nsecond")
+        .assert()
+        .success()
+        .stdout("2025-01-15 first\n2025-01-15 second\n");
+}
+
+#[test]
+fn stamp_elapsed_counts_from_the_start() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["stamp", "--elapsed", "-f", "%H:%M"])
+        .write_stdin("building\n")
+        .assert()
+        .success()
+        .stdout("00:00 building\n");
+    td_cmd(&tmp)
+        .args(["stamp", "-f", "%Q"])
+        .write_stdin("x\n")
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("invalid format string"));
+}
+
+#[test]
+fn delta_annotates_gaps_between_lines() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["delta", "--threshold", "1m"])
+        .write_stdin(
+            "2025-01-15T10:30:00Z start\nno stamp\n\
+             2025-01-15 10:30:01.250 ok\n2025-01-15T11:32:01+01:00 late\n",
+        )
+        .env("TZ", "UTC")
+        .assert()
+        .success()
+        .stdout(
+            "           +0s  2025-01-15T10:30:00Z start\n                \
+             no stamp\n     +1s 250ms  2025-01-15 10:30:01.250 ok\n \
+             !1m 59s 750ms  2025-01-15T11:32:01+01:00 late\n",
+        );
+}
+
+#[test]
+fn delta_seconds_prints_bare_deltas() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["delta", "--seconds", "-t", "UTC"])
+        .write_stdin("2025-01-15 10:30:00 a\nb\n2025-01-15 10:30:02,5 c\n")
+        .assert()
+        .success()
+        .stdout("0\n2.5\n");
+    td_cmd(&tmp)
+        .args(["delta", "--threshold", "soon"])
+        .write_stdin("")
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("invalid --threshold 'soon'"));
+}
+
+#[test]
+fn rewrite_normalizes_profile_timestamps() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["rewrite", "--profile", "nginx", "-t", "UTC"])
+        .write_stdin("1.2.3.4 - - [15/Jan/2025:10:30:00 -0300] \"GET /\" 200\nnoise\n")
+        .assert()
+        .success()
+        .stdout("1.2.3.4 - - [2025-01-15T13:30:00+00:00] \"GET /\" 200\nnoise\n");
+    td_cmd(&tmp)
+        .args([
+            "rewrite",
+            "-p",
+            "syslog",
+            "-f",
+            "%Y-%m-%d %H:%M",
+            "-t",
+            "UTC",
+        ])
+        .args(["--now", "2024-03-01T00:00:00Z"])
+        .write_stdin("Feb 29 23:59:01 host cron: ran\n")
+        .assert()
+        .success()
+        .stdout("2024-02-29 23:59 host cron: ran\n");
+}
+
+#[test]
+fn delta_reads_other_profiles() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["delta", "-p", "java", "--seconds", "-t", "UTC"])
+        .write_stdin("Jan 5, 2025 1:02:03 PM a\nJan 5, 2025 1:02:33 PM b\n")
+        .assert()
+        .success()
+        .stdout("0\n30\n");
+}
+
+#[test]
+fn sum_totals_mixed_duration_forms() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .arg("sum")
+        .write_stdin("1h30m\n45m\n\n2:15\n")
+        .assert()
+        .success()
+        .stdout("4h 30m\n");
+    td_cmd(&tmp)
+        .args(["sum", "-o", "clock"])
+        .write_stdin("23:45\n0:30:15\n")
+        .assert()
+        .success()
+        .stdout("24:15:15\n");
+}
+
+#[test]
+fn sum_group_by_totals_per_label() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["sum", "--group-by", "-o", "hours"])
+        .write_stdin("api 1h30m\nweb 45m\napi 2:15\n")
+        .assert()
+        .success()
+        .stdout("api    3.75\nweb    0.75\ntotal  4.5\n");
+    td_cmd(&tmp)
+        .arg("sum")
+        .write_stdin("1h\nlunch\n")
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("line 2: invalid duration 'lunch'"));
+}
+
+#[test]
+fn sum_reads_timesheet_intervals() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["sum", "--each", "-o", "clock"])
+        .write_stdin("09:00-12:30\n2025-01-15 13:15 - 17:45\n22:00-01:30\n")
+        .assert()
+        .success()
+        .stdout(
+            "09:00-12:30               3:30\n\
+             2025-01-15 13:15 - 17:45  4:30\n\
+             22:00-01:30               3:30\n\
+             total                     11:30\n",
+        );
+    td_cmd(&tmp)
+        .args(["sum", "-e", "-o", "hours", "--json"])
+        .write_stdin("2025-01-15T22:00 - 2025-01-16T02:00\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\"entries\":[{\"entry\":\"2025-01-15T22:00 - 2025-01-16T02:00\",\"seconds\":14400,\"total\":\"4\"}]",
+        ));
+    td_cmd(&tmp)
+        .arg("sum")
+        .write_stdin("2025-01-16 09:00 - 2025-01-15 10:00\n")
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("line 1: invalid duration"));
+}
+
+#[test]
+fn decimal_durations_round_as_configured() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["sum", "-o", "decimal"])
+        .write_stdin("1h20m\n45m\n2:10\n")
+        .assert()
+        .success()
+        .stdout("4.25h\n");
+    td_cmd(&tmp)
+        .args(["diff", "2025-01-01", "2025-01-03 06:00", "-o", "decimal"])
+        .args(["-t", "UTC", "--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("2.25d\n");
+
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%F\"\ntimezone = \"UTC\"\n\n[humanize]\ndecimals = 1\nrounding = \"up\"\n",
+    );
+    td_cmd(&tmp)
+        .args(["sum", "-o", "hours"])
+        .write_stdin("1h20m\n45m\n2:10\n")
+        .assert()
+        .success()
+        .stdout("4.3\n");
+    td_cmd(&tmp)
+        .args([
+            "sum",
+            "-o",
+            "hours",
+            "--decimals",
+            "2",
+            "--rounding",
+            "down",
+        ])
+        .write_stdin("1h41m\n")
+        .assert()
+        .success()
+        .stdout("1.68\n");
+}
+
+#[test]
+fn stats_summarizes_stdin_timestamps() {
+    let tmp = TempDir::new().unwrap();
+    let log = "2025-01-15T10:30:00Z a\nnoise\n2025-01-14T09:00:00Z b\n2025-01-15T12:00:00Z c\n";
+
+    td_cmd(&tmp)
+        .args(["stats", "-t", "UTC"])
+        .write_stdin(log)
+        .assert()
+        .success()
+        .stdout(
+            "count     3\nearliest  2025-01-14T09:00:00+00:00\n\
+             latest    2025-01-15T12:00:00+00:00\nspan      27h\n",
+        );
+    td_cmd(&tmp)
+        .args(["stats", "-t", "UTC", "--per-day", "-f", "%H:%M"])
+        .write_stdin(log)
+        .assert()
+        .success()
+        .stdout(
+            "DATE        COUNT  EARLIEST  LATEST  SPAN\n\
+             2025-01-14  1      09:00     09:00   0s\n\
+             2025-01-15  2      10:30     12:00   1h 30m\n",
+        );
+}
+
+#[test]
+fn stats_fails_without_timestamps() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .arg("stats")
+        .write_stdin("nothing here\n")
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("no timestamps found on stdin"));
+}
+
+#[test]
+fn hist_draws_bars_with_empty_buckets() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["hist", "-t", "UTC", "--width", "4"])
+        .write_stdin(
+            "2025-01-15T10:30:00Z a\n2025-01-15T10:40:00Z b\nnoise\n2025-01-15T12:00:00Z c\n",
+        )
+        .assert()
+        .success()
+        .stdout("2025-01-15 10:00  ████ 2\n2025-01-15 11:00  0\n2025-01-15 12:00  ██ 1\n");
+}
+
+#[test]
+fn hist_csv_by_week() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["hist", "-t", "UTC", "--by", "week", "--csv"])
+        .write_stdin("2025-01-13 09:00:00 mon\n2025-01-19 23:00:00 sun\n2025-01-27 08:00:00 next\n")
+        .assert()
+        .success()
+        .stdout("bucket,count\n2025-W03,2\n2025-W04,0\n2025-W05,1\n");
+}
+
+#[test]
+fn group_by_counts_per_day_in_the_configured_zone() {
+    let tmp = TempDir::new().unwrap();
+    write_config(&tmp, "format = \"%Y\"\ntimezone = \"Asia/Tokyo\"\n");
+
+    td_cmd(&tmp)
+        .args(["group-by", "day"])
+        .write_stdin("2025-01-14T14:00:00Z a\n2025-01-14T16:00:00Z b\n2025-01-15T01:00:00Z c\n")
+        .assert()
+        .success()
+        .stdout("2025-01-14  1\n2025-01-15  2\n");
+}
+
+#[test]
+fn group_by_lines_keeps_continuations_together() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["group-by", "month", "--lines", "-t", "UTC"])
+        .write_stdin("boot\n2025-02-01T00:00:00Z b\n2025-01-31T10:00:00Z a\n  at x\n")
+        .assert()
+        .success()
+        .stdout(
+            "boot\n\n== 2025-01 ==\n2025-01-31T10:00:00Z a\n  at x\n\n\
+             == 2025-02 ==\n2025-02-01T00:00:00Z b\n",
+        );
+}
+
+#[test]
+fn humanize_shows_the_requested_units() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args([
+            "humanize",
+            "2025-07-10T14:00:00Z",
+            "--granularity",
+            "2",
+            "--now",
+            "2025-06-27T14:00:00Z",
+            "-t",
+            "UTC",
+        ])
+        .assert()
+        .success()
+        .stdout("in 1 week 6 days\n");
+}
+
+#[test]
+fn relative_uses_the_humanize_config() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "format = \"%Y\"\ntimezone = \"UTC\"\n\n[humanize]\nnamed_day_after = \"48h\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args([
+            "yesterday 15:00",
+            "--relative",
+            "--now",
+            "2025-06-27T14:00:00Z",
+        ])
+        .assert()
+        .success()
+        .stdout("23 hours ago\n");
+    td_cmd(&tmp)
+        .args([
+            "humanize",
+            "yesterday 15:00",
+            "--now",
+            "2025-06-27T14:00:00Z",
+        ])
+        .assert()
+        .success()
+        .stdout("23 hours ago\n");
+}
+
+#[test]
+fn humanize_rejects_a_bad_threshold() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "format = \"%Y\"\ntimezone = \"UTC\"\n\n[humanize]\nnow_within = \"soon\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["humanize", "tomorrow"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("now_within = 'soon'"));
+}
+
+#[test]
+fn until_exit_status_follows_the_thresholds() {
+    let tmp = TempDir::new().unwrap();
+    let check = |target: &str| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.args([
+            "until",
+            target,
+            "--warn",
+            "30d",
+            "--crit",
+            "7d",
+            "--now",
+            "2025-06-27T14:00:00Z",
+            "-t",
+            "UTC",
+        ]);
+        cmd
+    };
+
+    check("2025-09-01")
+        .assert()
+        .code(0)
+        .stdout("OK: in 2 months (2025-09-01T00:00:00+00:00)\n");
+    check("2025-07-20")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::starts_with("WARNING: in 3 weeks"));
+    check("2025-06-30")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::starts_with("CRITICAL: in 2 days"));
+    check("2025-06-01")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::starts_with("CRITICAL: 4 weeks ago"));
+}
+
+#[test]
+fn until_labels_the_status_line_and_rejects_bad_durations() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["until", "2025-09-01", "-l", "cert expiry"])
+        .args(["--now", "2025-06-27T14:00:00Z", "-t", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("OK: cert expiry in 2 months"));
+    td_cmd(&tmp)
+        .args(["until", "2025-09-01", "--warn", "soon"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("invalid --warn duration 'soon'"));
+}
+
+#[test]
+fn if_past_and_if_future_pick_a_text() {
+    let tmp = TempDir::new().unwrap();
+    let run = |input: &str, flags: &[&str]| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.arg(input)
+            .args(flags)
+            .args(["--now", "2025-06-27T14:00:00Z", "-t", "UTC"]);
+        cmd
+    };
+    let both = [
+        "--if-past",
+        "OVERDUE by {relative}",
+        "--if-future",
+        "{relative} left",
+    ];
+
+    run("2025-06-30", &both)
+        .assert()
+        .success()
+        .stdout("2 days left\n");
+    run("2025-06-20", &both)
+        .assert()
+        .success()
+        .stdout("OVERDUE by 1 week\n");
+    run("2025-06-20", &["--if-future", "{fmt}"])
+        .assert()
+        .success()
+        .stdout("");
+    run("2025-06-30", &["--if-past", "{when}"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("unknown token '{when}'"));
+}
+
+#[test]
+fn clamp_moves_a_date_into_the_window() {
+    let tmp = TempDir::new().unwrap();
+    let clamp = |input: &str| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.args([
+            "clamp",
+            input,
+            "--min",
+            "today 22:00",
+            "--max",
+            "tomorrow 02:00",
+        ])
+        .args([
+            "-f",
+            "%d %H:%M",
+            "--now",
+            "2025-06-27T14:00:00Z",
+            "-t",
+            "UTC",
+        ]);
+        cmd
+    };
+
+    clamp("today 15:00").assert().success().stdout("27 22:00\n");
+    clamp("today 23:15").assert().success().stdout("27 23:15\n");
+    clamp("tomorrow 09:00")
+        .assert()
+        .success()
+        .stdout("28 02:00\n");
+
+    td_cmd(&tmp)
+        .args(["clamp", "now", "--min", "tomorrow", "--max", "today"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("is after --max"));
+}
+
+#[test]
+fn min_and_max_pick_the_extremes() {
+    let tmp = TempDir::new().unwrap();
+    let now = [
+        "--now",
+        "2025-06-27T14:00:00Z",
+        "-t",
+        "UTC",
+        "-f",
+        "%Y-%m-%d",
+    ];
+
+    td_cmd(&tmp)
+        .args(["min", "next friday", "2025-06-30", "in 2 days"])
+        .args(now)
+        .assert()
+        .success()
+        .stdout("2025-06-29\n");
+    td_cmd(&tmp)
+        .arg("max")
+        .args(now)
+        .write_stdin("2025-06-30\n\nnext friday\n")
+        .assert()
+        .success()
+        .stdout("2025-07-04\n");
+    td_cmd(&tmp).arg("max").write_stdin("").assert().code(64);
+}
+
+#[test]
+fn keep_offset_prints_in_the_input_offset() {
+    let tmp = TempDir::new().unwrap();
+    let fmt = "%Y-%m-%dT%H:%M:%S%:z";
+
+    td_cmd(&tmp)
+        .args(["2025-06-24T15:00:00-03:00", "-t", "Asia/Tokyo", "-f", fmt])
+        .assert()
+        .success()
+        .stdout("2025-06-25T03:00:00+09:00\n");
+    td_cmd(&tmp)
+        .args(["2025-06-24T15:00:00-03:00", "-t", "Asia/Tokyo", "-f", fmt])
+        .arg("--keep-offset")
+        .assert()
+        .success()
+        .stdout("2025-06-24T15:00:00-03:00\n");
+    td_cmd(&tmp)
+        .args(["today 3pm UTC+2", "--keep-offset", "-t", "UTC", "-f", fmt])
+        .args(["--now", "2025-06-24T09:00:00Z"])
+        .assert()
+        .success()
+        .stdout("2025-06-24T15:00:00+02:00\n");
+    td_cmd(&tmp)
+        .args(["today 3pm", "--keep-offset", "-t", "UTC", "-f", fmt])
+        .args(["--now", "2025-06-24T09:00:00Z"])
+        .assert()
+        .success()
+        .stdout("2025-06-24T15:00:00+00:00\n");
+}
+
+#[test]
+fn convert_reads_only_machine_formats() {
+    let tmp = TempDir::new().unwrap();
+    let fmt = "%Y-%m-%dT%H:%M:%S%:z";
+
+    td_cmd(&tmp)
+        .args(["Tue, 24 Jun 2025 15:00:00 -0300", "--convert", "-t", "UTC"])
+        .args(["-f", fmt])
+        .assert()
+        .success()
+        .stdout("2025-06-24T18:00:00+00:00\n");
+    td_cmd(&tmp)
+        .args(["1750788000123", "--convert", "-t", "Asia/Tokyo", "-f", fmt])
+        .assert()
+        .success()
+        .stdout("2025-06-25T03:00:00+09:00\n");
+    td_cmd(&tmp)
+        .args(["@1750788000s", "--convert", "-t", "UTC", "-f", "epoch"])
+        .assert()
+        .success()
+        .stdout("1750788000\n");
+    td_cmd(&tmp)
+        .args([
+            "Tue, 24 Jun 2025 15:00:00 -0300",
+            "--convert",
+            "--keep-offset",
+        ])
+        .args(["-t", "UTC", "-f", fmt])
+        .assert()
+        .success()
+        .stdout("2025-06-24T15:00:00-03:00\n");
+}
+
+#[test]
+fn convert_rejects_expressions() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["next friday", "--convert"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains(
+            "--convert expects RFC 3339, RFC 2822 or a Unix timestamp",
+        ));
+    td_cmd(&tmp)
+        .args(["--convert", "-t", "UTC"])
+        .write_stdin("2025-06-24T15:00:00Z\ntomorrow\n")
+        .arg("--skip-errors")
+        .assert()
+        .code(1)
+        .stdout("2025-06-24T15:00:00\n\n");
+}
+
+#[test]
+fn zone_tokens_name_the_zone() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["2025-06-24T18:00:00Z", "-t", "America/Sao_Paulo"])
+        .args(["-f", "%FT%T {tzid} {tzabbr} {tzoffset}"])
+        .assert()
+        .success()
+        .stdout("2025-06-24T15:00:00 America/Sao_Paulo -03 -10800\n");
+    td_cmd(&tmp)
+        .args(["2025-06-24T18:00:00Z", "-t", "Asia/Tokyo", "--dry-run"])
+        .args(["--exec", "notify --zone {tzid} --offset {tzoffset}"])
+        .assert()
+        .success()
+        .stdout("notify --zone Asia/Tokyo --offset 32400\n");
+}
+
+#[test]
+fn beats_format_prints_swatch_internet_time() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["2025-01-15T10:30:00Z", "-f", "beats", "-t", "Asia/Tokyo"])
+        .assert()
+        .success()
+        .stdout("@479\n");
+    td_cmd(&tmp)
+        .args(["2025-01-15T10:30:00Z", "--exec", "echo {beats}"])
+        .assert()
+        .success()
+        .stdout("479\n");
+}
+
+#[test]
+fn scale_prints_tai_and_gps() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["2025-06-24T15:00:00Z", "--scale", "tai", "-f", "%T"])
+        .assert()
+        .success()
+        .stdout("15:00:37\n");
+    td_cmd(&tmp)
+        .args(["2025-06-24T15:00:00Z", "--scale", "gps", "-f", "%T"])
+        .assert()
+        .success()
+        .stdout("15:00:18\n");
+    td_cmd(&tmp)
+        .args(["1971-06-24T15:00:00Z", "--scale", "tai"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("time scales start on 1972-01-01"));
+}
+
+#[test]
+fn scale_warns_past_the_leap_second_table() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["2030-01-01T00:00:00Z", "--scale", "gps", "-f", "%T"])
+        .assert()
+        .success()
+        .stdout("00:00:18\n")
+        .stderr(predicate::str::contains("leap-second table ends on"));
+    td_cmd(&tmp)
+        .args(["2030-01-01T00:00:00Z", "--scale", "gps", "--deny-warnings"])
+        .assert()
+        .code(64)
+        .stdout("");
+    td_cmd(&tmp)
+        .args(["2030-01-01T00:00:00Z", "-f", "%T"])
+        .assert()
+        .success()
+        .stderr("");
+}
+
+#[test]
+fn allow_extreme_reads_128_bit_epochs() {
+    let tmp = TempDir::new().unwrap();
+
+    td_cmd(&tmp)
+        .args(["@99999999999999999999", "-t", "UTC"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("does not fit in 64 bits"));
+    td_cmd(&tmp)
+        .args(["@99999999999999999999", "--allow-extreme", "-t", "UTC"])
+        .args(["-f", "%Y-%m-%dT%H:%M:%S"])
+        .assert()
+        .success()
+        .stdout("5138-11-16T09:46:39\n");
+    td_cmd(&tmp)
+        .args(["@99999999999999999999s", "--allow-extreme", "-t", "UTC"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains(
+            "seconds must be between -377705023201 and 253402207200",
+        ));
+}
+
+#[test]
+fn help_follows_the_message_locale() {
+    let tmp = TempDir::new().unwrap();
+    td_cmd(&tmp)
+        .env("LANG", "pt_BR.UTF-8")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Uso: td"))
+        .stdout(predicate::str::contains("Formato de saída."));
+
+    td_cmd(&tmp)
+        .env("LANG", "pt_BR.UTF-8")
+        .env("LC_MESSAGES", "C")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage: td"));
+}
+
+#[test]
+fn localized_command_still_parses_the_input() {
+    let tmp = TempDir::new().unwrap();
+    let td_pt = |args: &[&str]| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.env("LANG", "pt_BR.UTF-8").args(args).args([
+            "--now",
+            "2025-01-15T10:30:00Z",
+            "-t",
+            "UTC",
+        ]);
+        cmd.assert()
+    };
+
+    td_pt(&["tomorrow", "-f", "%F"])
+        .success()
+        .stdout("2025-01-16\n");
+    td_pt(&["-f", "%F %T", "now"])
+        .success()
+        .stdout("2025-01-15 10:30:00\n");
+    td_pt(&["diff", "today", "tomorrow"])
+        .success()
+        .stdout("1d\n");
+}
+
+#[test]
+fn plain_output_uses_tabs_and_ascii() {
+    let tmp = TempDir::new().unwrap();
+    td_cmd(&tmp)
+        .args(["info", "2025-01-01", "-t", "UTC", "--plain"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Date\tWednesday, January  1, 2025\n",
+        ))
+        .stdout(predicate::str::contains("Quarter\tQ1\n"));
+
+    td_cmd(&tmp)
+        .args(["hist", "--by", "hour", "--width", "4", "--plain"])
+        .write_stdin("2025-01-01T00:00:00Z a\n2025-01-01T01:00:00Z b\n2025-01-01T01:30:00Z c\n")
+        .assert()
+        .success()
+        .stdout("2025-01-01 00:00\t## 1\n2025-01-01 01:00\t#### 2\n");
+}
+
+#[test]
+fn ascii_bars_follow_the_flag_and_the_locale() {
+    let tmp = TempDir::new().unwrap();
+    let input = "2025-01-01T00:00:00Z a\n2025-01-01T00:10:00Z b\n";
+    td_cmd(&tmp)
+        .args(["hist", "-t", "UTC", "--width", "3", "--ascii"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout("2025-01-01 00:00  ### 2\n");
+
+    td_cmd(&tmp)
+        .env("LANG", "C")
+        .args(["hist", "-t", "UTC", "--width", "3"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout("2025-01-01 00:00  ### 2\n");
+
+    td_cmd(&tmp)
+        .args(["hist", "-t", "UTC", "--width", "3"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout("2025-01-01 00:00  ███ 2\n");
+}
+
+#[test]
+fn line_ending_applies_in_single_and_batch_mode() {
+    let tmp = TempDir::new().unwrap();
+    td_cmd(&tmp)
+        .args([
+            "2025-01-01",
+            "-t",
+            "UTC",
+            "-f",
+            "%F",
+            "--line-ending",
+            "crlf",
+        ])
+        .assert()
+        .success()
+        .stdout("2025-01-01\r\n");
+
+    td_cmd(&tmp)
+        .args([
+            "-t",
+            "UTC",
+            "-f",
+            "%F",
+            "--skip-errors",
+            "--line-ending",
+            "crlf",
+        ])
+        .args(["--now", "2025-01-01T00:00:00Z"])
+        .write_stdin("today\r\nnot a date\r\ntomorrow\r\n")
+        .assert()
+        .failure()
+        .stdout("2025-01-01\r\n\r\n2025-01-02\r\n");
+
+    td_cmd(&tmp)
+        .args(["2025-01-01", "-t", "UTC", "-f", "%F", "--line-ending", "lf"])
+        .assert()
+        .success()
+        .stdout("2025-01-01\n");
+}
+
+#[test]
+fn tz_list_prints_every_zone_without_paging_a_pipe() {
+    let tmp = TempDir::new().unwrap();
+    td_cmd(&tmp)
+        .env("TARDIS_PAGER", "false")
+        .args(["tz", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\nAmerica/Sao_Paulo\n"))
+        .stdout(predicate::str::contains("\nEurope/London\n"));
+}
+
+#[test]
+fn columns_pick_and_order_table_columns() {
+    let tmp = TempDir::new().unwrap();
+    td_cmd(&tmp)
+        .args(["tz", "list", "--plain", "--columns", "abbr,zone,offset"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\nUTC\tUTC\t+00:00\n"));
+
+    td_cmd(&tmp)
+        .args(["stats", "--columns", "value", "--max-width", "4"])
+        .write_stdin("2025-01-01T00:00:00Z a\n2025-01-01T00:00:30Z b\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("2\n202…\n202…\n30s\n"));
+
+    td_cmd(&tmp)
+        .args(["tz", "list", "--columns", "zone,city"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains(
+            "unknown column 'city'; choose from zone, offset, abbr, country",
+        ));
+}
+
+#[test]
+fn tz_list_sorts_by_offset_and_country() {
+    let tmp = TempDir::new().unwrap();
+    let out = td_cmd(&tmp)
+        .args([
+            "tz",
+            "list",
+            "--plain",
+            "--sort",
+            "offset",
+            "--columns",
+            "offset",
+        ])
+        .output()
+        .unwrap();
+    let offsets = String::from_utf8(out.stdout).unwrap();
+    let first = offsets.lines().next().unwrap();
+    assert_eq!(first, "-12:00");
+    assert!(offsets.trim_end().ends_with("+14:00"));
+
+    td_cmd(&tmp)
+        .args([
+            "tz",
+            "list",
+            "--plain",
+            "--sort",
+            "country",
+            "--columns",
+            "country,zone",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("AE\tAsia/Dubai\n"));
+}
+
+#[test]
+fn tz_list_filters_by_offset_and_dst() {
+    let tmp = TempDir::new().unwrap();
+    td_cmd(&tmp)
+        .args(["tz", "list", "--offset", "+5:30"])
+        .assert()
+        .success()
+        .stdout("Asia/Calcutta\nAsia/Colombo\nAsia/Kolkata\n");
+
+    let summer = [
+        "tz",
+        "list",
+        "--dst-active",
+        "--now",
+        "2025-07-01T12:00:00Z",
+    ];
+    td_cmd(&tmp)
+        .args(summer)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Europe/London\n"))
+        .stdout(predicate::str::contains("Asia/Tokyo").not());
+    td_cmd(&tmp)
+        .args([
+            "tz",
+            "list",
+            "--dst-active",
+            "--now",
+            "2025-01-01T12:00:00Z",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Europe/London").not())
+        .stdout(predicate::str::contains("America/Sao_Paulo").not());
+
+    td_cmd(&tmp)
+        .args(["tz", "list", "--offset", "+25"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("invalid UTC offset '+25'"));
+}
+
+#[test]
+fn tz_transitions_lists_offset_changes() {
+    let tmp = TempDir::new().unwrap();
+    td_cmd(&tmp)
+        .args(["tz", "transitions", "Europe/London", "2025"])
+        .assert()
+        .success()
+        .stdout(
+            "2025-03-30T01:00:00Z  +00:00  +01:00  2025-03-30 01:00 -> 02:00  GMT -> BST\n\
+             2025-10-26T01:00:00Z  +01:00  +00:00  2025-10-26 02:00 -> 01:00  BST -> GMT\n",
+        );
+
+    td_cmd(&tmp)
+        .args([
+            "tz",
+            "transitions",
+            "Australia/Sydney",
+            "2025..2026",
+            "--columns",
+            "local",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2026-10-04 02:00 -> 03:00\n"));
+
+    td_cmd(&tmp)
+        .args(["tz", "transitions", "Asia/Tokyo", "2025"])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "Asia/Tokyo has no offset changes in 2025",
+        ));
+
+    td_cmd(&tmp)
+        .args(["tz", "transitions", "UTC", "2026..2025"])
+        .assert()
+        .code(64);
+}
+
+#[test]
+fn tz_compare_flags_mismatched_dst_windows() {
+    let tmp = TempDir::new().unwrap();
+    td_cmd(&tmp)
+        .args([
+            "tz",
+            "compare",
+            "America/New_York",
+            "Europe/London",
+            "--now",
+            "2025-01-15T00:00:00Z",
+            "--columns",
+            "from,difference,note",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            "2025-01-15T00:00:00Z  +05:00\n\
+             2025-03-09T07:00:00Z  +04:00  shifted -01:00\n\
+             2025-03-30T01:00:00Z  +05:00\n\
+             2025-10-26T01:00:00Z  +04:00  shifted -01:00\n\
+             2025-11-02T06:00:00Z  +05:00\n",
+        );
+}
+
+#[test]
+fn tz_next_change_counts_down_to_the_next_offset_change() {
+    let tmp = TempDir::new().unwrap();
+    td_cmd(&tmp)
+        .args([
+            "tz",
+            "next-change",
+            "Europe/London",
+            "--now",
+            "2025-10-20T00:00:00Z",
+        ])
+        .assert()
+        .success()
+        .stdout("2025-10-26T01:00:00+00:00 (in 6 days, BST -> GMT)\n");
+
+    td_cmd(&tmp)
+        .env("TZ", "America/New_York")
+        .args([
+            "tz",
+            "next-change",
+            "-f",
+            "epoch",
+            "--now",
+            "2025-10-20T00:00:00Z",
+        ])
+        .assert()
+        .success()
+        .stdout("1762063200 (in 2 weeks, EDT -> EST)\n");
+
+    td_cmd(&tmp)
+        .args(["tz", "next-change", "America/Sao_Paulo"])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "America/Sao_Paulo has no upcoming offset changes",
+        ));
+}
+
+#[test]
+fn split_divides_a_range_into_slots() {
+    let tmp = TempDir::new().unwrap();
+    let now = "2025-01-15T10:30:00Z";
+    td_cmd(&tmp)
+        .args([
+            "split",
+            "tomorrow 9am to 10am",
+            "--by",
+            "25m",
+            "-f",
+            "%H:%M",
+        ])
+        .args(["-t", "UTC", "--now", now])
+        .assert()
+        .success()
+        .stdout("09:00\t09:25\n09:25\t09:50\n09:50\t10:00\n");
+
+    td_cmd(&tmp)
+        .args([
+            "split", "today", "--by", "10h", "--full", "-f", "%H", "-d", "-",
+        ])
+        .args(["-t", "UTC", "--now", now])
+        .assert()
+        .success()
+        .stdout("00-10\n10-20\n");
+
+    td_cmd(&tmp)
+        .args([
+            "split",
+            "today 5pm to today 9am",
+            "--by",
+            "1h",
+            "--now",
+            now,
+        ])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("ends before it starts"));
+}
+
+#[test]
+fn split_checks_the_format_and_the_slot_count() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"br\"\ntimezone = \"UTC\"\n\n[formats]\nbr = \"%d/%m %Hh\"\n",
+    );
+    let now = "2025-01-15T10:30:00Z";
+
+    td_cmd(&tmp)
+        .args(["split", "today", "--by", "12h", "--now", now])
+        .assert()
+        .success()
+        .stdout("15/01 00h\t15/01 12h\n15/01 12h\t16/01 00h\n");
+    td_cmd(&tmp)
+        .args(["split", "today", "--by", "12h", "-f", "%", "--now", now])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("invalid format string"));
+    td_cmd(&tmp)
+        .args(["split", "this year", "--by", "1s", "--now", now])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("more than 1000000 slots"));
+}
+
+#[test]
+fn workweek_prints_the_working_days() {
+    let tmp = TempDir::new().unwrap();
+    let now = "2025-01-15T10:30:00Z";
+    td_cmd(&tmp)
+        .args(["workweek", "-t", "UTC", "--now", now])
+        .assert()
+        .success()
+        .stdout("2025-01-13\n2025-01-14\n2025-01-15\n2025-01-16\n2025-01-17\n");
+
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n\n[workweek]\ndays = [\"sun\", \"mon\", \"tue\", \"wed\", \"thu\"]\nweek_start = \"sunday\"\n",
+    );
+    td_cmd(&tmp)
+        .args(["workweek", "2025-01-18", "--range", "-d", ",", "--now", now])
+        .assert()
+        .success()
+        .stdout("2025-01-12,2025-01-16\n");
+    td_cmd(&tmp)
+        .args(["workweek", "--days", "fri", "-f", "%a", "--now", now])
+        .assert()
+        .success()
+        .stdout("Fri\n");
+
+    td_cmd(&tmp)
+        .args(["workweek", "--days", "funday", "--now", now])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("unknown weekday 'funday'"));
+}
+
+#[test]
+fn quarter_counts_from_the_fiscal_year_start() {
+    let tmp = TempDir::new().unwrap();
+    let now = "2025-01-15T10:30:00Z";
+    td_cmd(&tmp)
+        .args(["quarter", "-t", "UTC", "-f", "%F", "--now", now])
+        .assert()
+        .success()
+        .stdout("2025-01-01\n2025-03-31\n");
+
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%F\"\ntimezone = \"UTC\"\n\n[fiscal]\nyear_start = \"october\"\n",
+    );
+    td_cmd(&tmp)
+        .args(["quarter", "2024-11-20", "-d", ",", "--now", now])
+        .assert()
+        .success()
+        .stdout("2024-10-01,2024-12-31\n");
+    td_cmd(&tmp)
+        .args(["quarter", "2024-11-20", "--name", "--now", now])
+        .assert()
+        .success()
+        .stdout("FY2025 Q1\n");
+    td_cmd(&tmp)
+        .args([
+            "quarter",
+            "2024-11-20",
+            "--calendar",
+            "--name",
+            "--now",
+            now,
+        ])
+        .assert()
+        .success()
+        .stdout("2024 Q4\n");
+
+    td_cmd(&tmp)
+        .args(["quarter", "--fiscal-start", "smarch", "--now", now])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("unknown month 'smarch'"));
+}
+
+#[test]
+fn quarter_expands_a_preset_default_format() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"br\"\ntimezone = \"UTC\"\n\n[formats]\nbr = \"%d/%m/%Y\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["quarter", "--now", "2025-01-15T10:30:00Z"])
+        .assert()
+        .success()
+        .stdout("01/01/2025\n31/03/2025\n");
+}
+
+#[test]
+fn next_occurrence_finds_the_coming_anniversary() {
+    let tmp = TempDir::new().unwrap();
+    let now = "2025-01-15T10:30:00Z";
+    let next = |args: &[&str]| {
+        td_cmd(&tmp)
+            .arg("next-occurrence")
+            .args(args)
+            .args(["-t", "UTC", "--now", now])
+            .assert()
+    };
+    next(&["--01-15"]).success().stdout("2025-01-15 (today)\n");
+    next(&["01-14"])
+        .success()
+        .stdout("2026-01-14 (in 364 days)\n");
+    next(&["1990-06-24"])
+        .success()
+        .stdout("2025-06-24 (in 160 days, 35 years)\n");
+    next(&["2000-02-29"])
+        .success()
+        .stdout("2025-02-28 (in 44 days, 25 years)\n");
+    next(&["02-29", "--feb29", "skip", "--json"])
+        .success()
+        .stdout(predicate::str::contains("\"date\":\"2028-02-29\""))
+        .stdout(predicate::str::contains("\"years\":null"));
+
+    next(&["02-30"]).code(64);
+}
+
+#[test]
+fn compat_date_reads_gnu_date_options() {
+    let tmp = TempDir::new().unwrap();
+    let date = |args: &[&str]| {
+        td_cmd(&tmp)
+            .args(["--compat", "date", "--now", "2025-01-15T10:30:00Z"])
+            .args(args)
+            .write_stdin("ignored")
+            .assert()
+    };
+    date(&["-u"])
+        .success()
+        .stdout("Wed Jan 15 10:30:00 UTC 2025\n");
+    date(&["-u", "-d", "-3 days", "+%F"])
+        .success()
+        .stdout("2025-01-12\n");
+    date(&["-d", "@0", "-u", "-R"])
+        .success()
+        .stdout("Thu, 01 Jan 1970 00:00:00 +0000\n");
+    date(&["--utc", "--date=tomorrow", "--iso-8601=minutes"])
+        .success()
+        .stdout("2025-01-16T00:00+00:00\n");
+    date(&["-u", "-Iweeks"])
+        .code(64)
+        .stderr(predicate::str::contains("choose from date, hours"));
+}
+
+#[test]
+fn rfc_email_and_iso_8601_flags_pick_the_format() {
+    let tmp = TempDir::new().unwrap();
+    let td = |args: &[&str]| {
+        td_cmd(&tmp)
+            .args(["--now", "2025-01-15T10:30:00Z", "-t", "UTC"])
+            .args(args)
+            .assert()
+    };
+    td(&["now", "-R"])
+        .success()
+        .stdout("Wed, 15 Jan 2025 10:30:00 +0000\n");
+    td(&["now", "-I"]).success().stdout("2025-01-15\n");
+    td(&["-Ihours", "now"])
+        .success()
+        .stdout("2025-01-15T10+00:00\n");
+    td(&["now", "--iso-8601=seconds"])
+        .success()
+        .stdout("2025-01-15T10:30:00+00:00\n");
+    td(&["now", "-R", "-f", "%F"]).code(2);
+    td(&["now", "-Iweeks"])
+        .code(2)
+        .stderr(predicate::str::contains("[possible values: date, hours"));
+}
+
+#[test]
+fn v_adjustments_apply_in_order() {
+    let tmp = TempDir::new().unwrap();
+    let td = |args: &[&str]| {
+        td_cmd(&tmp)
+            .args(["--now", "2025-01-15T10:30:00Z", "-t", "UTC"])
+            .args(args)
+            .assert()
+    };
+    td(&["now", "-v+1m", "-v1d", "-v-1d", "-f", "%F"])
+        .success()
+        .stdout("2025-01-31\n");
+    td(&["now", "-v+fri", "-v17H", "-v0M", "-f", "%a %H:%M"])
+        .success()
+        .stdout("Fri 17:00\n");
+    td(&["now", "--adjust", "-1H", "-f", "%H:%M"])
+        .success()
+        .stdout("09:30\n");
+    td(&["now", "-vn", "-f", "%F"])
+        .success()
+        .stdout("2025-01-15")
+        .stderr(predicate::str::contains("[parse]"));
+    td(&["now", "-v32d"])
+        .code(64)
+        .stderr(predicate::str::contains("cannot apply adjustment"));
+    td(&["now", "--adjust", "+3x"])
+        .code(64)
+        .stderr(predicate::str::contains("invalid adjustment '+3x'"));
+}
+
+#[test]
+fn exit_codes_follow_the_config_table_and_classic_flag() {
+    let tmp = TempDir::new().unwrap();
+    td_cmd(&tmp).arg("not a date at all").assert().code(64);
+    td_cmd(&tmp)
+        .args(["not a date at all", "--classic-exit-codes"])
+        .assert()
+        .code(1);
+    td_cmd(&tmp)
+        .args(["--no-such-flag", "--classic-exit-codes"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("unexpected argument"));
+
+    let config =
+        |table: &str| format!("format = \"%Y\"\ntimezone = \"UTC\"\n\n[exit_codes]\n{table}\n");
+    write_config(&tmp, &config("usage = 3"));
+    td_cmd(&tmp).arg("not a date at all").assert().code(3);
+    td_cmd(&tmp)
+        .args(["diff", "not a date", "now", "--classic-exit-codes"])
+        .assert()
+        .code(1);
+
+    write_config(&tmp, &config("config = 0"));
+    td_cmd(&tmp)
+        .arg("now")
+        .assert()
+        .code(78)
+        .stderr(predicate::str::contains("expected a nonzero u8"));
+    td_cmd(&tmp)
+        .args(["now", "--classic-exit-codes"])
+        .assert()
+        .code(1);
+}
+
+#[cfg(unix)]
+#[test]
+fn closed_stdout_stops_batch_mode_without_a_panic() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::{Command, Stdio};
+
+    let tmp = TempDir::new().unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_td"))
+        .env("XDG_CONFIG_HOME", tmp.path())
+        .env_remove("TARDIS_CONFIG_DIR")
+        .args(["--now", "2025-01-15T10:30:00Z", "-t", "UTC", "-f", "%F"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = std::thread::spawn(move || {
+        for _ in 0..200_000 {
+            if writeln!(stdin, "tomorrow").is_err() {
+                break;
+            }
+        }
+    });
+    let mut first = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut first)
+        .unwrap();
+    assert_eq!(first, "2025-01-16\n");
+
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap();
+    assert_eq!(output.status.signal(), Some(libc::SIGPIPE));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
+
+#[test]
+fn buffering_flags_keep_batch_output_and_conflict() {
+    let tmp = TempDir::new().unwrap();
+    let input = "@0\n@86400\n@172800\n";
+    for flag in ["--line-buffered", "--block-buffered"] {
+        td_cmd(&tmp)
+            .args(["-t", "UTC", "-f", "%F", flag])
+            .write_stdin(input)
+            .assert()
+            .success()
+            .stdout("1970-01-01\n1970-01-02\n1970-01-03\n");
+    }
+    td_cmd(&tmp)
+        .args([
+            "stamp",
+            "--block-buffered",
+            "-f",
+            "%Y",
+            "--now",
+            "2025-01-15T10:30:00Z",
+        ])
+        .write_stdin("a\nb\n")
+        .assert()
+        .success()
+        .stdout("2025 a\n2025 b\n");
+    td_cmd(&tmp)
+        .args(["now", "--line-buffered", "--block-buffered"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn on_error_records_failed_lines_and_keeps_going() {
+    let tmp = TempDir::new().unwrap();
+    let td = |args: &[&str]| {
+        td_cmd(&tmp)
+            .args(["-f", "%F", "-t", "UTC", "--now", "2025-01-15T10:30:00Z"])
+            .args(args)
+            .write_stdin("tomorrow\n$$$invalid\nyesterday\n")
+            .assert()
+    };
+
+    let out = td(&["--on-error", "record"])
+        .success()
+        .stderr(predicate::str::contains("1 of 3 lines failed"));
+    let stdout = String::from_utf8_lossy(&out.get_output().stdout).into_owned();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "2025-01-16");
+    let record: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+    assert_eq!(record["input"], "$$$invalid");
+    assert_eq!(record["line"], 2);
+    assert!(
+        record["error"]
+            .as_str()
+            .unwrap()
+            .contains("could not parse")
+    );
+    assert_eq!(lines[2], "2025-01-14");
+
+    td(&["--on-error", "skip"])
+        .success()
+        .stdout("2025-01-16\n\n2025-01-14\n");
+    td(&["--on-error", "record", "--strict"]).code(1);
+    td(&[]).code(64).stdout("2025-01-16\n");
+    td(&["--on-error", "skip", "--skip-errors"]).code(2);
+}
+
+#[test]
+fn define_declares_and_overrides_presets_for_one_run() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\n[formats]\nday = \"%d/%m/%Y\"\nlog = \"{day} %H:%M\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args(["2025-01-02 09:30", "-f", "log", "--define", "day=%F"])
+        .assert()
+        .success()
+        .stdout("2025-01-02 09:30\n");
+    td_cmd(&tmp)
+        .args(["2025-01-02", "-f", "stamp", "--define", "stamp=%Y%m%d"])
+        .assert()
+        .success()
+        .stdout("20250102\n");
+    td_cmd(&tmp)
+        .args(["2025-01-02", "-f", "day"])
+        .assert()
+        .success()
+        .stdout("02/01/2025\n");
+    td_cmd(&tmp)
+        .args(["2025-01-02", "--define", "day=%Q"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains(
+            "defined preset 'day' has an invalid format: %Q",
+        ));
+}
+
+#[test]
+fn no_presets_ignores_the_formats_table() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%Y\"\ntimezone = \"UTC\"\npreset_group = \"work\"\n[formats]\niso = \"%Y\"\nbad = \"%Q\"\nday = \"%d/%m\"\n",
+    );
+
+    td_cmd(&tmp)
+        .args([
+            "2025-01-02 09:30",
+            "-f",
+            "iso",
+            "--no-presets",
+            "--deny-warnings",
+        ])
+        .assert()
+        .success()
+        .stderr("")
+        .stdout("2025-01-02T09:30:00+00:00\n");
+    td_cmd(&tmp)
+        .args([
+            "2025-01-02",
+            "-f",
+            "day",
+            "--no-presets",
+            "--define",
+            "day=%F",
+        ])
+        .assert()
+        .success()
+        .stdout("2025-01-02\n");
+    td_cmd(&tmp)
+        .args(["now", "--no-presets", "--preset-group", "work"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn day_boundary_buckets_epochs_by_another_zones_days() {
+    let tmp = TempDir::new().unwrap();
+    let events = "1736983800 a\n1736989200 b\n1737028800 c\n";
+    let td = |args: &[&str]| {
+        td_cmd(&tmp)
+            .args(args)
+            .args(["-p", "epoch", "-t", "UTC"])
+            .write_stdin(events)
+            .assert()
+            .success()
+    };
+
+    td(&["group-by", "day"]).stdout("2025-01-15  1\n2025-01-16  2\n");
+    td(&["group-by", "day", "--day-boundary", "America/Sao_Paulo"])
+        .stdout("2025-01-15  2\n2025-01-16  1\n");
+    td(&[
+        "hist",
+        "--by",
+        "day",
+        "--csv",
+        "--day-boundary",
+        "America/Sao_Paulo",
+    ])
+    .stdout("bucket,count\n2025-01-15,2\n2025-01-16,1\n");
+    let out = td(&[
+        "stats",
+        "--per-day",
+        "--json",
+        "--day-boundary",
+        "America/Sao_Paulo",
+    ]);
+    let json: serde_json::Value = serde_json::from_slice(&out.get_output().stdout).unwrap();
+    assert_eq!(json["days"][0]["date"], "2025-01-15");
+    assert_eq!(json["days"][0]["count"], 2);
+    assert_eq!(json["days"][0]["latest"], "2025-01-16T01:00:00+00:00");
+}
+
+#[test]
+fn month_overflow_policy_applies_to_expressions_and_adjustments() {
+    let tmp = TempDir::new().unwrap();
+    let td = |args: &[&str]| {
+        td_cmd(&tmp)
+            .args(["-f", "%F", "-t", "UTC", "--now", "2025-01-31T10:00:00Z"])
+            .args(args)
+            .assert()
+    };
+
+    td(&["in 1 month"]).success().stdout("2025-02-28\n");
+    td(&["in 1 month", "--month-overflow", "overflow"])
+        .success()
+        .stdout("2025-03-03\n");
+    td(&["2024-02-29 + 1 year", "--month-overflow", "overflow"])
+        .success()
+        .stdout("2025-03-01\n");
+    td(&["now", "--adjust", "+1m", "--month-overflow", "overflow"])
+        .success()
+        .stdout("2025-03-03\n");
+    td(&["in 1 month", "--month-overflow", "error"])
+        .code(64)
+        .stderr(predicate::str::contains(
+            "2025-01-31 + 1mo falls on 2025-02-31, which does not exist",
+        ));
+    td(&["in 2 months", "--month-overflow", "error"])
+        .success()
+        .stdout("2025-03-31\n");
+}
+
+#[test]
+fn config_validate_ci_reports_errors_and_warnings_as_json() {
+    let tmp = TempDir::new().unwrap();
+    let original = "version = 1\nformat = \"%F\"\ntimezone = \"Mars/Base\"\ntimezon = \"UTC\"\n";
+    write_config(&tmp, original);
+
+    let out = td_cmd(&tmp)
+        .args(["config", "validate", "--ci"])
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(78));
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["valid"], false);
+    assert_eq!(report["exit_code"], 78);
+    assert_eq!(report["errors"][0]["key"], "timezone");
+    assert_eq!(report["errors"][0]["line"], 3);
+    assert_eq!(report["warnings"][0]["key"], "timezon");
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join("tardis/config.toml")).unwrap(),
+        original
+    );
+
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%F\"\ntimezone = \"UTC\"\ntimezon = \"UTC\"\n",
+    );
+    td_cmd(&tmp)
+        .args(["config", "validate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "config.toml:4:1: warning: timezon: unknown key 'timezon' is ignored",
+        ))
+        .stdout(predicate::str::contains("0 errors, 1 warning"));
+    td_cmd(&tmp)
+        .args(["config", "validate", "--ci", "--deny-warnings"])
+        .assert()
+        .code(78)
+        .stdout(predicate::str::contains("\"valid\":true"));
+}
+
+#[test]
+fn overlaps_exits_by_whether_ranges_share_an_instant() {
+    let tmp = TempDir::new().unwrap();
+    let overlaps = |args: &[&str]| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.arg("overlaps").args(args).args([
+            "--now",
+            "2025-01-15T10:00:00Z",
+            "-t",
+            "UTC",
+            "-f",
+            "%H:%M",
+        ]);
+        cmd.assert()
+    };
+
+    overlaps(&["from today 9am to 11am", "today 10:30 to 12:00", "-p"])
+        .success()
+        .stdout("10:30\t11:00\n");
+    overlaps(&["today 9am", "today 11am", "today 10:30", "today 12:00"])
+        .success()
+        .stdout("");
+    overlaps(&["today 9am to 11am", "today 10:30", "today 12:00", "--json"])
+        .success()
+        .stdout(predicate::str::contains(r#""overlaps":true"#));
+    overlaps(&["today 9am to 11am", "today 11am to 12:00", "-p"])
+        .code(1)
+        .stdout("");
+    overlaps(&["tomorrow", "today 9am to 11am", "--json"])
+        .code(1)
+        .stdout("{\"overlaps\":false}\n");
+    overlaps(&["today", "today 9am", "today 11am"])
+        .code(64)
+        .stderr(predicate::str::contains("cannot tell the two ranges apart"));
+}
+
+#[test]
+fn overlaps_checks_the_format_and_expands_presets() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"br\"\ntimezone = \"UTC\"\n\n[formats]\nbr = \"%d/%m %Hh\"\n",
+    );
+    let now = "2025-01-15T10:00:00Z";
+
+    td_cmd(&tmp)
+        .args(["overlaps", "today", "today 9am to 11am", "--print"])
+        .args(["--now", now])
+        .assert()
+        .success()
+        .stdout("15/01 09h\t15/01 11h\n");
+    td_cmd(&tmp)
+        .args(["overlaps", "today", "today", "--print", "-f", "%"])
+        .args(["--now", now])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("invalid format string"));
+}
+
+#[test]
+fn floor_and_ceil_snap_inputs_to_named_and_cron_schedules() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "format = \"%Y\"\ntimezone = \"UTC\"\n[recurrences]\nstandup = \"every weekday at 9:30\"\n",
+    );
+    let snap = |sub: &str, args: &[&str]| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.arg(sub).args(args).args([
+            "--now",
+            "2025-01-15T10:00:00Z",
+            "-t",
+            "UTC",
+            "-f",
+            "%a %H:%M",
+        ]);
+        cmd
+    };
+
+    snap("floor", &["standup", "2025-01-18 12:00"])
+        .assert()
+        .success()
+        .stdout("Fri 09:30\n");
+    snap("ceil", &["standup", "2025-01-15 09:30"])
+        .assert()
+        .success()
+        .stdout("Wed 09:30\n");
+    snap("ceil", &["*/20 * * * *"])
+        .write_stdin("2025-01-15T10:01:00Z\n\n2025-01-15T10:40:00Z\n")
+        .assert()
+        .success()
+        .stdout("Wed 10:20\nWed 10:40\n");
+    snap("floor", &["0 25 * * *"])
+        .write_stdin("")
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("invalid cron expression"));
+    snap("floor", &["every 7 minutes", "2025-01-15T10:41:30Z"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("add 'starting DATE'"));
+    snap(
+        "floor",
+        &[
+            "every 7 minutes starting 2025-01-15T10:00:00Z",
+            "2025-01-15T10:41:30Z",
+        ],
+    )
+    .assert()
+    .success()
+    .stdout("Wed 10:35\n");
+    snap("ceil", &["every other monday", "2025-01-15"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("add 'starting DATE'"));
+}
+
+#[test]
+fn require_rejects_input_of_another_kind_and_json_reports_it() {
+    let tmp = TempDir::new().unwrap();
+    let td = |args: &[&str]| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.args(args)
+            .args(["--now", "2025-01-15T10:30:00Z", "-t", "UTC"]);
+        cmd.assert()
+    };
+
+    td(&["tomorrow 9am", "--require", "datetime", "-f", "%F %H:%M"])
+        .success()
+        .stdout("2025-01-16 09:00\n");
+    td(&["tomorrow", "--require", "datetime"])
+        .code(64)
+        .stderr(predicate::str::contains(
+            "'tomorrow' is a date, but --require asks for a datetime",
+        ));
+    td(&["eod", "--require", "date", "--json"])
+        .success()
+        .stdout(predicate::str::contains(r#""kind":"date""#));
+    td(&["@1735689600", "--json"])
+        .success()
+        .stdout(predicate::str::contains(r#""kind":"datetime""#));
+}
+
+#[test]
+fn default_time_applies_to_bare_dates_from_config_and_flag() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "format = \"%F %H:%M\"\ntimezone = \"UTC\"\ndefault_time = \"09:00\"\n",
+    );
+    let td = |args: &[&str]| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.args(args).args(["--now", "2025-01-15T10:30:00Z"]);
+        cmd.assert()
+    };
+
+    td(&["next friday"]).success().stdout("2025-01-17 09:00\n");
+    td(&["next friday 3pm"])
+        .success()
+        .stdout("2025-01-17 15:00\n");
+    td(&["next month"]).success().stdout("2025-02-01 00:00\n");
+    td(&["2025-03-01", "--default-time", "end-of-day", "-f", "%T"])
+        .success()
+        .stdout("23:59:59\n");
+    td(&["today", "--default-time", "teatime"])
+        .code(64)
+        .stderr(predicate::str::contains("invalid --default-time 'teatime'"));
+    td(&["tomorrow", "--default-time", "25:00"])
+        .code(64)
+        .stderr(predicate::str::contains("invalid --default-time '25:00'"));
+
+    write_config(
+        &tmp,
+        "format = \"%F\"\ntimezone = \"UTC\"\ndefault_time = \"late\"\n",
+    );
+    td(&["today"])
+        .failure()
+        .stderr(predicate::str::contains("invalid value for 'default_time'"));
+
+    write_config(
+        &tmp,
+        "format = \"%F\"\ntimezone = \"UTC\"\ndefault_time = \"24:30\"\n",
+    );
+    td(&["today"])
+        .failure()
+        .stderr(predicate::str::contains("invalid value for 'default_time'"));
+}
+
+#[test]
+fn span_prints_utc_and_the_target_zone() {
+    let tmp = TempDir::new().unwrap();
+    let span = |args: &[&str]| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.arg("span")
+            .args(args)
+            .args(["--now", "2025-01-15T10:30:00Z"]);
+        cmd.assert()
+    };
+
+    span(&["2025-06-01T12:00:00Z", "-t", "America/New_York"])
+        .success()
+        .stdout("2025-06-01T12:00:00+00:00\t2025-06-01T08:00:00-04:00\n");
+    span(&["-t", "Asia/Kolkata", "-f", "%H:%M", "-d", " / "])
+        .success()
+        .stdout("10:30 / 16:00\n");
+    span(&["today", "-t", "Asia/Tokyo", "--json"])
+        .success()
+        .stdout(predicate::str::contains(
+            r#""local":"2025-01-15T00:00:00+09:00","timezone":"Asia/Tokyo","utc":"2025-01-14T15:00:00+00:00""#,
+        ));
+}
+
+#[test]
+fn diff_visual_marks_the_components_that_differ() {
+    let tmp = TempDir::new().unwrap();
+    let diff = |args: &[&str]| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.arg("diff")
+            .args(args)
+            .args(["--now", "2025-01-15T10:30:00Z", "-t", "UTC"]);
+        cmd.assert()
+    };
+
+    diff(&[
+        "--visual",
+        "2025-01-15T10:30:00+00:00",
+        "2025-01-15T10:30:00-03:00",
+    ])
+    .success()
+    .stdout("2025-01-15T10:30:00+00:00\n2025-01-15T10:30:00-03:00\n                   ^^^\n3h\n");
+    diff(&["--visual", "2025-01-15", "2025-01-17", "-f", "%a %d %b %Y"])
+        .success()
+        .stdout("Wed 15 Jan 2025\nFri 17 Jan 2025\n^^^ ^^\n2d\n");
+    diff(&["--visual", "today", "2025-01-15"])
+        .success()
+        .stdout("2025-01-15T00:00:00+00:00\n2025-01-15T00:00:00+00:00\n0s\n");
+    diff(&["--visual", "--json", "today", "tomorrow"])
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    diff(&["-f", "%F", "today", "tomorrow"])
+        .failure()
+        .stderr(predicate::str::contains("--visual"));
+}
+
+#[test]
+fn preset_locale_warns_only_when_the_preset_is_used() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"%F\"\ntimezone = \"UTC\"\n[formats]\nde = { format = \"%d.%m.%Y\", locale = \"de_DE\" }\n",
+    );
+    let td = |args: &[&str]| {
+        let mut cmd = td_cmd(&tmp);
+        cmd.args(args)
+            .args(["--now", "2025-01-15T10:30:00Z", "--deny-warnings"]);
+        cmd.assert()
+    };
+
+    td(&["now"]).success().stdout("2025-01-15\n").stderr("");
+    td(&["now", "-f", "de"])
+        .code(64)
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "preset 'de' sets locale 'de_DE', which td has no names for",
+        ));
+}
+
+#[test]
+fn preset_locale_prints_localized_names() {
+    let tmp = TempDir::new().unwrap();
+    write_config(
+        &tmp,
+        "version = 1\nformat = \"br\"\ntimezone = \"UTC\"\n[formats]\nbr = { format = \"%A, %-d de %B de %Y\", locale = \"pt-BR\" }\ncurto = { format = \"%a %d %b {hora}\", locale = \"pt_BR\" }\n",
+    );
+    let now = "2025-01-15T10:30:00Z";
+
+    td_cmd(&tmp)
+        .args(["next friday", "--now", now, "--deny-warnings"])
+        .assert()
+        .success()
+        .stdout("sexta-feira, 17 de janeiro de 2025\n");
+    td_cmd(&tmp)
+        .args(["today", "-f", "curto", "--param", "hora=9h", "--now", now])
+        .assert()
+        .success()
+        .stdout("qua 15 jan 9h\n");
+    td_cmd(&tmp)
+        .args(["quarter", "--now", now])
+        .assert()
+        .success()
+        .stdout("quarta-feira, 1 de janeiro de 2025\nsegunda-feira, 31 de março de 2025\n");
+}
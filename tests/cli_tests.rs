@@ -32,7 +32,35 @@ fn man_page_structure() {
         "docs/td-tz.1.md",
         "docs/td-info.1.md",
         "docs/td-range.1.md",
+        "docs/td-split.1.md",
+        "docs/td-workweek.1.md",
+        "docs/td-quarter.1.md",
+        "docs/td-next-occurrence.1.md",
+        "docs/td-holiday.1.md",
+        "docs/td-is.1.md",
+        "docs/td-is-open.1.md",
+        "docs/td-next-open.1.md",
         "docs/td-config.1.md",
+        "docs/td-cache.1.md",
+        "docs/td-lint-format.1.md",
+        "docs/td-prompt.1.md",
+        "docs/td-meet.1.md",
+        "docs/td-worldclock.1.md",
+        "docs/td-epoch.1.md",
+        "docs/td-sleep-until.1.md",
+        "docs/td-every.1.md",
+        "docs/td-stamp.1.md",
+        "docs/td-delta.1.md",
+        "docs/td-rewrite.1.md",
+        "docs/td-sum.1.md",
+        "docs/td-stats.1.md",
+        "docs/td-hist.1.md",
+        "docs/td-group-by.1.md",
+        "docs/td-humanize.1.md",
+        "docs/td-until.1.md",
+        "docs/td-clamp.1.md",
+        "docs/td-min.1.md",
+        "docs/td-max.1.md",
         "docs/td-completions.1.md",
     ];
 
@@ -64,7 +92,35 @@ fn man_page_roff_snapshots() {
         "docs/man/td-tz.1",
         "docs/man/td-info.1",
         "docs/man/td-range.1",
+        "docs/man/td-split.1",
+        "docs/man/td-workweek.1",
+        "docs/man/td-quarter.1",
+        "docs/man/td-next-occurrence.1",
+        "docs/man/td-holiday.1",
+        "docs/man/td-is.1",
+        "docs/man/td-is-open.1",
+        "docs/man/td-next-open.1",
         "docs/man/td-config.1",
+        "docs/man/td-cache.1",
+        "docs/man/td-lint-format.1",
+        "docs/man/td-prompt.1",
+        "docs/man/td-meet.1",
+        "docs/man/td-worldclock.1",
+        "docs/man/td-epoch.1",
+        "docs/man/td-sleep-until.1",
+        "docs/man/td-every.1",
+        "docs/man/td-stamp.1",
+        "docs/man/td-delta.1",
+        "docs/man/td-rewrite.1",
+        "docs/man/td-sum.1",
+        "docs/man/td-stats.1",
+        "docs/man/td-hist.1",
+        "docs/man/td-group-by.1",
+        "docs/man/td-humanize.1",
+        "docs/man/td-until.1",
+        "docs/man/td-clamp.1",
+        "docs/man/td-min.1",
+        "docs/man/td-max.1",
         "docs/man/td-completions.1",
     ];
 
@@ -19,6 +19,7 @@ fn td_golden(expression: &str) -> String {
     let output = Command::cargo_bin("td")
         .unwrap()
         .env("XDG_CONFIG_HOME", tmp.path())
+        .env("XDG_CACHE_HOME", tmp.path().join("cache"))
         .env("LANG", "en_US.UTF-8")
         .env_remove("LC_TIME")
         .args(["--now", NOW, "--format", FMT, "--timezone", TZ])
@@ -46,6 +47,7 @@ fn td_golden_args(args: &[&str]) -> String {
     let output = Command::cargo_bin("td")
         .unwrap()
         .env("XDG_CONFIG_HOME", tmp.path())
+        .env("XDG_CACHE_HOME", tmp.path().join("cache"))
         .env("LANG", "en_US.UTF-8")
         .env_remove("LC_TIME")
         .env_remove("NO_COLOR")
@@ -443,6 +445,47 @@ fn golden_absolute_past_date() {
     assert_snapshot!(td_golden("1999-12-31 23:59:59"), @"1999-12-31T23:59:59");
 }
 
+#[test]
+fn golden_absolute_exif_datetime() {
+    assert_snapshot!(td_golden("2025:06:27 09:00:00"), @"2025-06-27T09:00:00");
+}
+
+#[test]
+fn golden_absolute_exif_date_only() {
+    assert_snapshot!(td_golden("2025:06:27"), @"2025-06-27T00:00:00");
+}
+
+#[test]
+fn golden_offset_rfc3339() {
+    assert_snapshot!(td_golden("2025-06-24T15:00:00-03:00"), @"2025-06-24T18:00:00");
+}
+
+#[test]
+fn golden_offset_utc_plus_hours() {
+    assert_snapshot!(td_golden("today 3pm UTC+2"), @"2025-06-15T13:00:00");
+}
+
+#[test]
+fn golden_offset_bare_colon() {
+    assert_snapshot!(td_golden("tomorrow 9:00 -03:00"), @"2025-06-16T12:00:00");
+}
+
+#[test]
+fn golden_offset_bare_compact() {
+    assert_snapshot!(td_golden("tomorrow 15:00 +0530"), @"2025-06-16T09:30:00");
+}
+
+#[test]
+fn golden_offset_gmt() {
+    assert_snapshot!(td_golden("today at 9:00 GMT"), @"2025-06-15T09:00:00");
+}
+
+#[test]
+fn golden_offset_shifts_the_calendar_day() {
+    // Noon UTC is already the 16th in UTC+14.
+    assert_snapshot!(td_golden("today 9am UTC+14"), @"2025-06-15T19:00:00");
+}
+
 #[test]
 fn golden_epoch_standard() {
     assert_snapshot!(td_golden("@1735689600"), @"2025-01-01T00:00:00");
@@ -711,6 +754,49 @@ fn golden_error_not_a_date() {
     ");
 }
 
+#[test]
+fn golden_error_suggests_whole_phrase() {
+    assert_snapshot!(td_golden("nxet fridya at 9:30"), @r"
+    ERROR: Invalid date format: could not parse 'nxet fridya at 9:30' as a date expression
+
+    Did you mean 'next friday at 9:30'?
+    ");
+}
+
+#[test]
+fn golden_error_date_beyond_year_9999() {
+    assert_snapshot!(td_golden("10000-01-01"), @"ERROR: Invalid date format: date out of range: td handles -9999-01-02 to 9999-12-30");
+    assert_snapshot!(td_golden("in 8000 years"), @"ERROR: Invalid date format: date out of range: td handles -9999-01-02 to 9999-12-30");
+}
+
+#[test]
+fn golden_error_offset_too_large_for_a_span() {
+    assert_snapshot!(td_golden("in 20000 years"), @"ERROR: Invalid date format: date out of range: td handles -9999-01-02 to 9999-12-30");
+    assert_snapshot!(td_golden("20000 years ago"), @"ERROR: Invalid date format: date out of range: td handles -9999-01-02 to 9999-12-30");
+    assert_snapshot!(td_golden("in 99999999 days"), @"ERROR: Invalid date format: date out of range: td handles -9999-01-02 to 9999-12-30");
+    assert_snapshot!(td_golden("in 9999999 months"), @"ERROR: Invalid date format: date out of range: td handles -9999-01-02 to 9999-12-30");
+}
+
+#[test]
+fn golden_error_impossible_day() {
+    assert_snapshot!(td_golden("2025-02-30"), @"ERROR: Invalid date format: 2025-02-30 is not a valid date");
+}
+
+#[test]
+fn golden_error_epoch_out_of_range() {
+    assert_snapshot!(td_golden("@253402300800"), @"ERROR: Invalid date format: epoch 253402300800 is out of range: seconds must be between -377705023201 and 253402207200");
+    assert_snapshot!(td_golden("@99999999999999999999"), @"ERROR: Invalid date format: 99999999999999999999 does not fit in 64 bits; --allow-extreme reads it as a 128-bit epoch");
+}
+
+#[test]
+fn golden_error_suggests_split_words() {
+    assert_snapshot!(td_golden("nextfriday"), @r"
+    ERROR: Invalid date format: could not parse 'nextfriday' as a date expression
+
+    Did you mean 'next friday'?
+    ");
+}
+
 #[test]
 fn golden_error_invalid_epoch() {
     assert_snapshot!(td_golden("@abc"), @"ERROR: Invalid date format: could not parse '@abc' as a date expression");